@@ -0,0 +1,266 @@
+use crate::{
+    Result,
+    time::{TickSource, TimestampTracker},
+};
+
+/// Content source that renders onto a display while its slot is active.
+///
+/// `D` is whatever the provider draws on, typically [`Max7219`](crate::driver::Max7219)
+/// or one of the higher-level display wrappers.
+pub trait ContentProvider<D> {
+    /// Called once at the start of every slot before the first
+    /// [`render`](Self::render), e.g. to clear leftovers from the other provider.
+    fn enter(&mut self, _display: &mut D) -> Result<()> {
+        Ok(())
+    }
+
+    /// Render the provider's content. `slot_elapsed_ms` is the time spent in
+    /// the current slot so far.
+    fn render(&mut self, display: &mut D, slot_elapsed_ms: u32) -> Result<()>;
+}
+
+/// Which provider currently owns the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    First,
+    Second,
+}
+
+impl Slot {
+    fn other(self) -> Self {
+        match self {
+            Self::First => Self::Second,
+            Self::Second => Self::First,
+        }
+    }
+}
+
+/// Alternates a display between two providers on a fixed schedule,
+/// e.g. 8 s of clock followed by 4 s of weather.
+///
+/// Only the provider owning the current slot is asked to render, so the two
+/// never interleave output on the chain.
+pub struct Arbiter<A, B> {
+    first: A,
+    second: B,
+    durations_ms: [u32; 2],
+    active: Slot,
+    elapsed_ms: u32,
+    entered: bool,
+    timestamps: TimestampTracker,
+}
+
+impl<A, B> Arbiter<A, B> {
+    pub fn new(first: A, first_ms: u32, second: B, second_ms: u32) -> Self {
+        Self {
+            first,
+            second,
+            durations_ms: [first_ms, second_ms],
+            active: Slot::First,
+            elapsed_ms: 0,
+            entered: false,
+            timestamps: TimestampTracker::new(),
+        }
+    }
+
+    /// Provider that owns the display right now.
+    pub fn active(&self) -> Slot {
+        self.active
+    }
+
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// Restart the schedule at the beginning of the first slot.
+    pub fn reset(&mut self) {
+        self.active = Slot::First;
+        self.elapsed_ms = 0;
+        self.entered = false;
+        self.timestamps.reset();
+    }
+
+    /// Advance the schedule to the absolute timestamp `now_ms` instead of by
+    /// a delta. The first call only establishes the time base.
+    pub fn advance_to<D>(&mut self, display: &mut D, now_ms: u32) -> Result<Slot>
+    where
+        A: ContentProvider<D>,
+        B: ContentProvider<D>,
+    {
+        let delta_ms = self.timestamps.delta_to(now_ms);
+        self.tick(display, delta_ms)
+    }
+
+    /// Advance the schedule to the current time of `clock`, like
+    /// [`advance_to`](Self::advance_to).
+    pub fn advance_with<D, T>(&mut self, display: &mut D, clock: &mut T) -> Result<Slot>
+    where
+        A: ContentProvider<D>,
+        B: ContentProvider<D>,
+        T: TickSource,
+    {
+        self.advance_to(display, clock.now_ms())
+    }
+
+    /// Advance the schedule by `delta_ms`, switching slots as needed, and let
+    /// the active provider render.
+    ///
+    /// Returns the slot that rendered.
+    pub fn tick<D>(&mut self, display: &mut D, delta_ms: u32) -> Result<Slot>
+    where
+        A: ContentProvider<D>,
+        B: ContentProvider<D>,
+    {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(delta_ms);
+        self.advance_slots();
+
+        if !self.entered {
+            match self.active {
+                Slot::First => self.first.enter(display)?,
+                Slot::Second => self.second.enter(display)?,
+            }
+            self.entered = true;
+        }
+
+        match self.active {
+            Slot::First => self.first.render(display, self.elapsed_ms)?,
+            Slot::Second => self.second.render(display, self.elapsed_ms)?,
+        }
+        Ok(self.active)
+    }
+
+    fn duration(&self, slot: Slot) -> u32 {
+        match slot {
+            Slot::First => self.durations_ms[0],
+            Slot::Second => self.durations_ms[1],
+        }
+    }
+
+    fn advance_slots(&mut self) {
+        let period = self.durations_ms[0].saturating_add(self.durations_ms[1]);
+        if period == 0 {
+            return;
+        }
+        // Whole periods end in the same slot they started in
+        if self.elapsed_ms >= period {
+            self.elapsed_ms %= period;
+        }
+        while self.elapsed_ms >= self.duration(self.active) {
+            self.elapsed_ms -= self.duration(self.active);
+            self.active = self.active.other();
+            self.entered = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Named(&'static str);
+
+    impl ContentProvider<Vec<String>> for Named {
+        fn enter(&mut self, display: &mut Vec<String>) -> Result<()> {
+            display.push(format!("enter {}", self.0));
+            Ok(())
+        }
+
+        fn render(&mut self, display: &mut Vec<String>, slot_elapsed_ms: u32) -> Result<()> {
+            display.push(format!("{} {}", self.0, slot_elapsed_ms));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_alternates_between_providers() {
+        let mut log = Vec::new();
+        let mut arbiter = Arbiter::new(Named("clock"), 8000, Named("weather"), 4000);
+
+        assert_eq!(arbiter.tick(&mut log, 0), Ok(Slot::First));
+        assert_eq!(arbiter.tick(&mut log, 5000), Ok(Slot::First));
+        assert_eq!(arbiter.tick(&mut log, 3000), Ok(Slot::Second));
+        assert_eq!(arbiter.tick(&mut log, 3999), Ok(Slot::Second));
+        assert_eq!(arbiter.tick(&mut log, 1), Ok(Slot::First));
+
+        assert_eq!(
+            log,
+            [
+                "enter clock",
+                "clock 0",
+                "clock 5000",
+                "enter weather",
+                "weather 0",
+                "weather 3999",
+                "enter clock",
+                "clock 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_delta_skips_whole_periods() {
+        let mut log = Vec::new();
+        let mut arbiter = Arbiter::new(Named("a"), 10, Named("b"), 5);
+
+        // 3 full periods + 12 ms lands 2 ms into the second slot
+        assert_eq!(arbiter.tick(&mut log, 57), Ok(Slot::Second));
+        assert_eq!(log.last().map(String::as_str), Some("b 2"));
+    }
+
+    #[test]
+    fn test_zero_duration_slot_is_skipped() {
+        let mut log = Vec::new();
+        let mut arbiter = Arbiter::new(Named("a"), 0, Named("b"), 5);
+
+        assert_eq!(arbiter.tick(&mut log, 0), Ok(Slot::Second));
+        assert_eq!(arbiter.tick(&mut log, 4), Ok(Slot::Second));
+    }
+
+    #[test]
+    fn test_advance_to_absolute_timestamps() {
+        let mut log = Vec::new();
+        let mut arbiter = Arbiter::new(Named("a"), 100, Named("b"), 100);
+
+        assert_eq!(arbiter.advance_to(&mut log, 5_000), Ok(Slot::First));
+        // Irregular wakeups still land in the right slot
+        assert_eq!(arbiter.advance_to(&mut log, 5_130), Ok(Slot::Second));
+        assert_eq!(log.last().map(String::as_str), Some("b 30"));
+        assert_eq!(arbiter.advance_to(&mut log, 5_201), Ok(Slot::First));
+        assert_eq!(log.last().map(String::as_str), Some("a 1"));
+    }
+
+    #[test]
+    fn test_advance_with_clock() {
+        let mut log = Vec::new();
+        let mut arbiter = Arbiter::new(Named("a"), 100, Named("b"), 100);
+        let mut now = 0;
+        let mut clock = || {
+            now += 60;
+            now
+        };
+        assert_eq!(arbiter.advance_with(&mut log, &mut clock), Ok(Slot::First));
+        assert_eq!(arbiter.advance_with(&mut log, &mut clock), Ok(Slot::First));
+        assert_eq!(arbiter.advance_with(&mut log, &mut clock), Ok(Slot::Second));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut log = Vec::new();
+        let mut arbiter = Arbiter::new(Named("a"), 10, Named("b"), 10);
+        arbiter.tick(&mut log, 15).unwrap();
+        assert_eq!(arbiter.active(), Slot::Second);
+
+        arbiter.reset();
+        assert_eq!(arbiter.active(), Slot::First);
+        arbiter.tick(&mut log, 0).unwrap();
+        assert_eq!(log.last().map(String::as_str), Some("a 0"));
+    }
+}
@@ -0,0 +1,186 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+use crate::{Result, error::Error, registers::Intensity};
+
+/// Drives chain intensity from an ambient light sensor (LDR, ALS).
+///
+/// Readings, in whatever unit the sensor gives (lux or raw ADC counts), are
+/// smoothed, then mapped linearly from `dark` (minimum intensity) to
+/// `bright` (maximum intensity). `dark` may be the larger value, e.g. for
+/// an LDR whose reading drops as light increases. The intensity only changes once the
+/// smoothed reading has moved at least the hysteresis past the point where
+/// the new level starts, so a reading hovering on a boundary doesn't make
+/// the display flicker between two levels.
+#[derive(Debug, Clone)]
+pub struct AutoDimmer {
+    dark: u16,
+    bright: u16,
+    min: u8,
+    max: u8,
+    hysteresis: u16,
+    // Exponential average weight, 1 / 2^smoothing per new reading
+    smoothing: u8,
+    // Smoothed reading with 8 fractional bits
+    average: Option<u32>,
+    applied: Option<u8>,
+}
+
+impl AutoDimmer {
+    /// Map readings from `dark` to `bright` onto intensities 0-15, without
+    /// smoothing or hysteresis.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] with `max` 0 if `dark` equals `bright`,
+    ///   leaving no readings to map intensities onto.
+    pub fn new(dark: u16, bright: u16) -> Result<Self> {
+        if dark == bright {
+            return Err(Error::InvalidIntensity { value: 0, max: 0 });
+        }
+        Ok(Self {
+            dark,
+            bright,
+            min: Intensity::MIN.value(),
+            max: Intensity::MAX.value(),
+            hysteresis: 0,
+            smoothing: 0,
+            average: None,
+            applied: None,
+        })
+    }
+
+    /// Use `min` in the dark and `max` in bright light instead of the full
+    /// range.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `max` is above 15 or below `min`.
+    pub fn with_intensity_range(mut self, min: u8, max: u8) -> Result<Self> {
+        if min > max {
+            return Err(Error::InvalidIntensity { value: min, max });
+        }
+        Intensity::try_from(max)?;
+        self.min = min;
+        self.max = max;
+        Ok(self)
+    }
+
+    /// Reading units the smoothed value must move past a level boundary
+    /// before the intensity follows.
+    pub fn with_hysteresis(mut self, hysteresis: u16) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Average readings exponentially, each new one weighing 1/2^`shift`
+    /// (0 = no smoothing, capped at 8).
+    pub fn with_smoothing(mut self, shift: u8) -> Self {
+        self.smoothing = shift.min(8);
+        self
+    }
+
+    /// Intensity a reading maps to, before smoothing and hysteresis.
+    pub fn intensity_for(&self, reading: u16) -> u8 {
+        let span = u32::from(self.dark.abs_diff(self.bright));
+        let reading = reading.clamp(self.dark.min(self.bright), self.dark.max(self.bright));
+        let offset = u32::from(reading.abs_diff(self.dark));
+        let levels = u32::from(self.max - self.min);
+        self.min + ((offset * levels + span / 2) / span) as u8
+    }
+
+    /// Intensity written by the last update, `None` before the first.
+    pub fn intensity(&self) -> Option<u8> {
+        self.applied
+    }
+
+    /// Feed a new sensor reading and adjust every device's intensity if
+    /// needed. Returns whether it wrote.
+    pub fn update_ambient<SPI>(&mut self, driver: &mut Max7219<SPI>, reading: u16) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        let sample = u32::from(reading) << 8;
+        let average = match self.average {
+            Some(average) => average - (average >> self.smoothing) + (sample >> self.smoothing),
+            None => sample,
+        };
+        self.average = Some(average);
+        let smoothed = (average >> 8) as u16;
+
+        let target = self.intensity_for(smoothed);
+        // Levels at both ends of the hysteresis band around the reading
+        let lower = self.intensity_for(smoothed.saturating_sub(self.hysteresis));
+        let upper = self.intensity_for(smoothed.saturating_add(self.hysteresis));
+        let change = match self.applied {
+            None => true,
+            Some(current) if target > current => lower.min(upper) > current,
+            Some(current) if target < current => lower.max(upper) < current,
+            Some(_) => false,
+        };
+        if !change {
+            return Ok(false);
+        }
+        driver.set_intensity_all(target)?;
+        self.applied = Some(target);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::EmulatedSpi;
+
+    #[test]
+    fn test_mapping() {
+        let dimmer = AutoDimmer::new(100, 1600).unwrap();
+        assert_eq!(dimmer.intensity_for(0), 0);
+        assert_eq!(dimmer.intensity_for(850), 8);
+        assert_eq!(dimmer.intensity_for(5000), 15);
+
+        let dimmer = dimmer.with_intensity_range(2, 6).unwrap();
+        assert_eq!(dimmer.intensity_for(100), 2);
+        assert_eq!(dimmer.intensity_for(1600), 6);
+        assert!(AutoDimmer::new(10, 10).is_err());
+
+        // LDR reading falling with light
+        let inverted = AutoDimmer::new(4000, 1000).unwrap();
+        assert_eq!(inverted.intensity_for(4095), 0);
+        assert_eq!(inverted.intensity_for(1000), 15);
+        assert!(
+            AutoDimmer::new(0, 10)
+                .unwrap()
+                .with_intensity_range(3, 16)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        // One level per 100 reading units, boundaries at 50, 150, ...
+        let mut dimmer = AutoDimmer::new(0, 1500).unwrap().with_hysteresis(20);
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi);
+            assert_eq!(dimmer.update_ambient(&mut driver, 140), Ok(true));
+            assert_eq!(dimmer.intensity(), Some(1));
+            // Just past the boundary: not enough
+            assert_eq!(dimmer.update_ambient(&mut driver, 160), Ok(false));
+            assert_eq!(dimmer.update_ambient(&mut driver, 170), Ok(true));
+            assert_eq!(dimmer.intensity(), Some(2));
+            assert_eq!(dimmer.update_ambient(&mut driver, 140), Ok(false));
+            assert_eq!(dimmer.update_ambient(&mut driver, 129), Ok(true));
+        }
+        assert_eq!(spi.emulator().device(0).unwrap().intensity, 1);
+    }
+
+    #[test]
+    fn test_smoothing() {
+        let mut dimmer = AutoDimmer::new(0, 1500).unwrap().with_smoothing(1);
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi);
+        dimmer.update_ambient(&mut driver, 0).unwrap();
+        // A single spike only moves the average halfway
+        dimmer.update_ambient(&mut driver, 1500).unwrap();
+        assert_eq!(dimmer.intensity(), Some(8));
+    }
+}
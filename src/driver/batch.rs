@@ -0,0 +1,275 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+use crate::{
+    MAX_DISPLAYS, Result,
+    error::Error,
+    registers::{Intensity, Register},
+};
+
+/// Registers a device can be written, NoOp aside
+const DEPTH: usize = 13;
+
+/// Register writes recorded across devices and sent together by
+/// [`commit`](Self::commit), see [`Max7219::begin_batch`].
+///
+/// Frame n of the commit carries the n-th write of every device, so an
+/// update touching k registers of each device costs k transactions instead
+/// of one per write. Writing a register again replaces the recorded value
+/// and keeps its first position. Dropping the batch discards it.
+pub struct Batch<'a, SPI>
+where
+    SPI: SpiDevice,
+{
+    driver: &'a mut Max7219<SPI>,
+    ops: [[(Register, u8); DEPTH]; MAX_DISPLAYS],
+    lens: [usize; MAX_DISPLAYS],
+    // Nominal intensities to record in the driver once committed
+    intensities: [Option<u8>; MAX_DISPLAYS],
+}
+
+impl<SPI> Batch<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Number of transactions [`commit`](Self::commit) will send.
+    pub fn frame_count(&self) -> usize {
+        self.lens.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frame_count() == 0
+    }
+
+    /// Record [`Max7219::set_intensity`], calibration applied.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `intensity` is above 15.
+    /// - [`Error::InvalidDeviceIndex`] if the device is not on the chain.
+    pub fn set_intensity(&mut self, device_index: usize, intensity: u8) -> Result<()> {
+        let intensity = Intensity::try_from(intensity)?.value();
+        self.check_device(device_index)?;
+        let register = self.driver.calibrated(device_index, intensity);
+        self.push(device_index, Register::Intensity, register);
+        self.intensities[device_index] = Some(intensity);
+        Ok(())
+    }
+
+    /// Record [`Max7219::write_raw_digit`].
+    ///
+    /// # Errors
+    /// Same as [`Max7219::write_raw_digit`], checked against the driver's
+    /// state when recording.
+    pub fn write_raw_digit(&mut self, device_index: usize, digit: u8, value: u8) -> Result<()> {
+        let digit_register = Register::try_digit(digit)?;
+        self.check_device(device_index)?;
+        if !self.driver.is_scanned(device_index, digit) {
+            return Err(Error::DigitNotScanned);
+        }
+        if self.driver.is_decoded(device_index, digit) {
+            return Err(Error::DecodeModeMismatch);
+        }
+        self.push(device_index, digit_register, value);
+        Ok(())
+    }
+
+    /// Record [`Max7219::write_decoded_digit`].
+    ///
+    /// # Errors
+    /// Same as [`Max7219::write_decoded_digit`], checked against the
+    /// driver's state when recording.
+    pub fn write_decoded_digit(&mut self, device_index: usize, digit: u8, value: u8) -> Result<()> {
+        let digit_register = Register::try_digit(digit)?;
+        self.check_device(device_index)?;
+        if !self.driver.is_scanned(device_index, digit) {
+            return Err(Error::DigitNotScanned);
+        }
+        if !self.driver.is_decoded(device_index, digit) {
+            return Err(Error::DecodeModeMismatch);
+        }
+        self.push(device_index, digit_register, value);
+        Ok(())
+    }
+
+    /// Record [`Max7219::write_row`].
+    pub fn write_row(&mut self, device_index: usize, row: u8, value: u8) -> Result<()> {
+        self.write_raw_digit(device_index, row, value)
+    }
+
+    /// Record turning a device on or putting it in shutdown.
+    pub fn set_power(&mut self, device_index: usize, on: bool) -> Result<()> {
+        self.check_device(device_index)?;
+        self.push(device_index, Register::Shutdown, u8::from(on));
+        Ok(())
+    }
+
+    /// Run `f` once per device on the chain to record its writes, stopping
+    /// at the first error. Nothing is sent before [`commit`](Self::commit).
+    pub fn for_each_device<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, usize) -> Result<()>,
+    {
+        for device_index in 0..self.driver.device_count() {
+            f(self, device_index)?;
+        }
+        Ok(())
+    }
+
+    /// Send the recorded writes in as few transactions as possible.
+    ///
+    /// # Errors
+    /// - Any SPI error; writes in earlier frames have been applied then,
+    ///   and the driver keeps its previous intensities.
+    pub fn commit(self) -> Result<()> {
+        let device_count = self.driver.device_count();
+        for frame in 0..self.frame_count() {
+            let mut ops = [(Register::NoOp, 0); MAX_DISPLAYS];
+            for ((op, queue), &len) in ops.iter_mut().zip(&self.ops).zip(&self.lens) {
+                if frame < len {
+                    *op = queue[frame];
+                }
+            }
+            self.driver.write_all_registers(&ops[..device_count])?;
+        }
+        for (device_index, intensity) in self.intensities.iter().enumerate() {
+            if let Some(intensity) = *intensity {
+                self.driver.record_intensity(device_index, intensity);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_device(&self, device_index: usize) -> Result<()> {
+        if device_index >= self.driver.device_count() {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.driver.device_count(),
+            });
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, device_index: usize, register: Register, data: u8) {
+        let len = self.lens[device_index];
+        let queue = &mut self.ops[device_index];
+        match queue[..len]
+            .iter_mut()
+            .find(|(queued, _)| *queued == register)
+        {
+            Some(op) => op.1 = data,
+            None => {
+                queue[len] = (register, data);
+                self.lens[device_index] += 1;
+            }
+        }
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Start recording register writes to send with
+    /// [`Batch::commit`], e.g. a new intensity plus several digits on each
+    /// device in a handful of transactions.
+    pub fn begin_batch(&mut self) -> Batch<'_, SPI> {
+        Batch {
+            driver: self,
+            ops: [[(Register::NoOp, 0); DEPTH]; MAX_DISPLAYS],
+            lens: [0; MAX_DISPLAYS],
+            intensities: [None; MAX_DISPLAYS],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{host::EmulatedSpi, registers::DecodeMode};
+
+    #[test]
+    fn test_commit_interleaves_devices() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .with_device_count(3)
+                .unwrap()
+                .with_calibration(&[0, 0, -1])
+                .unwrap();
+            let mut batch = driver.begin_batch();
+            for device_index in 0..3 {
+                batch.set_intensity(device_index, 8).unwrap();
+                batch.write_row(device_index, 0, 0x18).unwrap();
+            }
+            batch.write_row(1, 1, 0x24).unwrap();
+            batch.write_row(1, 2, 0x42).unwrap();
+            // Replaces the first value, no extra frame
+            batch.write_row(0, 0, 0x81).unwrap();
+            assert_eq!(batch.frame_count(), 4);
+            batch.commit().unwrap();
+            assert_eq!(driver.intensity(2), Ok(8));
+        }
+        assert_eq!(spi.transactions(), 4);
+
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap()[0], 0x81);
+        assert_eq!(emulator.digits(1).unwrap()[..3], [0x18, 0x24, 0x42]);
+        assert_eq!(emulator.device(2).unwrap().intensity, 7);
+    }
+
+    #[test]
+    fn test_for_each_device() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+            let mut batch = driver.begin_batch();
+            batch
+                .for_each_device(|batch, device_index| {
+                    batch.set_intensity(device_index, 2)?;
+                    batch.write_row(device_index, 7, 0xF0)
+                })
+                .unwrap();
+            assert_eq!(batch.frame_count(), 2);
+            batch.commit().unwrap();
+        }
+        assert_eq!(spi.transactions(), 2);
+        for device_index in 0..4 {
+            assert_eq!(spi.emulator().digits(device_index).unwrap()[7], 0xF0);
+            assert_eq!(spi.emulator().device(device_index).unwrap().intensity, 2);
+        }
+    }
+
+    #[test]
+    fn test_record_checks() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi);
+            driver.set_decode_mode_all(DecodeMode::Digit0).unwrap();
+
+            let mut batch = driver.begin_batch();
+            assert_eq!(
+                batch.set_intensity(0, 16),
+                Err(Error::InvalidIntensity { value: 16, max: 15 })
+            );
+            assert_eq!(
+                batch.set_power(1, true),
+                Err(Error::InvalidDeviceIndex {
+                    index: 1,
+                    device_count: 1
+                })
+            );
+            assert_eq!(
+                batch.write_raw_digit(0, 0, 0xFF),
+                Err(Error::DecodeModeMismatch)
+            );
+            batch.write_decoded_digit(0, 0, 0x05).unwrap();
+            batch.set_intensity(0, 3).unwrap();
+
+            // Never committed, so nothing is sent
+            assert_eq!(driver.intensity(0), Ok(0));
+            assert!(driver.begin_batch().is_empty());
+        }
+        // Only the decode mode write
+        assert_eq!(spi.transactions(), 1);
+    }
+}
@@ -0,0 +1,159 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+use crate::{MAX_DISPLAYS, Result, error::Error, registers::Register};
+
+/// Blinks whole devices by toggling their Shutdown register.
+///
+/// The chip keeps its digit registers while shut down, so content comes
+/// back without being rewritten: each [`tick`](Self::tick) is a single
+/// chain transaction however much is shown. Devices that don't blink are
+/// sent no-ops and left alone.
+#[derive(Debug, Clone)]
+pub struct Blinker {
+    // bit n set = device n blinks
+    mask: u8,
+    visible: bool,
+}
+
+impl Blinker {
+    pub fn new() -> Self {
+        Self {
+            mask: 0,
+            visible: true,
+        }
+    }
+
+    /// Start or stop blinking a device. A device taken out while blanked
+    /// stays off until [`stop`](Self::stop) or a power on.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceIndex`] if `device_index` is not below
+    ///   [`MAX_DISPLAYS`].
+    pub fn set_blinking(&mut self, device_index: usize, enabled: bool) -> Result<()> {
+        if device_index >= MAX_DISPLAYS {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: MAX_DISPLAYS,
+            });
+        }
+        if enabled {
+            self.mask |= 1 << device_index;
+        } else {
+            self.mask &= !(1 << device_index);
+        }
+        Ok(())
+    }
+
+    /// Blink every device of a chain of `device_count`.
+    pub fn blink_all(&mut self, device_count: usize) {
+        self.mask = ((1u16 << device_count.min(MAX_DISPLAYS)) - 1) as u8;
+    }
+
+    pub fn is_blinking(&self, device_index: usize) -> bool {
+        device_index < MAX_DISPLAYS && self.mask & (1 << device_index) != 0
+    }
+
+    /// Whether blinking devices are currently on.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Toggle the blinking devices between shutdown and normal operation.
+    ///
+    /// Call this at the blink rate, e.g. every 500 ms. Returns whether the
+    /// blinking devices are now on. Devices past the chain are ignored.
+    pub fn tick<SPI>(&mut self, driver: &mut Max7219<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        self.write(driver, !self.visible)?;
+        self.visible = !self.visible;
+        Ok(self.visible)
+    }
+
+    /// Turn the blinking devices back on and stop blinking them.
+    pub fn stop<SPI>(&mut self, driver: &mut Max7219<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        self.write(driver, true)?;
+        self.visible = true;
+        self.mask = 0;
+        Ok(())
+    }
+
+    fn write<SPI>(&self, driver: &mut Max7219<SPI>, on: bool) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let mut ops = [(Register::NoOp, 0); MAX_DISPLAYS];
+        for (device_index, op) in ops.iter_mut().enumerate() {
+            if self.is_blinking(device_index) {
+                *op = (Register::Shutdown, u8::from(on));
+            }
+        }
+        driver.write_all_registers(&ops[..driver.device_count()])
+    }
+}
+
+impl Default for Blinker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::EmulatedSpi;
+
+    #[test]
+    fn test_set_blinking() {
+        let mut blinker = Blinker::new();
+        assert_eq!(
+            blinker.set_blinking(MAX_DISPLAYS, true),
+            Err(Error::InvalidDeviceIndex {
+                index: MAX_DISPLAYS,
+                device_count: MAX_DISPLAYS
+            })
+        );
+        blinker.blink_all(3);
+        assert!(blinker.is_blinking(2));
+        assert!(!blinker.is_blinking(3));
+        blinker.set_blinking(2, false).unwrap();
+        assert!(!blinker.is_blinking(2));
+    }
+
+    #[test]
+    fn test_tick_toggles_shutdown() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut blinker = Blinker::new();
+        blinker.set_blinking(1, true).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.init().unwrap();
+            driver.fill(1, 0xAA).unwrap();
+            assert_eq!(blinker.tick(&mut driver), Ok(false));
+            assert_eq!(blinker.tick(&mut driver), Ok(true));
+            assert_eq!(blinker.tick(&mut driver), Ok(false));
+        }
+        {
+            let emulator = spi.emulator();
+            assert!(!emulator.device(0).unwrap().shutdown);
+            assert!(emulator.device(1).unwrap().shutdown);
+            // Content survives shutdown
+            assert_eq!(emulator.digits(1).unwrap(), [0xAA; 8]);
+        }
+
+        let before = spi.transactions();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            blinker.stop(&mut driver).unwrap();
+        }
+        assert!(!blinker.is_blinking(1));
+        assert!(blinker.is_visible());
+        assert_eq!(spi.transactions(), before + 1);
+        assert!(!spi.emulator().device(1).unwrap().shutdown);
+    }
+}
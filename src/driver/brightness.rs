@@ -0,0 +1,36 @@
+/// Lowest perceptual brightness for each hardware intensity step 1-15.
+///
+/// The Intensity register sets the LED duty cycle to (2n + 1)/32, which
+/// looks much brighter than linear at the low end. Each entry is the first
+/// brightness whose gamma 2.2 duty cycle is closest to that step.
+const GAMMA_THRESHOLDS: [u8; 15] = [
+    73, 100, 120, 136, 151, 164, 176, 187, 197, 206, 216, 224, 233, 240, 248,
+];
+
+/// Hardware intensity step (0-15) closest to a perceptual brightness
+/// (0-255, gamma 2.2).
+///
+/// Brightness 0 maps to the dimmest step rather than off; use
+/// [`Max7219::power_off`](super::Max7219::power_off) to blank the chain.
+pub const fn brightness_to_intensity(brightness: u8) -> u8 {
+    let mut intensity = 0;
+    while intensity < GAMMA_THRESHOLDS.len() && brightness >= GAMMA_THRESHOLDS[intensity] {
+        intensity += 1;
+    }
+    intensity as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brightness_to_intensity() {
+        assert_eq!(brightness_to_intensity(0), 0);
+        assert_eq!(brightness_to_intensity(72), 0);
+        assert_eq!(brightness_to_intensity(73), 1);
+        assert_eq!(brightness_to_intensity(128), 3);
+        assert_eq!(brightness_to_intensity(247), 14);
+        assert_eq!(brightness_to_intensity(255), 15);
+    }
+}
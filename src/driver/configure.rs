@@ -0,0 +1,152 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+use crate::{
+    Result,
+    registers::{DecodeMode, Intensity},
+};
+
+/// Chainable view of the driver's configuration calls, see
+/// [`Max7219::configure`].
+///
+/// Each method forwards to the driver method of the same name and returns
+/// the view again, so bring-up code reads as one expression:
+/// `driver.configure().init()?.set_intensity_all(4)?.power_on()?;`
+pub struct Configure<'a, SPI>
+where
+    SPI: SpiDevice,
+{
+    driver: &'a mut Max7219<SPI>,
+}
+
+impl<SPI> Configure<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    /// The driver being configured, for calls without a chainable form.
+    pub fn driver(&mut self) -> &mut Max7219<SPI> {
+        self.driver
+    }
+
+    pub fn init(&mut self) -> Result<&mut Self> {
+        self.driver.init()?;
+        Ok(self)
+    }
+
+    pub fn power_on(&mut self) -> Result<&mut Self> {
+        self.driver.power_on()?;
+        Ok(self)
+    }
+
+    pub fn power_off(&mut self) -> Result<&mut Self> {
+        self.driver.power_off()?;
+        Ok(self)
+    }
+
+    pub fn test_all(&mut self, enable: bool) -> Result<&mut Self> {
+        self.driver.test_all(enable)?;
+        Ok(self)
+    }
+
+    pub fn clear_all(&mut self) -> Result<&mut Self> {
+        self.driver.clear_all()?;
+        Ok(self)
+    }
+
+    pub fn set_intensity(&mut self, device_index: usize, intensity: u8) -> Result<&mut Self> {
+        self.driver.set_intensity(device_index, intensity)?;
+        Ok(self)
+    }
+
+    pub fn set_intensity_all(&mut self, intensity: u8) -> Result<&mut Self> {
+        self.driver.set_intensity_all(intensity)?;
+        Ok(self)
+    }
+
+    pub fn set_intensity_level_all(&mut self, intensity: Intensity) -> Result<&mut Self> {
+        self.driver.set_intensity_level_all(intensity)?;
+        Ok(self)
+    }
+
+    pub fn set_brightness_all(&mut self, brightness: u8) -> Result<&mut Self> {
+        self.driver.set_brightness_all(brightness)?;
+        Ok(self)
+    }
+
+    pub fn set_device_scan_limit(&mut self, device_index: usize, limit: u8) -> Result<&mut Self> {
+        self.driver.set_device_scan_limit(device_index, limit)?;
+        Ok(self)
+    }
+
+    pub fn set_scan_limit_all(&mut self, limit: u8) -> Result<&mut Self> {
+        self.driver.set_scan_limit_all(limit)?;
+        Ok(self)
+    }
+
+    pub fn set_device_decode_mode(
+        &mut self,
+        device_index: usize,
+        mode: DecodeMode,
+    ) -> Result<&mut Self> {
+        self.driver.set_device_decode_mode(device_index, mode)?;
+        Ok(self)
+    }
+
+    pub fn set_decode_mode_all(&mut self, mode: DecodeMode) -> Result<&mut Self> {
+        self.driver.set_decode_mode_all(mode)?;
+        Ok(self)
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Borrow the driver as a [`Configure`] view whose setters can be
+    /// chained with `?`.
+    pub fn configure(&mut self) -> Configure<'_, SPI> {
+        Configure { driver: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, host::EmulatedSpi};
+
+    #[test]
+    fn test_chained_configuration() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver
+                .configure()
+                .init()
+                .unwrap()
+                .set_scan_limit_all(4)
+                .unwrap()
+                .set_device_decode_mode(1, DecodeMode::Digits0To3)
+                .unwrap()
+                .set_intensity_all(6)
+                .unwrap()
+                .power_on()
+                .unwrap();
+            assert_eq!(driver.scan_limit(0), Ok(4));
+            assert_eq!(driver.decode_mask(1), Ok(0x0F));
+
+            // The first failing call ends the chain
+            let mut configure = driver.configure();
+            assert_eq!(
+                configure
+                    .set_intensity_all(16)
+                    .and_then(|c| c.power_off())
+                    .err(),
+                Some(Error::InvalidIntensity { value: 16, max: 15 })
+            );
+            assert_eq!(configure.driver().intensity(0), Ok(6));
+        }
+        let emulator = spi.emulator();
+        assert!(!emulator.device(0).unwrap().shutdown);
+        assert_eq!(emulator.device(1).unwrap().intensity, 6);
+    }
+}
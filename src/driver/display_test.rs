@@ -0,0 +1,102 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+use crate::Result;
+
+/// Keeps every device in display-test mode (all LEDs on at full current)
+/// while alive, see [`Max7219::display_test_guard`].
+///
+/// Dropping the guard turns test mode off again. A failed SPI write during
+/// drop can't be reported and leaves the panel lit; call
+/// [`finish`](Self::finish) instead to get the error and retry.
+pub struct DisplayTestGuard<'a, SPI>
+where
+    SPI: SpiDevice,
+{
+    driver: &'a mut Max7219<SPI>,
+    finished: bool,
+}
+
+impl<SPI> DisplayTestGuard<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Turn test mode off, reporting a failed write.
+    ///
+    /// The guard is consumed either way; on error, retry with
+    /// [`Max7219::test_all`].
+    pub fn finish(mut self) -> Result<()> {
+        self.finished = true;
+        self.driver.test_all(false)
+    }
+}
+
+impl<SPI> Drop for DisplayTestGuard<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    fn drop(&mut self) {
+        if !self.finished {
+            // Nothing to report to from drop, see the type docs
+            let _ = self.driver.test_all(false);
+        }
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Put every device in display-test mode until the returned guard is
+    /// dropped or [finished](DisplayTestGuard::finish), so test mode can't
+    /// be left on by an early return.
+    pub fn display_test_guard(&mut self) -> Result<DisplayTestGuard<'_, SPI>> {
+        self.test_all(true)?;
+        Ok(DisplayTestGuard {
+            driver: self,
+            finished: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
+
+    fn display_test(data: u8) -> [Transaction<u8>; 3] {
+        [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::DisplayTest.addr(), data]),
+            Transaction::transaction_end(),
+        ]
+    }
+
+    #[test]
+    fn test_guard_disables_on_drop() {
+        let expected: Vec<_> = display_test(0x01)
+            .into_iter()
+            .chain(display_test(0x00))
+            .collect();
+        let mut spi = SpiMock::new(&expected);
+        let mut driver = Max7219::new(&mut spi);
+        {
+            let _guard = driver.display_test_guard().unwrap();
+        }
+        spi.done();
+    }
+
+    #[test]
+    fn test_finish_writes_once() {
+        let expected: Vec<_> = display_test(0x01)
+            .into_iter()
+            .chain(display_test(0x00))
+            .collect();
+        let mut spi = SpiMock::new(&expected);
+        let mut driver = Max7219::new(&mut spi);
+        let guard = driver.display_test_guard().unwrap();
+        guard.finish().unwrap();
+        spi.done();
+    }
+}
@@ -0,0 +1,133 @@
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use embedded_hal_02::{blocking::spi::Write, digital::v2::OutputPin};
+
+use super::Max7219;
+
+/// [`Max7219`] on an `embedded-hal` 0.2 bus and chip-select pin.
+pub type Max7219Eh02<SPI, CS> = Max7219<Eh02Device<SPI, CS>>;
+
+/// `embedded-hal` 1.0 [`SpiDevice`] over an `embedded-hal` 0.2 blocking SPI
+/// bus and a chip-select pin, for HALs that don't implement 1.0 yet.
+///
+/// Only supports the writes the driver makes: every transaction pulls CS
+/// low, writes its buffers and releases CS again. Reads, transfers and
+/// delays fail with [`ErrorKind::Other`].
+#[derive(Debug)]
+pub struct Eh02Device<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS> Eh02Device<SPI, CS>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        Self { spi, cs }
+    }
+
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+}
+
+impl<SPI, CS> ErrorType for Eh02Device<SPI, CS> {
+    type Error = ErrorKind;
+}
+
+impl<SPI, CS> SpiDevice for Eh02Device<SPI, CS>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> {
+        self.cs.set_low().map_err(|_| ErrorKind::ChipSelectFault)?;
+        let result = operations.iter().try_for_each(|operation| match operation {
+            Operation::Write(words) => self.spi.write(words).map_err(|_| ErrorKind::Other),
+            _ => Err(ErrorKind::Other),
+        });
+        // Release CS even after a failed write
+        let released = self.cs.set_high().map_err(|_| ErrorKind::ChipSelectFault);
+        result.and(released)
+    }
+}
+
+impl<SPI, CS> Max7219<Eh02Device<SPI, CS>>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    /// Create a driver for an `embedded-hal` 0.2 bus, see [`Eh02Device`].
+    pub fn new_eh02(spi: SPI, cs: CS) -> Self {
+        Self::new(Eh02Device::new(spi, cs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::host::Emulator;
+
+    /// Records the bytes written between CS edges, like an eh 0.2 HAL.
+    #[derive(Default)]
+    struct Bus {
+        selected: bool,
+        pending: Vec<u8>,
+        frames: Vec<Vec<u8>>,
+    }
+
+    struct Spi<'a>(&'a RefCell<Bus>);
+    struct Cs<'a>(&'a RefCell<Bus>);
+
+    impl Write<u8> for Spi<'_> {
+        type Error = ();
+
+        fn write(&mut self, words: &[u8]) -> Result<(), ()> {
+            let mut bus = self.0.borrow_mut();
+            assert!(bus.selected);
+            bus.pending.extend_from_slice(words);
+            Ok(())
+        }
+    }
+
+    impl OutputPin for Cs<'_> {
+        type Error = ();
+
+        fn set_low(&mut self) -> Result<(), ()> {
+            self.0.borrow_mut().selected = true;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), ()> {
+            let mut bus = self.0.borrow_mut();
+            bus.selected = false;
+            let frame = core::mem::take(&mut bus.pending);
+            bus.frames.push(frame);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_eh02_bus() {
+        let bus = RefCell::new(Bus::default());
+        let mut driver = Max7219::new_eh02(Spi(&bus), Cs(&bus))
+            .with_device_count(2)
+            .unwrap();
+        driver.init().unwrap();
+        driver.write_row(1, 3, 0xA5).unwrap();
+        driver.set_intensity(0, 6).unwrap();
+
+        let mut emulator = Emulator::new(2).unwrap();
+        for frame in &bus.borrow().frames {
+            emulator.write(frame);
+        }
+        assert!(!emulator.device(1).unwrap().shutdown);
+        assert_eq!(emulator.digits(1).unwrap()[3], 0xA5);
+        assert_eq!(emulator.device(0).unwrap().intensity, 6);
+        assert!(!bus.borrow().selected);
+    }
+}
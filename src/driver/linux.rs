@@ -0,0 +1,58 @@
+use std::{io, path::Path};
+
+use linux_embedded_hal::{
+    SpidevDevice,
+    spidev::{SpiModeFlags, Spidev, SpidevOptions},
+};
+
+use super::Max7219;
+
+/// [`Max7219`] on a Linux `spidev` device, e.g. a Raspberry Pi's SPI0.
+pub type Max7219Linux = Max7219<SpidevDevice>;
+
+/// SPI clock [`Max7219::open_spidev`] callers can start from; the MAX7219
+/// is specified up to 10 MHz, long jumper wires to a chain often need less.
+pub const SPIDEV_SPEED_HZ: u32 = 1_000_000;
+
+impl Max7219<SpidevDevice> {
+    /// Open `path` (e.g. `/dev/spidev0.0`) and configure it for the chain:
+    /// SPI mode 0, 8-bit words, clocked at `speed_hz`. The kernel driver
+    /// toggles the chip-select line of the device around every transfer.
+    ///
+    /// ```no_run
+    /// use max7219_driver_project::driver::{Max7219, SPIDEV_SPEED_HZ};
+    ///
+    /// let mut driver = Max7219::open_spidev("/dev/spidev0.0", SPIDEV_SPEED_HZ)?
+    ///     .with_device_count(4)
+    ///     .unwrap();
+    /// driver.init().unwrap();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the I/O error if the device can't be opened or configured.
+    pub fn open_spidev(path: impl AsRef<Path>, speed_hz: u32) -> io::Result<Self> {
+        let mut spidev = Spidev::open(path)?;
+        let options = SpidevOptions::new()
+            .bits_per_word(8)
+            .max_speed_hz(speed_hz)
+            .mode(SpiModeFlags::SPI_MODE_0)
+            .build();
+        spidev.configure(&options)?;
+        Ok(Self::new(SpidevDevice(spidev)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_missing_device() {
+        let result = Max7219::open_spidev("/dev/spidev-missing", SPIDEV_SPEED_HZ);
+        assert_eq!(
+            result.err().map(|err| err.kind()),
+            Some(io::ErrorKind::NotFound)
+        );
+    }
+}
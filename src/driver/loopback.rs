@@ -0,0 +1,46 @@
+use crate::{Result, encoder::MAX_FRAME_SIZE, error::Error};
+
+/// What the chain's shift registers should hold, for checking the bytes
+/// read back from the last device's DOUT when it is wired to MISO.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Loopback {
+    enabled: bool,
+    // Last frame sent, `None` until one was or after a failed transfer
+    expected: Option<[u8; MAX_FRAME_SIZE]>,
+}
+
+impl Loopback {
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.expected = None;
+    }
+
+    /// Forget the expected contents, e.g. when the chain length changes.
+    pub(crate) fn reset(&mut self) {
+        self.expected = None;
+    }
+
+    /// Compare the bytes shifted out while sending `frame` against the
+    /// previous frame, then expect `frame` back next time.
+    pub(crate) fn verify(&mut self, read: &[u8], frame: &[u8]) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let matches = match &self.expected {
+            Some(expected) => expected.get(..read.len()) == Some(read),
+            None => true,
+        };
+        let mut sent = [0; MAX_FRAME_SIZE];
+        let frame_len = frame.len().min(MAX_FRAME_SIZE);
+        sent[..frame_len].copy_from_slice(&frame[..frame_len]);
+        self.expected = Some(sent);
+        if !matches {
+            return Err(Error::LoopbackMismatch);
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,98 @@
-use embedded_hal::spi::SpiDevice;
+use core::fmt;
 
+use embedded_hal::{
+    delay::DelayNs,
+    spi::{Operation, SpiDevice},
+};
+
+use super::{BusStats, Variant, brightness_to_intensity, loopback::Loopback};
+#[cfg(feature = "matrix")]
+use crate::matrix::Canvas;
 use crate::{
     MAX_DISPLAYS, NUM_DIGITS, Result,
+    easing::Easing,
+    encoder::{self, MAX_FRAME_SIZE, MAX_TILES_SIZE},
     error::Error,
-    registers::{DecodeMode, Register},
+    registers::{CodeBChar, DecodeMode, Intensity, Register},
 };
 
-/// Driver for the MAX7219 LED display controller.
+/// Code B character that lights no segments
+const CODE_B_BLANK: u8 = 0x0F;
+
+/// Driver for the MAX7219 LED display controller and the MAX7221, see [`Variant`].
 /// Communicates over SPI using the embedded-hal `SpiDevice` trait.
 pub struct Max7219<SPI> {
     spi: SPI,
-    buffer: [u8; MAX_DISPLAYS * 2],
+    buffer: [u8; MAX_FRAME_SIZE],
+    // Device whose packet is the only one in `buffer` that isn't a NoOp, if
+    // the last frame was a single-device write
+    single_packet: Option<usize>,
     device_count: usize,
+    // Shadow of each device's DecodeMode register, bit n = DIGn decoded
+    decode_masks: [u8; MAX_DISPLAYS],
+    // Shadow of each device's Shutdown register, `true` once powered on
+    powered: [bool; MAX_DISPLAYS],
+    // Shadow of each device's scan limit as a digit count (1-8)
+    scan_limits: [u8; MAX_DISPLAYS],
+    // Intensity each device was last set to, before calibration
+    intensities: [u8; MAX_DISPLAYS],
+    // Per-device offsets added to every intensity written
+    calibrations: [i8; MAX_DISPLAYS],
+    // Replaces the built-in gamma curve of the brightness methods
+    brightness_lut: Option<&'static [u8; 256]>,
+    // Timing of the steps of `fade_to` and `fade_all_to`
+    fade_easing: Easing,
+    // Upper bound on every intensity register, set by the matrix current limit
+    intensity_cap: Option<u8>,
+    stats: BusStats,
+    loopback: Loopback,
+    // Wait after selecting the chain, before clocking a frame in
+    settle_ns: u32,
+    variant: Variant,
+    // Set once `init` has configured the chain
+    initialized: bool,
+}
+
+/// Shows the chain configuration from the driver's shadow registers, one
+/// entry per device: power, nominal intensity, scan limit and decode mask.
+impl<SPI> fmt::Debug for Max7219<SPI> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let devices = DeviceConfigs {
+            powered: &self.powered[..self.device_count],
+            intensities: &self.intensities[..self.device_count],
+            scan_limits: &self.scan_limits[..self.device_count],
+            decode_masks: &self.decode_masks[..self.device_count],
+        };
+        f.debug_struct("Max7219")
+            .field("variant", &self.variant)
+            .field("device_count", &self.device_count)
+            .field("devices", &devices)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+struct DeviceConfigs<'a> {
+    powered: &'a [bool],
+    intensities: &'a [u8],
+    scan_limits: &'a [u8],
+    decode_masks: &'a [u8],
+}
+
+impl fmt::Debug for DeviceConfigs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for device_index in 0..self.powered.len() {
+            list.entry(&format_args!(
+                "{{ powered: {}, intensity: {}, scan_limit: {}, decode_mask: {:#04x} }}",
+                self.powered[device_index],
+                self.intensities[device_index],
+                self.scan_limits[device_index],
+                self.decode_masks[device_index]
+            ));
+        }
+        list.finish()
+    }
 }
 
 impl<SPI> Max7219<SPI>
@@ -22,22 +103,146 @@ where
         Self {
             spi,
             device_count: 1, // Default to 1, use with_device_count to increase count
-            buffer: [0; MAX_DISPLAYS * 2],
+            buffer: [0; MAX_FRAME_SIZE],
+            single_packet: None,
+            decode_masks: [0; MAX_DISPLAYS],
+            powered: [false; MAX_DISPLAYS],
+            scan_limits: [NUM_DIGITS; MAX_DISPLAYS],
+            intensities: [0; MAX_DISPLAYS],
+            calibrations: [0; MAX_DISPLAYS],
+            brightness_lut: None,
+            fade_easing: Easing::Linear,
+            intensity_cap: None,
+            stats: BusStats::default(),
+            loopback: Loopback::default(),
+            settle_ns: 0,
+            variant: Variant::default(),
+            initialized: false,
         }
     }
     pub fn device_count(&self) -> usize {
         self.device_count
     }
 
+    /// Run `f` once per device on the chain, in index order, stopping at
+    /// the first error. See [`Batch::for_each_device`](super::Batch::for_each_device)
+    /// to pack the writes into chain-wide transactions instead.
+    pub fn for_each_device<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, usize) -> Result<()>,
+    {
+        for device_index in 0..self.device_count {
+            f(self, device_index)?;
+        }
+        Ok(())
+    }
+
+    /// Number of daisy-chained devices, 1 to [`MAX_DISPLAYS`].
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if `count` is 0 or above
+    ///   [`MAX_DISPLAYS`].
     pub fn with_device_count(mut self, count: usize) -> Result<Self> {
-        if count > MAX_DISPLAYS {
+        if !(1..=MAX_DISPLAYS).contains(&count) {
             return Err(Error::InvalidDeviceCount);
         }
         self.device_count = count;
+        self.single_packet = None;
+        self.loopback.reset();
         Ok(self)
     }
 
+    /// Part the chain is built from, [`Variant::Max7219`] by default.
+    /// Only recorded for diagnostics: both parts are driven the same way.
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Check every frame against the bytes read back from the last
+    /// device's DOUT, for boards wiring it to MISO. See
+    /// [`set_loopback_verify`](Self::set_loopback_verify).
+    pub fn with_loopback_verify(mut self, enabled: bool) -> Self {
+        self.set_loopback_verify(enabled);
+        self
+    }
+
+    /// Send frames with `SpiDevice::transfer` instead of `write` and check
+    /// that the bytes shifted out of the chain are the previous frame's, as
+    /// each device passes on the packet it held. A broken link or a bit
+    /// error then fails the next write with [`Error::LoopbackMismatch`].
+    ///
+    /// The first frame after enabling, after a failed transfer or after
+    /// changing the device count is not checked. A MAX7219 chain on a bus
+    /// shared with other devices also clocks in their traffic, so only
+    /// enable this if [`Variant::can_share_bus`] or the bus is exclusive. Frames still latch on a
+    /// mismatch, so redraw once the wiring is fixed.
+    pub fn set_loopback_verify(&mut self, enabled: bool) {
+        self.loopback.set_enabled(enabled);
+    }
+
+    pub fn loopback_verify(&self) -> bool {
+        self.loopback.enabled()
+    }
+
+    /// Wait `ns` nanoseconds after selecting the chain before clocking each
+    /// frame in. See [`set_settle_delay_ns`](Self::set_settle_delay_ns).
+    pub fn with_settle_delay_ns(mut self, ns: u32) -> Self {
+        self.settle_ns = ns;
+        self
+    }
+
+    /// Give long or noisy chains time to settle between frames: every
+    /// frame becomes a `SpiDevice` transaction that starts with an
+    /// `Operation::DelayNs(ns)`, so the lines rest at least `ns` after the
+    /// previous frame's chip-select release. 0, the default, disables it.
+    ///
+    /// The wait is done by the `SpiDevice` implementation, which has to
+    /// support delays; the `eh02` feature's `Eh02Device` doesn't.
+    pub fn set_settle_delay_ns(&mut self, ns: u32) {
+        self.settle_ns = ns;
+    }
+
+    pub fn settle_delay_ns(&self) -> u32 {
+        self.settle_ns
+    }
+
+    /// Count the devices on a chain whose last DOUT is wired to MISO, e.g.
+    /// to pass to [`with_device_count`](Self::with_device_count) when the
+    /// number of plugged in modules varies.
+    ///
+    /// Clears the chain's shift registers with NoOps, then clocks a NoOp
+    /// marker packet through in the same transfer: it comes back one packet
+    /// per device later. Only NoOps are latched, so the display is left as is.
+    ///
+    /// # Errors
+    /// - [`Error::LoopbackMismatch`] if the marker doesn't come back within
+    ///   [`MAX_DISPLAYS`] packets, e.g. because DOUT isn't wired.
+    /// - Returns an SPI error if the transfer fails.
+    pub fn detect_device_count(&mut self) -> Result<usize> {
+        const MARKER: [u8; encoder::PACKET_SIZE] = encoder::packet(Register::NoOp, 0xA5);
+        const PROBE_SIZE: usize = (2 * MAX_DISPLAYS + 1) * encoder::PACKET_SIZE;
+
+        let mut probe = [0; PROBE_SIZE];
+        probe[MAX_FRAME_SIZE..][..encoder::PACKET_SIZE].copy_from_slice(&MARKER);
+        let mut read = [0; PROBE_SIZE];
+        self.loopback.reset();
+        let result = self.spi.transfer(&mut read, &probe);
+        self.stats.record(PROBE_SIZE, result.is_ok());
+        result?;
+
+        read.chunks_exact(encoder::PACKET_SIZE)
+            .skip(MAX_DISPLAYS)
+            .position(|packet| packet == MARKER)
+            .ok_or(Error::LoopbackMismatch)
+    }
+
     pub fn init(&mut self) -> Result<()> {
+        self.reset_stats();
         self.power_on()?;
 
         self.test_all(false)?;
@@ -46,6 +251,67 @@ where
 
         self.clear_all()?;
 
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Skip [`init`](Self::init) in tests that script every SPI transaction.
+    #[cfg(test)]
+    pub(crate) fn assume_initialized(mut self) -> Self {
+        self.initialized = true;
+        self
+    }
+
+    /// Fails with [`Error::NotInitialized`] until [`init`](Self::init)
+    /// succeeded.
+    pub(crate) fn check_initialized(&self) -> Result<()> {
+        if !self.initialized {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+
+    /// Shut every device down, wait `off_ms` milliseconds using `delay`,
+    /// then power up and rewrite the control registers from the driver's
+    /// shadow state: display test off, each device's scan limit, decode
+    /// mode and calibrated intensity.
+    ///
+    /// Meant as a recovery for chips that latched up from noise on long
+    /// cables. Digits are blanked since their content is not tracked here;
+    /// redraw afterwards, e.g. with [`LedMatrix::flush`](crate::matrix::LedMatrix::flush).
+    pub fn power_cycle<D>(&mut self, delay: &mut D, off_ms: u32) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        self.power_off()?;
+        delay.delay_ms(off_ms);
+        self.power_on()?;
+        self.test_all(false)?;
+
+        let count = self.device_count;
+        let mut ops = [(Register::NoOp, 0); MAX_DISPLAYS];
+        for (op, &limit) in ops.iter_mut().zip(&self.scan_limits[..count]) {
+            *op = (Register::ScanLimit, limit - 1);
+        }
+        self.write_all_registers(&ops[..count])?;
+        for (op, &mask) in ops.iter_mut().zip(&self.decode_masks[..count]) {
+            *op = (Register::DecodeMode, mask);
+        }
+        self.write_all_registers(&ops[..count])?;
+        let intensities = self.intensities;
+        self.write_intensities(&intensities[..count])?;
+
+        for (digit, digit_register) in Register::digits().enumerate() {
+            for (device_index, op) in ops[..count].iter_mut().enumerate() {
+                let blank = if self.is_decoded(device_index, digit as u8) {
+                    CODE_B_BLANK
+                } else {
+                    0x00
+                };
+                *op = (digit_register, blank);
+            }
+            self.write_all_registers(&ops[..count])?;
+        }
         Ok(())
     }
 
@@ -55,47 +321,113 @@ where
         register: Register,
         data: u8,
     ) -> Result<()> {
-        if device_index >= self.device_count {
-            return Err(Error::InvalidDeviceIndex);
+        // After another single-device write only two packets change
+        match self.single_packet {
+            Some(previous) if device_index < self.device_count => {
+                encoder::write_packet(&mut self.buffer, previous, Register::NoOp, 0)?;
+                encoder::write_packet(&mut self.buffer, device_index, register, data)?;
+            }
+            _ => {
+                encoder::encode_device(
+                    &mut self.buffer,
+                    self.device_count,
+                    device_index,
+                    register,
+                    data,
+                )?;
+            }
+        }
+        self.single_packet = Some(device_index);
+        let frame = &self.buffer[..self.device_count * encoder::PACKET_SIZE];
+        Self::send(
+            &mut self.spi,
+            &mut self.stats,
+            &mut self.loopback,
+            self.settle_ns,
+            frame,
+        )?;
+        if register == Register::Shutdown {
+            self.powered[device_index] = data & 0x01 != 0;
         }
-
-        self.buffer = [0; MAX_DISPLAYS * 2];
-
-        let offset = device_index * 2; // 2 bytes(16 bits packet) per display
-        self.buffer[offset] = register as u8;
-        self.buffer[offset + 1] = data;
-
-        self.spi.write(&self.buffer[0..self.device_count * 2])?;
-
         Ok(())
     }
 
     /// Write each (register, data) tuple to its corresponding MAX7219 device in the daisy chain.
     ///
-    /// The number of tuples in `ops` must exactly match `self.device_count`.
+    /// Devices past the end of `ops` are sent a NoOp, see [`encoder::encode_chain`].
     /// Convention: ops[0] = furthest device from MCU, ops[device_count-1] = nearest device
     /// Because The first one we send in the SPI gets pushed till the last device.
     ///
-    /// # Panics (only in debug builds)
-    /// - If `ops.len() != self.device_count`.
-    ///
     /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if there are more ops than devices.
     /// - Returns an SPI error if the write operation fails.
     pub(crate) fn write_all_registers(&mut self, ops: &[(Register, u8)]) -> Result<()> {
-        // clear the buffer: 2 bytes per device
-        self.buffer = [0; MAX_DISPLAYS * 2];
+        // send exactly device_count packets
+        self.single_packet = None;
+        let frame = encoder::encode_chain(&mut self.buffer, self.device_count, ops)?;
+        Self::send(
+            &mut self.spi,
+            &mut self.stats,
+            &mut self.loopback,
+            self.settle_ns,
+            frame,
+        )?;
+        for (powered, &(register, data)) in self.powered.iter_mut().zip(ops) {
+            if register == Register::Shutdown {
+                *powered = data & 0x01 != 0;
+            }
+        }
+        Ok(())
+    }
 
-        for (i, &(reg, data)) in ops.iter().enumerate() {
-            let offset = i * 2;
-            self.buffer[offset] = reg as u8;
-            self.buffer[offset + 1] = data;
+    /// Write one chip-select frame, counting it in the bus statistics.
+    fn send(
+        spi: &mut SPI,
+        stats: &mut BusStats,
+        loopback: &mut Loopback,
+        settle_ns: u32,
+        frame: &[u8],
+    ) -> Result<()> {
+        #[cfg(feature = "log")]
+        for (device_index, packet) in frame.chunks_exact(encoder::PACKET_SIZE).enumerate() {
+            if packet[0] != Register::NoOp.addr() {
+                log::trace!(
+                    "max7219: device {} register {:#04x} <- {:#04x}",
+                    device_index,
+                    packet[0],
+                    packet[1]
+                );
+            }
+        }
+        let mut read = [0; MAX_FRAME_SIZE];
+        let read_len = frame.len().min(MAX_FRAME_SIZE);
+        let result = {
+            let data = if loopback.enabled() {
+                Operation::Transfer(&mut read[..read_len], frame)
+            } else {
+                Operation::Write(frame)
+            };
+            let mut operations = [Operation::DelayNs(settle_ns), data];
+            // Without a settle delay this is a plain write or transfer
+            let skip = usize::from(settle_ns == 0);
+            spi.transaction(&mut operations[skip..])
+        };
+        stats.record(frame.len(), result.is_ok());
+        if result.is_err() {
+            loopback.reset();
         }
+        result?;
+        loopback.verify(&read[..read_len], frame)
+    }
 
-        // send exactly device_count packets
-        let len = self.device_count * 2;
-        self.spi.write(&self.buffer[..len])?;
+    /// SPI traffic since [`init`](Self::init) or the last
+    /// [`reset_stats`](Self::reset_stats).
+    pub fn stats(&self) -> BusStats {
+        self.stats
+    }
 
-        Ok(())
+    pub fn reset_stats(&mut self) {
+        self.stats = BusStats::default();
     }
 
     pub fn power_on(&mut self) -> Result<()> {
@@ -134,6 +466,8 @@ where
         Ok(())
     }
 
+    /// Blank every digit of every device, one transaction per digit register
+    /// since each frame has to be latched by its own CS rising edge.
     pub fn clear_all(&mut self) -> Result<()> {
         for digit_register in Register::digits() {
             let ops = [(digit_register, 0x00); MAX_DISPLAYS];
@@ -143,16 +477,328 @@ where
         Ok(())
     }
 
-    pub fn set_intensity(&mut self, device_index: usize, intensity: u8) -> Result<()> {
-        if intensity > 0x0F {
-            return Err(Error::InvalidIntensity);
+    /// Set every digit register of one device to `pattern`, e.g. `0xFF` to
+    /// light everything or `0xAA` for stripes.
+    ///
+    /// # Errors
+    /// - [`Error::DecodeModeMismatch`] if any digit of the device is set to Code B decoding.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
+    pub fn fill(&mut self, device_index: usize, pattern: u8) -> Result<()> {
+        if self.decode_mask(device_index)? != 0 {
+            return Err(Error::DecodeModeMismatch);
+        }
+        self.check_initialized()?;
+        for digit_register in Register::digits() {
+            self.write_device_register(device_index, digit_register, pattern)?;
         }
-        self.write_device_register(device_index, Register::Intensity, intensity)
+        Ok(())
+    }
+
+    /// Set every digit register of every device to `pattern`, one
+    /// transaction per digit register.
+    ///
+    /// # Errors
+    /// - [`Error::DecodeModeMismatch`] if any device has Code B decoding enabled.
+    pub fn fill_all(&mut self, pattern: u8) -> Result<()> {
+        let tiles = [[pattern; NUM_DIGITS as usize]; MAX_DISPLAYS];
+        self.write_tiles(&tiles[..self.device_count])
+    }
+
+    /// Show the same 8-row frame on every device, one transaction per digit
+    /// register with every packet carrying data.
+    ///
+    /// # Errors
+    /// - [`Error::DecodeModeMismatch`] if any device has Code B decoding enabled.
+    pub fn write_frame_all(&mut self, frame: &[u8; NUM_DIGITS as usize]) -> Result<()> {
+        let tiles = [*frame; MAX_DISPLAYS];
+        self.write_tiles(&tiles[..self.device_count])
+    }
+
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `intensity` is above 15; see
+    ///   [`set_intensity_level`](Self::set_intensity_level) to rule that
+    ///   out at compile time.
+    pub fn set_intensity(&mut self, device_index: usize, intensity: u8) -> Result<()> {
+        self.set_intensity_level(device_index, Intensity::try_from(intensity)?)
     }
 
     pub fn set_intensity_all(&mut self, intensity: u8) -> Result<()> {
-        let ops = [(Register::Intensity, intensity); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        self.set_intensity_level_all(Intensity::try_from(intensity)?)
+    }
+
+    pub fn set_intensity_level(&mut self, device_index: usize, intensity: Intensity) -> Result<()> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        let register = self.calibrated(device_index, intensity.value());
+        self.write_device_register(device_index, Register::Intensity, register)?;
+        self.intensities[device_index] = intensity.value();
+        Ok(())
+    }
+
+    pub fn set_intensity_level_all(&mut self, intensity: Intensity) -> Result<()> {
+        let levels = [intensity.value(); MAX_DISPLAYS];
+        self.write_intensities(&levels[..self.device_count])
+    }
+
+    /// Give every device its own intensity in a single chain transaction;
+    /// `intensities[0]` goes to device 0.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if there isn't exactly one intensity
+    ///   per device.
+    /// - [`Error::InvalidIntensity`] if any value is above 15; nothing is
+    ///   written then.
+    pub fn set_intensities(&mut self, intensities: &[u8]) -> Result<()> {
+        if intensities.len() != self.device_count {
+            return Err(Error::InvalidDeviceCount);
+        }
+        for &intensity in intensities {
+            Intensity::try_from(intensity)?;
+        }
+        self.write_intensities(intensities)
+    }
+
+    /// Offset added to every intensity written to a device, so modules from
+    /// mixed batches look equally bright at the same setting. Results are
+    /// clamped to 0-15.
+    ///
+    /// Applied by all intensity, brightness and fade methods from their
+    /// next write on; [`intensity`](Self::intensity) keeps reporting the
+    /// value before calibration.
+    pub fn set_calibration(&mut self, device_index: usize, offset: i8) -> Result<()> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        self.calibrations[device_index] = offset;
+        Ok(())
+    }
+
+    /// Set the calibration offsets of all devices, `offsets[0]` for device 0.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if there isn't exactly one offset
+    ///   per device.
+    pub fn with_calibration(mut self, offsets: &[i8]) -> Result<Self> {
+        if offsets.len() != self.device_count {
+            return Err(Error::InvalidDeviceCount);
+        }
+        self.calibrations[..self.device_count].copy_from_slice(offsets);
+        Ok(self)
+    }
+
+    pub fn calibration(&self, device_index: usize) -> Result<i8> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        Ok(self.calibrations[device_index])
+    }
+
+    /// Register value for `intensity` on a device, calibration applied.
+    pub(crate) fn calibrated(&self, device_index: usize, intensity: u8) -> u8 {
+        let offset = self.calibrations[device_index];
+        let register = (i16::from(intensity) + i16::from(offset)).clamp(0, 0x0F) as u8;
+        self.intensity_cap.map_or(register, |cap| register.min(cap))
+    }
+
+    /// Hold every intensity register at or below `cap` without changing the
+    /// nominal intensities, rewriting them all when the cap changes.
+    #[cfg(feature = "matrix")]
+    pub(crate) fn set_intensity_cap(&mut self, cap: Option<u8>) -> Result<()> {
+        if cap == self.intensity_cap {
+            return Ok(());
+        }
+        self.intensity_cap = cap;
+        let intensities = self.intensities;
+        self.write_intensities(&intensities[..self.device_count])
+    }
+
+    /// Note an intensity written to a device outside the setters.
+    pub(crate) fn record_intensity(&mut self, device_index: usize, intensity: u8) {
+        self.intensities[device_index] = intensity;
+    }
+
+    /// Write one valid intensity per device in a single transaction.
+    fn write_intensities(&mut self, intensities: &[u8]) -> Result<()> {
+        let mut ops = [(Register::Intensity, 0); MAX_DISPLAYS];
+        for (device_index, (op, &intensity)) in ops.iter_mut().zip(intensities).enumerate() {
+            op.1 = self.calibrated(device_index, intensity);
+        }
+        self.write_all_registers(&ops[..self.device_count])?;
+        self.intensities[..self.device_count].copy_from_slice(intensities);
+        Ok(())
+    }
+
+    /// Map brightness through `lut` instead of the built-in gamma curve,
+    /// e.g. a dimming curve tuned for specific LEDs and diffusers.
+    /// `lut[b]` is the intensity (0-15) for brightness `b`.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if an entry is above 15.
+    pub fn with_brightness_lut(mut self, lut: &'static [u8; 256]) -> Result<Self> {
+        self.set_brightness_lut(Some(lut))?;
+        Ok(self)
+    }
+
+    /// Replace the brightness mapping, `None` restoring the built-in curve.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if an entry is above 15.
+    pub fn set_brightness_lut(&mut self, lut: Option<&'static [u8; 256]>) -> Result<()> {
+        if let Some(lut) = lut {
+            for &intensity in lut {
+                Intensity::try_from(intensity)?;
+            }
+        }
+        self.brightness_lut = lut;
+        Ok(())
+    }
+
+    /// Space the steps of [`fade_to`](Self::fade_to) and
+    /// [`fade_all_to`](Self::fade_all_to) along `easing` instead of evenly.
+    ///
+    /// The intensity still moves one of the 16 hardware steps at a time; the
+    /// easing only decides when each step happens.
+    pub fn with_fade_easing(mut self, easing: Easing) -> Self {
+        self.fade_easing = easing;
+        self
+    }
+
+    pub fn set_fade_easing(&mut self, easing: Easing) {
+        self.fade_easing = easing;
+    }
+
+    pub fn fade_easing(&self) -> Easing {
+        self.fade_easing
+    }
+
+    /// Set a device's perceptual brightness (0-255), mapped to the nearest
+    /// of the 16 intensity steps through a gamma curve (see
+    /// [`brightness_to_intensity`]) or the table set with
+    /// [`with_brightness_lut`](Self::with_brightness_lut).
+    pub fn set_brightness(&mut self, device_index: usize, brightness: u8) -> Result<()> {
+        self.set_intensity(device_index, self.brightness_intensity(brightness))
+    }
+
+    /// Set every device's perceptual brightness, like
+    /// [`set_brightness`](Self::set_brightness).
+    pub fn set_brightness_all(&mut self, brightness: u8) -> Result<()> {
+        self.set_intensity_all(self.brightness_intensity(brightness))
+    }
+
+    /// Microseconds between writes `step - 1` and `step` of the `steps` of
+    /// a fade lasting `duration_ms`, following the fade easing.
+    fn step_us(&self, duration_ms: u32, step: u8, steps: u8) -> u32 {
+        let total = u64::from(duration_ms) * 1000;
+        let at = |step: u8| {
+            let progress = u32::from(step) * u32::from(u16::MAX) / u32::from(steps);
+            let time = self.fade_easing.time_for(progress as u16);
+            total * u64::from(time) / u64::from(u16::MAX)
+        };
+        u32::try_from(at(step) - at(step - 1)).unwrap_or(u32::MAX)
+    }
+
+    fn brightness_intensity(&self, brightness: u8) -> u8 {
+        match self.brightness_lut {
+            Some(lut) => lut[usize::from(brightness)],
+            None => brightness_to_intensity(brightness),
+        }
+    }
+
+    /// Set a device's intensity as a percentage (0-100) of the register's
+    /// range, rounded to the nearest of the 16 steps.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `percent` is above 100.
+    pub fn set_brightness_percent(&mut self, device_index: usize, percent: u8) -> Result<()> {
+        self.set_intensity(device_index, percent_to_intensity(percent)?)
+    }
+
+    /// Set every device's intensity as a percentage, like
+    /// [`set_brightness_percent`](Self::set_brightness_percent).
+    pub fn set_brightness_percent_all(&mut self, percent: u8) -> Result<()> {
+        self.set_intensity_all(percent_to_intensity(percent)?)
+    }
+
+    /// Intensity a device was last set to.
+    ///
+    /// Tracked from the intensity writes made through this driver; the
+    /// chip powers up at the minimum, 0.
+    pub fn intensity(&self, device_index: usize) -> Result<u8> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        Ok(self.intensities[device_index])
+    }
+
+    /// Step a device's intensity one level at a time from its current value
+    /// (see [`intensity`](Self::intensity)) to `intensity`, spreading the
+    /// steps over `duration_ms` milliseconds using `delay`: evenly, or as
+    /// set with [`with_fade_easing`](Self::with_fade_easing).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `intensity` is above 15.
+    /// - [`Error::InvalidDeviceIndex`] if the device is not on the chain.
+    pub fn fade_to<D>(
+        &mut self,
+        device_index: usize,
+        intensity: u8,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        Intensity::try_from(intensity)?;
+        let from = self.intensity(device_index)?;
+        let steps = from.abs_diff(intensity);
+        for step in 1..=steps {
+            delay.delay_us(self.step_us(duration_ms, step, steps));
+            let level = fade_level(from, intensity, step, steps);
+            self.set_intensity(device_index, level)?;
+        }
+        Ok(())
+    }
+
+    /// Fade every device to `intensity` over `duration_ms` milliseconds,
+    /// like [`fade_to`](Self::fade_to). Devices starting at different
+    /// levels arrive together; each step updates the whole chain in one
+    /// transaction.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `intensity` is above 15.
+    pub fn fade_all_to<D>(&mut self, intensity: u8, duration_ms: u32, delay: &mut D) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        Intensity::try_from(intensity)?;
+        let start = self.intensities;
+        let steps = start[..self.device_count]
+            .iter()
+            .map(|&from| from.abs_diff(intensity))
+            .max()
+            .unwrap_or(0);
+        for step in 1..=steps {
+            delay.delay_us(self.step_us(duration_ms, step, steps));
+            let mut levels = [0; MAX_DISPLAYS];
+            for (level, &from) in levels.iter_mut().zip(&start[..self.device_count]) {
+                *level = fade_level(from, intensity, step, steps);
+            }
+            self.write_intensities(&levels[..self.device_count])?;
+        }
+        Ok(())
     }
 
     pub fn set_device_scan_limit(&mut self, device_index: usize, limit: u8) -> Result<()> {
@@ -160,7 +806,9 @@ where
             return Err(Error::InvalidScanLimit);
         }
 
-        self.write_device_register(device_index, Register::ScanLimit, limit - 1)
+        self.write_device_register(device_index, Register::ScanLimit, limit - 1)?;
+        self.scan_limits[device_index] = limit;
+        Ok(())
     }
 
     pub fn set_scan_limit_all(&mut self, limit: u8) -> Result<()> {
@@ -169,29 +817,361 @@ where
         }
         let val = limit - 1;
         let ops: [(Register, u8); MAX_DISPLAYS] = [(Register::ScanLimit, val); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        self.write_all_registers(&ops[..self.device_count])?;
+        self.scan_limits[..self.device_count].fill(limit);
+        Ok(())
+    }
+
+    /// Number of digits (DIG0 upwards) a device scans.
+    ///
+    /// Tracked from the scan limit writes made through this driver. Until
+    /// one is made all 8 digits are assumed to be scanned.
+    pub fn scan_limit(&self, device_index: usize) -> Result<u8> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        Ok(self.scan_limits[device_index])
     }
 
     pub fn set_device_decode_mode(&mut self, device_index: usize, mode: DecodeMode) -> Result<()> {
-        self.write_device_register(device_index, Register::DecodeMode, mode as u8)
+        self.write_device_register(device_index, Register::DecodeMode, mode as u8)?;
+        self.decode_masks[device_index] = mode as u8;
+        Ok(())
     }
 
     pub fn set_decode_mode_all(&mut self, mode: DecodeMode) -> Result<()> {
         let byte = mode as u8;
         let ops: [(Register, u8); MAX_DISPLAYS] = [(Register::DecodeMode, byte); MAX_DISPLAYS];
-        self.write_all_registers(&ops[..self.device_count])
+        self.write_all_registers(&ops[..self.device_count])?;
+        self.decode_masks[..self.device_count].fill(byte);
+        Ok(())
+    }
+
+    /// Digits of a device that last had Code B decoding enabled, bit n = DIGn.
+    ///
+    /// Tracked from the decode mode writes made through this driver; the
+    /// chip's power-on default is no decoding.
+    pub fn decode_mask(&self, device_index: usize) -> Result<u8> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        Ok(self.decode_masks[device_index])
     }
 
+    /// Write raw segment data (DP A B C D E F G) to a digit.
+    ///
+    /// # Errors
+    /// - [`Error::DigitNotScanned`] if the digit is beyond the scan limit.
+    /// - [`Error::DecodeModeMismatch`] if the digit is set to Code B decoding.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
     pub fn write_raw_digit(&mut self, device_index: usize, digit: u8, value: u8) -> Result<()> {
         let digit_register = Register::try_digit(digit)?;
+        if !self.is_scanned(device_index, digit) {
+            return Err(Error::DigitNotScanned);
+        }
+        if self.is_decoded(device_index, digit) {
+            return Err(Error::DecodeModeMismatch);
+        }
+        self.check_initialized()?;
+        self.write_device_register(device_index, digit_register, value)
+    }
+
+    /// Write the same raw segment data to one digit of every device in a
+    /// single transaction.
+    ///
+    /// # Errors
+    /// - [`Error::DigitNotScanned`] if the digit is beyond any device's scan limit.
+    /// - [`Error::DecodeModeMismatch`] if the digit is set to Code B decoding on any device.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
+    pub fn write_digit_all(&mut self, digit: u8, value: u8) -> Result<()> {
+        let digit_register = Register::try_digit(digit)?;
+        for device_index in 0..self.device_count {
+            if !self.is_scanned(device_index, digit) {
+                return Err(Error::DigitNotScanned);
+            }
+            if self.is_decoded(device_index, digit) {
+                return Err(Error::DecodeModeMismatch);
+            }
+        }
+        self.check_initialized()?;
+        let ops = [(digit_register, value); MAX_DISPLAYS];
+        self.write_all_registers(&ops[..self.device_count])
+    }
+
+    /// Write a Code B character to a digit: `0x0`-`0x9` for digits, then
+    /// `-`, `E`, `H`, `L`, `P` and blank for `0xA`-`0xF`. Bit 7 lights the
+    /// decimal point.
+    ///
+    /// # Errors
+    /// - [`Error::DigitNotScanned`] if the digit is beyond the scan limit.
+    /// - [`Error::DecodeModeMismatch`] if the digit is not set to Code B decoding.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
+    pub fn write_decoded_digit(&mut self, device_index: usize, digit: u8, value: u8) -> Result<()> {
+        let digit_register = Register::try_digit(digit)?;
+        if device_index < self.device_count {
+            if !self.is_scanned(device_index, digit) {
+                return Err(Error::DigitNotScanned);
+            }
+            if !self.is_decoded(device_index, digit) {
+                return Err(Error::DecodeModeMismatch);
+            }
+        }
+        self.check_initialized()?;
         self.write_device_register(device_index, digit_register, value)
     }
+
+    /// Write Code B characters to consecutive digits of one device, starting
+    /// at `digit`, one transaction per digit.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if the characters run past digit 7.
+    /// - [`Error::DigitNotScanned`] or [`Error::DecodeModeMismatch`] if any
+    ///   of the digits is beyond the scan limit or not set to Code B
+    ///   decoding; nothing is written then.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
+    pub fn write_digits(
+        &mut self,
+        device_index: usize,
+        digit: u8,
+        chars: &[CodeBChar],
+    ) -> Result<()> {
+        self.decode_mask(device_index)?;
+        if usize::from(digit) + chars.len() > usize::from(NUM_DIGITS) {
+            return Err(Error::InvalidDigit);
+        }
+        let start = device_index * usize::from(NUM_DIGITS) + usize::from(digit);
+        self.write_chain_digits(start, chars)
+    }
+
+    /// Write Code B characters to consecutive digit positions of the chain,
+    /// position `p` being digit `p % 8` of device `p / 8`.
+    ///
+    /// Updates of the same digit register on different devices share one
+    /// transaction, so at most 8 transactions are sent however long the
+    /// chain.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if the characters run past the last device.
+    /// - [`Error::DigitNotScanned`] or [`Error::DecodeModeMismatch`] if any
+    ///   of the digits is beyond the scan limit or not set to Code B
+    ///   decoding; nothing is written then.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
+    pub fn write_chain_digits(&mut self, start: usize, chars: &[CodeBChar]) -> Result<()> {
+        let digits = usize::from(NUM_DIGITS);
+        let end = start
+            .checked_add(chars.len())
+            .filter(|&end| end <= self.device_count * digits)
+            .ok_or(Error::InvalidDigit)?;
+        for position in start..end {
+            let (device_index, digit) = (position / digits, (position % digits) as u8);
+            if !self.is_scanned(device_index, digit) {
+                return Err(Error::DigitNotScanned);
+            }
+            if !self.is_decoded(device_index, digit) {
+                return Err(Error::DecodeModeMismatch);
+            }
+        }
+        self.check_initialized()?;
+
+        let count = self.device_count;
+        for (digit, digit_register) in Register::digits().enumerate() {
+            let mut ops = [(Register::NoOp, 0); MAX_DISPLAYS];
+            let mut touched = false;
+            for (device_index, op) in ops[..count].iter_mut().enumerate() {
+                let position = device_index * digits + digit;
+                if let Some(c) = position.checked_sub(start).and_then(|i| chars.get(i)) {
+                    *op = (digit_register, c.value());
+                    touched = true;
+                }
+            }
+            if touched {
+                self.write_all_registers(&ops[..count])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `digit` is within the device's scan limit; unknown devices
+    /// pass so the index is reported by the register write.
+    pub(crate) fn is_scanned(&self, device_index: usize, digit: u8) -> bool {
+        self.scan_limits
+            .get(device_index)
+            .is_none_or(|&limit| digit < limit)
+    }
+
+    /// Write one row of an 8x8 matrix module straight to its digit register,
+    /// bit 7 being the leftmost column.
+    ///
+    /// Only that register is updated, so the rest of the module keeps its
+    /// content.
+    pub fn write_row(&mut self, device_index: usize, row: u8, value: u8) -> Result<()> {
+        self.write_raw_digit(device_index, row, value)
+    }
+
+    /// Blank one digit (or matrix row).
+    ///
+    /// Digits set to Code B decoding are sent the Code B blank character,
+    /// raw digits are sent all segments off.
+    pub fn clear_digit(&mut self, device_index: usize, digit: u8) -> Result<()> {
+        Register::try_digit(digit)?;
+        if self.is_decoded(device_index, digit) {
+            self.write_decoded_digit(device_index, digit, CODE_B_BLANK)
+        } else {
+            self.write_raw_digit(device_index, digit, 0x00)
+        }
+    }
+
+    pub(crate) fn is_decoded(&self, device_index: usize, digit: u8) -> bool {
+        self.decode_masks
+            .get(device_index)
+            .is_some_and(|mask| mask & (1 << digit) != 0)
+    }
+
+    /// Show a [`Canvas`] on the chain, one transaction per digit register.
+    ///
+    /// Canvas tiles are assigned to devices in row-major order: the top-left
+    /// module goes to device 0, the next module to its right to device 1, and
+    /// so on. The canvas must cover exactly `device_count` modules.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if the canvas and chain sizes differ.
+    /// - [`Error::DecodeModeMismatch`] if any device has Code B decoding enabled.
+    #[cfg(feature = "matrix")]
+    pub fn write_canvas(&mut self, canvas: &Canvas) -> Result<()> {
+        let tiles = self.canvas_tiles(canvas)?;
+        self.write_tiles(&tiles[..self.device_count])
+    }
+
+    #[cfg(feature = "matrix")]
+    fn canvas_tiles(&self, canvas: &Canvas) -> Result<[[u8; NUM_DIGITS as usize]; MAX_DISPLAYS]> {
+        let modules_wide = canvas.modules_wide();
+        if modules_wide * canvas.modules_high() != self.device_count {
+            return Err(Error::InvalidCanvasSize);
+        }
+
+        let mut tiles = [[0; NUM_DIGITS as usize]; MAX_DISPLAYS];
+        for (device_index, tile) in tiles[..self.device_count].iter_mut().enumerate() {
+            *tile = canvas.tile(device_index % modules_wide, device_index / modules_wide)?;
+        }
+        Ok(tiles)
+    }
+
+    /// Write one 8-row tile per device, one transaction per digit register.
+    ///
+    /// `tiles[0]` goes to device 0; `tiles.len()` must equal the device count.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if the tile and device counts differ.
+    /// - [`Error::DecodeModeMismatch`] if any device has Code B decoding enabled.
+    pub(crate) fn write_tiles(&mut self, tiles: &[[u8; NUM_DIGITS as usize]]) -> Result<()> {
+        self.check_initialized()?;
+        let mut buf = [0; MAX_TILES_SIZE];
+        let frames = self.encode_tiles(&mut buf, tiles)?;
+        // Not grouped into one `SpiDevice::transaction`: CS stays low across
+        // all of its operations, and the chips only latch on the CS rising
+        // edge, so every frame but the last would be shifted out unseen.
+        for frame in frames.chunks_exact(self.device_count * encoder::PACKET_SIZE) {
+            self.send_frame(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Send one already encoded chain-wide frame, e.g. from
+    /// [`encode_tiles`](Self::encode_tiles).
+    pub(crate) fn send_frame(&mut self, frame: &[u8]) -> Result<()> {
+        Self::send(
+            &mut self.spi,
+            &mut self.stats,
+            &mut self.loopback,
+            self.settle_ns,
+            frame,
+        )
+    }
+
+    /// Encode [`write_tiles`](Self::write_tiles) into `buf` after the same checks.
+    pub(crate) fn encode_tiles<'b>(
+        &self,
+        buf: &'b mut [u8],
+        tiles: &[[u8; NUM_DIGITS as usize]],
+    ) -> Result<&'b [u8]> {
+        if tiles.len() != self.device_count {
+            return Err(Error::InvalidCanvasSize);
+        }
+        if self.decode_masks[..self.device_count]
+            .iter()
+            .any(|&mask| mask != 0)
+        {
+            return Err(Error::DecodeModeMismatch);
+        }
+        // Rows the chips don't scan would silently never show
+        #[cfg(feature = "strict")]
+        for (tile, &limit) in tiles.iter().zip(&self.scan_limits) {
+            if tile[usize::from(limit)..].iter().any(|&row| row != 0) {
+                return Err(Error::DigitNotScanned);
+            }
+        }
+        encoder::encode_tiles(buf, tiles)
+    }
+
+    /// Encode the frame writing `data` to `register` of one device into
+    /// `buf` instead of sending it, e.g. to hand it to a DMA engine or queue
+    /// it. The driver's shadow state is not updated.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceIndex`] if the device is not on the chain.
+    /// - [`Error::BufferTooSmall`] if `buf` is shorter than
+    ///   `device_count * PACKET_SIZE` bytes.
+    pub fn encode_register<'b>(
+        &self,
+        buf: &'b mut [u8],
+        device_index: usize,
+        register: Register,
+        data: u8,
+    ) -> Result<&'b [u8]> {
+        encoder::encode_device(buf, self.device_count, device_index, register, data)
+    }
+
+    /// Encode [`write_canvas`](Self::write_canvas) into `buf` instead of
+    /// sending it: 8 frames back to back, one per digit register, each to be
+    /// sent in its own chip-select frame (see [`encoder::encode_tiles`]).
+    ///
+    /// # Errors
+    /// Same as [`write_canvas`](Self::write_canvas), and
+    /// [`Error::BufferTooSmall`] if `buf` can't hold all frames.
+    #[cfg(feature = "matrix")]
+    pub fn encode_canvas<'b>(&self, canvas: &Canvas, buf: &'b mut [u8]) -> Result<&'b [u8]> {
+        let tiles = self.canvas_tiles(canvas)?;
+        self.encode_tiles(buf, &tiles[..self.device_count])
+    }
+}
+
+/// Nearest intensity step to `percent` of the full range.
+fn percent_to_intensity(percent: u8) -> Result<u8> {
+    if percent > 100 {
+        return Err(Error::InvalidIntensity {
+            value: percent,
+            max: 100,
+        });
+    }
+    Ok(((u16::from(percent) * 0x0F + 50) / 100) as u8)
+}
+
+/// Intensity after `step` of `steps` going from `from` to `to`.
+fn fade_level(from: u8, to: u8, step: u8, steps: u8) -> u8 {
+    let distance = i16::from(to) - i16::from(from);
+    (i16::from(from) + distance * i16::from(step) / i16::from(steps)) as u8
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::MAX_DISPLAYS;
+    use crate::{MAX_DISPLAYS, host::EmulatedSpi};
     use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
 
     #[test]
@@ -215,6 +1195,60 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    fn test_for_each_device() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(3)
+                .unwrap();
+            driver
+                .for_each_device(|driver, device_index| {
+                    driver.write_row(device_index, 0, 1 << device_index)
+                })
+                .unwrap();
+
+            let mut visited = 0;
+            let result = driver.for_each_device(|_, device_index| {
+                visited += 1;
+                if device_index == 1 {
+                    return Err(Error::InvalidDigit);
+                }
+                Ok(())
+            });
+            assert_eq!(result, Err(Error::InvalidDigit));
+            assert_eq!(visited, 2);
+        }
+        assert_eq!(spi.transactions(), 3);
+        for device_index in 0..3 {
+            assert_eq!(
+                spi.emulator().digits(device_index).unwrap()[0],
+                1 << device_index
+            );
+        }
+    }
+
+    #[test]
+    fn test_debug_shows_configuration() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        driver.init().unwrap();
+        driver.set_intensity(1, 9).unwrap();
+        driver.set_device_scan_limit(0, 4).unwrap();
+        driver
+            .set_device_decode_mode(1, DecodeMode::Digits0To3)
+            .unwrap();
+        driver.power_off_device(0).unwrap();
+
+        let debug = format!("{driver:?}");
+        assert!(debug.starts_with("Max7219 { variant: Max7219, device_count: 2, devices: ["));
+        assert!(debug.contains(
+            "{ powered: false, intensity: 0, scan_limit: 4, decode_mask: 0x00 }, \
+             { powered: true, intensity: 9, scan_limit: 8, decode_mask: 0x0f }]"
+        ));
+    }
+
     #[test]
     fn test_with_device_count_invalid() {
         let mut spi = SpiMock::new(&[]);
@@ -222,6 +1256,13 @@ mod tests {
         let result = driver.with_device_count(MAX_DISPLAYS + 1);
         assert!(matches!(result, Err(Error::InvalidDeviceCount)));
 
+        // An empty chain would have nothing to frame a write for
+        let driver = Max7219::new(&mut spi);
+        assert!(matches!(
+            driver.with_device_count(0),
+            Err(Error::InvalidDeviceCount)
+        ));
+
         spi.done();
     }
 
@@ -257,8 +1298,49 @@ mod tests {
             .expect("Should accept valid count");
 
         let result = driver.write_device_register(2, Register::Shutdown, 0x01); // Index 2 is invalid for device_count=2
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+
+        spi.done();
+    }
 
+    #[test]
+    fn test_consecutive_device_writes() {
+        let frames: [&[u8]; 4] = [
+            &[0x0A, 0x03, 0x00, 0x00],
+            &[0x00, 0x00, 0x0A, 0x07],
+            &[0x0C, 0x01, 0x0C, 0x01],
+            &[0x00, 0x00, 0x01, 0xFF],
+        ];
+        let mut expected_transactions = Vec::new();
+        for frame in frames {
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(frame.to_vec()));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+
+        // The previous device's packet must be reset to a NoOp
+        driver.set_intensity(0, 3).unwrap();
+        driver.set_intensity(1, 7).unwrap();
+        driver.power_on().unwrap();
+        assert_eq!(
+            driver.write_device_register(2, Register::Digit0, 0xFF),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+        driver.write_raw_digit(1, 0, 0xFF).unwrap();
         spi.done();
     }
 
@@ -390,10 +1472,22 @@ mod tests {
         let mut driver = Max7219::new(&mut spi).with_device_count(1).unwrap();
 
         let result = driver.power_on_device(1);
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex {
+                index: 1,
+                device_count: 1
+            })
+        );
 
         let result = driver.power_off_device(1);
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex {
+                index: 1,
+                device_count: 1
+            })
+        );
         spi.done();
     }
 
@@ -503,7 +1597,13 @@ mod tests {
             .expect("valid device count");
 
         let result = driver.clear_display(1);
-        assert_eq!(result, Err(Error::InvalidDeviceIndex));
+        assert_eq!(
+            result,
+            Err(Error::InvalidDeviceIndex {
+                index: 1,
+                device_count: 1
+            })
+        );
         spi.done();
     }
 
@@ -531,10 +1631,281 @@ mod tests {
         let mut driver = Max7219::new(&mut spi);
 
         let result = driver.set_intensity(0, 0x10); // Invalid intensity > 0x0F
-        assert_eq!(result, Err(Error::InvalidIntensity));
+        assert_eq!(result, Err(Error::InvalidIntensity { value: 16, max: 15 }));
+        spi.done();
+    }
+
+    #[test]
+    fn test_set_intensity_level() {
+        const DIM: Intensity = Intensity::new(2).unwrap();
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.set_intensity_level_all(Intensity::MAX).unwrap();
+            driver.set_intensity_level(0, DIM).unwrap();
+            assert_eq!(driver.intensity(0), Ok(2));
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.device(0).unwrap().intensity, 2);
+        assert_eq!(emulator.device(1).unwrap().intensity, 15);
+    }
+
+    #[test]
+    fn test_set_intensities() {
+        let expected_transactions = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![
+                Register::Intensity.addr(),
+                0x01,
+                Register::Intensity.addr(),
+                0x0F,
+            ]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        driver.set_intensities(&[0x01, 0x0F]).unwrap();
+        assert_eq!(driver.intensity(1), Ok(0x0F));
+        assert_eq!(
+            driver.set_intensities(&[0x01]),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(
+            driver.set_intensities(&[0x01, 0x10]),
+            Err(Error::InvalidIntensity { value: 16, max: 15 })
+        );
         spi.done();
     }
 
+    #[test]
+    fn test_brightness_lut() {
+        static LINEAR: [u8; 256] = {
+            let mut lut = [0; 256];
+            let mut brightness = 0;
+            while brightness < 256 {
+                lut[brightness] = (brightness / 16) as u8;
+                brightness += 1;
+            }
+            lut
+        };
+        static INVALID: [u8; 256] = [0x10; 256];
+
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_brightness_lut(&LINEAR).unwrap();
+        driver.set_brightness(0, 128).unwrap();
+        assert_eq!(driver.intensity(0), Ok(8));
+
+        assert_eq!(
+            driver.set_brightness_lut(Some(&INVALID)),
+            Err(Error::InvalidIntensity { value: 16, max: 15 })
+        );
+        driver.set_brightness_lut(None).unwrap();
+        driver.set_brightness(0, 128).unwrap();
+        assert_eq!(driver.intensity(0), Ok(3));
+    }
+
+    #[test]
+    fn test_calibration() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .with_device_count(3)
+                .unwrap()
+                .with_calibration(&[0, -2, 3])
+                .unwrap();
+            driver.set_intensity_all(14).unwrap();
+            assert_eq!(driver.intensity(1), Ok(14));
+            assert_eq!(driver.calibration(2), Ok(3));
+
+            driver.set_calibration(0, -20).unwrap();
+            driver.set_brightness(0, 255).unwrap();
+            assert_eq!(
+                driver.set_calibration(3, 1),
+                Err(Error::InvalidDeviceIndex {
+                    index: 3,
+                    device_count: 3
+                })
+            );
+        }
+        // Offsets applied and clamped to the register range
+        let emulator = spi.emulator();
+        assert_eq!(emulator.device(0).unwrap().intensity, 0);
+        assert_eq!(emulator.device(1).unwrap().intensity, 12);
+        assert_eq!(emulator.device(2).unwrap().intensity, 15);
+        assert!(matches!(
+            Max7219::new(&mut spi).with_calibration(&[1, 1]),
+            Err(Error::InvalidDeviceCount)
+        ));
+    }
+
+    #[test]
+    fn test_set_brightness() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.set_brightness_all(255).unwrap();
+            driver.set_brightness(1, 128).unwrap();
+            assert_eq!(driver.intensity(1), Ok(3));
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.device(0).unwrap().intensity, 15);
+        assert_eq!(emulator.device(1).unwrap().intensity, 3);
+    }
+
+    #[test]
+    fn test_set_brightness_percent() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi);
+        for (percent, intensity) in [(0, 0), (50, 8), (53, 8), (97, 15), (100, 15)] {
+            driver.set_brightness_percent(0, percent).unwrap();
+            assert_eq!(driver.intensity(0), Ok(intensity));
+        }
+        assert_eq!(
+            driver.set_brightness_percent_all(101),
+            Err(Error::InvalidIntensity {
+                value: 101,
+                max: 100
+            })
+        );
+    }
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        us: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.us += ns / 1000;
+        }
+
+        fn delay_us(&mut self, us: u32) {
+            self.us += us;
+        }
+    }
+
+    #[test]
+    fn test_fade_to() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let mut driver = Max7219::new(&mut spi);
+            driver.set_intensity(0, 3).unwrap();
+            driver.fade_to(0, 11, 400, &mut delay).unwrap();
+            assert_eq!(driver.intensity(0), Ok(11));
+            assert_eq!(
+                driver.fade_to(0, 16, 400, &mut delay),
+                Err(Error::InvalidIntensity { value: 16, max: 15 })
+            );
+            assert_eq!(
+                driver.fade_to(1, 0, 400, &mut delay),
+                Err(Error::InvalidDeviceIndex {
+                    index: 1,
+                    device_count: 1
+                })
+            );
+        }
+        assert_eq!(delay.us, 400_000);
+        // One write per level after the initial set
+        assert_eq!(spi.transactions(), 1 + 8);
+        assert_eq!(spi.emulator().device(0).unwrap().intensity, 11);
+    }
+
+    #[test]
+    fn test_fade_all_to() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.set_intensity(0, 15).unwrap();
+            driver.set_intensity(1, 5).unwrap();
+            driver.fade_all_to(0, 1500, &mut delay).unwrap();
+            assert_eq!(driver.intensity(1), Ok(0));
+        }
+        assert_eq!(delay.us, 1_500_000);
+        // Both arrive together after the larger distance of 15 steps
+        assert_eq!(spi.transactions(), 2 + 15);
+        assert_eq!(spi.emulator().device(0).unwrap().intensity, 0);
+    }
+
+    #[derive(Default)]
+    struct RecordingDelay {
+        waits: Vec<u32>,
+    }
+
+    impl DelayNs for RecordingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.waits.push(ns / 1000);
+        }
+
+        fn delay_us(&mut self, us: u32) {
+            self.waits.push(us);
+        }
+    }
+
+    #[test]
+    fn test_fade_easing() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_fade_easing(Easing::EaseIn);
+        let mut delay = RecordingDelay::default();
+        driver.fade_to(0, 15, 1500, &mut delay).unwrap();
+        assert_eq!(delay.waits.len(), 15);
+        assert_eq!(delay.waits.iter().sum::<u32>(), 1_500_000);
+        // Slow start: the first step waits longest
+        assert!(delay.waits[0] > delay.waits[14] * 5);
+
+        driver.set_fade_easing(Easing::Linear);
+        let mut delay = RecordingDelay::default();
+        driver.fade_all_to(0, 1500, &mut delay).unwrap();
+        assert!(delay.waits.iter().all(|&us| us.abs_diff(100_000) <= 1));
+        assert_eq!(driver.fade_easing(), Easing::Linear);
+    }
+
+    #[test]
+    fn test_power_cycle() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .with_device_count(2)
+                .unwrap()
+                .with_calibration(&[0, -2])
+                .unwrap();
+            driver.init().unwrap();
+            driver.set_device_scan_limit(0, 4).unwrap();
+            driver
+                .set_device_decode_mode(1, DecodeMode::Digits0To3)
+                .unwrap();
+            driver.set_intensity_all(9).unwrap();
+
+            // Noise scrambles the registers
+            driver.test_all(true).unwrap();
+            let garbage = [(Register::ScanLimit, 0x00), (Register::DecodeMode, 0xFF)];
+            driver.write_all_registers(&garbage).unwrap();
+            driver.fill(0, 0x5A).unwrap();
+
+            driver.power_cycle(&mut delay, 50).unwrap();
+        }
+        assert_eq!(delay.us, 50_000);
+
+        let emulator = spi.emulator();
+        let first = emulator.device(0).unwrap();
+        assert!(!first.shutdown && !first.display_test);
+        assert_eq!(first.scan_limit, 3);
+        assert_eq!(first.decode_mode, 0x00);
+        assert_eq!(first.intensity, 9);
+        assert_eq!(first.digits, [0x00; 8]);
+
+        let second = emulator.device(1).unwrap();
+        assert_eq!(second.scan_limit, 7);
+        assert_eq!(second.decode_mode, 0x0F);
+        assert_eq!(second.intensity, 7);
+        assert_eq!(second.digits[..4], [CODE_B_BLANK; 4]);
+        assert_eq!(second.digits[4..], [0x00; 4]);
+    }
+
     #[test]
     fn test_test_device_enable_disable() {
         let expected_transactions = [
@@ -623,7 +1994,7 @@ mod tests {
             Transaction::transaction_end(),
         ];
         let mut spi = SpiMock::new(&expected_transactions);
-        let mut driver = Max7219::new(&mut spi);
+        let mut driver = Max7219::new(&mut spi).assume_initialized();
 
         driver
             .write_raw_digit(device_index, digit, data)
@@ -643,6 +2014,85 @@ mod tests {
         spi.done();
     }
 
+    #[test]
+    #[cfg(feature = "matrix")]
+    fn test_write_canvas() {
+        let mut expected_transactions = Vec::new();
+        for digit_register in Register::digits() {
+            let (first, second) = if digit_register == Register::Digit1 {
+                (0x80, 0x01)
+            } else {
+                (0x00, 0x00)
+            };
+            expected_transactions.push(Transaction::transaction_start());
+            expected_transactions.push(Transaction::write_vec(vec![
+                digit_register.addr(),
+                first,
+                digit_register.addr(),
+                second,
+            ]));
+            expected_transactions.push(Transaction::transaction_end());
+        }
+        let mut spi = SpiMock::new(&expected_transactions);
+        let mut driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+
+        let mut canvas = Canvas::for_chain(2).unwrap();
+        canvas.set_pixel(0, 1, true).unwrap();
+        canvas.set_pixel(15, 1, true).unwrap();
+        driver.write_canvas(&canvas).expect("should write canvas");
+        spi.done();
+    }
+
+    #[test]
+    #[cfg(feature = "matrix")]
+    fn test_encode_canvas_and_register() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        let mut buf = [0; MAX_TILES_SIZE];
+        assert_eq!(
+            driver.encode_register(&mut buf, 1, Register::Intensity, 5),
+            Ok(&[0x00, 0x00, 0x0A, 0x05][..])
+        );
+        assert_eq!(
+            driver.encode_register(&mut buf, 2, Register::Intensity, 5),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+
+        let mut canvas = Canvas::for_chain(2).unwrap();
+        canvas.set_pixel(15, 1, true).unwrap();
+        let frames = driver.encode_canvas(&canvas, &mut buf).unwrap();
+        assert_eq!(frames[4..8], [0x02, 0x00, 0x02, 0x01]);
+        assert_eq!(
+            driver.encode_canvas(&canvas, &mut buf[..31]),
+            Err(Error::BufferTooSmall)
+        );
+
+        assert_eq!(
+            driver.encode_canvas(&Canvas::for_chain(1).unwrap(), &mut buf),
+            Err(Error::InvalidCanvasSize)
+        );
+        // Nothing was sent
+        spi.done();
+    }
+
+    #[test]
+    #[cfg(feature = "matrix")]
+    fn test_write_canvas_size_mismatch() {
+        let mut spi = SpiMock::new(&[]);
+        let mut driver = Max7219::new(&mut spi);
+
+        let canvas = Canvas::for_chain(2).unwrap();
+        assert_eq!(driver.write_canvas(&canvas), Err(Error::InvalidCanvasSize));
+        spi.done();
+    }
+
     #[test]
     fn test_set_device_decode_mode() {
         let mode = DecodeMode::Digits0To3;
@@ -659,4 +2109,404 @@ mod tests {
             .expect("Set decode mode failed");
         spi.done();
     }
+
+    #[test]
+    fn test_decode_mask_tracking() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        assert_eq!(driver.decode_mask(0), Ok(0x00));
+        driver.set_decode_mode_all(DecodeMode::AllDigits).unwrap();
+        driver
+            .set_device_decode_mode(1, DecodeMode::Digits0To3)
+            .unwrap();
+        assert_eq!(driver.decode_mask(0), Ok(0xFF));
+        assert_eq!(driver.decode_mask(1), Ok(0x0F));
+        assert_eq!(
+            driver.decode_mask(2),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_digit_writes_check_decode_mode() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi);
+            driver.init().unwrap();
+            driver
+                .set_device_decode_mode(0, DecodeMode::Digits0To3)
+                .unwrap();
+
+            assert_eq!(
+                driver.write_raw_digit(0, 2, 0x30),
+                Err(Error::DecodeModeMismatch)
+            );
+            assert_eq!(
+                driver.write_decoded_digit(0, 5, 0x01),
+                Err(Error::DecodeModeMismatch)
+            );
+            assert_eq!(
+                driver.write_decoded_digit(1, 2, 0x01),
+                Err(Error::InvalidDeviceIndex {
+                    index: 1,
+                    device_count: 1
+                })
+            );
+            driver.write_decoded_digit(0, 2, 0x87).unwrap();
+            driver.write_raw_digit(0, 5, 0x30).unwrap();
+
+            #[cfg(feature = "matrix")]
+            assert_eq!(
+                driver.write_canvas(&Canvas::for_chain(1).unwrap()),
+                Err(Error::DecodeModeMismatch)
+            );
+        }
+
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[2], 0x87);
+        assert_eq!(digits[5], 0x30);
+    }
+
+    #[test]
+    fn test_digit_writes_check_scan_limit() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        driver.init().unwrap();
+
+        assert_eq!(driver.scan_limit(0), Ok(8));
+        driver.set_scan_limit_all(6).unwrap();
+        driver.set_device_scan_limit(1, 4).unwrap();
+        assert_eq!(driver.scan_limit(0), Ok(6));
+        assert_eq!(driver.scan_limit(1), Ok(4));
+        assert_eq!(
+            driver.scan_limit(2),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+
+        driver.write_raw_digit(0, 5, 0x01).unwrap();
+        driver.write_raw_digit(1, 3, 0x01).unwrap();
+        assert_eq!(
+            driver.write_raw_digit(1, 4, 0x01),
+            Err(Error::DigitNotScanned)
+        );
+        driver.set_decode_mode_all(DecodeMode::AllDigits).unwrap();
+        assert_eq!(
+            driver.write_decoded_digit(0, 6, 0x01),
+            Err(Error::DigitNotScanned)
+        );
+    }
+
+    #[test]
+    fn test_write_row_and_clear_digit() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.init().unwrap();
+            driver.write_row(1, 3, 0xA5).unwrap();
+            driver.write_row(1, 4, 0x5A).unwrap();
+            driver.clear_digit(1, 4).unwrap();
+
+            driver
+                .set_device_decode_mode(0, DecodeMode::AllDigits)
+                .unwrap();
+            driver.write_decoded_digit(0, 0, 0x08).unwrap();
+            driver.clear_digit(0, 0).unwrap();
+
+            assert_eq!(driver.write_row(0, 8, 0xFF), Err(Error::InvalidDigit));
+            assert_eq!(driver.clear_digit(0, 8), Err(Error::InvalidDigit));
+            assert_eq!(driver.clear_digit(0, 255), Err(Error::InvalidDigit));
+            assert_eq!(
+                driver.clear_digit(2, 0),
+                Err(Error::InvalidDeviceIndex {
+                    index: 2,
+                    device_count: 2
+                })
+            );
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(1).unwrap()[3], 0xA5);
+        assert_eq!(emulator.digits(1).unwrap()[4], 0x00);
+        assert_eq!(emulator.digits(0).unwrap()[0], CODE_B_BLANK);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            driver.fill_all(0xAA).unwrap();
+            driver.fill(1, 0xFF).unwrap();
+
+            driver
+                .set_device_decode_mode(0, DecodeMode::Digit0)
+                .unwrap();
+            assert_eq!(driver.fill(0, 0x00), Err(Error::DecodeModeMismatch));
+            assert_eq!(driver.fill_all(0x00), Err(Error::DecodeModeMismatch));
+            assert_eq!(
+                driver.fill(2, 0x00),
+                Err(Error::InvalidDeviceIndex {
+                    index: 2,
+                    device_count: 2
+                })
+            );
+        }
+        // 8 chain-wide frames, then 8 for the single device, then decode mode
+        assert_eq!(spi.transactions(), 17);
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap(), [0xAA; 8]);
+        assert_eq!(emulator.digits(1).unwrap(), [0xFF; 8]);
+    }
+
+    #[test]
+    fn test_write_frame_all() {
+        let frame = [0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x7E, 0x3C, 0x18];
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(3)
+                .unwrap();
+            driver.write_frame_all(&frame).unwrap();
+
+            driver
+                .set_device_decode_mode(2, DecodeMode::Digit0)
+                .unwrap();
+            assert_eq!(
+                driver.write_frame_all(&frame),
+                Err(Error::DecodeModeMismatch)
+            );
+        }
+        assert_eq!(spi.transactions(), 9);
+        let emulator = spi.emulator();
+        for device_index in 0..3 {
+            assert_eq!(emulator.digits(device_index).unwrap(), frame);
+        }
+    }
+
+    #[test]
+    fn test_write_digit_all() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(3)
+                .unwrap();
+            driver.write_digit_all(2, 0x81).unwrap();
+            assert_eq!(driver.write_digit_all(8, 0x81), Err(Error::InvalidDigit));
+
+            driver.set_device_scan_limit(1, 2).unwrap();
+            assert_eq!(driver.write_digit_all(2, 0xFF), Err(Error::DigitNotScanned));
+            driver
+                .set_device_decode_mode(2, DecodeMode::Digit0)
+                .unwrap();
+            assert_eq!(
+                driver.write_digit_all(0, 0xFF),
+                Err(Error::DecodeModeMismatch)
+            );
+        }
+        // One frame for the digit, then scan limit and decode mode
+        assert_eq!(spi.transactions(), 3);
+        let emulator = spi.emulator();
+        for device_index in 0..3 {
+            assert_eq!(emulator.digits(device_index).unwrap()[2], 0x81);
+            assert_eq!(emulator.digits(device_index).unwrap()[1], 0x00);
+        }
+    }
+
+    #[test]
+    fn test_loopback_verify() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .with_device_count(2)
+                .unwrap()
+                .with_loopback_verify(true);
+            assert!(driver.loopback_verify());
+            driver.init().unwrap();
+            driver.write_row(1, 2, 0x81).unwrap();
+            driver.fill_all(0x18).unwrap();
+        }
+        assert_eq!(spi.emulator().digits(1).unwrap()[2], 0x18);
+
+        let mut driver = Max7219::new(&mut spi)
+            .with_device_count(2)
+            .unwrap()
+            .with_loopback_verify(true);
+        driver.power_on().unwrap();
+        // Noise shifts a stray packet into the chain
+        driver.spi.emulator_mut().shift_packet(0x01, 0xFF);
+        assert_eq!(driver.power_on(), Err(Error::LoopbackMismatch));
+        // The chain holds what was just sent again
+        driver.power_on().unwrap();
+    }
+
+    #[test]
+    fn test_detect_device_count() {
+        for count in [0, 1, 3, MAX_DISPLAYS] {
+            let mut spi = EmulatedSpi::new(count).unwrap();
+            let mut driver = Max7219::new(&mut spi);
+            assert_eq!(driver.detect_device_count(), Ok(count));
+        }
+
+        // DOUT not wired back, MISO reads zeros
+        let mut spi = SpiMock::new(&[
+            Transaction::transaction_start(),
+            Transaction::transfer(
+                [
+                    &[0; MAX_DISPLAYS * 2][..],
+                    &[0x00, 0xA5],
+                    &[0; MAX_DISPLAYS * 2],
+                ]
+                .concat(),
+                vec![0; (2 * MAX_DISPLAYS + 1) * 2],
+            ),
+            Transaction::transaction_end(),
+        ]);
+        let mut driver = Max7219::new(&mut spi);
+        assert_eq!(driver.detect_device_count(), Err(Error::LoopbackMismatch));
+        spi.done();
+    }
+
+    #[test]
+    fn test_variant() {
+        let mut spi = SpiMock::new(&[]);
+        let driver = Max7219::new(&mut spi);
+        assert_eq!(driver.variant(), Variant::Max7219);
+        let driver = driver.with_variant(Variant::Max7221);
+        assert_eq!(driver.variant(), Variant::Max7221);
+        assert!(driver.variant().can_share_bus());
+        assert!(!Variant::Max7219.dout_tristated());
+        assert_eq!(format!("{}", driver.variant()), "MAX7221");
+        spi.done();
+    }
+
+    #[test]
+    fn test_settle_delay() {
+        let expectations = [
+            Transaction::transaction_start(),
+            Transaction::delay(2_000),
+            Transaction::write_vec(vec![Register::Shutdown.addr(), 0x01]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expectations);
+        let mut driver = Max7219::new(&mut spi).with_settle_delay_ns(2_000);
+        assert_eq!(driver.settle_delay_ns(), 2_000);
+        driver.power_on().unwrap();
+
+        driver.set_settle_delay_ns(0);
+        assert_eq!(driver.settle_delay_ns(), 0);
+        spi.done();
+    }
+
+    #[test]
+    fn test_loopback_detects_short_chain() {
+        // Only one of the two configured devices answers
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi)
+            .with_device_count(2)
+            .unwrap()
+            .with_loopback_verify(true);
+        driver
+            .write_device_register(0, Register::Intensity, 1)
+            .unwrap();
+        assert_eq!(
+            driver.write_device_register(1, Register::Intensity, 2),
+            Err(Error::LoopbackMismatch)
+        );
+    }
+
+    #[test]
+    fn test_not_initialized() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            assert_eq!(driver.write_row(0, 0, 0xFF), Err(Error::NotInitialized));
+            assert_eq!(driver.fill(1, 0xFF), Err(Error::NotInitialized));
+            assert_eq!(driver.fill_all(0xFF), Err(Error::NotInitialized));
+            assert_eq!(driver.write_digit_all(0, 0xFF), Err(Error::NotInitialized));
+        }
+        assert_eq!(spi.transactions(), 0);
+
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        driver.init().unwrap();
+        driver.write_row(0, 0, 0xFF).unwrap();
+    }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn test_strict_checks() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        driver.init().unwrap();
+
+        // Lit rows beyond a scan limit would never show
+        driver.set_device_scan_limit(1, 4).unwrap();
+        let mut tiles = [[0; 8]; 2];
+        tiles[1][3] = 0x01;
+        driver.write_tiles(&tiles).unwrap();
+        tiles[1][4] = 0x01;
+        assert_eq!(driver.write_tiles(&tiles), Err(Error::DigitNotScanned));
+    }
+
+    #[test]
+    fn test_write_digits() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(3)
+                .unwrap();
+            driver.set_decode_mode_all(DecodeMode::AllDigits).unwrap();
+            let chars = [
+                CodeBChar::digit(1).unwrap(),
+                CodeBChar::digit(2).unwrap().with_dp(),
+                CodeBChar::DASH,
+            ];
+            driver.write_digits(2, 4, &chars).unwrap();
+            assert_eq!(driver.write_digits(2, 6, &chars), Err(Error::InvalidDigit));
+
+            // Digits 6 and 7 of device 0, then 0 and 1 of device 1
+            let hello = [CodeBChar::H, CodeBChar::E, CodeBChar::L, CodeBChar::L];
+            let stats = driver.stats();
+            driver.write_chain_digits(6, &hello).unwrap();
+            assert_eq!(driver.stats().transactions - stats.transactions, 4);
+
+            // Nine positions across two devices share eight transactions
+            let eights = [CodeBChar::digit(8).unwrap(); 9];
+            let stats = driver.stats();
+            driver.write_chain_digits(8, &eights).unwrap();
+            assert_eq!(driver.stats().transactions - stats.transactions, 8);
+
+            assert_eq!(
+                driver.write_chain_digits(20, &eights),
+                Err(Error::InvalidDigit)
+            );
+            assert_eq!(
+                driver.write_chain_digits(usize::MAX, &hello),
+                Err(Error::InvalidDigit)
+            );
+            driver
+                .set_device_decode_mode(1, DecodeMode::Digits0To3)
+                .unwrap();
+            assert_eq!(
+                driver.write_chain_digits(10, &hello),
+                Err(Error::DecodeModeMismatch)
+            );
+        }
+        let emulator = spi.emulator();
+        let last = emulator.digits(2).unwrap();
+        assert_eq!((last[0], &last[4..7]), (0x08, &[0x01, 0x82, 0x0A][..]));
+        assert_eq!(emulator.digits(0).unwrap()[6..], [0x0C, 0x0B]);
+        assert_eq!(emulator.digits(1).unwrap()[..2], [0x08, 0x08]);
+    }
 }
@@ -0,0 +1,175 @@
+use core::{cell::RefCell, ops::Range};
+
+use embedded_hal::spi::{Error as _, ErrorKind, ErrorType, Operation, SpiDevice};
+
+use super::Max7219;
+use crate::{
+    MAX_DISPLAYS, Result,
+    encoder::{MAX_FRAME_SIZE, PACKET_SIZE},
+    error::Error,
+};
+
+/// A chain mixing module types, e.g. two 8x8 matrices followed by an
+/// 8-digit 7-segment board, split into segments of consecutive devices
+/// that each get a [`Max7219`] of their own, e.g. wrapped in a
+/// [`LedMatrix`](crate::matrix::LedMatrix) for devices `0..2` and a
+/// [`SevenSegment`](crate::seven_segment::SevenSegment) for `2..3`.
+///
+/// Each driver only configures and draws on its own devices, with its own
+/// decode mode, scan limit and intensity; the rest of the chain is sent
+/// NoOps and keeps what it shows.
+pub struct MixedChain<SPI> {
+    spi: RefCell<SPI>,
+    device_count: usize,
+}
+
+impl<SPI> MixedChain<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if `device_count` exceeds [`MAX_DISPLAYS`].
+    pub fn new(spi: SPI, device_count: usize) -> Result<Self> {
+        if device_count > MAX_DISPLAYS {
+            return Err(Error::InvalidDeviceCount);
+        }
+        Ok(Self {
+            spi: RefCell::new(spi),
+            device_count,
+        })
+    }
+
+    pub fn device_count(&self) -> usize {
+        self.device_count
+    }
+
+    /// [`SpiDevice`] addressing the chain's devices `devices` as a chain of
+    /// their own, device `devices.start` being its device 0.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if `devices` is empty or reaches past
+    ///   the end of the chain.
+    pub fn segment(&self, devices: Range<usize>) -> Result<ChainSegment<'_, SPI>> {
+        if devices.is_empty() || devices.end > self.device_count {
+            return Err(Error::InvalidDeviceCount);
+        }
+        Ok(ChainSegment {
+            chain: self,
+            devices,
+        })
+    }
+
+    /// Driver for the devices `devices`, see [`segment`](Self::segment).
+    pub fn driver(&self, devices: Range<usize>) -> Result<Max7219<ChainSegment<'_, SPI>>> {
+        let segment = self.segment(devices)?;
+        let count = segment.device_count();
+        Max7219::new(segment).with_device_count(count)
+    }
+
+    pub fn into_inner(self) -> SPI {
+        self.spi.into_inner()
+    }
+}
+
+/// Consecutive devices of a [`MixedChain`]. Frames written to it are padded
+/// with NoOps for the other devices. Transactions can hold one write and
+/// delays; anything else fails with [`ErrorKind::Other`].
+pub struct ChainSegment<'a, SPI> {
+    chain: &'a MixedChain<SPI>,
+    devices: Range<usize>,
+}
+
+impl<SPI> ChainSegment<'_, SPI> {
+    pub fn device_count(&self) -> usize {
+        self.devices.len()
+    }
+}
+
+impl<SPI> ErrorType for ChainSegment<'_, SPI> {
+    type Error = ErrorKind;
+}
+
+impl<SPI> SpiDevice for ChainSegment<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), ErrorKind> {
+        let mut padded = [0; MAX_FRAME_SIZE];
+        let mut frame_len = 0;
+        let mut delay_ns = 0u32;
+        for operation in operations.iter() {
+            match operation {
+                Operation::DelayNs(ns) => delay_ns = delay_ns.saturating_add(*ns),
+                Operation::Write(frame) if frame_len == 0 => {
+                    // The chain's first packet goes to device 0, so NoOps
+                    // for the devices before the segment lead the frame
+                    let start = self.devices.start * PACKET_SIZE;
+                    let len = frame.len().min(self.devices.len() * PACKET_SIZE);
+                    padded[start..start + len].copy_from_slice(&frame[..len]);
+                    frame_len = self.chain.device_count * PACKET_SIZE;
+                }
+                // Only one frame per transaction: the chips latch on CS release
+                _ => return Err(ErrorKind::Other),
+            }
+        }
+        let mut physical = [
+            Operation::DelayNs(delay_ns),
+            Operation::Write(&padded[..frame_len]),
+        ];
+        let skip = usize::from(delay_ns == 0);
+        self.chain
+            .spi
+            .borrow_mut()
+            .transaction(&mut physical[skip..])
+            .map_err(|err| err.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::EmulatedSpi;
+    #[cfg(all(feature = "matrix", feature = "sevenseg"))]
+    use crate::{matrix::LedMatrix, registers::DecodeMode, seven_segment::SevenSegment};
+
+    #[test]
+    #[cfg(all(feature = "matrix", feature = "sevenseg"))]
+    fn test_matrix_and_seven_segment() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        {
+            let chain = MixedChain::new(&mut spi, 3).unwrap();
+            let mut matrix = LedMatrix::new(chain.driver(0..2).unwrap()).unwrap();
+            let mut digits = SevenSegment::new(chain.driver(2..3).unwrap());
+            matrix.init().unwrap();
+            digits.init().unwrap();
+            digits
+                .driver_mut()
+                .set_device_decode_mode(0, DecodeMode::AllDigits)
+                .unwrap();
+            digits.driver_mut().set_intensity(0, 4).unwrap();
+
+            // The 7-segment board's decode mode doesn't block matrix frames
+            matrix.set_pixel_xy(15, 0, true).unwrap();
+            matrix.flush().unwrap();
+            digits.driver_mut().write_decoded_digit(0, 3, 7).unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(1).unwrap()[0], 0x01);
+        assert_eq!(emulator.device(2).unwrap().decode_mode, 0xFF);
+        assert_eq!(emulator.device(2).unwrap().intensity, 4);
+        assert_eq!(emulator.digits(2).unwrap()[3], 7);
+        assert_eq!(emulator.device(0).unwrap().decode_mode, 0x00);
+        assert_eq!(emulator.device(0).unwrap().intensity, 0);
+    }
+
+    #[test]
+    fn test_invalid_segments() {
+        let chain = MixedChain::new(EmulatedSpi::new(3).unwrap(), 3).unwrap();
+        assert_eq!(chain.segment(2..4).err(), Some(Error::InvalidDeviceCount));
+        assert_eq!(chain.segment(1..1).err(), Some(Error::InvalidDeviceCount));
+        assert!(MixedChain::new(EmulatedSpi::new(1).unwrap(), MAX_DISPLAYS + 1).is_err());
+    }
+}
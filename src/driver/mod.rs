@@ -1,3 +1,46 @@
+mod ambient;
+mod batch;
+mod blinker;
+mod brightness;
+mod configure;
+mod display_test;
+#[cfg(feature = "eh02")]
+mod eh02;
+#[cfg(feature = "linux")]
+mod linux;
+mod loopback;
 mod max7219;
+mod mixed;
+#[cfg(feature = "heapless")]
+mod queue;
+#[cfg(feature = "sevenseg")]
+mod schedule;
+mod self_test;
+#[cfg(feature = "critical-section")]
+mod shared;
+mod star;
+mod stats;
+mod variant;
 
+pub use ambient::AutoDimmer;
+pub use batch::Batch;
+pub use blinker::Blinker;
+pub use brightness::brightness_to_intensity;
+pub use configure::Configure;
+pub use display_test::DisplayTestGuard;
+#[cfg(feature = "eh02")]
+pub use eh02::{Eh02Device, Max7219Eh02};
+#[cfg(feature = "linux")]
+pub use linux::{Max7219Linux, SPIDEV_SPEED_HZ};
 pub use max7219::Max7219;
+pub use mixed::{ChainSegment, MixedChain};
+#[cfg(feature = "heapless")]
+pub use queue::{Command, CommandQueue};
+#[cfg(feature = "sevenseg")]
+pub use schedule::BrightnessSchedule;
+pub use self_test::SELF_TEST_STEP_MS;
+#[cfg(feature = "critical-section")]
+pub use shared::SharedMax7219;
+pub use star::StarBus;
+pub use stats::BusStats;
+pub use variant::Variant;
@@ -0,0 +1,163 @@
+use embedded_hal::spi::SpiDevice;
+use heapless::spsc::{Consumer, Queue};
+
+use super::Max7219;
+use crate::Result;
+
+/// Fixed-capacity queue of [`Command`]s, holding up to `N - 1` of them.
+///
+/// [`split`](Queue::split) it: interrupt handlers push through the
+/// `Producer` without touching SPI, and the main loop drains the `Consumer`
+/// with [`Max7219::process`].
+pub type CommandQueue<const N: usize> = Queue<Command, N>;
+
+/// Display update deferred through a [`CommandQueue`], applied with the
+/// driver method of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    PowerOn,
+    PowerOff,
+    PowerOnDevice(usize),
+    PowerOffDevice(usize),
+    TestAll(bool),
+    ClearAll,
+    FillAll(u8),
+    SetIntensity {
+        device_index: usize,
+        intensity: u8,
+    },
+    SetIntensityAll(u8),
+    WriteRow {
+        device_index: usize,
+        row: u8,
+        value: u8,
+    },
+    WriteRawDigit {
+        device_index: usize,
+        digit: u8,
+        value: u8,
+    },
+    WriteDecodedDigit {
+        device_index: usize,
+        digit: u8,
+        value: u8,
+    },
+}
+
+impl Command {
+    pub fn apply<SPI>(self, driver: &mut Max7219<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        match self {
+            Self::PowerOn => driver.power_on(),
+            Self::PowerOff => driver.power_off(),
+            Self::PowerOnDevice(device_index) => driver.power_on_device(device_index),
+            Self::PowerOffDevice(device_index) => driver.power_off_device(device_index),
+            Self::TestAll(enable) => driver.test_all(enable),
+            Self::ClearAll => driver.clear_all(),
+            Self::FillAll(pattern) => driver.fill_all(pattern),
+            Self::SetIntensity {
+                device_index,
+                intensity,
+            } => driver.set_intensity(device_index, intensity),
+            Self::SetIntensityAll(intensity) => driver.set_intensity_all(intensity),
+            Self::WriteRow {
+                device_index,
+                row,
+                value,
+            } => driver.write_row(device_index, row, value),
+            Self::WriteRawDigit {
+                device_index,
+                digit,
+                value,
+            } => driver.write_raw_digit(device_index, digit, value),
+            Self::WriteDecodedDigit {
+                device_index,
+                digit,
+                value,
+            } => driver.write_decoded_digit(device_index, digit, value),
+        }
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Apply every queued command in order. Returns how many were applied.
+    ///
+    /// # Errors
+    /// The first error of a command; that command is dropped and the rest
+    /// stay queued for the next call.
+    pub fn process<const N: usize>(
+        &mut self,
+        commands: &mut Consumer<'_, Command, N>,
+    ) -> Result<usize> {
+        let mut applied = 0;
+        while let Some(command) = commands.dequeue() {
+            command.apply(self)?;
+            applied += 1;
+        }
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, host::EmulatedSpi};
+
+    #[test]
+    fn test_process_drains_queue() {
+        let mut queue = CommandQueue::<8>::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.init().unwrap();
+
+            // As an interrupt handler would
+            producer.enqueue(Command::PowerOn).unwrap();
+            producer
+                .enqueue(Command::SetIntensity {
+                    device_index: 1,
+                    intensity: 9,
+                })
+                .unwrap();
+            producer
+                .enqueue(Command::WriteRow {
+                    device_index: 0,
+                    row: 2,
+                    value: 0x3C,
+                })
+                .unwrap();
+            assert_eq!(driver.process(&mut consumer), Ok(3));
+            assert_eq!(driver.process(&mut consumer), Ok(0));
+        }
+        let emulator = spi.emulator();
+        assert!(!emulator.device(1).unwrap().shutdown);
+        assert_eq!(emulator.device(1).unwrap().intensity, 9);
+        assert_eq!(emulator.digits(0).unwrap()[2], 0x3C);
+    }
+
+    #[test]
+    fn test_process_stops_at_error() {
+        let mut queue = CommandQueue::<4>::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi);
+
+        producer.enqueue(Command::SetIntensityAll(16)).unwrap();
+        producer.enqueue(Command::ClearAll).unwrap();
+        producer.enqueue(Command::TestAll(false)).unwrap();
+        // Capacity is N - 1
+        assert_eq!(producer.enqueue(Command::PowerOff), Err(Command::PowerOff));
+
+        assert_eq!(
+            driver.process(&mut consumer),
+            Err(Error::InvalidIntensity { value: 16, max: 15 })
+        );
+        assert_eq!(driver.process(&mut consumer), Ok(2));
+    }
+}
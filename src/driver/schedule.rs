@@ -0,0 +1,125 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+use crate::{Result, registers::Intensity, seven_segment::TimeOfDay};
+
+/// Chain intensity by time of day, e.g. dimming a clock at night.
+///
+/// Each entry `(start, intensity)` applies from `start` until the start of
+/// the entry that follows it in the day; the latest entry carries on past
+/// midnight until the earliest one. Feed the current time to
+/// [`update`](Self::update) from the clock's tick.
+#[derive(Debug, Clone)]
+pub struct BrightnessSchedule<'a> {
+    entries: &'a [(TimeOfDay, u8)],
+    // Intensity written by the last update
+    applied: Option<u8>,
+}
+
+impl<'a> BrightnessSchedule<'a> {
+    /// Entries may be in any order.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`](crate::error::Error::InvalidIntensity)
+    ///   if an intensity is above 15.
+    pub fn new(entries: &'a [(TimeOfDay, u8)]) -> Result<Self> {
+        for &(_, intensity) in entries {
+            Intensity::try_from(intensity)?;
+        }
+        Ok(Self {
+            entries,
+            applied: None,
+        })
+    }
+
+    /// Intensity scheduled at `now`, `None` without entries.
+    pub fn intensity_at(&self, now: TimeOfDay) -> Option<u8> {
+        let now = seconds_of_day(now);
+        let latest_before = self
+            .entries
+            .iter()
+            .filter(|(start, _)| seconds_of_day(*start) <= now)
+            .max_by_key(|(start, _)| seconds_of_day(*start));
+        latest_before
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .max_by_key(|(start, _)| seconds_of_day(*start))
+            })
+            .map(|&(_, intensity)| intensity)
+    }
+
+    /// Apply the intensity scheduled at `now` to every device. Only writes
+    /// when it differs from the last update; returns whether it wrote.
+    pub fn update<SPI>(&mut self, driver: &mut Max7219<SPI>, now: TimeOfDay) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        let Some(intensity) = self.intensity_at(now) else {
+            return Ok(false);
+        };
+        if self.applied == Some(intensity) {
+            return Ok(false);
+        }
+        driver.set_intensity_all(intensity)?;
+        self.applied = Some(intensity);
+        Ok(true)
+    }
+
+    /// Make the next update write even if the level is unchanged, e.g.
+    /// after the intensity was changed by other means.
+    pub fn reset(&mut self) {
+        self.applied = None;
+    }
+}
+
+fn seconds_of_day(time: TimeOfDay) -> u32 {
+    (u32::from(time.hours()) * 60 + u32::from(time.minutes())) * 60 + u32::from(time.seconds())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, host::EmulatedSpi};
+
+    fn at(hours: u8, minutes: u8) -> TimeOfDay {
+        TimeOfDay::new(hours, minutes, 0).unwrap()
+    }
+
+    #[test]
+    fn test_intensity_at() {
+        let entries = [(at(22, 0), 1), (at(7, 0), 12), (at(19, 30), 6)];
+        let schedule = BrightnessSchedule::new(&entries).unwrap();
+        assert_eq!(schedule.intensity_at(at(12, 0)), Some(12));
+        assert_eq!(schedule.intensity_at(at(19, 30)), Some(6));
+        assert_eq!(schedule.intensity_at(at(23, 59)), Some(1));
+        // Before the earliest entry the night level carries on
+        assert_eq!(schedule.intensity_at(at(3, 0)), Some(1));
+
+        assert_eq!(
+            BrightnessSchedule::new(&[]).unwrap().intensity_at(at(0, 0)),
+            None
+        );
+        assert!(matches!(
+            BrightnessSchedule::new(&[(at(0, 0), 16)]),
+            Err(Error::InvalidIntensity { value: 16, max: 15 })
+        ));
+    }
+
+    #[test]
+    fn test_update_writes_on_change() {
+        let entries = [(at(7, 0), 12), (at(22, 0), 1)];
+        let mut schedule = BrightnessSchedule::new(&entries).unwrap();
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            assert_eq!(schedule.update(&mut driver, at(21, 58)), Ok(true));
+            assert_eq!(schedule.update(&mut driver, at(21, 59)), Ok(false));
+            assert_eq!(schedule.update(&mut driver, at(22, 0)), Ok(true));
+            schedule.reset();
+            assert_eq!(schedule.update(&mut driver, at(22, 1)), Ok(true));
+        }
+        assert_eq!(spi.transactions(), 3);
+        assert_eq!(spi.emulator().device(1).unwrap().intensity, 1);
+    }
+}
@@ -0,0 +1,122 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::Max7219;
+use crate::{MAX_DISPLAYS, Result, registers::Register};
+
+/// How long each step of [`Max7219::run_self_test`] is shown.
+pub const SELF_TEST_STEP_MS: u32 = 250;
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Walk the chain through a visual self-test, showing each step for
+    /// [`SELF_TEST_STEP_MS`] using `delay`:
+    ///
+    /// 1. display-test mode on each device in turn, lighting every LED of
+    ///    one module at a time,
+    /// 2. one lit row (digit) at a time from DIG0 to DIG7 on all devices,
+    /// 3. everything lit while stepping through all 16 intensity levels.
+    ///
+    /// Devices are left cleared with their previous intensities. Rows beyond
+    /// a device's scan limit stay dark during the sweep.
+    ///
+    /// # Errors
+    /// - [`Error::DecodeModeMismatch`](crate::error::Error::DecodeModeMismatch)
+    ///   if any device has Code B decoding enabled; nothing is shown then.
+    /// - Any SPI error; the test stops there and may leave LEDs lit.
+    pub fn run_self_test<D>(&mut self, delay: &mut D) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        let count = self.device_count();
+        self.fill_all(0x00)?;
+
+        self.test_all(false)?;
+        for device_index in 0..count {
+            self.test_device(device_index, true)?;
+            delay.delay_ms(SELF_TEST_STEP_MS);
+            self.test_device(device_index, false)?;
+        }
+
+        for digit_register in Register::digits() {
+            let lit = [(digit_register, 0xFF); MAX_DISPLAYS];
+            self.write_all_registers(&lit[..count])?;
+            delay.delay_ms(SELF_TEST_STEP_MS);
+            let dark = [(digit_register, 0x00); MAX_DISPLAYS];
+            self.write_all_registers(&dark[..count])?;
+        }
+
+        let mut saved = [0; MAX_DISPLAYS];
+        for (device_index, intensity) in saved[..count].iter_mut().enumerate() {
+            *intensity = self.intensity(device_index)?;
+        }
+        self.fill_all(0xFF)?;
+        for level in 0..=0x0F {
+            self.set_intensity_all(level)?;
+            delay.delay_ms(SELF_TEST_STEP_MS);
+        }
+        self.set_intensities(&saved[..count])?;
+        self.fill_all(0x00)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, host::EmulatedSpi, registers::DecodeMode};
+
+    /// Counts the steps waited for.
+    #[derive(Default)]
+    struct CountingDelay {
+        steps: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+
+        fn delay_ms(&mut self, ms: u32) {
+            assert_eq!(ms, SELF_TEST_STEP_MS);
+            self.steps += 1;
+        }
+    }
+
+    #[test]
+    fn test_self_test_restores_state() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+            driver.init().unwrap();
+            driver.set_intensities(&[2, 9, 15]).unwrap();
+            driver.fill(1, 0xAA).unwrap();
+
+            driver.run_self_test(&mut delay).unwrap();
+            assert_eq!(driver.intensity(1), Ok(9));
+        }
+        assert_eq!(delay.steps, 3 + 8 + 16);
+
+        let emulator = spi.emulator();
+        for (device_index, intensity) in [2, 9, 15].into_iter().enumerate() {
+            let device = emulator.device(device_index).unwrap();
+            assert!(!device.display_test);
+            assert_eq!(device.intensity, intensity);
+            assert_eq!(device.digits, [0x00; 8]);
+        }
+    }
+
+    #[test]
+    fn test_self_test_rejects_decode_mode() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut driver = Max7219::new(&mut spi);
+        driver.init().unwrap();
+        driver.set_decode_mode_all(DecodeMode::Digits0To3).unwrap();
+
+        let mut delay = CountingDelay::default();
+        assert_eq!(
+            driver.run_self_test(&mut delay),
+            Err(Error::DecodeModeMismatch)
+        );
+        assert_eq!(delay.steps, 0);
+    }
+}
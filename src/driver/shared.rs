@@ -0,0 +1,76 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embedded_hal::spi::SpiDevice;
+
+use super::Max7219;
+
+/// [`Max7219`] behind a `critical_section::Mutex`, so several firmware
+/// modules (or interrupt handlers) can share one chain, e.g. from a
+/// `static`.
+///
+/// Every call of [`lock`](Self::lock) runs inside a critical section with
+/// exclusive access to the driver and its whole API. Keep the closures
+/// short: interrupts stay masked for the SPI writes they make.
+pub struct SharedMax7219<SPI> {
+    driver: Mutex<RefCell<Max7219<SPI>>>,
+}
+
+impl<SPI> SharedMax7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    pub const fn new(driver: Max7219<SPI>) -> Self {
+        Self {
+            driver: Mutex::new(RefCell::new(driver)),
+        }
+    }
+
+    /// Run `f` with exclusive access to the driver.
+    ///
+    /// # Panics
+    /// If called again from within `f`.
+    pub fn lock<R>(&self, f: impl FnOnce(&mut Max7219<SPI>) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.driver.borrow_ref_mut(cs)))
+    }
+
+    pub fn into_inner(self) -> Max7219<SPI> {
+        self.driver.into_inner().into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::EmulatedSpi;
+
+    #[test]
+    fn test_modules_share_the_chain() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let shared = SharedMax7219::new(driver);
+
+            // Two independent users of the same chain
+            let show_clock = |bits| shared.lock(|driver| driver.write_row(0, 0, bits));
+            let show_status = || {
+                shared.lock(|driver| {
+                    driver.power_on_device(1)?;
+                    driver.write_row(1, 7, 0x01)
+                })
+            };
+            show_clock(0x42).unwrap();
+            show_status().unwrap();
+
+            let driver = shared.into_inner();
+            assert_eq!(driver.stats().transactions, 3);
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap()[0], 0x42);
+        assert_eq!(emulator.digits(1).unwrap()[7], 0x01);
+        assert!(!emulator.device(1).unwrap().shutdown);
+    }
+}
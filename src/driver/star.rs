@@ -0,0 +1,110 @@
+use embedded_hal::spi::{Error as _, ErrorKind, ErrorType, Operation, SpiDevice};
+
+use crate::{encoder::PACKET_SIZE, registers::Register};
+
+/// [`SpiDevice`] for modules that each have their own chip-select line
+/// instead of being daisy-chained DOUT to DIN, so a [`Max7219`](super::Max7219)
+/// and everything built on it drive them like a chain of `N` devices.
+///
+/// `devices[i]` is device `i`, e.g. one `embedded-hal-bus` device per CS
+/// pin sharing the bus. Each chain frame is split up: device `i` is sent
+/// packet `i` of the frame in a transaction of its own, and NoOp packets
+/// are skipped. A delay in a transaction is repeated before each packet;
+/// reads and transfers fail with [`ErrorKind::Other`], so loopback checks
+/// don't work.
+#[derive(Debug)]
+pub struct StarBus<D, const N: usize> {
+    devices: [D; N],
+}
+
+impl<D, const N: usize> StarBus<D, N>
+where
+    D: SpiDevice,
+{
+    pub fn new(devices: [D; N]) -> Self {
+        Self { devices }
+    }
+
+    pub fn device_mut(&mut self, device_index: usize) -> Option<&mut D> {
+        self.devices.get_mut(device_index)
+    }
+
+    pub fn release(self) -> [D; N] {
+        self.devices
+    }
+
+    fn write_frame(&mut self, frame: &[u8], delay_ns: u32) -> Result<(), ErrorKind> {
+        for (device, packet) in self.devices.iter_mut().zip(frame.chunks_exact(PACKET_SIZE)) {
+            if packet.first() == Some(&Register::NoOp.addr()) {
+                continue;
+            }
+            let mut operations = [Operation::DelayNs(delay_ns), Operation::Write(packet)];
+            let skip = usize::from(delay_ns == 0);
+            device
+                .transaction(&mut operations[skip..])
+                .map_err(|err| err.kind())?;
+        }
+        Ok(())
+    }
+}
+
+impl<D, const N: usize> ErrorType for StarBus<D, N> {
+    type Error = ErrorKind;
+}
+
+impl<D, const N: usize> SpiDevice for StarBus<D, N>
+where
+    D: SpiDevice,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> {
+        let mut delay_ns = 0u32;
+        for operation in operations.iter() {
+            match operation {
+                Operation::DelayNs(ns) => delay_ns = delay_ns.saturating_add(*ns),
+                Operation::Write(frame) => self.write_frame(frame, delay_ns)?,
+                _ => return Err(ErrorKind::Other),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::EmulatedSpi;
+    #[cfg(feature = "matrix")]
+    use crate::{driver::Max7219, matrix::LedMatrix};
+
+    #[test]
+    #[cfg(feature = "matrix")]
+    fn test_star_wired_matrix() {
+        let mut left = EmulatedSpi::new(1).unwrap();
+        let mut right = EmulatedSpi::new(1).unwrap();
+        {
+            let bus = StarBus::new([&mut left, &mut right]);
+            let driver = Max7219::new(bus).with_device_count(2).unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.init().unwrap();
+            matrix.set_pixel_xy(0, 0, true).unwrap();
+            matrix.set_pixel_xy(15, 7, true).unwrap();
+            matrix.flush().unwrap();
+            matrix.driver_mut().set_intensity(1, 9).unwrap();
+        }
+        assert!(!left.emulator().device(0).unwrap().shutdown);
+        assert_eq!(left.emulator().digits(0).unwrap()[0], 0x80);
+        assert_eq!(right.emulator().digits(0).unwrap()[7], 0x01);
+        assert_eq!(right.emulator().device(0).unwrap().intensity, 9);
+        assert_eq!(left.emulator().device(0).unwrap().intensity, 0);
+    }
+
+    #[test]
+    fn test_rejects_reads() {
+        let mut bus = StarBus::new([EmulatedSpi::new(1).unwrap()]);
+        let mut read = [0; 2];
+        assert_eq!(
+            bus.transfer(&mut read, &[0x0C, 0x01]),
+            Err(ErrorKind::Other)
+        );
+    }
+}
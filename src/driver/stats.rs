@@ -0,0 +1,88 @@
+/// SPI traffic counted by a [`Max7219`](super::Max7219) since
+/// [`init`](super::Max7219::init) or
+/// [`reset_stats`](super::Max7219::reset_stats), e.g. to check that
+/// batching or diffing actually reduces bus traffic.
+///
+/// Counters saturate instead of wrapping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BusStats {
+    /// Chip-select frames attempted, failed ones included
+    pub transactions: u32,
+    /// Bytes of the frames that were written successfully
+    pub bytes: u32,
+    /// Frames the SPI device reported an error for
+    pub errors: u32,
+}
+
+impl BusStats {
+    pub(crate) fn record(&mut self, len: usize, ok: bool) {
+        self.transactions = self.transactions.saturating_add(1);
+        if ok {
+            let len = u32::try_from(len).unwrap_or(u32::MAX);
+            self.bytes = self.bytes.saturating_add(len);
+        } else {
+            self.errors = self.errors.saturating_add(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+
+    use super::*;
+    use crate::{driver::Max7219, error::Error, host::EmulatedSpi};
+
+    /// Rejects every transaction.
+    struct FailingSpi;
+
+    impl ErrorType for FailingSpi {
+        type Error = ErrorKind;
+    }
+
+    impl SpiDevice for FailingSpi {
+        fn transaction(&mut self, _: &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> {
+            Err(ErrorKind::Other)
+        }
+    }
+
+    #[test]
+    fn test_counts_traffic() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+        driver.set_intensity(1, 4).unwrap();
+        driver.init().unwrap();
+        // init starts counting from zero: power, test, scan, decode, 8 digits
+        assert_eq!(
+            driver.stats(),
+            BusStats {
+                transactions: 12,
+                bytes: 12 * 6,
+                errors: 0
+            }
+        );
+        driver.fill_all(0xFF).unwrap();
+        assert_eq!(driver.stats().transactions, 20);
+
+        driver.reset_stats();
+        assert_eq!(driver.stats(), BusStats::default());
+    }
+
+    #[test]
+    fn test_counts_errors() {
+        let mut driver = Max7219::new(FailingSpi);
+        assert_eq!(driver.power_on(), Err(Error::SpiError(ErrorKind::Other)));
+        assert_eq!(
+            driver.set_intensity(0, 1),
+            Err(Error::SpiError(ErrorKind::Other))
+        );
+        assert_eq!(
+            driver.stats(),
+            BusStats {
+                transactions: 2,
+                bytes: 0,
+                errors: 2
+            }
+        );
+    }
+}
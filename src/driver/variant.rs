@@ -0,0 +1,41 @@
+use core::fmt;
+
+/// Which of the register-compatible Maxim parts the chain is built from.
+///
+/// Both take the same 16-bit packets and latch on the rising edge of
+/// LOAD/CS, so the driver sends the same frames to either; the
+/// differences only matter for how the chain may be wired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Shifts DIN in on every clock edge, selected or not, and always
+    /// drives DOUT
+    #[default]
+    Max7219,
+    /// SPI-compliant: ignores the clock while CS is high and tri-states
+    /// DOUT, with slew-rate limited segment drivers for lower EMI
+    Max7221,
+}
+
+impl Variant {
+    /// Whether the chain can share its SPI bus with other devices. A
+    /// MAX7219 chain clocks in their traffic too, which is harmless for the
+    /// display but makes the bytes shifted out of it unpredictable.
+    pub fn can_share_bus(self) -> bool {
+        self == Self::Max7221
+    }
+
+    /// Whether the last device's DOUT can share a MISO line with other
+    /// devices' outputs.
+    pub fn dout_tristated(self) -> bool {
+        self == Self::Max7221
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Max7219 => write!(f, "MAX7219"),
+            Self::Max7221 => write!(f, "MAX7221"),
+        }
+    }
+}
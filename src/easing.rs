@@ -0,0 +1,91 @@
+/// Shape of a transition over time, shared by brightness fades (see
+/// [`Max7219::with_fade_easing`](crate::driver::Max7219::with_fade_easing))
+/// and the matrix text scroller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Slow start, quadratic
+    EaseIn,
+    /// Slow end, quadratic
+    EaseOut,
+    /// Slow start and end, quadratic; suits "breathing" standby effects
+    EaseInOut,
+    /// Slow start doubling its pace every tenth of the transition
+    Exponential,
+}
+
+impl Easing {
+    /// Progress of the transition (0 to `u16::MAX`) after the fraction
+    /// `time` (0 to `u16::MAX`) of its duration.
+    pub const fn apply(self, time: u16) -> u16 {
+        const FULL: u32 = u16::MAX as u32;
+        let t = time as u32;
+        let eased = match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t / FULL,
+            Self::EaseOut => FULL - (FULL - t) * (FULL - t) / FULL,
+            Self::EaseInOut => {
+                if t < FULL / 2 {
+                    2 * t * t / FULL
+                } else {
+                    FULL - 2 * (FULL - t) * (FULL - t) / FULL
+                }
+            }
+            Self::Exponential => {
+                // 2^(10t) - 1, interpolated linearly between powers of two
+                let exponent = t * 10;
+                let pow = (FULL + exponent % FULL) << (exponent / FULL);
+                ((pow - FULL) as u64 * FULL as u64 / (FULL as u64 * 1023)) as u32
+            }
+        };
+        eased as u16
+    }
+
+    /// Earliest fraction of the duration at which the progress reaches
+    /// `progress`.
+    pub(crate) const fn time_for(self, progress: u16) -> u16 {
+        let (mut low, mut high) = (0u32, u16::MAX as u32);
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.apply(mid as u16) >= progress {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        low as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::Exponential,
+        ] {
+            assert_eq!(easing.apply(0), 0);
+            assert_eq!(easing.apply(u16::MAX), u16::MAX);
+            let mut previous = 0;
+            for time in (0..=u16::MAX).step_by(257) {
+                let progress = easing.apply(time);
+                assert!(progress >= previous, "{easing:?} at {time}");
+                previous = progress;
+            }
+            assert!(easing.apply(easing.time_for(20_000)) >= 20_000);
+        }
+        assert!(Easing::EaseIn.apply(16_384) < 16_384);
+        assert!(Easing::EaseOut.apply(16_384) > 16_384);
+        assert_eq!(Easing::EaseInOut.apply(32_767), 32_767);
+        assert!(Easing::Exponential.apply(32_768) < Easing::EaseIn.apply(32_768));
+        assert_eq!(Easing::Linear.time_for(1234), 1234);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{MAX_DISPLAYS, NUM_DIGITS, Result, error::Error, registers::Register};
+
+/// Bytes per device in a chain frame: register address, then data
+pub const PACKET_SIZE: usize = 2;
+
+/// Largest frame a chain of [`MAX_DISPLAYS`] devices needs
+pub const MAX_FRAME_SIZE: usize = MAX_DISPLAYS * PACKET_SIZE;
+
+/// Bytes [`encode_tiles`] needs for a chain of [`MAX_DISPLAYS`] devices
+pub const MAX_TILES_SIZE: usize = NUM_DIGITS as usize * MAX_FRAME_SIZE;
+
+/// The 16-bit packet setting `register` to `data` on one device.
+pub const fn packet(register: Register, data: u8) -> [u8; PACKET_SIZE] {
+    [register.addr(), data]
+}
+
+/// Encode a frame writing `data` to `register` of one device, with every
+/// other device of a `device_count` chain sent a NoOp.
+///
+/// Returns the `device_count * PACKET_SIZE` bytes to send in a single
+/// chip-select frame; the first packet goes to device 0.
+///
+/// # Errors
+/// - [`Error::InvalidDeviceCount`] if `device_count` exceeds [`MAX_DISPLAYS`].
+/// - [`Error::InvalidDeviceIndex`] if `device_index` is not below `device_count`.
+/// - [`Error::BufferTooSmall`] if `buf` can't hold the frame.
+pub fn encode_device(
+    buf: &mut [u8],
+    device_count: usize,
+    device_index: usize,
+    register: Register,
+    data: u8,
+) -> Result<&[u8]> {
+    let len = frame_len(device_count)?;
+    if device_index >= device_count {
+        return Err(Error::InvalidDeviceIndex {
+            index: device_index,
+            device_count,
+        });
+    }
+    let frame = buf.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+    for (i, bytes) in frame.chunks_exact_mut(PACKET_SIZE).enumerate() {
+        let packet = if i == device_index {
+            packet(register, data)
+        } else {
+            packet(Register::NoOp, 0)
+        };
+        bytes.copy_from_slice(&packet);
+    }
+    Ok(frame)
+}
+
+/// Overwrite the packet of one device in an encoded frame, leaving the
+/// other packets untouched.
+///
+/// # Errors
+/// - [`Error::BufferTooSmall`] if `frame` has no packet for `device_index`.
+pub fn write_packet(
+    frame: &mut [u8],
+    device_index: usize,
+    register: Register,
+    data: u8,
+) -> Result<()> {
+    frame
+        .chunks_exact_mut(PACKET_SIZE)
+        .nth(device_index)
+        .ok_or(Error::BufferTooSmall)?
+        .copy_from_slice(&packet(register, data));
+    Ok(())
+}
+
+/// Encode a frame writing `ops[i]` to device `i` of a `device_count`
+/// chain. Devices past the end of `ops` are sent a NoOp.
+///
+/// Returns the `device_count * PACKET_SIZE` bytes to send in a single
+/// chip-select frame.
+///
+/// # Errors
+/// - [`Error::InvalidDeviceCount`] if `device_count` exceeds [`MAX_DISPLAYS`]
+///   or there are more ops than devices.
+/// - [`Error::BufferTooSmall`] if `buf` can't hold the frame.
+pub fn encode_chain<'b>(
+    buf: &'b mut [u8],
+    device_count: usize,
+    ops: &[(Register, u8)],
+) -> Result<&'b [u8]> {
+    let len = frame_len(device_count)?;
+    if ops.len() > device_count {
+        return Err(Error::InvalidDeviceCount);
+    }
+    let frame = buf.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+
+    let noops = core::iter::repeat((Register::NoOp, 0));
+    for (bytes, (register, data)) in frame
+        .chunks_exact_mut(PACKET_SIZE)
+        .zip(ops.iter().copied().chain(noops))
+    {
+        bytes.copy_from_slice(&packet(register, data));
+    }
+    Ok(frame)
+}
+
+/// Encode the 8 frames that show one 8-row tile per device, `tiles[0]` on
+/// device 0: frame n sets digit register n of every device.
+///
+/// Returns the frames back to back, each `tiles.len() * PACKET_SIZE`
+/// bytes. Each frame must be sent in its own chip-select frame, e.g. as
+/// separate DMA transfers with CS toggled in between.
+///
+/// # Errors
+/// - [`Error::InvalidDeviceCount`] if there are more tiles than [`MAX_DISPLAYS`].
+/// - [`Error::BufferTooSmall`] if `buf` can't hold all frames.
+pub fn encode_tiles<'b>(
+    buf: &'b mut [u8],
+    tiles: &[[u8; NUM_DIGITS as usize]],
+) -> Result<&'b [u8]> {
+    let frame_size = frame_len(tiles.len())?;
+    let frames = buf
+        .get_mut(..NUM_DIGITS as usize * frame_size)
+        .ok_or(Error::BufferTooSmall)?;
+    if frame_size == 0 {
+        return Ok(frames);
+    }
+
+    let rows = frames.chunks_exact_mut(frame_size).zip(Register::digits());
+    for (row, (frame, digit_register)) in rows.enumerate() {
+        for (bytes, tile) in frame.chunks_exact_mut(PACKET_SIZE).zip(tiles) {
+            let data = tile.get(row).copied().ok_or(Error::InvalidDigit)?;
+            bytes.copy_from_slice(&packet(digit_register, data));
+        }
+    }
+    Ok(frames)
+}
+
+/// Bytes of a frame for `device_count` devices.
+fn frame_len(device_count: usize) -> Result<usize> {
+    if device_count > MAX_DISPLAYS {
+        return Err(Error::InvalidDeviceCount);
+    }
+    Ok(device_count * PACKET_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_device() {
+        let mut buf = [0xAA; MAX_FRAME_SIZE];
+        for device_index in 0..3 {
+            let frame = encode_device(&mut buf, 3, device_index, Register::Intensity, 7).unwrap();
+            assert_eq!(frame.len(), 6);
+            for (i, bytes) in frame.chunks(PACKET_SIZE).enumerate() {
+                let expected = if i == device_index {
+                    [Register::Intensity.addr(), 7]
+                } else {
+                    [Register::NoOp.addr(), 0]
+                };
+                assert_eq!(bytes, expected);
+            }
+        }
+
+        assert_eq!(
+            encode_device(&mut buf, 3, 3, Register::NoOp, 0),
+            Err(Error::InvalidDeviceIndex {
+                index: 3,
+                device_count: 3
+            })
+        );
+        assert_eq!(
+            encode_device(&mut buf, 9, 0, Register::NoOp, 0),
+            Err(Error::InvalidDeviceCount)
+        );
+    }
+
+    #[test]
+    fn test_write_packet() {
+        let mut frame = [0; 6];
+        write_packet(&mut frame, 2, Register::ScanLimit, 3).unwrap();
+        write_packet(&mut frame, 0, Register::Digit7, 0x42).unwrap();
+        assert_eq!(frame, [0x08, 0x42, 0x00, 0x00, 0x0B, 0x03]);
+        assert_eq!(
+            write_packet(&mut frame, 3, Register::NoOp, 0),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(
+            write_packet(&mut frame, usize::MAX, Register::NoOp, 0),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_encode_chain() {
+        let mut buf = [0xAA; MAX_FRAME_SIZE];
+        let ops = [(Register::Digit0, 0x81), (Register::Shutdown, 0x01)];
+        assert_eq!(
+            encode_chain(&mut buf, 3, &ops),
+            Ok(&[0x01, 0x81, 0x0C, 0x01, 0x00, 0x00][..])
+        );
+        assert_eq!(encode_chain(&mut buf, 0, &[]), Ok(&[][..]));
+
+        assert_eq!(
+            encode_chain(&mut buf, 1, &ops),
+            Err(Error::InvalidDeviceCount)
+        );
+        assert_eq!(
+            encode_chain(&mut buf[..5], 3, &ops),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_encode_tiles() {
+        let mut buf = [0xAA; MAX_TILES_SIZE];
+        let tiles = [[0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17], [0xFF; 8]];
+        let frames = encode_tiles(&mut buf, &tiles).unwrap();
+        assert_eq!(frames.len(), 8 * 4);
+        for (row, frame) in frames.chunks(4).enumerate() {
+            let digit = Register::Digit0.addr() + row as u8;
+            assert_eq!(frame, [digit, 0x10 + row as u8, digit, 0xFF]);
+        }
+
+        assert_eq!(encode_tiles(&mut buf, &[]), Ok(&[][..]));
+        assert_eq!(
+            encode_tiles(&mut buf[..31], &tiles),
+            Err(Error::BufferTooSmall)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use embedded_hal::spi::ErrorKind;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     /// The specified device count is invalid (exceeds maximum allowed).
@@ -7,34 +9,90 @@ pub enum Error {
     /// The specified register address is not valid for the MAX7219.
     InvalidRegister,
     /// Invalid device index (exceeds configured number of devices)
-    InvalidDeviceIndex,
+    InvalidDeviceIndex { index: usize, device_count: usize },
     /// Invalid digit position (0-7 for MAX7219)
     InvalidDigit,
-    /// Invalid intensity value (must be 0-15)
-    InvalidIntensity,
-    /// SPI communication error
-    SpiError,
+    /// Invalid intensity value (must be 0-15, or 0-`max` where noted)
+    InvalidIntensity { value: u8, max: u8 },
+    /// SPI communication error, with the kind the SPI device reported
+    SpiError(ErrorKind),
+    /// Character has no custom or built-in 7-segment glyph
+    UnsupportedChar,
+    /// Text needs more digits than the display provides
+    TextTooLong,
+    /// No room left to register another custom glyph
+    GlyphTableFull,
+    /// Number does not fit in the available digits
+    Overflow,
+    /// Pixel coordinate outside the matrix
+    InvalidPixel,
+    /// Canvas dimensions are not whole modules or exceed the supported chain
+    InvalidCanvasSize,
+    /// Time or date field out of range
+    InvalidTime,
+    /// Raw write to a Code B decoded digit, or Code B write to a raw digit
+    DecodeModeMismatch,
+    /// Digit is beyond the device's scan limit and would not be displayed
+    DigitNotScanned,
+    /// Segment map is not a permutation of the bits 0-7
+    InvalidSegmentMap,
+    /// Image data is malformed or not in a supported format
+    InvalidBitmap,
+    /// Frame lights more segments than the configured current limit allows
+    CurrentLimitExceeded,
+    /// Buffer is too small for the encoded frame
+    BufferTooSmall,
+    /// Display data written before `Max7219::init`
+    NotInitialized,
+    /// Bytes are not a valid protocol message
+    InvalidMessage,
+    /// Bytes read back from the chain's DOUT are not the ones shifted in
+    LoopbackMismatch,
 }
 
 impl<E> From<E> for Error
 where
     E: embedded_hal::spi::Error,
 {
-    fn from(_value: E) -> Self {
-        Self::SpiError
+    fn from(value: E) -> Self {
+        Self::SpiError(value.kind())
     }
 }
 
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::SpiError => write!(f, "SPI communication error"),
-            Self::InvalidDeviceIndex => write!(f, "Invalid device index"),
+            Self::SpiError(kind) => write!(f, "SPI communication error: {kind}"),
+            Self::InvalidDeviceIndex {
+                index,
+                device_count,
+            } => write!(
+                f,
+                "Invalid device index {index} (chain has {device_count} devices)"
+            ),
             Self::InvalidDigit => write!(f, "Invalid digit"),
-            Self::InvalidIntensity => write!(f, "Invalid intensity value"),
+            Self::InvalidIntensity { value, max } => {
+                write!(f, "Invalid intensity value {value} (must be 0-{max})")
+            }
             Self::InvalidScanLimit => write!(f, "Invalid scan limit value"),
             Self::InvalidDeviceCount => write!(f, "Invalid device count"),
             Self::InvalidRegister => write!(f, "Invalid register address"),
+            Self::UnsupportedChar => write!(f, "Unsupported character"),
+            Self::TextTooLong => write!(f, "Text too long for display"),
+            Self::GlyphTableFull => write!(f, "Custom glyph table full"),
+            Self::Overflow => write!(f, "Number does not fit display"),
+            Self::InvalidPixel => write!(f, "Invalid pixel coordinate"),
+            Self::InvalidCanvasSize => write!(f, "Invalid canvas size"),
+            Self::InvalidTime => write!(f, "Invalid time or date"),
+            Self::DecodeModeMismatch => write!(f, "Digit decode mode does not match write"),
+            Self::DigitNotScanned => write!(f, "Digit outside scan limit"),
+            Self::InvalidSegmentMap => write!(f, "Invalid segment map"),
+            Self::InvalidBitmap => write!(f, "Invalid bitmap data"),
+            Self::CurrentLimitExceeded => write!(f, "Frame exceeds current limit"),
+            Self::BufferTooSmall => write!(f, "Buffer too small"),
+            Self::NotInitialized => write!(f, "Driver not initialized"),
+            Self::InvalidMessage => write!(f, "Invalid protocol message"),
+            Self::LoopbackMismatch => write!(f, "Loopback data mismatch"),
         }
     }
 }
@@ -54,8 +112,8 @@ mod tests {
     }
 
     impl embedded_hal::spi::Error for MockSpiError {
-        fn kind(&self) -> embedded_hal::spi::ErrorKind {
-            embedded_hal::spi::ErrorKind::Other
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
         }
     }
 
@@ -74,15 +132,79 @@ mod tests {
             "Invalid register address"
         );
         assert_eq!(
-            format!("{}", Error::InvalidDeviceIndex),
-            "Invalid device index"
+            format!(
+                "{}",
+                Error::InvalidDeviceIndex {
+                    index: 4,
+                    device_count: 2
+                }
+            ),
+            "Invalid device index 4 (chain has 2 devices)"
         );
         assert_eq!(format!("{}", Error::InvalidDigit), "Invalid digit");
         assert_eq!(
-            format!("{}", Error::InvalidIntensity),
-            "Invalid intensity value"
+            format!("{}", Error::InvalidIntensity { value: 16, max: 15 }),
+            "Invalid intensity value 16 (must be 0-15)"
+        );
+        assert_eq!(
+            format!("{}", Error::SpiError(ErrorKind::ChipSelectFault)),
+            format!("SPI communication error: {}", ErrorKind::ChipSelectFault)
+        );
+        assert_eq!(
+            format!("{}", Error::UnsupportedChar),
+            "Unsupported character"
+        );
+        assert_eq!(
+            format!("{}", Error::TextTooLong),
+            "Text too long for display"
+        );
+        assert_eq!(
+            format!("{}", Error::GlyphTableFull),
+            "Custom glyph table full"
+        );
+        assert_eq!(
+            format!("{}", Error::Overflow),
+            "Number does not fit display"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidPixel),
+            "Invalid pixel coordinate"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidCanvasSize),
+            "Invalid canvas size"
+        );
+        assert_eq!(format!("{}", Error::InvalidTime), "Invalid time or date");
+        assert_eq!(
+            format!("{}", Error::DecodeModeMismatch),
+            "Digit decode mode does not match write"
+        );
+        assert_eq!(
+            format!("{}", Error::DigitNotScanned),
+            "Digit outside scan limit"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidSegmentMap),
+            "Invalid segment map"
+        );
+        assert_eq!(format!("{}", Error::InvalidBitmap), "Invalid bitmap data");
+        assert_eq!(
+            format!("{}", Error::CurrentLimitExceeded),
+            "Frame exceeds current limit"
+        );
+        assert_eq!(format!("{}", Error::BufferTooSmall), "Buffer too small");
+        assert_eq!(
+            format!("{}", Error::NotInitialized),
+            "Driver not initialized"
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidMessage),
+            "Invalid protocol message"
+        );
+        assert_eq!(
+            format!("{}", Error::LoopbackMismatch),
+            "Loopback data mismatch"
         );
-        assert_eq!(format!("{}", Error::SpiError), "SPI communication error");
     }
 
     #[test]
@@ -97,7 +219,7 @@ mod tests {
     fn test_from_spi_error() {
         let spi_error = MockSpiError;
         let error = Error::from(spi_error);
-        assert_eq!(error, Error::SpiError);
+        assert_eq!(error, Error::SpiError(ErrorKind::Other));
     }
 
     #[test]
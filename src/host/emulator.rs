@@ -0,0 +1,207 @@
+use crate::{MAX_DISPLAYS, NUM_DIGITS, Result, error::Error, registers::Register};
+
+/// Register contents of one emulated MAX7219.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceState {
+    /// Digit registers DIG0 to DIG7
+    pub digits: [u8; NUM_DIGITS as usize],
+    pub decode_mode: u8,
+    pub intensity: u8,
+    /// Raw scan limit register value (number of scanned digits minus one)
+    pub scan_limit: u8,
+    /// `true` while the chip is in shutdown mode
+    pub shutdown: bool,
+    pub display_test: bool,
+}
+
+impl Default for DeviceState {
+    /// The chip powers up in shutdown mode with every register cleared.
+    fn default() -> Self {
+        Self {
+            digits: [0; NUM_DIGITS as usize],
+            decode_mode: 0,
+            intensity: 0,
+            scan_limit: 0,
+            shutdown: true,
+            display_test: false,
+        }
+    }
+}
+
+impl DeviceState {
+    fn apply(&mut self, address: u8, data: u8) {
+        match address {
+            a if a == Register::NoOp.addr() => {}
+            a if (Register::Digit0.addr()..=Register::Digit7.addr()).contains(&a) => {
+                self.digits[usize::from(a - Register::Digit0.addr())] = data;
+            }
+            a if a == Register::DecodeMode.addr() => self.decode_mode = data,
+            a if a == Register::Intensity.addr() => self.intensity = data & 0x0F,
+            a if a == Register::ScanLimit.addr() => self.scan_limit = data & 0x07,
+            a if a == Register::Shutdown.addr() => self.shutdown = data & 0x01 == 0,
+            a if a == Register::DisplayTest.addr() => self.display_test = data & 0x01 != 0,
+            _ => {}
+        }
+    }
+}
+
+/// Software model of a daisy chain of MAX7219 chips.
+///
+/// Packets are shifted in through the nearest device and move one device
+/// further per packet, exactly like the chips' 16-bit shift registers. On
+/// [`latch`](Self::latch) (CS rising edge) every device applies whatever
+/// packet currently sits in its shift register, so short writes re-latch
+/// stale packets just as the hardware would.
+#[derive(Debug, Clone)]
+pub struct Emulator {
+    devices: [DeviceState; MAX_DISPLAYS],
+    shift: [(u8, u8); MAX_DISPLAYS],
+    device_count: usize,
+}
+
+impl Emulator {
+    pub fn new(device_count: usize) -> Result<Self> {
+        if device_count > MAX_DISPLAYS {
+            return Err(Error::InvalidDeviceCount);
+        }
+        Ok(Self {
+            devices: [DeviceState::default(); MAX_DISPLAYS],
+            shift: [(0, 0); MAX_DISPLAYS],
+            device_count,
+        })
+    }
+
+    pub fn device_count(&self) -> usize {
+        self.device_count
+    }
+
+    /// Shift one 16-bit packet into the chain.
+    ///
+    /// Returns the packet pushed out of the furthest device's DOUT.
+    pub fn shift_packet(&mut self, address: u8, data: u8) -> (u8, u8) {
+        if self.device_count == 0 {
+            return (address, data);
+        }
+        let chain = &mut self.shift[..self.device_count];
+        let out = chain[0];
+        chain.rotate_left(1);
+        chain[self.device_count - 1] = (address, data);
+        out
+    }
+
+    /// Shift raw bytes into the chain, two bytes per packet.
+    ///
+    /// A trailing odd byte is ignored.
+    pub fn shift_bytes(&mut self, bytes: &[u8]) {
+        for packet in bytes.chunks_exact(2) {
+            self.shift_packet(packet[0], packet[1]);
+        }
+    }
+
+    /// Apply the packets held in every shift register (CS rising edge).
+    pub fn latch(&mut self) {
+        for (device, &(address, data)) in self
+            .devices
+            .iter_mut()
+            .zip(&self.shift[..self.device_count])
+        {
+            device.apply(address, data);
+        }
+    }
+
+    /// Shift `bytes` in and latch them as one chip-select frame.
+    pub fn write(&mut self, bytes: &[u8]) {
+        self.shift_bytes(bytes);
+        self.latch();
+    }
+
+    pub fn device(&self, device_index: usize) -> Option<&DeviceState> {
+        self.devices[..self.device_count].get(device_index)
+    }
+
+    /// Digit registers of one device, or `None` for an invalid index.
+    pub fn digits(&self, device_index: usize) -> Option<[u8; NUM_DIGITS as usize]> {
+        self.device(device_index).map(|device| device.digits)
+    }
+
+    /// Reset every device to its power-up state.
+    pub fn reset(&mut self) {
+        self.devices = [DeviceState::default(); MAX_DISPLAYS];
+        self.shift = [(0, 0); MAX_DISPLAYS];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_up_state() {
+        let emulator = Emulator::new(2).unwrap();
+        let device = emulator.device(1).unwrap();
+        assert!(device.shutdown);
+        assert_eq!(device.digits, [0; 8]);
+        assert!(emulator.device(2).is_none());
+    }
+
+    #[test]
+    fn test_invalid_device_count() {
+        assert!(matches!(
+            Emulator::new(MAX_DISPLAYS + 1),
+            Err(Error::InvalidDeviceCount)
+        ));
+    }
+
+    #[test]
+    fn test_first_packet_reaches_furthest_device() {
+        let mut emulator = Emulator::new(3).unwrap();
+        emulator.write(&[
+            Register::Digit0.addr(),
+            0xAA,
+            Register::NoOp.addr(),
+            0x00,
+            Register::Intensity.addr(),
+            0x07,
+        ]);
+
+        assert_eq!(emulator.digits(0).unwrap()[0], 0xAA);
+        assert_eq!(emulator.digits(1).unwrap(), [0; 8]);
+        assert_eq!(emulator.device(2).unwrap().intensity, 0x07);
+    }
+
+    #[test]
+    fn test_short_frame_relatches_stale_packets() {
+        let mut emulator = Emulator::new(2).unwrap();
+        emulator.write(&[Register::Digit1.addr(), 0x11, Register::Digit2.addr(), 0x22]);
+        // A single packet lands on device 1 and pushes the old device-1 packet to device 0
+        emulator.write(&[Register::Digit3.addr(), 0x33]);
+
+        assert_eq!(emulator.digits(0).unwrap()[2], 0x22);
+        assert_eq!(emulator.digits(1).unwrap()[3], 0x33);
+    }
+
+    #[test]
+    fn test_control_registers() {
+        let mut emulator = Emulator::new(1).unwrap();
+        emulator.write(&[Register::Shutdown.addr(), 0x01]);
+        emulator.write(&[Register::ScanLimit.addr(), 0x03]);
+        emulator.write(&[Register::DecodeMode.addr(), 0xFF]);
+        emulator.write(&[Register::DisplayTest.addr(), 0x01]);
+
+        let device = emulator.device(0).unwrap();
+        assert!(!device.shutdown);
+        assert_eq!(device.scan_limit, 3);
+        assert_eq!(device.decode_mode, 0xFF);
+        assert!(device.display_test);
+
+        emulator.reset();
+        assert!(emulator.device(0).unwrap().shutdown);
+    }
+
+    #[test]
+    fn test_shift_out() {
+        let mut emulator = Emulator::new(1).unwrap();
+        assert_eq!(emulator.shift_packet(0x01, 0x02), (0x00, 0x00));
+        assert_eq!(emulator.shift_packet(0x03, 0x04), (0x01, 0x02));
+    }
+}
@@ -0,0 +1,13 @@
+mod emulator;
+#[cfg(any(test, feature = "test-util"))]
+mod recording;
+mod spi;
+#[cfg(any(test, feature = "std"))]
+mod terminal;
+
+pub use emulator::{DeviceState, Emulator};
+#[cfg(any(test, feature = "test-util"))]
+pub use recording::RecordingInterface;
+pub use spi::EmulatedSpi;
+#[cfg(any(test, feature = "std"))]
+pub use terminal::TerminalSpi;
@@ -0,0 +1,173 @@
+use core::convert::Infallible;
+
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+use crate::{MAX_DISPLAYS, Result, encoder::PACKET_SIZE, error::Error, registers::Register};
+
+/// [`SpiDevice`] that records every 16-bit packet the driver sends, per
+/// device, for unit-testing display logic without a mock expecting exact
+/// byte sequences.
+///
+/// Packets are `(register address << 8) | data`; in each frame the first
+/// packet is device 0's, as built by the [`encoder`](crate::encoder).
+/// Up to `N` packets are kept per device; later ones are counted in
+/// [`dropped`](Self::dropped). Never fails.
+#[derive(Debug, Clone)]
+pub struct RecordingInterface<const N: usize = 64> {
+    device_count: usize,
+    packets: [[u16; N]; MAX_DISPLAYS],
+    lens: [usize; MAX_DISPLAYS],
+    transactions: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> RecordingInterface<N> {
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if `device_count` exceeds [`MAX_DISPLAYS`].
+    pub fn new(device_count: usize) -> Result<Self> {
+        if device_count > MAX_DISPLAYS {
+            return Err(Error::InvalidDeviceCount);
+        }
+        Ok(Self {
+            device_count,
+            packets: [[0; N]; MAX_DISPLAYS],
+            lens: [0; MAX_DISPLAYS],
+            transactions: 0,
+            dropped: 0,
+        })
+    }
+
+    /// Every packet device `device_index` received, NoOps included, oldest
+    /// first. Empty for devices not on the chain.
+    pub fn packets(&self, device_index: usize) -> &[u16] {
+        match (self.packets.get(device_index), self.lens.get(device_index)) {
+            (Some(packets), Some(&len)) => packets.get(..len).unwrap_or_default(),
+            _ => &[],
+        }
+    }
+
+    /// `(register, data)` of every write device `device_index` received,
+    /// skipping NoOps.
+    pub fn writes(&self, device_index: usize) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.packets(device_index)
+            .iter()
+            .map(|&packet| ((packet >> 8) as u8, packet as u8))
+            .filter(|&(register, _)| register != Register::NoOp.addr())
+    }
+
+    /// Data of the last write to `register` of device `device_index`.
+    pub fn last_write(&self, device_index: usize, register: Register) -> Option<u8> {
+        self.writes(device_index)
+            .filter(|&(address, _)| address == register.addr())
+            .last()
+            .map(|(_, data)| data)
+    }
+
+    /// Number of chip-select frames seen so far.
+    pub fn transactions(&self) -> usize {
+        self.transactions
+    }
+
+    /// Packets that didn't fit in the `N` kept per device.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Forget everything recorded so far.
+    pub fn clear(&mut self) {
+        self.lens = [0; MAX_DISPLAYS];
+        self.transactions = 0;
+        self.dropped = 0;
+    }
+
+    fn record(&mut self, frame: &[u8]) {
+        let devices = self.packets[..self.device_count]
+            .iter_mut()
+            .zip(&mut self.lens);
+        for ((packets, len), bytes) in devices.zip(frame.chunks_exact(PACKET_SIZE)) {
+            let packet = u16::from_be_bytes([bytes[0], bytes[1]]);
+            match packets.get_mut(*len) {
+                Some(slot) => {
+                    *slot = packet;
+                    *len += 1;
+                }
+                None => self.dropped += 1,
+            }
+        }
+    }
+}
+
+impl<const N: usize> ErrorType for RecordingInterface<N> {
+    type Error = Infallible;
+}
+
+impl<const N: usize> SpiDevice for RecordingInterface<N> {
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), Infallible> {
+        for operation in operations.iter_mut() {
+            match operation {
+                Operation::Write(write) => self.record(write),
+                Operation::Transfer(read, write) => {
+                    read.fill(0);
+                    self.record(write);
+                }
+                Operation::TransferInPlace(buf) => {
+                    self.record(buf);
+                    buf.fill(0);
+                }
+                Operation::Read(read) => read.fill(0),
+                Operation::DelayNs(_) => {}
+            }
+        }
+        self.transactions += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driver::Max7219;
+    #[cfg(feature = "matrix")]
+    use crate::matrix::LedMatrix;
+
+    #[test]
+    fn test_records_packets_per_device() {
+        let mut spi = RecordingInterface::<16>::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.set_intensity(1, 5).unwrap();
+            driver.power_on().unwrap();
+        }
+        assert_eq!(spi.transactions(), 2);
+        assert_eq!(spi.packets(0), &[0x0000, 0x0C01]);
+        assert_eq!(spi.packets(1), &[0x0A05, 0x0C01]);
+        assert_eq!(spi.writes(0).collect::<std::vec::Vec<_>>(), [(0x0C, 0x01)]);
+        assert_eq!(spi.last_write(1, Register::Intensity), Some(5));
+        assert_eq!(spi.last_write(0, Register::Intensity), None);
+        assert!(spi.packets(2).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "matrix")]
+    fn test_drops_past_capacity() {
+        let mut spi = RecordingInterface::<4>::new(1).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi).assume_initialized()).unwrap();
+            matrix.set_row(0, 7, 0x81).unwrap();
+            matrix.flush().unwrap();
+        }
+        assert_eq!(spi.packets(0).len(), 4);
+        assert_eq!(spi.dropped(), 4);
+        assert_eq!(spi.last_write(0, Register::Digit7), None);
+
+        spi.clear();
+        assert!(spi.packets(0).is_empty());
+        assert_eq!(
+            RecordingInterface::<4>::new(9).err(),
+            Some(Error::InvalidDeviceCount)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use core::convert::Infallible;
+
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+
+use super::Emulator;
+use crate::Result;
+
+/// In-memory [`SpiDevice`] that feeds every write into an [`Emulator`].
+///
+/// Each `transaction` is one chip-select frame: all written bytes are
+/// shifted in and latched when the transaction ends. Reads return the bytes
+/// shifted out of the last device's DOUT. Never fails.
+///
+/// # Panics
+/// - On a `TransferInPlace` longer than `MAX_DISPLAYS * 2` bytes, which no
+///   real chain could accept.
+#[derive(Debug, Clone)]
+pub struct EmulatedSpi {
+    emulator: Emulator,
+    transactions: usize,
+    bytes_written: usize,
+}
+
+impl EmulatedSpi {
+    pub fn new(device_count: usize) -> Result<Self> {
+        Ok(Self {
+            emulator: Emulator::new(device_count)?,
+            transactions: 0,
+            bytes_written: 0,
+        })
+    }
+
+    pub fn emulator(&self) -> &Emulator {
+        &self.emulator
+    }
+
+    pub fn emulator_mut(&mut self) -> &mut Emulator {
+        &mut self.emulator
+    }
+
+    /// Number of chip-select frames seen so far.
+    pub fn transactions(&self) -> usize {
+        self.transactions
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    fn shift(&mut self, write: &[u8], read: &mut [u8]) {
+        read.fill(0);
+        for (index, packet) in write.chunks_exact(2).enumerate() {
+            let (address, data) = self.emulator.shift_packet(packet[0], packet[1]);
+            if let Some(out) = read.get_mut(index * 2..index * 2 + 2) {
+                out.copy_from_slice(&[address, data]);
+            }
+        }
+        self.bytes_written += write.len();
+    }
+}
+
+impl ErrorType for EmulatedSpi {
+    type Error = Infallible;
+}
+
+impl SpiDevice for EmulatedSpi {
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), Infallible> {
+        for operation in operations.iter_mut() {
+            match operation {
+                Operation::Write(write) => self.shift(write, &mut []),
+                Operation::Transfer(read, write) => self.shift(write, read),
+                Operation::TransferInPlace(buf) => {
+                    let mut write = [0; crate::MAX_DISPLAYS * 2];
+                    assert!(
+                        buf.len() <= write.len(),
+                        "in-place transfer longer than MAX_DISPLAYS packets"
+                    );
+                    let write = &mut write[..buf.len()];
+                    write.copy_from_slice(buf);
+                    self.shift(write, buf);
+                }
+                Operation::Read(read) => read.fill(0),
+                Operation::DelayNs(_) => {}
+            }
+        }
+        self.emulator.latch();
+        self.transactions += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driver::Max7219;
+    #[cfg(feature = "sevenseg")]
+    use crate::seven_segment::SevenSegment;
+
+    #[test]
+    fn test_driver_against_emulator() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+
+        driver.init().unwrap();
+        driver.set_intensity(1, 0x05).unwrap();
+        driver.write_raw_digit(0, 2, 0x3C).unwrap();
+
+        let emulator = spi.emulator();
+        assert!(!emulator.device(0).unwrap().shutdown);
+        assert_eq!(emulator.device(0).unwrap().scan_limit, 7);
+        assert_eq!(emulator.device(1).unwrap().intensity, 0x05);
+        assert_eq!(emulator.device(0).unwrap().intensity, 0x00);
+        assert_eq!(emulator.digits(0).unwrap()[2], 0x3C);
+        assert_eq!(emulator.digits(1).unwrap(), [0; 8]);
+    }
+
+    #[test]
+    #[cfg(feature = "sevenseg")]
+    fn test_seven_segment_against_emulator() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display.write_number(0, 42).unwrap();
+        assert_eq!(spi.emulator().digits(0).unwrap()[..2], [0x6D, 0x33]);
+        assert_eq!(spi.transactions(), 8);
+        assert_eq!(spi.bytes_written(), 16);
+    }
+
+    #[test]
+    fn test_transfer_reads_shifted_out_packets() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        spi.write(&[0x01, 0x02]).unwrap();
+
+        let mut read = [0; 2];
+        spi.transfer(&mut read, &[0x03, 0x04]).unwrap();
+        assert_eq!(read, [0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_transfer_in_place_reads_shifted_out_packets() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        spi.write(&[0x01, 0x02]).unwrap();
+
+        let mut buf = [0x03, 0x04];
+        spi.transfer_in_place(&mut buf).unwrap();
+        assert_eq!(buf, [0x01, 0x02]);
+    }
+
+    #[test]
+    #[should_panic(expected = "longer than MAX_DISPLAYS packets")]
+    fn test_transfer_in_place_rejects_oversized_buffer() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut buf = [0; crate::MAX_DISPLAYS * 2 + 2];
+        spi.transfer_in_place(&mut buf).unwrap();
+    }
+}
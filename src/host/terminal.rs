@@ -0,0 +1,144 @@
+use std::io;
+
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+
+use super::Emulator;
+use crate::{NUM_DIGITS, Result, registers::Register};
+
+/// Clears the screen and moves the cursor home
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// [`SpiDevice`] simulating the chain in a terminal, to try out layouts and
+/// animations on a PC: writes go into an [`Emulator`], and the modules are
+/// redrawn to `out` after every write to digit register 7, i.e. at the end
+/// of each [`LedMatrix::flush`](crate::matrix::LedMatrix::flush).
+///
+/// Modules are laid out like [`Canvas`](crate::matrix::Canvas) tiles: device
+/// 0 top left, then row by row. Shut down devices are drawn dark.
+pub struct TerminalSpi<W> {
+    emulator: Emulator,
+    out: W,
+    modules_wide: usize,
+}
+
+impl<W> TerminalSpi<W>
+where
+    W: io::Write,
+{
+    /// A single row of `device_count` modules drawn to `out`, e.g.
+    /// `std::io::stdout()`.
+    pub fn new(device_count: usize, out: W) -> Result<Self> {
+        Ok(Self {
+            emulator: Emulator::new(device_count)?,
+            out,
+            modules_wide: device_count.max(1),
+        })
+    }
+
+    /// Draw the modules as a grid `modules_wide` modules across.
+    pub fn with_modules_wide(mut self, modules_wide: usize) -> Self {
+        self.modules_wide = modules_wide.max(1);
+        self
+    }
+
+    pub fn emulator(&self) -> &Emulator {
+        &self.emulator
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Clear the terminal and draw what the chain shows now.
+    pub fn draw(&mut self) -> io::Result<()> {
+        let device_count = self.emulator.device_count();
+        let modules_high = device_count.div_ceil(self.modules_wide);
+        let mut screen = std::string::String::from(CLEAR);
+        for module_y in 0..modules_high {
+            for row in 0..usize::from(NUM_DIGITS) {
+                for module_x in 0..self.modules_wide {
+                    let device_index = module_y * self.modules_wide + module_x;
+                    let bits = self.visible_row(device_index, row);
+                    for x in 0..8 {
+                        let pixel = if bits & (0x80 >> x) != 0 {
+                            "██"
+                        } else {
+                            "  "
+                        };
+                        screen.push_str(pixel);
+                    }
+                }
+                screen.push('\n');
+            }
+        }
+        self.out.write_all(screen.as_bytes())?;
+        self.out.flush()
+    }
+
+    /// Lit pixels of one row of a device, bit 7 leftmost.
+    fn visible_row(&self, device_index: usize, row: usize) -> u8 {
+        match self.emulator.device(device_index) {
+            Some(device) if device.display_test => 0xFF,
+            Some(device) if !device.shutdown && row <= usize::from(device.scan_limit) => {
+                device.digits[row]
+            }
+            _ => 0x00,
+        }
+    }
+}
+
+impl<W> ErrorType for TerminalSpi<W> {
+    type Error = ErrorKind;
+}
+
+impl<W> SpiDevice for TerminalSpi<W>
+where
+    W: io::Write,
+{
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> core::result::Result<(), ErrorKind> {
+        let mut last_row = false;
+        for operation in operations.iter_mut() {
+            if let Operation::Write(write) = operation {
+                self.emulator.shift_bytes(write);
+                last_row |= write
+                    .chunks_exact(2)
+                    .any(|packet| packet[0] == Register::Digit7.addr());
+            }
+        }
+        self.emulator.latch();
+        if last_row {
+            self.draw().map_err(|_| ErrorKind::Other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "matrix"))]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, matrix::LedMatrix};
+
+    #[test]
+    fn test_draws_on_flush() {
+        let mut terminal = TerminalSpi::new(2, std::vec::Vec::new()).unwrap();
+        {
+            let driver = Max7219::new(&mut terminal).with_device_count(2).unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.init().unwrap();
+            matrix.set_pixel_xy(0, 0, true).unwrap();
+            matrix.set_pixel_xy(15, 7, true).unwrap();
+            matrix.flush().unwrap();
+        }
+        let out = std::string::String::from_utf8(terminal.into_inner()).unwrap();
+        // Once for the blanking in init, once for the flush
+        assert_eq!(out.matches(CLEAR).count(), 2);
+        let last = out.rsplit(CLEAR).next().unwrap();
+        let lines: std::vec::Vec<_> = last.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], format!("██{}", " ".repeat(30)));
+        assert_eq!(lines[7], format!("{}██", " ".repeat(30)));
+    }
+}
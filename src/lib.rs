@@ -1,9 +1,28 @@
 #![deny(unsafe_code)]
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+pub mod arbiter;
 pub mod driver;
+pub mod easing;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+pub mod encoder;
 pub mod error;
+#[cfg(any(test, feature = "host"))]
+pub mod host;
+#[cfg(feature = "matrix")]
+pub mod matrix;
+pub mod morse;
+#[cfg(feature = "protocol")]
+pub mod protocol;
 pub mod registers;
+#[cfg(feature = "sevenseg")]
+pub mod seven_segment;
+pub mod time;
 
 /// Crate-local `Result` type used throughout the MAX7219 driver.
 ///
@@ -0,0 +1,433 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{BlitMode, LedMatrix, MODULE_SIZE, Sprite, storage::Storage};
+use crate::Result;
+
+/// How often an [`Animation`] plays its frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Looping {
+    /// Play the frames once and keep showing the last one
+    Once,
+    /// Play the frames this many times; 0 is treated as 1
+    Times(u16),
+    /// Start over after the last frame, never finishing
+    #[default]
+    Forever,
+}
+
+#[derive(Debug, Clone)]
+enum Frames<'a> {
+    /// Module row bytes, shown on one device or on every device
+    Tiles(Storage<'a, [u8; MODULE_SIZE]>, Option<usize>),
+    /// Images covering the drawing surface from its top-left corner
+    Sprites(&'a [Sprite<'a>]),
+}
+
+/// Tick-driven player for a sequence of const frames.
+///
+/// Each [`tick`](Self::tick) draws and flushes the next frame, so playback
+/// can be driven from a timer interrupt or an async task; use
+/// [`play`](Self::play) to block instead. Once all loops are done the
+/// animation is [finished](Self::is_finished) and further ticks leave the
+/// matrix alone.
+#[derive(Debug, Clone)]
+pub struct Animation<'a> {
+    frames: Frames<'a>,
+    // Milliseconds per frame, overriding the playback rate
+    durations: Storage<'a, u32>,
+    looping: Looping,
+    // Frame shown by the next tick
+    frame: usize,
+    // Completed passes over all frames
+    loops: u16,
+    finished: bool,
+}
+
+impl<'a> Animation<'a> {
+    /// Play 8x8 frames, given as row bytes with bit 7 leftmost, on every
+    /// device of the chain.
+    pub const fn new(frames: &'a [[u8; MODULE_SIZE]]) -> Self {
+        Self::with_frames(Frames::Tiles(Storage::Borrowed(frames), None))
+    }
+
+    /// Like [`new`](Self::new), owning frames built at runtime.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(frames: alloc::vec::Vec<[u8; MODULE_SIZE]>) -> Self {
+        Self::with_frames(Frames::Tiles(Storage::Owned(frames), None))
+    }
+
+    /// Play frames covering the whole drawing surface. Each frame clears
+    /// the framebuffer and is drawn at the top-left corner, honouring the
+    /// canvas rotation.
+    pub const fn from_sprites(frames: &'a [Sprite<'a>]) -> Self {
+        Self::with_frames(Frames::Sprites(frames))
+    }
+
+    const fn with_frames(frames: Frames<'a>) -> Self {
+        Self {
+            frames,
+            durations: Storage::Borrowed(&[]),
+            looping: Looping::Forever,
+            frame: 0,
+            loops: 0,
+            finished: false,
+        }
+    }
+
+    /// Show 8x8 frames only on `device_index`, leaving the other modules
+    /// untouched. Has no effect on sprite frames.
+    pub fn with_device(mut self, device_index: usize) -> Self {
+        if let Frames::Tiles(_, device) = &mut self.frames {
+            *device = Some(device_index);
+        }
+        self
+    }
+
+    /// Show frame `n` for `durations[n]` milliseconds, e.g. a heartbeat
+    /// with a long pause after the beat. Frames without an entry use the
+    /// rate passed to [`play`](Self::play).
+    pub fn with_durations(mut self, durations: &'a [u32]) -> Self {
+        self.durations = Storage::Borrowed(durations);
+        self
+    }
+
+    /// Like [`with_durations`](Self::with_durations), owning the durations.
+    #[cfg(feature = "alloc")]
+    pub fn with_duration_vec(mut self, durations: alloc::vec::Vec<u32>) -> Self {
+        self.durations = Storage::Owned(durations);
+        self
+    }
+
+    /// How long frame `index` should stay, if set with
+    /// [`with_durations`](Self::with_durations).
+    pub fn duration(&self, index: usize) -> Option<u32> {
+        self.durations.get(index).copied()
+    }
+
+    pub fn with_looping(mut self, looping: Looping) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn looping(&self) -> Looping {
+        self.looping
+    }
+
+    pub fn frame_count(&self) -> usize {
+        match &self.frames {
+            Frames::Tiles(frames, _) => frames.len(),
+            Frames::Sprites(frames) => frames.len(),
+        }
+    }
+
+    /// Index of the frame shown by the next tick, or of the last frame once
+    /// finished.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Whether all loops have been played. Never true for
+    /// [`Looping::Forever`] unless there are no frames.
+    pub fn is_finished(&self) -> bool {
+        self.finished || self.frame_count() == 0
+    }
+
+    /// Start over from the first frame with the next tick.
+    pub fn reset(&mut self) {
+        self.frame = 0;
+        self.loops = 0;
+        self.finished = false;
+    }
+
+    /// Show the next frame. Returns `true` when this frame was the last one
+    /// of a loop, and also for every tick once the animation is finished.
+    ///
+    /// With per-frame durations, schedule the following tick after the
+    /// [`duration`](Self::duration) of the frame index read from
+    /// [`frame`](Self::frame) before this call.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceIndex`](crate::error::Error::InvalidDeviceIndex)
+    ///   if the device set with [`with_device`](Self::with_device) is not
+    ///   on the chain.
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        self.tick_with(matrix, |_, _| Ok(()))
+    }
+
+    /// Like [`tick`](Self::tick), calling `overlay` with the frame index
+    /// and the matrix after the frame is drawn and before it is flushed,
+    /// e.g. to put the time or a sensor reading on top of a canned
+    /// animation.
+    ///
+    /// # Errors
+    /// Same as [`tick`](Self::tick), plus any error `overlay` returns;
+    /// nothing is flushed then.
+    pub fn tick_with<SPI, O>(&mut self, matrix: &mut LedMatrix<SPI>, mut overlay: O) -> Result<bool>
+    where
+        SPI: SpiDevice,
+        O: FnMut(usize, &mut LedMatrix<SPI>) -> Result<()>,
+    {
+        if self.is_finished() {
+            return Ok(true);
+        }
+        self.draw(matrix)?;
+        overlay(self.frame, matrix)?;
+        matrix.flush()?;
+
+        if self.frame + 1 < self.frame_count() {
+            self.frame += 1;
+            return Ok(false);
+        }
+        self.loops = self.loops.saturating_add(1);
+        self.finished = match self.looping {
+            Looping::Once => true,
+            Looping::Times(count) => self.loops >= count.max(1),
+            Looping::Forever => false,
+        };
+        if !self.finished {
+            self.frame = 0;
+        }
+        Ok(true)
+    }
+
+    /// Play from the first frame until finished, showing each frame for its
+    /// [`duration`](Self::duration), or `frame_ms` milliseconds without
+    /// one, using `delay`.
+    ///
+    /// With [`Looping::Forever`] this only returns on error.
+    ///
+    /// # Errors
+    /// Same as [`tick`](Self::tick); playback stops at the first error.
+    pub fn play<SPI, D>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI>,
+        delay: &mut D,
+        frame_ms: u32,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+        D: DelayNs,
+    {
+        self.play_with(matrix, delay, frame_ms, |_, _| Ok(()))
+    }
+
+    /// Like [`play`](Self::play), calling `overlay` before every flush as
+    /// [`tick_with`](Self::tick_with) does.
+    pub fn play_with<SPI, D, O>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI>,
+        delay: &mut D,
+        frame_ms: u32,
+        mut overlay: O,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+        D: DelayNs,
+        O: FnMut(usize, &mut LedMatrix<SPI>) -> Result<()>,
+    {
+        self.reset();
+        while !self.is_finished() {
+            let shown = self.frame;
+            self.tick_with(matrix, &mut overlay)?;
+            delay.delay_ms(self.duration(shown).unwrap_or(frame_ms));
+        }
+        Ok(())
+    }
+
+    fn draw<SPI>(&self, matrix: &mut LedMatrix<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        match &self.frames {
+            Frames::Tiles(frames, device) => {
+                let devices = match *device {
+                    Some(index) => index..index + 1,
+                    None => 0..matrix.device_count(),
+                };
+                for device_index in devices {
+                    for (row, &bits) in frames[self.frame].iter().enumerate() {
+                        matrix.set_row(device_index, row, bits)?;
+                    }
+                }
+                Ok(())
+            }
+            Frames::Sprites(frames) => {
+                matrix.clear();
+                matrix.blit(&frames[self.frame], 0, 0, BlitMode::Copy)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, error::Error, host::EmulatedSpi};
+
+    const FRAMES: [[u8; MODULE_SIZE]; 2] = [[0x81; MODULE_SIZE], [0x18; MODULE_SIZE]];
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    #[test]
+    fn test_tick_loops_and_finishes() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            let mut animation = Animation::new(&FRAMES).with_looping(Looping::Times(2));
+
+            let mut results = [false; 4];
+            for result in &mut results {
+                *result = animation.tick(&mut matrix).unwrap();
+            }
+            assert_eq!(results, [false, true, false, true]);
+            assert!(animation.is_finished());
+            assert_eq!(animation.tick(&mut matrix), Ok(true));
+        }
+
+        // Both devices show the last frame; no writes after finishing
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap(), [0x18; 8]);
+        assert_eq!(emulator.digits(1).unwrap(), [0x18; 8]);
+        assert_eq!(spi.transactions(), 4 * 8);
+    }
+
+    #[test]
+    fn test_single_device() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        let mut animation = Animation::new(&FRAMES).with_device(1);
+        animation.tick(&mut matrix).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0x00));
+        assert_eq!(matrix.row(1, 0), Ok(0x81));
+
+        let mut missing = Animation::new(&FRAMES).with_device(2);
+        assert_eq!(
+            missing.tick(&mut matrix),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_play_sprites() {
+        let first = [0xFF; 8];
+        let second = [0x0F; 8];
+        let frames = [
+            Sprite::new(8, 8, &first).unwrap(),
+            Sprite::new(4, 8, &second).unwrap(),
+        ];
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+
+        let mut animation = Animation::from_sprites(&frames).with_looping(Looping::Once);
+        animation.play(&mut matrix, &mut delay, 100).unwrap();
+        assert_eq!(delay.ms, 200);
+        // The narrow second frame replaced the whole first one
+        assert_eq!(matrix.row(0, 0), Ok(0x00));
+        assert_eq!(animation.frame(), 1);
+    }
+
+    #[test]
+    fn test_play_durations() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+
+        // Third frame has no duration and falls back to the rate
+        let frames = [FRAMES[0], FRAMES[1], FRAMES[0]];
+        let mut animation = Animation::new(&frames)
+            .with_durations(&[100, 600])
+            .with_looping(Looping::Times(2));
+        assert_eq!(animation.duration(1), Some(600));
+        assert_eq!(animation.duration(2), None);
+        animation.play(&mut matrix, &mut delay, 50).unwrap();
+        assert_eq!(delay.ms, 2 * (100 + 600 + 50));
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi).assume_initialized()).unwrap();
+            let mut animation = Animation::new(&FRAMES).with_looping(Looping::Once);
+            let mut seen = [false; 2];
+            animation
+                .play_with(&mut matrix, &mut delay, 10, |frame, matrix| {
+                    seen[frame] = true;
+                    matrix.set_row(0, 7, 0xFF)
+                })
+                .unwrap();
+            assert_eq!(seen, [true, true]);
+
+            animation.reset();
+            assert_eq!(
+                animation.tick_with(&mut matrix, |_, _| Err(Error::InvalidPixel)),
+                Err(Error::InvalidPixel)
+            );
+        }
+        // The overlay row was drawn over each frame before flushing
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!((digits[0], digits[7]), (0x18, 0xFF));
+        assert_eq!(spi.transactions(), 2 * 8);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_owned_frames() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+
+        let frames = (0..4).map(|n| [1 << n; MODULE_SIZE]).collect();
+        let mut animation = Animation::from_vec(frames)
+            .with_duration_vec(std::vec![10; 4])
+            .with_looping(Looping::Once);
+        assert_eq!(animation.frame_count(), 4);
+        animation.play(&mut matrix, &mut delay, 50).unwrap();
+        assert_eq!(delay.ms, 40);
+        assert_eq!(matrix.row(0, 0), Ok(0x08));
+    }
+
+    #[test]
+    fn test_no_frames() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        let mut animation = Animation::new(&[]);
+        assert!(animation.is_finished());
+        assert_eq!(animation.tick(&mut matrix), Ok(true));
+    }
+}
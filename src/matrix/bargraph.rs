@@ -0,0 +1,211 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, MODULE_SIZE};
+use crate::{MAX_DISPLAYS, Result};
+
+/// Channels a [`Bargraph`] tracks peaks for, one per column of the widest
+/// surface
+const MAX_CHANNELS: usize = MAX_DISPLAYS * MODULE_SIZE;
+
+/// Direction [`Bargraph`] bars grow in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BarOrientation {
+    /// Bars side by side, growing up from the bottom row
+    #[default]
+    Vertical,
+    /// Bars stacked, growing right from the left column
+    Horizontal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Peak {
+    level: u16,
+    age: u16,
+}
+
+/// Level meter for one or more channels, e.g. a stereo VU meter across a
+/// chain.
+///
+/// The surface is split into one equal band per channel and each level in
+/// `0..=max` is drawn as a bar filling its band's share of the surface.
+/// With [peak hold](Self::with_peak_hold) the highest recent level of each
+/// channel stays marked by a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bargraph {
+    max: u16,
+    orientation: BarOrientation,
+    peak_hold: u16,
+    peaks: [Peak; MAX_CHANNELS],
+}
+
+impl Bargraph {
+    /// Vertical bars for levels up to `max`, without peak markers.
+    pub fn new(max: u16) -> Self {
+        Self {
+            max,
+            orientation: BarOrientation::default(),
+            peak_hold: 0,
+            peaks: [Peak::default(); MAX_CHANNELS],
+        }
+    }
+
+    pub fn with_orientation(mut self, orientation: BarOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Mark each channel's peak for `ticks` ticks after it was reached,
+    /// after which the marker falls back to the current level. 0 turns the
+    /// markers off.
+    pub fn with_peak_hold(mut self, ticks: u16) -> Self {
+        self.peak_hold = ticks;
+        self
+    }
+
+    pub fn max(&self) -> u16 {
+        self.max
+    }
+
+    pub fn orientation(&self) -> BarOrientation {
+        self.orientation
+    }
+
+    pub fn peak_hold(&self) -> u16 {
+        self.peak_hold
+    }
+
+    /// Held peak of `channel`, `None` past the last channel.
+    pub fn peak(&self, channel: usize) -> Option<u16> {
+        self.peaks.get(channel).map(|peak| peak.level)
+    }
+
+    /// Drop all held peaks.
+    pub fn reset_peaks(&mut self) {
+        self.peaks = [Peak::default(); MAX_CHANNELS];
+    }
+
+    /// Update the peaks with `levels`, one per channel, and show the bars
+    /// across the whole surface. Levels above [`max`](Self::max) are
+    /// clamped; channels that don't get a row or column are left out.
+    ///
+    /// # Errors
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>, levels: &[u16]) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let (across, along) = match self.orientation {
+            BarOrientation::Vertical => (matrix.width(), matrix.height()),
+            BarOrientation::Horizontal => (matrix.height(), matrix.width()),
+        };
+        let channels = levels.len().min(across);
+        matrix.clear();
+        for (channel, &level) in levels.iter().enumerate().take(channels) {
+            let level = level.min(self.max);
+            let peak = self.update_peak(channel, level);
+            let band = channel * across / channels..(channel + 1) * across / channels;
+            let length = self.length(level, along);
+            for offset in band {
+                for position in 0..length {
+                    self.draw(matrix, offset, position, along)?;
+                }
+                if let Some(peak) = peak.and_then(|peak| self.length(peak, along).checked_sub(1)) {
+                    self.draw(matrix, offset, peak, along)?;
+                }
+            }
+        }
+        matrix.flush()
+    }
+
+    /// Age the held peak of `channel` and raise it to `level`, returning the
+    /// peak to mark.
+    fn update_peak(&mut self, channel: usize, level: u16) -> Option<u16> {
+        let hold = self.peak_hold;
+        let peak = self.peaks.get_mut(channel)?;
+        peak.age = peak.age.saturating_add(1);
+        if level >= peak.level || peak.age > hold {
+            *peak = Peak { level, age: 0 };
+        }
+        (hold > 0).then_some(peak.level)
+    }
+
+    /// Pixels of a bar at `level` on a surface `along` pixels long.
+    fn length(&self, level: u16, along: usize) -> usize {
+        let max = usize::from(self.max.max(1));
+        (usize::from(level) * along * 2 + max) / (max * 2)
+    }
+
+    /// Light the pixel `position` pixels from the bars' base in row or
+    /// column `offset` of the surface.
+    fn draw<SPI>(
+        &self,
+        matrix: &mut LedMatrix<SPI>,
+        offset: usize,
+        position: usize,
+        along: usize,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        match self.orientation {
+            BarOrientation::Vertical => matrix.set_pixel_xy(offset, along - 1 - position, true),
+            BarOrientation::Horizontal => matrix.set_pixel_xy(position, offset, true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_vertical_bars() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+            matrix.init().unwrap();
+            let mut bargraph = Bargraph::new(100);
+            bargraph.tick(&mut matrix, &[100, 50, 0, 200]).unwrap();
+        }
+        // Four 2-column bands: full, half, empty and clamped to full
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[0], 0xC3);
+        assert_eq!(digits[4], 0xF3);
+        assert_eq!(digits[7], 0xF3);
+        assert_eq!(digits[3], 0xC3);
+    }
+
+    #[test]
+    fn test_horizontal_peak_hold() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(4)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        let mut vu = Bargraph::new(32)
+            .with_orientation(BarOrientation::Horizontal)
+            .with_peak_hold(2);
+
+        vu.tick(&mut matrix, &[32, 8]).unwrap();
+        for _ in 0..2 {
+            vu.tick(&mut matrix, &[4, 8]).unwrap();
+            // Left channel's peak is still marked at the far right
+            assert_eq!(vu.peak(0), Some(32));
+            assert_eq!(matrix.get_pixel_xy(31, 0), Ok(true));
+            assert_eq!(matrix.get_pixel_xy(30, 3), Ok(false));
+            assert_eq!(matrix.get_pixel_xy(3, 3), Ok(true));
+            assert_eq!(matrix.get_pixel_xy(4, 3), Ok(false));
+            assert_eq!(matrix.get_pixel_xy(7, 4), Ok(true));
+        }
+        // Held long enough, the marker falls back to the bar's end
+        vu.tick(&mut matrix, &[4, 8]).unwrap();
+        assert_eq!(vu.peak(0), Some(4));
+        assert_eq!(matrix.get_pixel_xy(31, 0), Ok(false));
+
+        vu.reset_peaks();
+        assert_eq!(vu.peak(1), Some(0));
+        assert_eq!(vu.peak(MAX_CHANNELS), None);
+    }
+}
@@ -0,0 +1,151 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, MODULE_SIZE};
+use crate::{Result, error::Error};
+
+/// Width of a [`LedMatrix::draw_big_digit`] numeral, one module
+pub const BIG_DIGIT_WIDTH: usize = MODULE_SIZE;
+/// Height of a [`LedMatrix::draw_big_digit`] numeral, two module rows
+pub const BIG_DIGIT_HEIGHT: usize = 2 * MODULE_SIZE;
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw `c`, a digit `0`-`9` or a space, as an 8x16 numeral with its
+    /// top-left corner at chain-wide (`x`, `y`). Returns `x` of the next
+    /// numeral.
+    ///
+    /// Meant for clocks on a grid of two module rows set up with
+    /// [`with_geometry`](Self::with_geometry), e.g. one numeral per column
+    /// of a 4 x 2 grid; the numeral is split across the stacked devices
+    /// like any other drawing. The whole cell is written, so a numeral
+    /// overwrites the previous one, and pixels off the surface are clipped.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] for any other character.
+    pub fn draw_big_digit(&mut self, x: i32, y: i32, c: char) -> Result<i32> {
+        let rows = match c {
+            ' ' => &[0; BIG_DIGIT_HEIGHT],
+            '0'..='9' => &BIG_DIGITS[c as usize - '0' as usize],
+            _ => return Err(Error::UnsupportedChar),
+        };
+        for (row, &bits) in (y..).zip(rows) {
+            for (column, bit) in (x..).zip(0..BIG_DIGIT_WIDTH) {
+                self.draw_pixel(column, row, bits & (0x80 >> bit) != 0)?;
+            }
+        }
+        Ok(x + BIG_DIGIT_WIDTH as i32)
+    }
+
+    /// Draw each character of `text` with
+    /// [`draw_big_digit`](Self::draw_big_digit), side by side. Returns `x`
+    /// of the numeral that would follow.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if `text` has anything but digits and
+    ///   spaces; numerals before it have already been drawn.
+    pub fn draw_big_digits(&mut self, x: i32, y: i32, text: &str) -> Result<i32> {
+        text.chars()
+            .try_fold(x, |x, c| self.draw_big_digit(x, y, c))
+    }
+}
+
+/// Rows of the numerals 0-9, bit 7 being the leftmost pixel
+const BIG_DIGITS: [[u8; BIG_DIGIT_HEIGHT]; 10] = [
+    // 0
+    [
+        0x3C, 0x66, 0xC3, 0xC3, 0xC7, 0xCF, 0xDB, 0xF3, 0xE3, 0xC3, 0xC3, 0xC3, 0xC3, 0x66, 0x3C,
+        0x00,
+    ],
+    // 1
+    [
+        0x18, 0x38, 0x78, 0xD8, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x7E,
+        0x00,
+    ],
+    // 2
+    [
+        0x3C, 0x66, 0xC3, 0x03, 0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0xC0, 0xC0, 0xC0, 0xC0, 0xFF,
+        0x00,
+    ],
+    // 3
+    [
+        0x7C, 0xC6, 0x03, 0x03, 0x03, 0x06, 0x3C, 0x06, 0x03, 0x03, 0x03, 0x03, 0x03, 0xC6, 0x7C,
+        0x00,
+    ],
+    // 4
+    [
+        0x06, 0x0E, 0x1E, 0x36, 0x66, 0xC6, 0xC6, 0xC6, 0xFF, 0x06, 0x06, 0x06, 0x06, 0x06, 0x06,
+        0x00,
+    ],
+    // 5
+    [
+        0xFF, 0xC0, 0xC0, 0xC0, 0xC0, 0xFC, 0x06, 0x03, 0x03, 0x03, 0x03, 0x03, 0xC3, 0x66, 0x3C,
+        0x00,
+    ],
+    // 6
+    [
+        0x1C, 0x30, 0x60, 0xC0, 0xC0, 0xC0, 0xFC, 0xE6, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0x66, 0x3C,
+        0x00,
+    ],
+    // 7
+    [
+        0xFF, 0x03, 0x03, 0x06, 0x06, 0x0C, 0x0C, 0x18, 0x18, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30,
+        0x00,
+    ],
+    // 8
+    [
+        0x3C, 0x66, 0xC3, 0xC3, 0xC3, 0x66, 0x3C, 0x66, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0x66, 0x3C,
+        0x00,
+    ],
+    // 9
+    [
+        0x3C, 0x66, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0x67, 0x3F, 0x03, 0x03, 0x03, 0x06, 0x0C, 0x38,
+        0x00,
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_big_digits_span_module_rows() {
+        let mut spi = EmulatedSpi::new(8).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(8)
+                .unwrap();
+            let mut matrix = LedMatrix::with_geometry(driver, 4, 2).unwrap();
+            matrix.fill(true);
+            assert_eq!(matrix.draw_big_digits(0, 0, "12 8"), Ok(32));
+            assert_eq!(
+                matrix.draw_big_digit(0, 0, ':'),
+                Err(Error::UnsupportedChar)
+            );
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        // Each numeral's top half is on the first module row, its bottom
+        // half on the device below
+        assert_eq!(emulator.digits(0).unwrap(), BIG_DIGITS[1][..8]);
+        assert_eq!(emulator.digits(4).unwrap(), BIG_DIGITS[1][8..]);
+        assert_eq!(emulator.digits(5).unwrap(), BIG_DIGITS[2][8..]);
+        assert_eq!(emulator.digits(2).unwrap(), [0; 8]);
+        assert_eq!(emulator.digits(7).unwrap()[6], 0x3C);
+    }
+
+    #[test]
+    fn test_big_digit_clipped() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::with_geometry(driver, 1, 2).unwrap();
+        assert_eq!(matrix.draw_big_digit(-4, 8, '0'), Ok(4));
+        // The left half is off the surface, the bottom half past the edge
+        assert_eq!(matrix.row(1, 0), Ok(0xC0));
+        assert_eq!(matrix.row(1, 7), Ok(0x30));
+        assert_eq!(matrix.row(0, 0), Ok(0x00));
+    }
+}
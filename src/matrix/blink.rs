@@ -0,0 +1,148 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, Viewport};
+
+/// Blinks the whole surface or a region of it on a duty cycle counted in
+/// ticks, e.g. to make an alert stand out.
+///
+/// It only decides what's [blanked](LedMatrix::set_blanked) on the next
+/// flush, so it works with anything that draws and flushes, e.g. call
+/// [`tick`](Self::tick) before a [`Scroller`](super::Scroller) tick to
+/// have scrolling text blink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blink {
+    region: Option<Viewport>,
+    on_ticks: u16,
+    off_ticks: u16,
+    phase: u32,
+}
+
+impl Blink {
+    /// Show the content for `on_ticks` ticks then blank it for `off_ticks`,
+    /// starting with the shown phase.
+    pub fn new(on_ticks: u16, off_ticks: u16) -> Self {
+        Self {
+            region: None,
+            on_ticks,
+            off_ticks,
+            phase: 0,
+        }
+    }
+
+    /// Blink only the chain-wide `region`, e.g. the field being edited.
+    pub fn with_region(mut self, region: Viewport) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Blinked region, `None` for the whole surface.
+    pub fn region(&self) -> Option<Viewport> {
+        self.region
+    }
+
+    pub fn on_ticks(&self) -> u16 {
+        self.on_ticks
+    }
+
+    pub fn off_ticks(&self) -> u16 {
+        self.off_ticks
+    }
+
+    /// Start over with the shown phase on the next tick.
+    pub fn reset(&mut self) {
+        self.phase = 0;
+    }
+
+    /// Set up the next flush of `matrix` for the current phase and advance.
+    /// Returns whether the region is shown.
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> bool
+    where
+        SPI: SpiDevice,
+    {
+        let period = u32::from(self.on_ticks) + u32::from(self.off_ticks);
+        let shown = period == 0 || self.phase % period < u32::from(self.on_ticks);
+        let region = self
+            .region
+            .unwrap_or(Viewport::new(0, 0, matrix.width(), matrix.height()));
+        matrix.set_blanked((!shown).then_some(region));
+        self.phase = (self.phase + 1) % period.max(1);
+        shown
+    }
+
+    /// Stop blinking, showing the region on the next flush.
+    pub fn stop<SPI>(&mut self, matrix: &mut LedMatrix<SPI>)
+    where
+        SPI: SpiDevice,
+    {
+        matrix.set_blanked(None);
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Result,
+        driver::Max7219,
+        host::EmulatedSpi,
+        matrix::{Font, MODULE_SIZE, Scroller},
+    };
+
+    /// Every glyph is a full 2-column block.
+    struct Bars;
+
+    impl Font for Bars {
+        fn height(&self) -> usize {
+            MODULE_SIZE
+        }
+
+        fn glyph(&self, _c: char) -> Result<&[u8]> {
+            Ok(&[0xFF; 2])
+        }
+    }
+
+    #[test]
+    fn test_blink_with_scroller() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        let mut scroller = Scroller::new("a", Bars);
+        let mut blink = Blink::new(1, 2);
+
+        let mut shown = Vec::new();
+        for _ in 0..7 {
+            shown.push(blink.tick(&mut matrix));
+            scroller.tick(&mut matrix).unwrap();
+            let lit = matrix.snapshot().unwrap().tiles()[0] != [0; 8];
+            // Drawing carries on while blanked
+            assert!(matrix.pixels().any(|(_, _, on)| on));
+            assert_eq!(lit, shown[shown.len() - 1]);
+        }
+        assert_eq!(shown, [true, false, false, true, false, false, true]);
+
+        blink.tick(&mut matrix);
+        blink.stop(&mut matrix);
+        assert_eq!(matrix.blanked(), None);
+    }
+
+    #[test]
+    fn test_blink_region() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.fill(true);
+            let mut blink = Blink::new(1, 1).with_region(Viewport::modules(1, 1));
+            blink.tick(&mut matrix);
+            assert!(!blink.tick(&mut matrix));
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap(), [0xFF; 8]);
+        assert_eq!(emulator.digits(1).unwrap(), [0x00; 8]);
+    }
+}
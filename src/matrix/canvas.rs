@@ -0,0 +1,318 @@
+use super::MODULE_SIZE;
+use crate::{MAX_DISPLAYS, Result, error::Error};
+
+/// Bytes needed for the largest canvas (every supported module, 1 bit per pixel)
+const CANVAS_BYTES: usize = MAX_DISPLAYS * MODULE_SIZE;
+
+/// Off-screen 1-bit pixel buffer covering one or more 8x8 modules.
+///
+/// Pixels are stored row-major, 8 pixels per byte with the most significant
+/// bit leftmost, so row `y` of an 8-pixel-wide canvas is exactly the byte
+/// written to digit register `y` of an unrotated module. Width and height
+/// are multiples of [`MODULE_SIZE`].
+///
+/// Besides backing a [`LedMatrix`](super::LedMatrix), canvases can be drawn
+/// on away from the hardware, e.g. to pre-render a widget or cache an
+/// expensive layout, and composed in with
+/// [`LedMatrix::blit_canvas`](super::LedMatrix::blit_canvas).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    data: [u8; CANVAS_BYTES],
+    width: usize,
+    height: usize,
+}
+
+impl Canvas {
+    /// Create a blank canvas of `width` x `height` pixels.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if a side is zero or not a multiple of
+    ///   [`MODULE_SIZE`], or the canvas covers more than [`MAX_DISPLAYS`] modules.
+    pub fn new(width: usize, height: usize) -> Result<Self> {
+        let modules = (width / MODULE_SIZE).checked_mul(height / MODULE_SIZE);
+        if width == 0
+            || height == 0
+            || !width.is_multiple_of(MODULE_SIZE)
+            || !height.is_multiple_of(MODULE_SIZE)
+            || modules.is_none_or(|modules| modules > MAX_DISPLAYS)
+        {
+            return Err(Error::InvalidCanvasSize);
+        }
+        Ok(Self {
+            data: [0; CANVAS_BYTES],
+            width,
+            height,
+        })
+    }
+
+    /// Create a canvas for a single row of `device_count` modules.
+    pub fn for_chain(device_count: usize) -> Result<Self> {
+        let width = device_count
+            .checked_mul(MODULE_SIZE)
+            .ok_or(Error::InvalidCanvasSize)?;
+        Self::new(width, MODULE_SIZE)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) -> Result<()> {
+        let (index, mask) = self.locate(x, y)?;
+        let byte = self.data.get_mut(index).ok_or(Error::InvalidPixel)?;
+        if on {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+        Ok(())
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Result<bool> {
+        let (index, mask) = self.locate(x, y)?;
+        let byte = self.data.get(index).ok_or(Error::InvalidPixel)?;
+        Ok(byte & mask != 0)
+    }
+
+    /// Every pixel as `(x, y, on)`, row by row from the top-left corner.
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| (x, y, self.get_pixel(x, y) == Ok(true)))
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.fill(false);
+    }
+
+    pub fn fill(&mut self, on: bool) {
+        self.data = [if on { 0xFF } else { 0x00 }; CANVAS_BYTES];
+    }
+
+    /// Move the content by `dx` pixels right and `dy` pixels down (negative
+    /// values move left/up).
+    ///
+    /// With `wrap` pixels leaving one edge re-enter at the opposite edge,
+    /// otherwise vacated pixels are turned off.
+    pub fn shift(&mut self, dx: isize, dy: isize, wrap: bool) {
+        let source = self.clone();
+        self.clear();
+        for y in 0..self.height {
+            let Some(source_y) = shifted_coord(y, dy, self.height, wrap) else {
+                continue;
+            };
+            for x in 0..self.width {
+                let Some(source_x) = shifted_coord(x, dx, self.width, wrap) else {
+                    continue;
+                };
+                if source.get_pixel(source_x, source_y) == Ok(true)
+                    && let Ok((index, mask)) = self.locate(x, y)
+                    && let Some(byte) = self.data.get_mut(index)
+                {
+                    *byte |= mask;
+                }
+            }
+        }
+    }
+
+    /// Raw row-major pixel bytes, `width / 8` bytes per row.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.get(..self.len()).unwrap_or_default()
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let len = self.len();
+        self.data.get_mut(..len).unwrap_or_default()
+    }
+
+    /// Number of 8x8 modules along the x axis.
+    pub fn modules_wide(&self) -> usize {
+        self.width / MODULE_SIZE
+    }
+
+    /// Number of 8x8 modules along the y axis.
+    pub fn modules_high(&self) -> usize {
+        self.height / MODULE_SIZE
+    }
+
+    /// Row bytes of the 8x8 tile in module column `module_x`, module row `module_y`.
+    pub fn tile(&self, module_x: usize, module_y: usize) -> Result<[u8; MODULE_SIZE]> {
+        if module_x >= self.modules_wide() || module_y >= self.modules_high() {
+            return Err(Error::InvalidPixel);
+        }
+        let mut tile = [0; MODULE_SIZE];
+        for (row, byte) in tile.iter_mut().enumerate() {
+            *byte = *self.tile_byte(module_x, module_y, row)?;
+        }
+        Ok(tile)
+    }
+
+    /// Overwrite the 8x8 tile in module column `module_x`, module row `module_y`.
+    pub fn set_tile(
+        &mut self,
+        module_x: usize,
+        module_y: usize,
+        tile: &[u8; MODULE_SIZE],
+    ) -> Result<()> {
+        if module_x >= self.modules_wide() || module_y >= self.modules_high() {
+            return Err(Error::InvalidPixel);
+        }
+        for (row, &byte) in tile.iter().enumerate() {
+            *self.tile_byte_mut(module_x, module_y, row)? = byte;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.width / 8 * self.height
+    }
+
+    /// Byte holding row `row` of a module's tile, the module being in range.
+    fn tile_byte(&self, module_x: usize, module_y: usize, row: usize) -> Result<&u8> {
+        let (index, _) = self.locate(module_x * MODULE_SIZE, module_y * MODULE_SIZE + row)?;
+        self.data.get(index).ok_or(Error::InvalidPixel)
+    }
+
+    fn tile_byte_mut(&mut self, module_x: usize, module_y: usize, row: usize) -> Result<&mut u8> {
+        let (index, _) = self.locate(module_x * MODULE_SIZE, module_y * MODULE_SIZE + row)?;
+        self.data.get_mut(index).ok_or(Error::InvalidPixel)
+    }
+
+    fn locate(&self, x: usize, y: usize) -> Result<(usize, u8)> {
+        if x >= self.width || y >= self.height {
+            return Err(Error::InvalidPixel);
+        }
+        Ok((y * (self.width / 8) + x / 8, 0x80 >> (x % 8)))
+    }
+}
+
+/// Coordinate `value - offset` on an axis of `len` pixels, wrapped around
+/// or `None` when it falls outside.
+fn shifted_coord(value: usize, offset: isize, len: usize, wrap: bool) -> Option<usize> {
+    let len = isize::try_from(len).ok()?;
+    let value = isize::try_from(value).ok()?;
+    let source = if wrap {
+        // Reduced first, so the difference can't overflow
+        value
+            .checked_sub(offset.checked_rem_euclid(len)?)?
+            .checked_rem_euclid(len)?
+    } else {
+        value.checked_sub(offset)?
+    };
+    usize::try_from(source)
+        .ok()
+        .filter(|&source| source < len as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_invalid_sizes() {
+        assert_eq!(Canvas::new(0, 8), Err(Error::InvalidCanvasSize));
+        assert_eq!(Canvas::new(12, 8), Err(Error::InvalidCanvasSize));
+        assert_eq!(Canvas::new(8, 9), Err(Error::InvalidCanvasSize));
+        assert_eq!(
+            Canvas::new(8 * (MAX_DISPLAYS + 1), 8),
+            Err(Error::InvalidCanvasSize)
+        );
+        assert!(Canvas::new(32, 16).is_ok());
+
+        // Sizes whose module count overflows
+        let huge = usize::MAX / MODULE_SIZE * MODULE_SIZE;
+        assert_eq!(Canvas::new(huge, huge), Err(Error::InvalidCanvasSize));
+        assert_eq!(Canvas::for_chain(usize::MAX), Err(Error::InvalidCanvasSize));
+    }
+
+    #[test]
+    fn test_set_get_pixel() {
+        let mut canvas = Canvas::for_chain(4).unwrap();
+        assert_eq!(canvas.width(), 32);
+        assert_eq!(canvas.height(), 8);
+
+        canvas.set_pixel(9, 2, true).unwrap();
+        assert_eq!(canvas.get_pixel(9, 2), Ok(true));
+        assert_eq!(canvas.as_bytes()[2 * 4 + 1], 0b0100_0000);
+
+        canvas.set_pixel(9, 2, false).unwrap();
+        assert_eq!(canvas.get_pixel(9, 2), Ok(false));
+
+        assert_eq!(canvas.set_pixel(32, 0, true), Err(Error::InvalidPixel));
+        assert_eq!(canvas.get_pixel(0, 8), Err(Error::InvalidPixel));
+    }
+
+    #[test]
+    fn test_pixels() {
+        let mut canvas = Canvas::new(8, 16).unwrap();
+        canvas.set_pixel(7, 0, true).unwrap();
+        canvas.set_pixel(2, 15, true).unwrap();
+        let mut pixels = canvas.pixels();
+        assert_eq!(pixels.next(), Some((0, 0, false)));
+        assert_eq!(pixels.nth(6), Some((7, 0, true)));
+        assert_eq!(pixels.next(), Some((0, 1, false)));
+        assert_eq!(canvas.pixels().count(), 128);
+        let lit: Vec<_> = canvas.pixels().filter(|&(_, _, on)| on).collect();
+        assert_eq!(lit, [(7, 0, true), (2, 15, true)]);
+    }
+
+    #[test]
+    fn test_tiles() {
+        let mut canvas = Canvas::new(16, 16).unwrap();
+        canvas.set_pixel(8, 9, true).unwrap();
+
+        assert_eq!(canvas.tile(1, 1).unwrap()[1], 0x80);
+        assert_eq!(canvas.tile(0, 1).unwrap(), [0; 8]);
+        assert_eq!(canvas.tile(2, 0), Err(Error::InvalidPixel));
+
+        canvas.set_tile(0, 0, &[0xFF; 8]).unwrap();
+        assert_eq!(canvas.get_pixel(7, 7), Ok(true));
+        assert_eq!(canvas.get_pixel(8, 7), Ok(false));
+    }
+
+    #[test]
+    fn test_fill_and_clear() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        canvas.fill(true);
+        assert_eq!(canvas.as_bytes(), &[0xFF; 8]);
+        canvas.clear();
+        assert_eq!(canvas.as_bytes(), &[0x00; 8]);
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut canvas = Canvas::new(16, 8).unwrap();
+        canvas.set_pixel(7, 0, true).unwrap();
+        canvas.set_pixel(0, 7, true).unwrap();
+
+        // Crosses the module boundary
+        canvas.shift(2, 0, false);
+        assert_eq!(canvas.get_pixel(9, 0), Ok(true));
+        assert_eq!(canvas.get_pixel(2, 7), Ok(true));
+
+        canvas.shift(-3, 1, false);
+        assert_eq!(canvas.get_pixel(6, 1), Ok(true));
+        // Pushed off the left and bottom edges
+        assert_eq!(
+            canvas
+                .as_bytes()
+                .iter()
+                .map(|b| b.count_ones())
+                .sum::<u32>(),
+            1
+        );
+
+        canvas.shift(-7, -2, true);
+        assert_eq!(canvas.get_pixel(15, 7), Ok(true));
+
+        // Extreme offsets don't overflow
+        canvas.shift(isize::MIN, isize::MAX, true);
+        assert_eq!(canvas.get_pixel(15, 6), Ok(true));
+        canvas.shift(isize::MIN, 0, false);
+        assert_eq!(canvas.as_bytes(), &[0; 16]);
+    }
+}
@@ -0,0 +1,154 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::LedMatrix;
+use crate::{Result, seven_segment::TimeOfDay};
+
+/// sin(k * 6°) scaled by 1024 for the first quarter turn
+const SINE: [i32; 16] = [
+    0, 107, 213, 316, 416, 512, 602, 685, 761, 828, 887, 935, 974, 1002, 1018, 1024,
+];
+
+/// Look of [`LedMatrix::draw_clock_face`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockFace {
+    /// Light the rim pixel at each hour
+    pub tick_marks: bool,
+    pub second_hand: bool,
+}
+
+impl Default for ClockFace {
+    /// Tick marks and no second hand.
+    fn default() -> Self {
+        Self {
+            tick_marks: true,
+            second_hand: false,
+        }
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw an analog clock showing `time`, e.g. on a 2 x 2 or 3 x 3 module
+    /// grid.
+    ///
+    /// The face is the largest square centered on the surface, with the
+    /// hour hand half and the minute hand four fifths of its radius long.
+    /// Hands and marks are lit over what's in the framebuffer, so
+    /// [`clear`](Self::clear) first to redraw.
+    pub fn draw_clock_face(&mut self, time: TimeOfDay, face: ClockFace) -> Result<()> {
+        let size = self.width().min(self.height()) as i32;
+        // Coordinates are in half pixels so the center of an even-sized
+        // face, between four pixels, is exact
+        let radius = size - 1;
+        let origin = (
+            self.width() as i32 - size + radius,
+            self.height() as i32 - size + radius,
+        );
+        let point = |position: u32, length: i32| {
+            let (sin, cos) = (sine(position), sine(position + 15));
+            let x = origin.0 + length * sin / 1024;
+            let y = origin.1 - length * cos / 1024;
+            ((x + 1).div_euclid(2), (y + 1).div_euclid(2))
+        };
+
+        if face.tick_marks {
+            for hour in 0..12 {
+                let (x, y) = point(hour * 5, radius);
+                self.draw_pixel(x, y, true)?;
+            }
+        }
+        let minutes = u32::from(time.minutes());
+        let hour_position = u32::from(time.hours() % 12) * 5 + minutes / 12;
+        let hands = [
+            (hour_position, radius / 2),
+            (minutes, radius * 4 / 5),
+            (u32::from(time.seconds()), radius * 9 / 10),
+        ];
+        let count = if face.second_hand { 3 } else { 2 };
+        let center = point(0, 0);
+        for (position, length) in hands.into_iter().take(count) {
+            let (x, y) = point(position, length);
+            self.draw_line(center.0, center.1, x, y, true)?;
+        }
+        Ok(())
+    }
+}
+
+/// sin of `position` sixtieths of a turn, scaled by 1024.
+fn sine(position: u32) -> i32 {
+    let position = position % 60;
+    let (quarter, step) = ((position / 15) as usize, (position % 15) as usize);
+    match quarter {
+        0 => SINE[step],
+        1 => SINE[15 - step],
+        2 => -SINE[step],
+        _ => -SINE[15 - step],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    fn grid(spi: &mut EmulatedSpi) -> LedMatrix<&mut EmulatedSpi> {
+        let driver = Max7219::new(spi).with_device_count(4).unwrap();
+        LedMatrix::with_geometry(driver, 2, 2).unwrap()
+    }
+
+    #[test]
+    fn test_sine() {
+        assert_eq!([sine(0), sine(15), sine(30), sine(45)], [0, 1024, 0, -1024]);
+        assert_eq!(sine(50), -887);
+        assert_eq!(sine(75), 1024);
+    }
+
+    #[test]
+    fn test_hands_at_three() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let mut matrix = grid(&mut spi);
+        let face = ClockFace {
+            tick_marks: false,
+            second_hand: false,
+        };
+        matrix
+            .draw_clock_face(TimeOfDay::new(15, 0, 0).unwrap(), face)
+            .unwrap();
+        let lit: Vec<_> = matrix
+            .pixels()
+            .filter(|&(_, _, on)| on)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        // Minute hand straight up from the center, hour hand to the right
+        assert!(lit.contains(&(8, 2)));
+        assert!(lit.contains(&(11, 8)));
+        assert!(lit.iter().all(|&(x, y)| x == 8 || y == 8));
+        assert_eq!(lit.len(), 10);
+    }
+
+    #[test]
+    fn test_tick_marks_and_second_hand() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let mut matrix = grid(&mut spi);
+        matrix
+            .draw_clock_face(TimeOfDay::default(), ClockFace::default())
+            .unwrap();
+        // 12, 3, 6 and 9 o'clock on the rim
+        for (x, y) in [(8, 0), (15, 8), (8, 15), (0, 8)] {
+            assert_eq!(matrix.get_pixel_xy(x, y), Ok(true));
+        }
+        assert_eq!(matrix.get_pixel_xy(15, 0), Ok(false));
+
+        matrix.clear();
+        let face = ClockFace {
+            tick_marks: false,
+            second_hand: true,
+        };
+        matrix
+            .draw_clock_face(TimeOfDay::new(0, 0, 30).unwrap(), face)
+            .unwrap();
+        assert_eq!(matrix.get_pixel_xy(8, 14), Ok(true));
+    }
+}
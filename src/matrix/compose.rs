@@ -0,0 +1,152 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{BlitMode, Canvas, Font, LedMatrix, MODULE_SIZE, Sprite};
+use crate::Result;
+
+impl Canvas {
+    /// Draw `sprite` with its top-left corner at (`x`, `y`), see
+    /// [`LedMatrix::blit`]. Parts outside the canvas are clipped.
+    pub fn blit(&mut self, sprite: &Sprite, x: i32, y: i32, mode: BlitMode) {
+        for (row, sprite_y) in (y..).zip(0..sprite.height()) {
+            for (column, sprite_x) in (x..).zip(0..sprite.width()) {
+                self.blend_pixel(column, row, sprite.pixel(sprite_x, sprite_y), mode);
+            }
+        }
+    }
+
+    /// Draw all of `source` with its top-left corner at (`x`, `y`),
+    /// combining pixels per `mode`. Parts outside the canvas are clipped.
+    pub fn blit_canvas(&mut self, source: &Canvas, x: i32, y: i32, mode: BlitMode) {
+        for (row, source_y) in (y..).zip(0..source.height()) {
+            for (column, source_x) in (x..).zip(0..source.width()) {
+                let on = source.get_pixel(source_x, source_y) == Ok(true);
+                self.blend_pixel(column, row, on, mode);
+            }
+        }
+    }
+
+    /// Draw `text` in `font` starting at (`x`, `y`) with `spacing` blank
+    /// columns after each character, like [`LedMatrix::draw_text`]. Returns
+    /// `x` of the character that would follow.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character; characters before it have
+    ///   already been drawn.
+    pub fn draw_text<F: Font>(
+        &mut self,
+        x: i32,
+        y: i32,
+        text: &str,
+        font: F,
+        spacing: usize,
+    ) -> Result<i32> {
+        let mut x = x;
+        for c in text.chars() {
+            let glyph = font.glyph(c)?;
+            let advance = glyph.len() + spacing;
+            for (column, offset) in (x..).zip(0..advance) {
+                let bits = glyph.get(offset).copied().unwrap_or(0);
+                for (row, bit) in (y..).zip(0..font.height().min(MODULE_SIZE)) {
+                    self.blend_pixel(column, row, bits & (0x80 >> bit) != 0, BlitMode::Copy);
+                }
+            }
+            x += advance as i32;
+        }
+        Ok(x)
+    }
+
+    /// Combine one pixel at signed coordinates, ignoring pixels off the
+    /// canvas.
+    pub(super) fn blend_pixel(&mut self, x: i32, y: i32, on: bool, mode: BlitMode) {
+        let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+            return;
+        };
+        if let Ok(current) = self.get_pixel(x, y) {
+            let _ = self.set_pixel(x, y, mode.blend(current, on));
+        }
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw all of `source` with its top-left corner at chain-wide
+    /// (`x`, `y`), combining pixels per `mode`. Honours the canvas rotation
+    /// like [`blit`](Self::blit); parts outside the surface are clipped.
+    pub fn blit_canvas(&mut self, source: &Canvas, x: i32, y: i32, mode: BlitMode) -> Result<()> {
+        for (row, source_y) in (y..).zip(0..source.height()) {
+            for (column, source_x) in (x..).zip(0..source.width()) {
+                let on = source.get_pixel(source_x, source_y)?;
+                self.blend_pixel(column, row, on, mode)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, error::Error, host::EmulatedSpi};
+
+    /// Every glyph is two lit columns.
+    struct Bars;
+
+    impl Font for Bars {
+        fn height(&self) -> usize {
+            MODULE_SIZE
+        }
+
+        fn glyph(&self, c: char) -> Result<&[u8]> {
+            match c {
+                'x' => Err(Error::UnsupportedChar),
+                _ => Ok(&[0xFF, 0xFF]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_on_canvas() {
+        let mut widget = Canvas::for_chain(1).unwrap();
+        assert_eq!(widget.draw_text(1, 0, "ab", Bars, 1), Ok(7));
+        assert_eq!(widget.get_pixel(2, 7), Ok(true));
+        assert_eq!(widget.get_pixel(3, 0), Ok(false));
+        assert_eq!(widget.get_pixel(4, 0), Ok(true));
+        assert_eq!(
+            widget.draw_text(0, 0, "x", Bars, 1),
+            Err(Error::UnsupportedChar)
+        );
+
+        let dot = Sprite::new(1, 1, &[0x80]).unwrap();
+        widget.blit(&dot, 7, 7, BlitMode::Xor);
+        widget.blit(&dot, -1, 8, BlitMode::Or);
+        assert_eq!(widget.get_pixel(7, 7), Ok(true));
+
+        let mut target = Canvas::for_chain(2).unwrap();
+        target.fill(true);
+        target.blit_canvas(&widget, 10, 0, BlitMode::And);
+        assert_eq!(target.get_pixel(9, 0), Ok(true));
+        assert_eq!(target.get_pixel(10, 0), Ok(false));
+        assert_eq!(target.get_pixel(11, 0), Ok(true));
+    }
+
+    #[test]
+    fn test_blit_canvas_into_matrix() {
+        let mut widget = Canvas::for_chain(1).unwrap();
+        widget.set_pixel(0, 0, true).unwrap();
+        widget.set_pixel(7, 7, true).unwrap();
+
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        // Cached once, drawn twice
+        matrix.blit_canvas(&widget, 4, 0, BlitMode::Copy).unwrap();
+        matrix.blit_canvas(&widget, 12, 1, BlitMode::Or).unwrap();
+        assert_eq!(matrix.get_pixel_xy(4, 0), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(11, 7), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(12, 1), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(5, 0), Ok(false));
+    }
+}
@@ -0,0 +1,92 @@
+use core::fmt::{self, Write};
+
+use super::Canvas;
+
+/// Character [`Canvas::write_ascii`] draws lit pixels with
+pub const ASCII_ON: char = '█';
+/// Character [`Canvas::write_ascii`] draws dark pixels with
+pub const ASCII_OFF: char = ' ';
+
+impl Canvas {
+    /// Draw the canvas as text, one line per pixel row, lit pixels as
+    /// [`ASCII_ON`] and dark ones as [`ASCII_OFF`].
+    pub fn write_ascii<W: Write>(&self, out: &mut W) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let on = self.get_pixel(x, y) == Ok(true);
+                out.write_char(if on { ASCII_ON } else { ASCII_OFF })?;
+            }
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// Write the canvas as a plain PBM (`P1`) image, lit pixels black.
+    pub fn write_pbm<W: Write>(&self, out: &mut W) -> fmt::Result {
+        writeln!(out, "P1")?;
+        writeln!(out, "{} {}", self.width(), self.height())?;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                if x > 0 {
+                    out.write_char(' ')?;
+                }
+                let on = self.get_pixel(x, y) == Ok(true);
+                out.write_char(if on { '1' } else { '0' })?;
+            }
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// [`write_ascii`](Self::write_ascii) into a `String`, e.g. for test
+    /// failure messages and bug reports.
+    #[cfg(any(test, feature = "std"))]
+    pub fn render_ascii(&self) -> std::string::String {
+        let mut out = std::string::String::new();
+        // Writing to a String can't fail
+        let _ = self.write_ascii(&mut out);
+        out
+    }
+
+    /// [`write_pbm`](Self::write_pbm) into a `String`.
+    #[cfg(any(test, feature = "std"))]
+    pub fn render_pbm(&self) -> std::string::String {
+        let mut out = std::string::String::new();
+        let _ = self.write_pbm(&mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagonal() -> Canvas {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        for i in 0..3 {
+            canvas.set_pixel(i, i, true).unwrap();
+        }
+        canvas
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let ascii = diagonal().render_ascii();
+        let lines: std::vec::Vec<_> = ascii.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "█       ");
+        assert_eq!(lines[2], "  █     ");
+        assert_eq!(lines[7], "        ");
+    }
+
+    #[test]
+    fn test_render_pbm() {
+        let pbm = diagonal().render_pbm();
+        let mut lines = pbm.lines();
+        assert_eq!(lines.next(), Some("P1"));
+        assert_eq!(lines.next(), Some("8 8"));
+        assert_eq!(lines.next(), Some("1 0 0 0 0 0 0 0"));
+        assert_eq!(lines.next(), Some("0 1 0 0 0 0 0 0"));
+        assert_eq!(lines.count(), 6);
+    }
+}
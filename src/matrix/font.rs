@@ -0,0 +1,455 @@
+#[cfg(feature = "font-8x8")]
+use super::Frame;
+use crate::Result;
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+use crate::error::Error;
+
+/// Width of a space in [`BuiltinFont::Proportional`]
+#[cfg(feature = "font-5x7")]
+const PROPORTIONAL_SPACE: [u8; 3] = [0; 3];
+
+/// Glyph source for the matrix text methods such as
+/// [`LedMatrix::draw_text`](super::LedMatrix::draw_text).
+///
+/// Implement it to draw with custom or localized fonts. Glyphs are column
+/// bitmaps at most 8 pixels tall and may differ in width; the gap between
+/// characters is added by the matrix's
+/// [letter spacing](super::LedMatrix::set_letter_spacing), so glyphs should
+/// not include it.
+pub trait Font {
+    /// Glyph height in pixels, at most 8. Rows below it are left untouched.
+    fn height(&self) -> usize;
+
+    /// Columns of `c`, one byte per column from left to right, bit 7 being
+    /// the top pixel. The glyph width is the number of columns.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if the font has no glyph for `c`.
+    fn glyph(&self, c: char) -> Result<&[u8]>;
+}
+
+impl<F: Font + ?Sized> Font for &F {
+    fn height(&self) -> usize {
+        (**self).height()
+    }
+
+    fn glyph(&self, c: char) -> Result<&[u8]> {
+        (**self).glyph(c)
+    }
+}
+
+/// Bitmap fonts built into the crate for matrix text.
+///
+/// They cover printable ASCII plus `°`, `µ` (also for Greek `μ`), the arrows
+/// `←↑→↓` and a full and low battery (`🔋`, `🪫`).
+///
+/// Each font is behind a cargo feature so unused glyph tables don't cost
+/// flash: `font-8x8` for `Large`, `font-5x7` for the others. Both are
+/// enabled by default.
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinFont {
+    /// 8x8 IBM PC style font, one character per module at the default
+    /// spacing of 1
+    #[cfg(feature = "font-8x8")]
+    Large,
+    /// Monospace 5x7 font, five characters on an 8x32 panel
+    #[cfg(feature = "font-5x7")]
+    Compact,
+    /// The 5x7 glyphs with blank side columns trimmed, e.g. `!` is one
+    /// column wide, which reads better in scrolling tickers
+    #[cfg(feature = "font-5x7")]
+    Proportional,
+}
+
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+impl Font for BuiltinFont {
+    fn height(&self) -> usize {
+        match self {
+            #[cfg(feature = "font-8x8")]
+            Self::Large => 8,
+            #[cfg(feature = "font-5x7")]
+            Self::Compact | Self::Proportional => 7,
+        }
+    }
+
+    fn glyph(&self, c: char) -> Result<&[u8]> {
+        let index = glyph_index(c)?;
+        Ok(match self {
+            #[cfg(feature = "font-8x8")]
+            Self::Large => FONT_8X8.get(index).ok_or(Error::UnsupportedChar)?,
+            #[cfg(feature = "font-5x7")]
+            Self::Compact => FONT_5X7.get(index).ok_or(Error::UnsupportedChar)?,
+            #[cfg(feature = "font-5x7")]
+            Self::Proportional if c == ' ' => &PROPORTIONAL_SPACE,
+            #[cfg(feature = "font-5x7")]
+            Self::Proportional => trim(FONT_5X7.get(index).ok_or(Error::UnsupportedChar)?),
+        })
+    }
+}
+
+/// `columns` without blank columns on either side.
+#[cfg(feature = "font-5x7")]
+fn trim(columns: &[u8]) -> &[u8] {
+    let start = columns.iter().position(|&bits| bits != 0).unwrap_or(0);
+    let end = columns
+        .iter()
+        .rposition(|&bits| bits != 0)
+        .map_or(start, |last| last + 1);
+    columns.get(start..end).unwrap_or_default()
+}
+
+/// Render `text` in the [`BuiltinFont::Large`] font at compile time, one
+/// character per 8x8 frame with the spacing column on its right, so static
+/// labels live in flash instead of being drawn at runtime:
+/// `const LABEL: [Frame; 4] = render_static("BOOT");`
+///
+/// Frames past the end of `text` are blank. `text` must be printable ASCII
+/// and at most `N` characters long, or evaluation panics, which is a build
+/// error in a const context.
+#[cfg(feature = "font-8x8")]
+pub const fn render_static<const N: usize>(text: &str) -> [Frame; N] {
+    let mut frames = [Frame::EMPTY; N];
+    let mut text = text.as_bytes();
+    let mut rest: &mut [Frame] = &mut frames;
+    while let [c, text_tail @ ..] = text {
+        let [frame, rest_tail @ ..] = rest else {
+            panic!("text has more characters than frames");
+        };
+        assert!(
+            *c >= b' ' && *c <= b'~',
+            "render_static only supports printable ASCII"
+        );
+        let Some((_, [glyph, ..])) = FONT_8X8.split_at_checked((*c - b' ') as usize) else {
+            unreachable!();
+        };
+        // Glyph columns become frame rows' bits, left to right
+        let mut columns = glyph.as_slice();
+        let mut column_bit = 0x80;
+        while let [bits, columns_tail @ ..] = columns {
+            let mut rows = frame.0.as_mut_slice();
+            let mut row_bit = 0x80;
+            while let [row, rows_tail @ ..] = rows {
+                if *bits & row_bit != 0 {
+                    *row |= column_bit;
+                }
+                row_bit >>= 1;
+                rows = rows_tail;
+            }
+            column_bit >>= 1;
+            columns = columns_tail;
+        }
+        text = text_tail;
+        rest = rest_tail;
+    }
+    frames
+}
+
+/// Table index of `c` in the built-in fonts.
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+fn glyph_index(c: char) -> Result<usize> {
+    // Greek small mu is drawn like the micro sign
+    let c = if c == 'μ' { 'µ' } else { c };
+    if (' '..='~').contains(&c) {
+        return Ok(c as usize - ' ' as usize);
+    }
+    SYMBOLS
+        .iter()
+        .position(|&symbol| symbol == c)
+        .map(|position| ASCII_COUNT + position)
+        .ok_or(Error::UnsupportedChar)
+}
+
+/// Printable ASCII, `' '` through `'~'`, starts every font table
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+const ASCII_COUNT: usize = 95;
+
+/// Non-ASCII symbols following the ASCII glyphs in every font table
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+const SYMBOLS: [char; 8] = ['°', 'µ', '←', '↑', '→', '↓', '🔋', '🪫'];
+
+/// Glyphs per font
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+const GLYPH_COUNT: usize = ASCII_COUNT + SYMBOLS.len();
+
+/// 8x8 font in the style of the IBM PC (CP437) ROM font.
+///
+/// One entry per glyph, one byte per column from left to right, bit 7 being
+/// the top pixel. Capitals sit on rows 0..7 and row 7 is kept for
+/// descenders. The eighth column of the classic font is left to letter
+/// spacing.
+#[cfg(feature = "font-8x8")]
+const FONT_8X8: [[u8; 7]; GLYPH_COUNT] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0xFA, 0xFA, 0x00, 0x00, 0x00], // '!'
+    [0x00, 0xE0, 0xC0, 0x00, 0xE0, 0xC0, 0x00], // '"'
+    [0x28, 0xFE, 0xFE, 0x28, 0xFE, 0xFE, 0x28], // '#'
+    [0x20, 0x74, 0x54, 0xFE, 0x54, 0x4C, 0x08], // '$'
+    [0xC6, 0xCC, 0x18, 0x30, 0x66, 0xC6, 0x80], // '%'
+    [0x0C, 0x5E, 0xF2, 0xBA, 0xEC, 0x5E, 0x12], // '&'
+    [0x00, 0x20, 0xE0, 0xC0, 0x00, 0x00, 0x00], // '\''
+    [0x00, 0x38, 0x7C, 0xC6, 0x82, 0x00, 0x00], // '('
+    [0x00, 0x82, 0xC6, 0x7C, 0x38, 0x00, 0x00], // ')'
+    [0x10, 0x54, 0x38, 0x10, 0x38, 0x54, 0x10], // '*'
+    [0x10, 0x10, 0x7C, 0x7C, 0x10, 0x10, 0x00], // '+'
+    [0x00, 0x01, 0x07, 0x06, 0x00, 0x00, 0x00], // ','
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00], // '-'
+    [0x00, 0x00, 0x06, 0x06, 0x00, 0x00, 0x00], // '.'
+    [0x06, 0x0C, 0x18, 0x30, 0x60, 0xC0, 0x80], // '/'
+    [0x7C, 0xFE, 0x9A, 0xB2, 0xFE, 0x7C, 0x00], // '0'
+    [0x02, 0x42, 0xFE, 0xFE, 0x02, 0x02, 0x00], // '1'
+    [0x46, 0xCE, 0x9A, 0x92, 0xF6, 0x66, 0x00], // '2'
+    [0x44, 0xC6, 0x92, 0x92, 0xFE, 0x6C, 0x00], // '3'
+    [0x18, 0x38, 0x68, 0xCA, 0xFE, 0xFE, 0x0A], // '4'
+    [0xE4, 0xE6, 0xA2, 0xA2, 0xBE, 0x9C, 0x00], // '5'
+    [0x3C, 0x7E, 0xD2, 0x92, 0x9E, 0x0C, 0x00], // '6'
+    [0xC0, 0xC0, 0x8E, 0x9E, 0xF0, 0xE0, 0x00], // '7'
+    [0x6C, 0xFE, 0x92, 0x92, 0xFE, 0x6C, 0x00], // '8'
+    [0x60, 0xF2, 0x92, 0x96, 0xFC, 0x78, 0x00], // '9'
+    [0x00, 0x00, 0x66, 0x66, 0x00, 0x00, 0x00], // ':'
+    [0x00, 0x01, 0x67, 0x66, 0x00, 0x00, 0x00], // ';'
+    [0x10, 0x38, 0x6C, 0xC6, 0x82, 0x00, 0x00], // '<'
+    [0x24, 0x24, 0x24, 0x24, 0x24, 0x24, 0x00], // '='
+    [0x00, 0x82, 0xC6, 0x6C, 0x38, 0x10, 0x00], // '>'
+    [0x40, 0xC0, 0x8A, 0x9A, 0xF0, 0x60, 0x00], // '?'
+    [0x7C, 0xFE, 0x82, 0xBA, 0xBA, 0xF8, 0x78], // '@'
+    [0x3E, 0x7E, 0xC8, 0xC8, 0x7E, 0x3E, 0x00], // 'A'
+    [0x82, 0xFE, 0xFE, 0x92, 0x92, 0xFE, 0x6C], // 'B'
+    [0x38, 0x7C, 0xC6, 0x82, 0x82, 0xC6, 0x44], // 'C'
+    [0x82, 0xFE, 0xFE, 0x82, 0xC6, 0x7C, 0x38], // 'D'
+    [0x82, 0xFE, 0xFE, 0x92, 0xBA, 0x82, 0xC6], // 'E'
+    [0x82, 0xFE, 0xFE, 0x92, 0xB8, 0x80, 0xC0], // 'F'
+    [0x38, 0x7C, 0xC6, 0x82, 0x8A, 0xCE, 0x4E], // 'G'
+    [0xFE, 0xFE, 0x10, 0x10, 0xFE, 0xFE, 0x00], // 'H'
+    [0x00, 0x82, 0xFE, 0xFE, 0x82, 0x00, 0x00], // 'I'
+    [0x0C, 0x0E, 0x02, 0x82, 0xFE, 0xFC, 0x80], // 'J'
+    [0x82, 0xFE, 0xFE, 0x10, 0x38, 0xEE, 0xC6], // 'K'
+    [0x82, 0xFE, 0xFE, 0x82, 0x02, 0x06, 0x0E], // 'L'
+    [0xFE, 0xFE, 0x70, 0x38, 0x70, 0xFE, 0xFE], // 'M'
+    [0xFE, 0xFE, 0x60, 0x30, 0x18, 0xFE, 0xFE], // 'N'
+    [0x38, 0x7C, 0xC6, 0x82, 0xC6, 0x7C, 0x38], // 'O'
+    [0x82, 0xFE, 0xFE, 0x92, 0x90, 0xF0, 0x60], // 'P'
+    [0x78, 0xFC, 0x84, 0x8E, 0xFE, 0x7A, 0x00], // 'Q'
+    [0x82, 0xFE, 0xFE, 0x90, 0x98, 0xFE, 0x66], // 'R'
+    [0x64, 0xF6, 0xB2, 0x9A, 0xCE, 0x4C, 0x00], // 'S'
+    [0xC0, 0x82, 0xFE, 0xFE, 0x82, 0xC0, 0x00], // 'T'
+    [0xFE, 0xFE, 0x02, 0x02, 0xFE, 0xFE, 0x00], // 'U'
+    [0xF8, 0xFC, 0x06, 0x06, 0xFC, 0xF8, 0x00], // 'V'
+    [0xFE, 0xFE, 0x0C, 0x18, 0x0C, 0xFE, 0xFE], // 'W'
+    [0xC2, 0xE6, 0x3C, 0x18, 0x3C, 0xE6, 0xC2], // 'X'
+    [0xE0, 0xF2, 0x1E, 0x1E, 0xF2, 0xE0, 0x00], // 'Y'
+    [0xE2, 0xC6, 0x8E, 0x9A, 0xB2, 0xE6, 0xCE], // 'Z'
+    [0x00, 0xFE, 0xFE, 0x82, 0x82, 0x00, 0x00], // '['
+    [0x80, 0xC0, 0x60, 0x30, 0x18, 0x0C, 0x06], // '\\'
+    [0x00, 0x82, 0x82, 0xFE, 0xFE, 0x00, 0x00], // ']'
+    [0x10, 0x30, 0x60, 0xC0, 0x60, 0x30, 0x10], // '^'
+    [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01], // '_'
+    [0x00, 0x00, 0xC0, 0xE0, 0x20, 0x00, 0x00], // '`'
+    [0x04, 0x2E, 0x2A, 0x2A, 0x3C, 0x1E, 0x02], // 'a'
+    [0x82, 0xFE, 0xFC, 0x12, 0x12, 0x1E, 0x0C], // 'b'
+    [0x1C, 0x3E, 0x22, 0x22, 0x36, 0x14, 0x00], // 'c'
+    [0x0C, 0x1E, 0x12, 0x92, 0xFC, 0xFE, 0x02], // 'd'
+    [0x1C, 0x3E, 0x2A, 0x2A, 0x3A, 0x18, 0x00], // 'e'
+    [0x12, 0x7E, 0xFE, 0x92, 0xC0, 0x40, 0x00], // 'f'
+    [0x19, 0x3D, 0x25, 0x25, 0x1F, 0x3E, 0x20], // 'g'
+    [0x82, 0xFE, 0xFE, 0x10, 0x20, 0x3E, 0x1E], // 'h'
+    [0x00, 0x22, 0xBE, 0xBE, 0x02, 0x00, 0x00], // 'i'
+    [0x06, 0x07, 0x01, 0x01, 0xBF, 0xBE, 0x00], // 'j'
+    [0x82, 0xFE, 0xFE, 0x08, 0x1C, 0x36, 0x22], // 'k'
+    [0x00, 0x82, 0xFE, 0xFE, 0x02, 0x00, 0x00], // 'l'
+    [0x3E, 0x3E, 0x18, 0x1C, 0x38, 0x3E, 0x1E], // 'm'
+    [0x3E, 0x3E, 0x20, 0x20, 0x3E, 0x1E, 0x00], // 'n'
+    [0x1C, 0x3E, 0x22, 0x22, 0x3E, 0x1C, 0x00], // 'o'
+    [0x21, 0x3F, 0x1F, 0x25, 0x24, 0x3C, 0x18], // 'p'
+    [0x18, 0x3C, 0x24, 0x25, 0x1F, 0x3F, 0x21], // 'q'
+    [0x22, 0x3E, 0x1E, 0x32, 0x20, 0x38, 0x18], // 'r'
+    [0x12, 0x3A, 0x2A, 0x2A, 0x2E, 0x24, 0x00], // 's'
+    [0x00, 0x20, 0x7C, 0xFE, 0x22, 0x24, 0x00], // 't'
+    [0x3C, 0x3E, 0x02, 0x02, 0x3C, 0x3E, 0x02], // 'u'
+    [0x38, 0x3C, 0x06, 0x06, 0x3C, 0x38, 0x00], // 'v'
+    [0x3C, 0x3E, 0x0E, 0x1C, 0x0E, 0x3E, 0x3C], // 'w'
+    [0x22, 0x36, 0x1C, 0x08, 0x1C, 0x36, 0x22], // 'x'
+    [0x39, 0x3D, 0x05, 0x05, 0x3F, 0x3E, 0x00], // 'y'
+    [0x32, 0x26, 0x2E, 0x3A, 0x32, 0x26, 0x00], // 'z'
+    [0x10, 0x10, 0x7C, 0xEE, 0x82, 0x82, 0x00], // '{'
+    [0x00, 0x00, 0x00, 0xEE, 0xEE, 0x00, 0x00], // '|'
+    [0x82, 0x82, 0xEE, 0x7C, 0x10, 0x10, 0x00], // '}'
+    [0x40, 0xC0, 0x80, 0xC0, 0x40, 0xC0, 0x80], // '~'
+    [0x40, 0xE0, 0xA0, 0xE0, 0x40, 0x00, 0x00], // '°'
+    [0x3F, 0x3F, 0x04, 0x04, 0x3C, 0x3C, 0x00], // 'µ'
+    [0x10, 0x38, 0x7C, 0x10, 0x10, 0x10, 0x10], // '←'
+    [0x00, 0x20, 0x60, 0xFE, 0x60, 0x20, 0x00], // '↑'
+    [0x10, 0x10, 0x10, 0x10, 0x7C, 0x38, 0x10], // '→'
+    [0x00, 0x08, 0x0C, 0xFE, 0x0C, 0x08, 0x00], // '↓'
+    [0x7E, 0x7E, 0x7E, 0x7E, 0x7E, 0x7E, 0x18], // '🔋'
+    [0x7E, 0x5A, 0x42, 0x42, 0x42, 0x7E, 0x18], // '🪫'
+];
+
+/// 5x7 font, laid out like the 8x8 one with row 7 unused.
+#[cfg(feature = "font-5x7")]
+const FONT_5X7: [[u8; 5]; GLYPH_COUNT] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0xFA, 0x00, 0x00], // '!'
+    [0x00, 0xE0, 0x00, 0xE0, 0x00], // '"'
+    [0x28, 0xFE, 0x28, 0xFE, 0x28], // '#'
+    [0x24, 0x54, 0xFE, 0x54, 0x48], // '$'
+    [0xC4, 0xC8, 0x10, 0x26, 0x46], // '%'
+    [0x6C, 0x92, 0xAA, 0x44, 0x0A], // '&'
+    [0x00, 0xA0, 0xC0, 0x00, 0x00], // '\''
+    [0x00, 0x38, 0x44, 0x82, 0x00], // '('
+    [0x00, 0x82, 0x44, 0x38, 0x00], // ')'
+    [0x28, 0x10, 0x7C, 0x10, 0x28], // '*'
+    [0x10, 0x10, 0x7C, 0x10, 0x10], // '+'
+    [0x00, 0x0A, 0x0C, 0x00, 0x00], // ','
+    [0x10, 0x10, 0x10, 0x10, 0x10], // '-'
+    [0x00, 0x06, 0x06, 0x00, 0x00], // '.'
+    [0x04, 0x08, 0x10, 0x20, 0x40], // '/'
+    [0x7C, 0x8A, 0x92, 0xA2, 0x7C], // '0'
+    [0x00, 0x42, 0xFE, 0x02, 0x00], // '1'
+    [0x42, 0x86, 0x8A, 0x92, 0x62], // '2'
+    [0x84, 0x82, 0xA2, 0xD2, 0x8C], // '3'
+    [0x18, 0x28, 0x48, 0xFE, 0x08], // '4'
+    [0xE4, 0xA2, 0xA2, 0xA2, 0x9C], // '5'
+    [0x3C, 0x52, 0x92, 0x92, 0x0C], // '6'
+    [0x80, 0x8E, 0x90, 0xA0, 0xC0], // '7'
+    [0x6C, 0x92, 0x92, 0x92, 0x6C], // '8'
+    [0x60, 0x92, 0x92, 0x94, 0x78], // '9'
+    [0x00, 0x6C, 0x6C, 0x00, 0x00], // ':'
+    [0x00, 0x6A, 0x6C, 0x00, 0x00], // ';'
+    [0x10, 0x28, 0x44, 0x82, 0x00], // '<'
+    [0x28, 0x28, 0x28, 0x28, 0x28], // '='
+    [0x00, 0x82, 0x44, 0x28, 0x10], // '>'
+    [0x40, 0x80, 0x8A, 0x90, 0x60], // '?'
+    [0x4C, 0x92, 0x9E, 0x82, 0x7C], // '@'
+    [0x7E, 0x88, 0x88, 0x88, 0x7E], // 'A'
+    [0xFE, 0x92, 0x92, 0x92, 0x6C], // 'B'
+    [0x7C, 0x82, 0x82, 0x82, 0x44], // 'C'
+    [0xFE, 0x82, 0x82, 0x44, 0x38], // 'D'
+    [0xFE, 0x92, 0x92, 0x92, 0x82], // 'E'
+    [0xFE, 0x90, 0x90, 0x90, 0x80], // 'F'
+    [0x7C, 0x82, 0x92, 0x92, 0x5E], // 'G'
+    [0xFE, 0x10, 0x10, 0x10, 0xFE], // 'H'
+    [0x00, 0x82, 0xFE, 0x82, 0x00], // 'I'
+    [0x04, 0x02, 0x82, 0xFC, 0x80], // 'J'
+    [0xFE, 0x10, 0x28, 0x44, 0x82], // 'K'
+    [0xFE, 0x02, 0x02, 0x02, 0x02], // 'L'
+    [0xFE, 0x40, 0x30, 0x40, 0xFE], // 'M'
+    [0xFE, 0x20, 0x10, 0x08, 0xFE], // 'N'
+    [0x7C, 0x82, 0x82, 0x82, 0x7C], // 'O'
+    [0xFE, 0x90, 0x90, 0x90, 0x60], // 'P'
+    [0x7C, 0x82, 0x8A, 0x84, 0x7A], // 'Q'
+    [0xFE, 0x90, 0x98, 0x94, 0x62], // 'R'
+    [0x62, 0x92, 0x92, 0x92, 0x8C], // 'S'
+    [0x80, 0x80, 0xFE, 0x80, 0x80], // 'T'
+    [0xFC, 0x02, 0x02, 0x02, 0xFC], // 'U'
+    [0xF8, 0x04, 0x02, 0x04, 0xF8], // 'V'
+    [0xFC, 0x02, 0x1C, 0x02, 0xFC], // 'W'
+    [0xC6, 0x28, 0x10, 0x28, 0xC6], // 'X'
+    [0xE0, 0x10, 0x0E, 0x10, 0xE0], // 'Y'
+    [0x86, 0x8A, 0x92, 0xA2, 0xC2], // 'Z'
+    [0x00, 0xFE, 0x82, 0x82, 0x00], // '['
+    [0x40, 0x20, 0x10, 0x08, 0x04], // '\\'
+    [0x00, 0x82, 0x82, 0xFE, 0x00], // ']'
+    [0x20, 0x40, 0x80, 0x40, 0x20], // '^'
+    [0x02, 0x02, 0x02, 0x02, 0x02], // '_'
+    [0x00, 0x80, 0x40, 0x20, 0x00], // '`'
+    [0x04, 0x2A, 0x2A, 0x2A, 0x1E], // 'a'
+    [0xFE, 0x12, 0x22, 0x22, 0x1C], // 'b'
+    [0x1C, 0x22, 0x22, 0x22, 0x04], // 'c'
+    [0x1C, 0x22, 0x22, 0x12, 0xFE], // 'd'
+    [0x1C, 0x2A, 0x2A, 0x2A, 0x18], // 'e'
+    [0x10, 0x7E, 0x90, 0x80, 0x40], // 'f'
+    [0x30, 0x4A, 0x4A, 0x4A, 0x7C], // 'g'
+    [0xFE, 0x10, 0x20, 0x20, 0x1E], // 'h'
+    [0x00, 0x22, 0xBE, 0x02, 0x00], // 'i'
+    [0x04, 0x02, 0x22, 0xBC, 0x00], // 'j'
+    [0xFE, 0x08, 0x14, 0x22, 0x00], // 'k'
+    [0x00, 0x82, 0xFE, 0x02, 0x00], // 'l'
+    [0x3E, 0x20, 0x18, 0x20, 0x1E], // 'm'
+    [0x3E, 0x10, 0x20, 0x20, 0x1E], // 'n'
+    [0x1C, 0x22, 0x22, 0x22, 0x1C], // 'o'
+    [0x3E, 0x28, 0x28, 0x28, 0x10], // 'p'
+    [0x10, 0x28, 0x28, 0x18, 0x3E], // 'q'
+    [0x3E, 0x10, 0x20, 0x20, 0x10], // 'r'
+    [0x12, 0x2A, 0x2A, 0x2A, 0x04], // 's'
+    [0x20, 0xFC, 0x22, 0x02, 0x04], // 't'
+    [0x3C, 0x02, 0x02, 0x04, 0x3E], // 'u'
+    [0x38, 0x04, 0x02, 0x04, 0x38], // 'v'
+    [0x3C, 0x02, 0x0C, 0x02, 0x3C], // 'w'
+    [0x22, 0x14, 0x08, 0x14, 0x22], // 'x'
+    [0x30, 0x0A, 0x0A, 0x0A, 0x3C], // 'y'
+    [0x22, 0x26, 0x2A, 0x32, 0x22], // 'z'
+    [0x00, 0x10, 0x6C, 0x82, 0x00], // '{'
+    [0x00, 0x00, 0xFE, 0x00, 0x00], // '|'
+    [0x00, 0x82, 0x6C, 0x10, 0x00], // '}'
+    [0x10, 0x20, 0x10, 0x08, 0x10], // '~'
+    [0x60, 0x90, 0x90, 0x60, 0x00], // '°'
+    [0x3E, 0x04, 0x04, 0x38, 0x04], // 'µ'
+    [0x10, 0x38, 0x54, 0x10, 0x10], // '←'
+    [0x20, 0x40, 0xFE, 0x40, 0x20], // '↑'
+    [0x10, 0x10, 0x54, 0x38, 0x10], // '→'
+    [0x08, 0x04, 0xFE, 0x04, 0x08], // '↓'
+    [0x7E, 0xFE, 0xFE, 0xFE, 0x7E], // '🔋'
+    [0x7E, 0xC6, 0xC6, 0xC6, 0x7E], // '🪫'
+];
+
+#[cfg(all(test, any(feature = "font-8x8", feature = "font-5x7")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "font-8x8")]
+    fn test_glyph_range() {
+        assert_eq!(BuiltinFont::Large.glyph(' '), Ok(&[0u8; 7][..]));
+        assert_eq!(BuiltinFont::Large.glyph('~').map(<[u8]>::len), Ok(7));
+        assert_eq!(
+            BuiltinFont::Large.glyph('\u{7F}'),
+            Err(Error::UnsupportedChar)
+        );
+        assert_eq!(BuiltinFont::Large.glyph('\n'), Err(Error::UnsupportedChar));
+    }
+
+    #[test]
+    #[cfg(feature = "font-8x8")]
+    fn test_render_static() {
+        use crate::{driver::Max7219, host::EmulatedSpi, matrix::LedMatrix};
+
+        const LABEL: [Frame; 3] = render_static("Hi");
+        assert_eq!(LABEL[2], Frame::EMPTY);
+
+        // Same pixels as drawing the text at runtime
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(3).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.draw_text(0, 0, "Hi", BuiltinFont::Large).unwrap();
+        for (device_index, frame) in LABEL.iter().enumerate() {
+            for (row, &bits) in frame.rows().iter().enumerate() {
+                assert_eq!(matrix.row(device_index, row), Ok(bits));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "more characters than frames")]
+    #[cfg(feature = "font-8x8")]
+    fn test_render_static_too_long() {
+        let _: [Frame; 1] = render_static("OK");
+    }
+
+    #[test]
+    #[cfg(feature = "font-5x7")]
+    fn test_proportional_widths() {
+        assert_eq!(BuiltinFont::Compact.glyph('~').map(<[u8]>::len), Ok(5));
+        let width = |c| BuiltinFont::Proportional.glyph(c).map(<[u8]>::len);
+        assert_eq!(width('i'), Ok(3));
+        assert_eq!(width('l'), Ok(3));
+        assert_eq!(width('!'), Ok(1));
+        assert_eq!(width('M'), Ok(5));
+        assert_eq!(width(' '), Ok(3));
+        assert_eq!(
+            BuiltinFont::Proportional.glyph('1'),
+            Ok(&FONT_5X7['1' as usize - 0x20][1..4])
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::{MODULE_SIZE, Sprite};
+
+/// One 8x8 module image, row 0 first with bit 7 the leftmost column, as
+/// written to the digit registers.
+///
+/// The bitwise operators combine frames pixel by pixel, so icons and masks
+/// can be composed before being sent, e.g. with
+/// [`Max7219::write_frame_all`](crate::driver::Max7219::write_frame_all):
+/// `(icon | border) & !mask`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Frame(pub [u8; MODULE_SIZE]);
+
+impl Frame {
+    pub const EMPTY: Self = Self([0x00; MODULE_SIZE]);
+    pub const FULL: Self = Self([0xFF; MODULE_SIZE]);
+
+    pub const fn rows(&self) -> &[u8; MODULE_SIZE] {
+        &self.0
+    }
+
+    /// Whether the pixel is lit; out of range coordinates are off.
+    pub const fn pixel(&self, x: usize, y: usize) -> bool {
+        x < MODULE_SIZE && y < MODULE_SIZE && self.0[y] & (0x80 >> x) != 0
+    }
+
+    pub const fn with_pixel(mut self, x: usize, y: usize, on: bool) -> Self {
+        if x < MODULE_SIZE && y < MODULE_SIZE {
+            if on {
+                self.0[y] |= 0x80 >> x;
+            } else {
+                self.0[y] &= !(0x80 >> x);
+            }
+        }
+        self
+    }
+
+    /// Move the image `n` columns left; columns shifted in are off.
+    pub const fn shifted_left(mut self, n: usize) -> Self {
+        let mut y = 0;
+        while y < MODULE_SIZE {
+            self.0[y] = if n < MODULE_SIZE { self.0[y] << n } else { 0 };
+            y += 1;
+        }
+        self
+    }
+
+    pub const fn shifted_right(mut self, n: usize) -> Self {
+        let mut y = 0;
+        while y < MODULE_SIZE {
+            self.0[y] = if n < MODULE_SIZE { self.0[y] >> n } else { 0 };
+            y += 1;
+        }
+        self
+    }
+
+    /// Move the image `n` rows up; rows shifted in are off.
+    pub const fn shifted_up(self, n: usize) -> Self {
+        let mut rows = [0; MODULE_SIZE];
+        let mut y = 0;
+        while y + n < MODULE_SIZE {
+            rows[y] = self.0[y + n];
+            y += 1;
+        }
+        Self(rows)
+    }
+
+    pub const fn shifted_down(self, n: usize) -> Self {
+        let mut rows = [0; MODULE_SIZE];
+        let mut y = n;
+        while y < MODULE_SIZE {
+            rows[y] = self.0[y - n];
+            y += 1;
+        }
+        Self(rows)
+    }
+
+    /// Borrow the frame as an 8x8 [`Sprite`], e.g. to
+    /// [blit](super::LedMatrix::blit) it anywhere on a matrix.
+    pub fn as_sprite(&self) -> Sprite<'_> {
+        Sprite::new(MODULE_SIZE, MODULE_SIZE, &self.0).expect("8x8 sprite fits its rows")
+    }
+}
+
+impl From<[u8; MODULE_SIZE]> for Frame {
+    fn from(rows: [u8; MODULE_SIZE]) -> Self {
+        Self(rows)
+    }
+}
+
+impl From<Frame> for [u8; MODULE_SIZE] {
+    fn from(frame: Frame) -> Self {
+        frame.0
+    }
+}
+
+impl Not for Frame {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(self.0.map(|row| !row))
+    }
+}
+
+impl BitOr for Frame {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self {
+        self |= rhs;
+        self
+    }
+}
+
+impl BitOrAssign for Frame {
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (row, other) in self.0.iter_mut().zip(rhs.0) {
+            *row |= other;
+        }
+    }
+}
+
+impl BitAnd for Frame {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self {
+        self &= rhs;
+        self
+    }
+}
+
+impl BitAndAssign for Frame {
+    fn bitand_assign(&mut self, rhs: Self) {
+        for (row, other) in self.0.iter_mut().zip(rhs.0) {
+            *row &= other;
+        }
+    }
+}
+
+impl BitXor for Frame {
+    type Output = Self;
+
+    fn bitxor(mut self, rhs: Self) -> Self {
+        self ^= rhs;
+        self
+    }
+}
+
+impl BitXorAssign for Frame {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        for (row, other) in self.0.iter_mut().zip(rhs.0) {
+            *row ^= other;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BORDER: Frame = Frame([0xFF, 0x81, 0x81, 0x81, 0x81, 0x81, 0x81, 0xFF]);
+    const DOT: Frame = Frame::EMPTY.with_pixel(3, 3, true);
+
+    #[test]
+    fn test_operators() {
+        let framed = BORDER | DOT;
+        assert!(framed.pixel(3, 3));
+        assert!(framed.pixel(0, 7));
+        assert_eq!(framed & DOT, DOT);
+        assert_eq!(framed ^ BORDER, DOT);
+        assert_eq!(!Frame::EMPTY, Frame::FULL);
+        assert_eq!((!BORDER).0[1], 0x7E);
+
+        let mut frame = Frame::EMPTY;
+        frame |= BORDER;
+        frame &= !Frame::from([0xFF, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(frame.rows()[0], 0x00);
+        assert_eq!(<[u8; 8]>::from(frame)[7], 0xFF);
+    }
+
+    #[test]
+    fn test_shifts() {
+        assert_eq!(DOT.shifted_left(3), Frame::EMPTY.with_pixel(0, 3, true));
+        assert_eq!(DOT.shifted_right(4), Frame::EMPTY.with_pixel(7, 3, true));
+        assert_eq!(DOT.shifted_up(3), Frame::EMPTY.with_pixel(3, 0, true));
+        assert_eq!(DOT.shifted_down(2), Frame::EMPTY.with_pixel(3, 5, true));
+        // Shifted all the way out
+        assert_eq!(BORDER.shifted_left(8), Frame::EMPTY);
+        assert_eq!(BORDER.shifted_down(8), Frame::EMPTY);
+        assert_eq!(BORDER.shifted_up(usize::MAX), Frame::EMPTY);
+        assert_eq!(BORDER.shifted_down(0), BORDER);
+    }
+
+    #[test]
+    fn test_as_sprite() {
+        let sprite = DOT.as_sprite();
+        assert!(sprite.pixel(3, 3));
+        assert!(!sprite.pixel(4, 3));
+        assert!(!DOT.pixel(8, 3));
+    }
+}
@@ -0,0 +1,124 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, MODULE_SIZE, Sparkline};
+use crate::{MAX_DISPLAYS, Result};
+
+/// Samples kept, enough for one per column of the widest surface
+const HISTORY: usize = MAX_DISPLAYS * MODULE_SIZE;
+
+/// Live chart fed one sample per tick, e.g. CPU load or temperature.
+///
+/// Keeps the latest samples and redraws them as a [`Sparkline`] on every
+/// [`tick`](Self::tick), so the plot moves one column left per sample.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrollingGraph {
+    history: [u16; HISTORY],
+    len: usize,
+    sparkline: Sparkline,
+}
+
+impl Default for ScrollingGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrollingGraph {
+    /// Empty graph drawn with the default [`Sparkline`].
+    pub fn new() -> Self {
+        Self {
+            history: [0; HISTORY],
+            len: 0,
+            sparkline: Sparkline::default(),
+        }
+    }
+
+    pub fn with_sparkline(mut self, sparkline: Sparkline) -> Self {
+        self.sparkline = sparkline;
+        self
+    }
+
+    pub fn set_sparkline(&mut self, sparkline: Sparkline) {
+        self.sparkline = sparkline;
+    }
+
+    pub fn sparkline(&self) -> Sparkline {
+        self.sparkline
+    }
+
+    /// Samples kept, oldest first.
+    pub fn samples(&self) -> &[u16] {
+        &self.history[..self.len]
+    }
+
+    /// Add a sample without drawing, dropping the oldest if the history is
+    /// full.
+    pub fn push(&mut self, sample: u16) {
+        if self.len == HISTORY {
+            self.history.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        self.history[self.len] = sample;
+        self.len += 1;
+    }
+
+    /// Forget all samples.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Add `sample` and show the updated chart across the whole surface.
+    ///
+    /// # Errors
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>, sample: u16) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        self.push(sample);
+        matrix.clear();
+        matrix.draw_sparkline(self.samples(), self.sparkline)?;
+        matrix.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, matrix::SparklineStyle};
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut graph = ScrollingGraph::new();
+        for sample in 0..100 {
+            graph.push(sample);
+        }
+        assert_eq!(graph.samples().len(), HISTORY);
+        assert_eq!(graph.samples()[0], 100 - HISTORY as u16);
+        assert_eq!(graph.samples().last(), Some(&99));
+        graph.clear();
+        assert!(graph.samples().is_empty());
+    }
+
+    #[test]
+    fn test_tick_shifts_plot_left() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+            matrix.init().unwrap();
+            let mut graph = ScrollingGraph::new().with_sparkline(Sparkline {
+                style: SparklineStyle::Line,
+                range: Some((0, 7)),
+            });
+            graph.tick(&mut matrix, 7).unwrap();
+            assert_eq!(matrix.get_pixel_xy(7, 0), Ok(true));
+            for _ in 0..3 {
+                graph.tick(&mut matrix, 0).unwrap();
+            }
+        }
+        let digits = spi.emulator().digits(0).unwrap();
+        // The peak has moved three columns left
+        assert_eq!(digits[0], 0x08);
+        assert_eq!(digits[7], 0x07);
+    }
+}
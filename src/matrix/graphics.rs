@@ -0,0 +1,216 @@
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    Pixel,
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::ImageDrawable,
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+};
+
+use embedded_hal::spi::SpiDevice;
+
+use super::{BlitMode, Canvas, LedMatrix};
+use crate::error::Error;
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+/// Draw with `embedded-graphics` straight into the canvas; pixels outside
+/// the canvas are clipped.
+impl DrawTarget for Canvas {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (usize::try_from(point.x), usize::try_from(point.y)) {
+                let _ = self.set_pixel(x, y, color.is_on());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draw the canvas onto any `embedded-graphics` target, e.g. a `Framebuffer`.
+impl ImageDrawable for Canvas {
+    type Color = BinaryColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw_sub_image(
+            target,
+            &Rectangle::new(Point::zero(), OriginDimensions::size(self)),
+        )
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let origin = area.top_left;
+        let pixels = (0..area.size.height as i32).flat_map(move |dy| {
+            (0..area.size.width as i32).filter_map(move |dx| {
+                let x = usize::try_from(origin.x + dx).ok()?;
+                let y = usize::try_from(origin.y + dy).ok()?;
+                let on = self.get_pixel(x, y).ok()?;
+                Some(Pixel(Point::new(dx, dy), BinaryColor::from(on)))
+            })
+        });
+        target.draw_iter(pixels)
+    }
+}
+
+impl Canvas {
+    /// Blit any 1-bit `embedded-graphics` image (for example a `Framebuffer`
+    /// via `as_image()`, or an `ImageRaw`) onto the canvas at its origin.
+    pub fn draw_image<I>(&mut self, image: &I)
+    where
+        I: ImageDrawable<Color = BinaryColor>,
+    {
+        let Ok(()) = image.draw(self);
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Blit any 1-bit `embedded-graphics` image with its top-left corner at
+    /// chain-wide (`x`, `y`), like [`blit`](Self::blit) does for a
+    /// [`Sprite`](super::Sprite).
+    ///
+    /// This takes BMP assets made in an image editor: load them with
+    /// `tinybmp::Bmp::<BinaryColor>::from_slice(include_bytes!("icon.bmp"))`
+    /// and pass the result.
+    pub fn blit_image<I>(&mut self, image: &I, x: i32, y: i32, mode: BlitMode) -> crate::Result<()>
+    where
+        I: ImageDrawable<Color = BinaryColor>,
+    {
+        image.draw(&mut BlitTarget {
+            matrix: self,
+            origin: Point::new(x, y),
+            mode,
+        })
+    }
+}
+
+/// Draw target placing image pixels onto a [`LedMatrix`] at an offset.
+struct BlitTarget<'a, SPI> {
+    matrix: &'a mut LedMatrix<SPI>,
+    origin: Point,
+    mode: BlitMode,
+}
+
+impl<SPI> OriginDimensions for BlitTarget<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    fn size(&self) -> Size {
+        Size::new(self.matrix.width() as u32, self.matrix.height() as u32)
+    }
+}
+
+impl<SPI> DrawTarget for BlitTarget<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    type Color = BinaryColor;
+    type Error = Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let point = point + self.origin;
+            self.matrix
+                .blend_pixel(point.x, point.y, color.is_on(), self.mode)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_draw_target_clips() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        canvas
+            .draw_iter([
+                Pixel(Point::new(1, 1), BinaryColor::On),
+                Pixel(Point::new(-1, 0), BinaryColor::On),
+                Pixel(Point::new(8, 0), BinaryColor::On),
+            ])
+            .unwrap();
+        assert_eq!(canvas.get_pixel(1, 1), Ok(true));
+        assert_eq!(
+            canvas
+                .as_bytes()
+                .iter()
+                .map(|b| b.count_ones())
+                .sum::<u32>(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_image_drawable() {
+        let mut source = Canvas::new(16, 8).unwrap();
+        source.set_pixel(3, 4, true).unwrap();
+        source.set_pixel(15, 7, true).unwrap();
+
+        let mut copy = Canvas::new(16, 8).unwrap();
+        copy.draw_image(&source);
+        assert_eq!(copy, source);
+    }
+
+    #[test]
+    fn test_draw_sub_image() {
+        let mut source = Canvas::new(16, 8).unwrap();
+        source.set_pixel(9, 2, true).unwrap();
+
+        let mut target = Canvas::for_chain(1).unwrap();
+        source
+            .draw_sub_image(
+                &mut target,
+                &Rectangle::new(Point::new(8, 0), Size::new(8, 8)),
+            )
+            .unwrap();
+        assert_eq!(target.get_pixel(1, 2), Ok(true));
+    }
+
+    #[test]
+    fn test_blit_image() {
+        let mut image = Canvas::new(8, 8).unwrap();
+        image.set_pixel(0, 0, true).unwrap();
+        image.set_pixel(7, 7, true).unwrap();
+
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.fill(true);
+        matrix.blit_image(&image, 4, 0, BlitMode::Xor).unwrap();
+
+        assert_eq!(matrix.get_pixel_xy(4, 0), Ok(false));
+        assert_eq!(matrix.get_pixel_xy(11, 7), Ok(false));
+        assert_eq!(matrix.get_pixel_xy(5, 0), Ok(true));
+
+        // Copy clears the covered area, clipped at the edges
+        matrix.blit_image(&image, 12, -1, BlitMode::Copy).unwrap();
+        assert_eq!(matrix.row(1, 0), Ok(0xF0));
+        assert_eq!(matrix.row(1, 6), Ok(0xF0));
+    }
+}
@@ -0,0 +1,175 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Canvas, LedMatrix};
+use crate::{Result, error::Error};
+
+/// Brightness levels of a [`GrayCanvas`] pixel, 0 (off) to 3 (fully on)
+pub const GRAY_LEVELS: u8 = 4;
+
+/// Off-screen buffer with 2 bits per pixel, shown by temporal dithering.
+///
+/// The chip only switches LEDs on or off, so [`refresh`](Self::refresh)
+/// cycles through three 1-bit subframes: a pixel of level `n` is lit in `n`
+/// of them. Call it at a steady rate of at least 150 Hz, e.g. from a timer
+/// interrupt, or the levels show as flicker; the SPI clock must keep up
+/// with one [`LedMatrix::flush`] per call. Coordinates are those of the
+/// matrix's drawing surface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrayCanvas {
+    // Bit 0 and bit 1 of every pixel's level
+    planes: [Canvas; 2],
+    // Subframe shown by the next refresh
+    phase: u8,
+}
+
+impl GrayCanvas {
+    /// Create a blank canvas of `width` x `height` pixels.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] on the same sizes as [`Canvas::new`].
+    pub fn new(width: usize, height: usize) -> Result<Self> {
+        let plane = Canvas::new(width, height)?;
+        Ok(Self {
+            planes: [plane.clone(), plane],
+            phase: 0,
+        })
+    }
+
+    /// Create a blank canvas covering the drawing surface of `matrix`.
+    pub fn for_matrix<SPI>(matrix: &LedMatrix<SPI>) -> Result<Self>
+    where
+        SPI: SpiDevice,
+    {
+        Self::new(matrix.width(), matrix.height())
+    }
+
+    pub fn width(&self) -> usize {
+        self.planes[0].width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.planes[0].height()
+    }
+
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if the pixel is outside the canvas.
+    /// - [`Error::InvalidIntensity`] if `level` is not below [`GRAY_LEVELS`].
+    pub fn set_pixel(&mut self, x: usize, y: usize, level: u8) -> Result<()> {
+        if level >= GRAY_LEVELS {
+            return Err(Error::InvalidIntensity {
+                value: level,
+                max: GRAY_LEVELS - 1,
+            });
+        }
+        for (bit, plane) in self.planes.iter_mut().enumerate() {
+            plane.set_pixel(x, y, level & (1 << bit) != 0)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Result<u8> {
+        let mut level = 0;
+        for (bit, plane) in self.planes.iter().enumerate() {
+            level |= u8::from(plane.get_pixel(x, y)?) << bit;
+        }
+        Ok(level)
+    }
+
+    pub fn clear(&mut self) {
+        for plane in &mut self.planes {
+            plane.clear();
+        }
+    }
+
+    /// Set every pixel to `level`.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidIntensity`] if `level` is not below [`GRAY_LEVELS`].
+    pub fn fill(&mut self, level: u8) -> Result<()> {
+        if level >= GRAY_LEVELS {
+            return Err(Error::InvalidIntensity {
+                value: level,
+                max: GRAY_LEVELS - 1,
+            });
+        }
+        for (bit, plane) in self.planes.iter_mut().enumerate() {
+            plane.fill(level & (1 << bit) != 0);
+        }
+        Ok(())
+    }
+
+    /// Draw the next subframe into the matrix framebuffer and flush it.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if the canvas is not the size of the
+    ///   matrix's drawing surface.
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn refresh<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        if (self.width(), self.height()) != (matrix.width(), matrix.height()) {
+            return Err(Error::InvalidCanvasSize);
+        }
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let level = self.get_pixel(x, y)?;
+                matrix.set_pixel_xy(x, y, level > self.phase)?;
+            }
+        }
+        matrix.flush()?;
+        self.phase = (self.phase + 1) % (GRAY_LEVELS - 1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_levels() {
+        let mut gray = GrayCanvas::new(8, 8).unwrap();
+        gray.set_pixel(1, 2, 2).unwrap();
+        assert_eq!(gray.get_pixel(1, 2), Ok(2));
+        assert_eq!(gray.get_pixel(0, 2), Ok(0));
+        assert_eq!(
+            gray.set_pixel(0, 0, 4),
+            Err(Error::InvalidIntensity { value: 4, max: 3 })
+        );
+        assert_eq!(gray.set_pixel(8, 0, 1), Err(Error::InvalidPixel));
+        gray.fill(3).unwrap();
+        assert_eq!(gray.get_pixel(7, 7), Ok(3));
+        assert!(GrayCanvas::new(12, 8).is_err());
+    }
+
+    #[test]
+    fn test_refresh_duty_cycle() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        let mut gray = GrayCanvas::for_matrix(&matrix).unwrap();
+        for level in 0..GRAY_LEVELS {
+            gray.set_pixel(usize::from(level), 0, level).unwrap();
+        }
+
+        // Each pixel is lit in as many of the three subframes as its level
+        let mut lit = [0; 4];
+        for _ in 0..3 {
+            gray.refresh(&mut matrix).unwrap();
+            for (x, count) in lit.iter_mut().enumerate() {
+                *count += u8::from(matrix.get_pixel_xy(x, 0).unwrap());
+            }
+        }
+        assert_eq!(lit, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_refresh_size_mismatch() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        let mut gray = GrayCanvas::new(16, 8).unwrap();
+        assert_eq!(gray.refresh(&mut matrix), Err(Error::InvalidCanvasSize));
+    }
+}
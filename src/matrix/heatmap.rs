@@ -0,0 +1,204 @@
+use super::MODULE_SIZE;
+use crate::{MAX_DISPLAYS, Result, error::Error};
+
+const PIXELS: usize = MODULE_SIZE * MODULE_SIZE;
+
+/// Accumulates how often each pixel of a matrix chain has been lit.
+///
+/// Frames use the matrix row layout: byte `row` of a device frame is the
+/// value of digit register `row`, with bit 7 as column 0. Counts saturate
+/// at `u16::MAX`.
+pub struct Heatmap {
+    counts: [[u16; PIXELS]; MAX_DISPLAYS],
+    device_count: usize,
+    samples: u32,
+}
+
+impl Heatmap {
+    pub fn new(device_count: usize) -> Result<Self> {
+        if device_count > MAX_DISPLAYS {
+            return Err(Error::InvalidDeviceCount);
+        }
+        Ok(Self {
+            counts: [[0; PIXELS]; MAX_DISPLAYS],
+            device_count,
+            samples: 0,
+        })
+    }
+
+    pub fn device_count(&self) -> usize {
+        self.device_count
+    }
+
+    /// Number of frames passed to [`accumulate`](Self::accumulate) since the last reset.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Add every lit pixel of one chain frame (one `[u8; 8]` per device).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if more frames than devices are given.
+    pub fn accumulate(&mut self, frames: &[[u8; MODULE_SIZE]]) -> Result<()> {
+        if frames.len() > self.device_count {
+            return Err(Error::InvalidDeviceCount);
+        }
+
+        for (counts, frame) in self.counts.iter_mut().zip(frames) {
+            for (row, bits) in frame.iter().enumerate() {
+                for col in 0..MODULE_SIZE {
+                    if bits & (0x80 >> col) != 0 {
+                        let count = &mut counts[row * MODULE_SIZE + col];
+                        *count = count.saturating_add(1);
+                    }
+                }
+            }
+        }
+        self.samples = self.samples.saturating_add(1);
+        Ok(())
+    }
+
+    /// Record a single hit, e.g. from touch or motion data fed by the application.
+    pub fn record(&mut self, device_index: usize, row: usize, col: usize) -> Result<()> {
+        let index = self.pixel_index(device_index, row, col)?;
+        let count = &mut self.counts[device_index][index];
+        *count = count.saturating_add(1);
+        Ok(())
+    }
+
+    pub fn count(&self, device_index: usize, row: usize, col: usize) -> Result<u16> {
+        let index = self.pixel_index(device_index, row, col)?;
+        Ok(self.counts[device_index][index])
+    }
+
+    /// Highest count of any pixel in the chain.
+    pub fn max_count(&self) -> u16 {
+        self.counts[..self.device_count]
+            .iter()
+            .flat_map(|counts| counts.iter().copied())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Render a thresholded view: a pixel is lit if it was counted at least
+    /// `threshold` times.
+    ///
+    /// Entries beyond the device count are left blank.
+    pub fn render(&self, threshold: u16) -> [[u8; MODULE_SIZE]; MAX_DISPLAYS] {
+        let mut frames = [[0; MODULE_SIZE]; MAX_DISPLAYS];
+        for (frame, counts) in frames.iter_mut().zip(&self.counts[..self.device_count]) {
+            for (index, count) in counts.iter().enumerate() {
+                if *count >= threshold && *count > 0 {
+                    frame[index / MODULE_SIZE] |= 0x80 >> (index % MODULE_SIZE);
+                }
+            }
+        }
+        frames
+    }
+
+    pub fn reset(&mut self) {
+        self.counts = [[0; PIXELS]; MAX_DISPLAYS];
+        self.samples = 0;
+    }
+
+    fn pixel_index(&self, device_index: usize, row: usize, col: usize) -> Result<usize> {
+        if device_index >= self.device_count {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count,
+            });
+        }
+        if row >= MODULE_SIZE || col >= MODULE_SIZE {
+            return Err(Error::InvalidPixel);
+        }
+        Ok(row * MODULE_SIZE + col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_invalid_device_count() {
+        assert!(matches!(
+            Heatmap::new(MAX_DISPLAYS + 1),
+            Err(Error::InvalidDeviceCount)
+        ));
+    }
+
+    #[test]
+    fn test_accumulate_counts_lit_pixels() {
+        let mut heatmap = Heatmap::new(2).unwrap();
+        let mut frame = [0u8; 8];
+        frame[2] = 0b1000_0001;
+
+        heatmap.accumulate(&[frame, [0; 8]]).unwrap();
+        heatmap.accumulate(&[frame]).unwrap();
+
+        assert_eq!(heatmap.samples(), 2);
+        assert_eq!(heatmap.count(0, 2, 0), Ok(2));
+        assert_eq!(heatmap.count(0, 2, 7), Ok(2));
+        assert_eq!(heatmap.count(0, 2, 1), Ok(0));
+        assert_eq!(heatmap.count(1, 2, 0), Ok(0));
+        assert_eq!(heatmap.max_count(), 2);
+    }
+
+    #[test]
+    fn test_accumulate_too_many_frames() {
+        let mut heatmap = Heatmap::new(1).unwrap();
+        assert_eq!(
+            heatmap.accumulate(&[[0; 8], [0; 8]]),
+            Err(Error::InvalidDeviceCount)
+        );
+    }
+
+    #[test]
+    fn test_record_and_render_threshold() {
+        let mut heatmap = Heatmap::new(1).unwrap();
+        heatmap.record(0, 0, 0).unwrap();
+        heatmap.record(0, 0, 0).unwrap();
+        heatmap.record(0, 7, 7).unwrap();
+
+        let frames = heatmap.render(2);
+        assert_eq!(frames[0][0], 0x80);
+        assert_eq!(frames[0][7], 0x00);
+
+        let frames = heatmap.render(1);
+        assert_eq!(frames[0][7], 0x01);
+
+        // A zero threshold never lights pixels that were never hit
+        let frames = heatmap.render(0);
+        assert_eq!(frames[0][3], 0x00);
+    }
+
+    #[test]
+    fn test_invalid_coordinates() {
+        let mut heatmap = Heatmap::new(1).unwrap();
+        assert_eq!(heatmap.record(0, 8, 0), Err(Error::InvalidPixel));
+        assert_eq!(
+            heatmap.record(1, 0, 0),
+            Err(Error::InvalidDeviceIndex {
+                index: 1,
+                device_count: 1
+            })
+        );
+        assert_eq!(
+            heatmap.count(1, 8, 8),
+            Err(Error::InvalidDeviceIndex {
+                index: 1,
+                device_count: 1
+            })
+        );
+        assert_eq!(heatmap.count(0, 0, 8), Err(Error::InvalidPixel));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut heatmap = Heatmap::new(1).unwrap();
+        heatmap.accumulate(&[[0xFF; 8]]).unwrap();
+        heatmap.reset();
+        assert_eq!(heatmap.samples(), 0);
+        assert_eq!(heatmap.max_count(), 0);
+    }
+}
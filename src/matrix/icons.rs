@@ -0,0 +1,200 @@
+use super::Sprite;
+use crate::sprite;
+
+#[rustfmt::skip]
+pub const SUN: Sprite = sprite![
+    "#..#..#.",
+    ".#.#.#..",
+    "..###...",
+    "#######.",
+    "..###...",
+    ".#.#.#..",
+    "#..#..#.",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const CLOUD: Sprite = sprite![
+    "........",
+    "...##...",
+    "..####..",
+    ".######.",
+    "########",
+    "########",
+    ".######.",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const RAIN: Sprite = sprite![
+    "..###...",
+    ".#####..",
+    "#######.",
+    "#######.",
+    "........",
+    ".#..#..#",
+    "#..#..#.",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const SNOW: Sprite = sprite![
+    "...#....",
+    "#..#..#.",
+    ".#.#.#..",
+    "..###...",
+    ".#.#.#..",
+    "#..#..#.",
+    "...#....",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const LIGHTNING: Sprite = sprite![
+    "....##..",
+    "...##...",
+    "..##....",
+    ".######.",
+    "....##..",
+    "...##...",
+    "..#.....",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const BATTERY_FULL: Sprite = sprite![
+    "........",
+    "######..",
+    "######..",
+    "#######.",
+    "#######.",
+    "######..",
+    "######..",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const BATTERY_HALF: Sprite = sprite![
+    "........",
+    "######..",
+    "###..#..",
+    "###..##.",
+    "###..##.",
+    "###..#..",
+    "######..",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const BATTERY_EMPTY: Sprite = sprite![
+    "........",
+    "######..",
+    "#....#..",
+    "#....##.",
+    "#....##.",
+    "#....#..",
+    "######..",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const WIFI: Sprite = sprite![
+    "........",
+    ".#####..",
+    "#.....#.",
+    "..###...",
+    ".#...#..",
+    "........",
+    "...#....",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const HEART: Sprite = sprite![
+    "........",
+    ".##.##..",
+    "#######.",
+    "#######.",
+    ".#####..",
+    "..###...",
+    "...#....",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const ARROW_UP: Sprite = sprite![
+    "...#....",
+    "..###...",
+    ".#####..",
+    "#######.",
+    "..###...",
+    "..###...",
+    "..###...",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const ARROW_DOWN: Sprite = sprite![
+    "..###...",
+    "..###...",
+    "..###...",
+    "#######.",
+    ".#####..",
+    "..###...",
+    "...#....",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const ARROW_LEFT: Sprite = sprite![
+    "...#....",
+    "..##....",
+    ".#######",
+    "########",
+    ".#######",
+    "..##....",
+    "...#....",
+    "........",
+];
+
+#[rustfmt::skip]
+pub const ARROW_RIGHT: Sprite = sprite![
+    "....#...",
+    "....##..",
+    "#######.",
+    "########",
+    "#######.",
+    "....##..",
+    "....#...",
+    "........",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::MODULE_SIZE;
+
+    #[test]
+    fn test_icons_fill_one_module() {
+        let icons = [
+            SUN,
+            CLOUD,
+            RAIN,
+            SNOW,
+            LIGHTNING,
+            BATTERY_FULL,
+            BATTERY_HALF,
+            BATTERY_EMPTY,
+            WIFI,
+            HEART,
+            ARROW_UP,
+            ARROW_DOWN,
+            ARROW_LEFT,
+            ARROW_RIGHT,
+        ];
+        for icon in icons {
+            assert_eq!((icon.width(), icon.height()), (MODULE_SIZE, MODULE_SIZE));
+            assert!(icon.as_bytes().iter().any(|&row| row != 0));
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{BlitMode, Canvas, LedMatrix, Viewport};
+
+/// Drawing planes of a [`LedMatrix`], combined bottom to top on flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// Drawn first, e.g. a static frame or grid
+    Background,
+    /// The framebuffer all drawing methods work on, lit pixels added over
+    /// the background
+    Content,
+    /// Drawn last with the [overlay mode](LedMatrix::set_overlay_mode),
+    /// e.g. an alert icon blinking over scrolling text
+    Overlay,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Layers {
+    background: Option<Canvas>,
+    overlay: Option<Canvas>,
+    overlay_mode: BlitMode,
+    // Indexed by `Layer as usize`
+    hidden: [bool; 3],
+    blanked: Option<Viewport>,
+}
+
+impl Layers {
+    pub(super) const fn new() -> Self {
+        Self {
+            background: None,
+            overlay: None,
+            overlay_mode: BlitMode::Or,
+            hidden: [false; 3],
+            blanked: None,
+        }
+    }
+
+    /// What the chain shows with `content` as the content layer, `None`
+    /// if that's just `content`.
+    pub(super) fn compose(&self, content: &Canvas) -> Option<Canvas> {
+        let visible = |layer: Layer| !self.hidden[layer as usize];
+        let background = self
+            .background
+            .as_ref()
+            .filter(|_| visible(Layer::Background));
+        let overlay = self.overlay.as_ref().filter(|_| visible(Layer::Overlay));
+        if background.is_none() && overlay.is_none() && visible(Layer::Content) {
+            return None;
+        }
+
+        let mut frame = background.cloned().unwrap_or_else(|| {
+            let mut blank = content.clone();
+            blank.clear();
+            blank
+        });
+        if visible(Layer::Content) {
+            frame.blit_canvas(content, 0, 0, BlitMode::Or);
+        }
+        if let Some(overlay) = overlay {
+            frame.blit_canvas(overlay, 0, 0, self.overlay_mode);
+        }
+        Some(frame)
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Pixels of `layer`, `None` for a background or overlay not drawn on
+    /// yet. Like the [framebuffer](Self::framebuffer), layers are in
+    /// physical panel coordinates.
+    pub fn layer(&self, layer: Layer) -> Option<&Canvas> {
+        match layer {
+            Layer::Background => self.layers().background.as_ref(),
+            Layer::Content => Some(self.framebuffer()),
+            Layer::Overlay => self.layers().overlay.as_ref(),
+        }
+    }
+
+    /// Pixels of `layer` to draw on, starting blank for a background or
+    /// overlay used for the first time.
+    pub fn layer_mut(&mut self, layer: Layer) -> &mut Canvas {
+        let blank = {
+            let mut blank = self.framebuffer().clone();
+            blank.clear();
+            blank
+        };
+        match layer {
+            Layer::Background => self.layers_mut().background.get_or_insert(blank),
+            Layer::Content => self.framebuffer_mut(),
+            Layer::Overlay => self.layers_mut().overlay.get_or_insert(blank),
+        }
+    }
+
+    /// Drop a background or overlay, or clear the content layer.
+    pub fn remove_layer(&mut self, layer: Layer) {
+        match layer {
+            Layer::Background => self.layers_mut().background = None,
+            Layer::Content => self.clear(),
+            Layer::Overlay => self.layers_mut().overlay = None,
+        }
+    }
+
+    /// Leave `layer` out of flushes without discarding it, e.g. to blink
+    /// the overlay.
+    pub fn set_layer_visible(&mut self, layer: Layer, visible: bool) {
+        self.layers_mut().hidden[layer as usize] = !visible;
+    }
+
+    pub fn is_layer_visible(&self, layer: Layer) -> bool {
+        !self.layers().hidden[layer as usize]
+    }
+
+    /// How the overlay combines with the layers below, [`BlitMode::Or`] by
+    /// default; [`BlitMode::Xor`] keeps an icon readable over lit pixels.
+    pub fn set_overlay_mode(&mut self, mode: BlitMode) {
+        self.layers_mut().overlay_mode = mode;
+    }
+
+    pub fn overlay_mode(&self) -> BlitMode {
+        self.layers().overlay_mode
+    }
+
+    /// Show the chain-wide `region` dark on flushes without touching the
+    /// layers, e.g. for the off phase of a [`Blink`](super::Blink). `None`
+    /// shows everything again.
+    pub fn set_blanked(&mut self, region: Option<Viewport>) {
+        self.layers_mut().blanked = region;
+    }
+
+    pub fn blanked(&self) -> Option<Viewport> {
+        self.layers().blanked
+    }
+
+    /// What the chain shows after a flush in framebuffer coordinates,
+    /// `None` if that's just the framebuffer.
+    pub(super) fn visible_frame(&self) -> Option<Canvas> {
+        let mut frame = self.layers().compose(self.framebuffer());
+        if let Some(region) = self.blanked() {
+            let frame = frame.get_or_insert_with(|| self.framebuffer().clone());
+            for y in region.y()
+                ..region
+                    .y()
+                    .saturating_add(region.height())
+                    .min(self.height())
+            {
+                for x in region.x()..region.x().saturating_add(region.width()).min(self.width()) {
+                    if let Ok((x, y)) = self.physical(x, y) {
+                        let _ = frame.set_pixel(x, y, false);
+                    }
+                }
+            }
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_layers_combine_on_flush() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+            matrix.init().unwrap();
+            assert!(matrix.layer(Layer::Overlay).is_none());
+            matrix
+                .layer_mut(Layer::Background)
+                .set_pixel(0, 0, true)
+                .unwrap();
+            matrix.set_row(0, 1, 0xF0).unwrap();
+            matrix
+                .layer_mut(Layer::Overlay)
+                .set_pixel(7, 1, true)
+                .unwrap();
+            matrix.set_overlay_mode(BlitMode::Xor);
+            matrix
+                .layer_mut(Layer::Overlay)
+                .set_pixel(0, 1, true)
+                .unwrap();
+            matrix.flush().unwrap();
+        }
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[0], 0x80);
+        assert_eq!(digits[1], 0x71);
+    }
+
+    #[test]
+    fn test_hidden_and_removed_layers() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.set_row(0, 0, 0x0F).unwrap();
+        matrix
+            .layer_mut(Layer::Overlay)
+            .set_pixel(0, 0, true)
+            .unwrap();
+
+        matrix.set_layer_visible(Layer::Overlay, false);
+        assert!(!matrix.is_layer_visible(Layer::Overlay));
+        assert_eq!(matrix.snapshot().unwrap().tiles()[0][0], 0x0F);
+
+        matrix.set_layer_visible(Layer::Overlay, true);
+        matrix.set_layer_visible(Layer::Content, false);
+        assert_eq!(matrix.snapshot().unwrap().tiles()[0][0], 0x80);
+
+        matrix.remove_layer(Layer::Overlay);
+        matrix.set_layer_visible(Layer::Content, true);
+        assert!(matrix.layer(Layer::Overlay).is_none());
+        assert_eq!(matrix.snapshot().unwrap().tiles()[0][0], 0x0F);
+        // Drawing keeps going to the content layer
+        assert_eq!(matrix.row(0, 0), Ok(0x0F));
+    }
+}
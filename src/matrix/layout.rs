@@ -0,0 +1,120 @@
+use super::{Flip, MODULE_SIZE, Rotation};
+
+/// Maps a chain-wide pixel `(x, y)` to `(device_index, row, bit)`, where
+/// `row` is the digit register (0-7) and `bit` the bit within it (7 = MSB).
+pub type PixelRemap = fn(usize, usize) -> (usize, u8, u8);
+
+/// Wiring of a framebuffer pixel to the hardware, for arrangements the
+/// [`Layout`] presets don't cover, e.g. circular displays or panels with
+/// gaps. See [`LedMatrix::with_pixel_mapping`](super::LedMatrix::with_pixel_mapping).
+///
+/// [`Layout`] and [`PixelRemap`] functions implement it too.
+pub trait PixelMapping {
+    /// `(device_index, row, bit)` lighting chain-wide pixel (`x`, `y`) of a
+    /// framebuffer `modules_wide` modules across, `row` being the digit
+    /// register (0-7) and `bit` the bit within it (7 = MSB). `None` for
+    /// pixels with no LED behind them.
+    fn map(&self, x: usize, y: usize, modules_wide: usize) -> Option<(usize, u8, u8)>;
+}
+
+impl PixelMapping for PixelRemap {
+    fn map(&self, x: usize, y: usize, _modules_wide: usize) -> Option<(usize, u8, u8)> {
+        Some(self(x, y))
+    }
+}
+
+impl<M: PixelMapping + ?Sized> PixelMapping for &M {
+    fn map(&self, x: usize, y: usize, modules_wide: usize) -> Option<(usize, u8, u8)> {
+        (**self).map(x, y, modules_wide)
+    }
+}
+
+/// Modules in chain order row by row, each wired like `self`.
+impl PixelMapping for Layout {
+    fn map(&self, x: usize, y: usize, modules_wide: usize) -> Option<(usize, u8, u8)> {
+        let Some((rotation, flip)) = self.transform() else {
+            return match self {
+                Self::Custom(remap) => remap.map(x, y, modules_wide),
+                _ => None,
+            };
+        };
+        if x >= modules_wide * MODULE_SIZE {
+            return None;
+        }
+        let device_index = y / MODULE_SIZE * modules_wide + x / MODULE_SIZE;
+        let (x, y) = rotation.map_point(x % MODULE_SIZE, y % MODULE_SIZE);
+        let (x, y) = flip.map_point(x, y);
+        Some((device_index, y as u8, (MODULE_SIZE - 1 - x) as u8))
+    }
+}
+
+/// Wiring of common 8x8 module boards.
+///
+/// The presets describe how a board connects its LEDs to the digit
+/// registers and are applied on flush; drawing code always works in upright
+/// coordinates. Use [`Layout::Custom`] for anything else.
+#[derive(Debug, Clone, Copy)]
+pub enum Layout {
+    /// FC-16 style boards: digit register n drives row n, bit 7 the
+    /// leftmost column. This is the native layout.
+    Fc16,
+    /// Generic (often green) modules: digit register n drives column n,
+    /// bit 7 the top row.
+    Generic,
+    /// Parola style boards: digit register n drives row n, bit 0 the
+    /// leftmost column.
+    Parola,
+    /// ICStation style boards: rows and columns both reversed.
+    IcStation,
+    /// User-provided mapping for every pixel.
+    Custom(PixelRemap),
+}
+
+impl Layout {
+    /// Module rotation and flip that implement a preset, or `None` for
+    /// [`Layout::Custom`].
+    pub fn transform(self) -> Option<(Rotation, Flip)> {
+        match self {
+            Self::Fc16 => Some((Rotation::Deg0, Flip::NONE)),
+            // A quarter turn plus a mirror transposes rows and columns
+            Self::Generic => Some((Rotation::Deg90, Flip::X)),
+            Self::Parola => Some((Rotation::Deg0, Flip::X)),
+            Self::IcStation => Some((Rotation::Deg0, Flip::BOTH)),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_transposes() {
+        let (rotation, flip) = Layout::Generic.transform().unwrap();
+        // Top row lit -> leftmost column lit, i.e. bit 7 of every register
+        let tile = [0xFF, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(flip.apply(&rotation.apply(&tile)), [0x80; 8]);
+    }
+
+    #[test]
+    fn test_custom_has_no_transform() {
+        fn remap(x: usize, y: usize) -> (usize, u8, u8) {
+            (x / 8, y as u8, (x % 8) as u8)
+        }
+        assert!(Layout::Custom(remap).transform().is_none());
+        assert!(Layout::Fc16.transform().is_some());
+        assert_eq!(Layout::Custom(remap).map(12, 3, 2), Some((1, 3, 4)));
+    }
+
+    #[test]
+    fn test_preset_mapping() {
+        assert_eq!(Layout::Fc16.map(0, 0, 2), Some((0, 0, 7)));
+        assert_eq!(Layout::Fc16.map(9, 10, 2), Some((3, 2, 6)));
+        // Register n drives column n, bit 7 the top row
+        assert_eq!(Layout::Generic.map(2, 0, 1), Some((0, 2, 7)));
+        assert_eq!(Layout::Parola.map(0, 1, 1), Some((0, 1, 0)));
+        assert_eq!(Layout::IcStation.map(0, 0, 1), Some((0, 7, 0)));
+        assert_eq!(Layout::Fc16.map(16, 0, 2), None);
+    }
+}
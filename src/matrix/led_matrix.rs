@@ -0,0 +1,1473 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{
+    Canvas, CurrentLimit, Flip, Layout, MODULE_SIZE, PixelMapping, PixelRemap, Rotation,
+    layers::Layers,
+};
+use crate::{
+    MAX_DISPLAYS, Result, driver::Max7219, encoder::MAX_TILES_SIZE, error::Error,
+    registers::DecodeMode,
+};
+
+/// Dot-matrix oriented wrapper over [`Max7219`] for chains of 8x8 modules.
+///
+/// Drawing goes to an in-memory framebuffer; nothing reaches the hardware
+/// until [`flush`](Self::flush). Modules are numbered like the chain:
+/// device 0 is the top-left module, followed by the rest of its row, then
+/// the next row of modules. Within a module, row 0 is digit register 0 and
+/// bit 7 of a row byte is the leftmost column.
+///
+/// Per-module orientation fixes ([`Rotation`], then [`Flip`]) or a
+/// [`Layout`] are applied on flush, so drawing code always works in upright
+/// coordinates.
+pub struct LedMatrix<SPI> {
+    driver: Max7219<SPI>,
+    framebuffer: Canvas,
+    rotations: [Rotation; MAX_DISPLAYS],
+    flips: [Flip; MAX_DISPLAYS],
+    mapping: Option<Mapping>,
+    serpentine: bool,
+    canvas_rotation: Rotation,
+    inverted: bool,
+    letter_spacing: usize,
+    fallback_char: Option<char>,
+    current_limit: Option<CurrentLimit>,
+    layers: Layers,
+}
+
+/// Per-pixel wiring replacing the module transforms on flush
+#[derive(Clone, Copy)]
+enum Mapping {
+    Remap(PixelRemap),
+    Custom(&'static (dyn PixelMapping + Sync)),
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Wrap a driver whose modules form a single horizontal row.
+    pub fn new(driver: Max7219<SPI>) -> Result<Self> {
+        let device_count = driver.device_count();
+        Self::with_geometry(driver, device_count, 1)
+    }
+
+    /// Wrap a driver whose modules form a grid `modules_wide` x `modules_high`,
+    /// e.g. 4 x 2 modules for a 32x16 pixel panel.
+    ///
+    /// Devices fill the grid row by row: device `modules_wide` is the first
+    /// module of the second row, so y 8-15 maps onto it and its neighbours.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if the grid doesn't hold exactly the
+    ///   driver's device count.
+    pub fn with_geometry(
+        driver: Max7219<SPI>,
+        modules_wide: usize,
+        modules_high: usize,
+    ) -> Result<Self> {
+        if modules_wide.checked_mul(modules_high) != Some(driver.device_count()) {
+            return Err(Error::InvalidCanvasSize);
+        }
+        let framebuffer = Canvas::new(modules_wide * MODULE_SIZE, modules_high * MODULE_SIZE)?;
+        Ok(Self {
+            driver,
+            framebuffer,
+            rotations: [Rotation::default(); MAX_DISPLAYS],
+            flips: [Flip::NONE; MAX_DISPLAYS],
+            mapping: None,
+            serpentine: false,
+            canvas_rotation: Rotation::Deg0,
+            inverted: false,
+            letter_spacing: 1,
+            fallback_char: None,
+            current_limit: None,
+            layers: Layers::new(),
+        })
+    }
+
+    /// Turn the whole drawing surface clockwise onto the panel, e.g. to use
+    /// an 8x32 ticker mounted sideways as a 32-pixel-tall portrait display.
+    ///
+    /// Affects the chain-wide coordinates of [`set_pixel_xy`](Self::set_pixel_xy),
+    /// [`get_pixel_xy`](Self::get_pixel_xy), [`locate`](Self::locate),
+    /// [`width`](Self::width) and [`height`](Self::height); per-device
+    /// methods and the framebuffer keep addressing the physical modules.
+    pub fn with_canvas_rotation(mut self, rotation: Rotation) -> Self {
+        self.canvas_rotation = rotation;
+        self
+    }
+
+    pub fn set_canvas_rotation(&mut self, rotation: Rotation) {
+        self.canvas_rotation = rotation;
+    }
+
+    /// Change the canvas rotation and turn the framebuffer content with it,
+    /// so what was drawn reads the right way up in the new orientation from
+    /// the next flush on, e.g. when an accelerometer reports the device
+    /// was flipped.
+    ///
+    /// Pixels that fall outside the new surface, e.g. when a non-square
+    /// panel turns from landscape to portrait, are dropped. Layers are left
+    /// as they are.
+    pub fn reorient_canvas(&mut self, rotation: Rotation) {
+        let previous = self.framebuffer.clone();
+        let (width, height) = (self.width(), self.height());
+        let old_rotation = self.canvas_rotation;
+        self.canvas_rotation = rotation;
+        self.framebuffer.clear();
+        for y in 0..height {
+            for x in 0..width {
+                let (old_x, old_y) = Self::rotate_point(old_rotation, &previous, x, y);
+                if previous.get_pixel(old_x, old_y) == Ok(true)
+                    && let Ok((x, y)) = self.physical(x, y)
+                {
+                    let _ = self.framebuffer.set_pixel(x, y, true);
+                }
+            }
+        }
+    }
+
+    pub fn canvas_rotation(&self) -> Rotation {
+        self.canvas_rotation
+    }
+
+    /// Blank columns drawn after each character by the text methods, 1 by
+    /// default. Use 0 for tight tickers or more to spread text out.
+    pub fn with_letter_spacing(mut self, spacing: usize) -> Self {
+        self.letter_spacing = spacing;
+        self
+    }
+
+    pub fn set_letter_spacing(&mut self, spacing: usize) {
+        self.letter_spacing = spacing;
+    }
+
+    pub fn letter_spacing(&self) -> usize {
+        self.letter_spacing
+    }
+
+    /// Character drawn in place of any the font has no glyph for, e.g. `'?'`.
+    /// Without one, the text methods fail on such characters.
+    pub fn with_fallback_char(mut self, fallback: Option<char>) -> Self {
+        self.fallback_char = fallback;
+        self
+    }
+
+    pub fn set_fallback_char(&mut self, fallback: Option<char>) {
+        self.fallback_char = fallback;
+    }
+
+    pub fn fallback_char(&self) -> Option<char> {
+        self.fallback_char
+    }
+
+    /// Chain wired boustrophedon: every second module row runs right to left
+    /// and is mounted upside down, e.g. on a 4 x 2 grid device 4 is the
+    /// bottom-right module.
+    ///
+    /// The 180° turn of the reversed rows is added on top of the per-module
+    /// rotation; set those modules to [`Rotation::Deg180`] if they are
+    /// mounted upright.
+    pub fn with_serpentine(mut self, enabled: bool) -> Self {
+        self.set_serpentine(enabled);
+        self
+    }
+
+    pub fn set_serpentine(&mut self, enabled: bool) {
+        self.serpentine = enabled;
+    }
+
+    pub fn is_serpentine(&self) -> bool {
+        self.serpentine
+    }
+
+    /// Configure every module for a board [`Layout`].
+    ///
+    /// Presets replace the per-module rotation and flip; a
+    /// [`Layout::Custom`] remap takes over the whole flush and ignores them.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.set_layout(layout);
+        self
+    }
+
+    /// Switch to another board [`Layout`], like
+    /// [`with_layout`](Self::with_layout). The framebuffer is kept and
+    /// shown through the new layout from the next flush on.
+    pub fn set_layout(&mut self, layout: Layout) {
+        if let Layout::Custom(remap) = layout {
+            self.mapping = Some(Mapping::Remap(remap));
+        } else if let Some((rotation, flip)) = layout.transform() {
+            self.rotations = [rotation; MAX_DISPLAYS];
+            self.flips = [flip; MAX_DISPLAYS];
+            self.mapping = None;
+        }
+    }
+
+    /// Wire every pixel through `mapping` on flush, like a
+    /// [`Layout::Custom`] remap; pixels it maps to `None` are not shown.
+    pub fn with_pixel_mapping(mut self, mapping: &'static (dyn PixelMapping + Sync)) -> Self {
+        self.set_pixel_mapping(Some(mapping));
+        self
+    }
+
+    /// Set or, with `None`, remove the pixel mapping, going back to the
+    /// per-module rotation and flip.
+    pub fn set_pixel_mapping(&mut self, mapping: Option<&'static (dyn PixelMapping + Sync)>) {
+        self.mapping = mapping.map(Mapping::Custom);
+    }
+
+    /// Rotate every module by `rotation` on flush.
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.set_rotation_all(rotation);
+        self
+    }
+
+    /// Rotate every module by `rotation` from the next flush on.
+    pub fn set_rotation_all(&mut self, rotation: Rotation) {
+        self.rotations = [rotation; MAX_DISPLAYS];
+    }
+
+    /// Rotate one module by `rotation` on flush.
+    pub fn set_rotation(&mut self, device_index: usize, rotation: Rotation) -> Result<()> {
+        if device_index >= self.device_count() {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count(),
+            });
+        }
+        if let Some(slot) = self.rotations.get_mut(device_index) {
+            *slot = rotation;
+        }
+        Ok(())
+    }
+
+    pub fn rotation(&self, device_index: usize) -> Result<Rotation> {
+        if device_index >= self.device_count() {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count(),
+            });
+        }
+        Ok(self
+            .rotations
+            .get(device_index)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    pub fn driver(&self) -> &Max7219<SPI> {
+        &self.driver
+    }
+
+    pub fn driver_mut(&mut self) -> &mut Max7219<SPI> {
+        &mut self.driver
+    }
+
+    pub fn into_inner(self) -> Max7219<SPI> {
+        self.driver
+    }
+
+    /// Initialize the driver and blank the framebuffer to match.
+    pub fn init(&mut self) -> Result<()> {
+        self.framebuffer.clear();
+        self.driver.init()
+    }
+
+    pub fn device_count(&self) -> usize {
+        self.driver.device_count()
+    }
+
+    /// Number of modules along the x axis.
+    pub fn modules_wide(&self) -> usize {
+        self.framebuffer.modules_wide()
+    }
+
+    /// Number of modules along the y axis.
+    pub fn modules_high(&self) -> usize {
+        self.framebuffer.modules_high()
+    }
+
+    /// Width of the drawing surface in pixels, after canvas rotation.
+    pub fn width(&self) -> usize {
+        match self.canvas_rotation {
+            Rotation::Deg0 | Rotation::Deg180 => self.framebuffer.width(),
+            Rotation::Deg90 | Rotation::Deg270 => self.framebuffer.height(),
+        }
+    }
+
+    /// Height of the drawing surface in pixels, after canvas rotation.
+    pub fn height(&self) -> usize {
+        match self.canvas_rotation {
+            Rotation::Deg0 | Rotation::Deg180 => self.framebuffer.height(),
+            Rotation::Deg90 | Rotation::Deg270 => self.framebuffer.width(),
+        }
+    }
+
+    /// Pixel buffer in physical panel coordinates, ignoring canvas rotation.
+    pub fn framebuffer(&self) -> &Canvas {
+        &self.framebuffer
+    }
+
+    pub fn framebuffer_mut(&mut self) -> &mut Canvas {
+        &mut self.framebuffer
+    }
+
+    pub(super) fn layers(&self) -> &Layers {
+        &self.layers
+    }
+
+    pub(super) fn layers_mut(&mut self) -> &mut Layers {
+        &mut self.layers
+    }
+
+    /// Turn pixel (`x`, `y`) of one module on or off, (0, 0) being top-left.
+    pub fn set_pixel(&mut self, device_index: usize, x: usize, y: usize, on: bool) -> Result<()> {
+        if x >= MODULE_SIZE {
+            return Err(Error::InvalidPixel);
+        }
+        let byte = self.row_mut(device_index, y)?;
+        if on {
+            *byte |= 0x80 >> x;
+        } else {
+            *byte &= !(0x80 >> x);
+        }
+        Ok(())
+    }
+
+    /// Framebuffer state of pixel (`x`, `y`) of one module.
+    pub fn get_pixel(&self, device_index: usize, x: usize, y: usize) -> Result<bool> {
+        if x >= MODULE_SIZE {
+            return Err(Error::InvalidPixel);
+        }
+        Ok(self.row(device_index, y)? & (0x80 >> x) != 0)
+    }
+
+    /// Turn the pixel at chain-wide coordinates (`x`, `y`) on or off.
+    ///
+    /// The whole chain is one [`width`](Self::width) x [`height`](Self::height)
+    /// surface, e.g. x in `0..32` on an 8x32 FC-16 panel.
+    pub fn set_pixel_xy(&mut self, x: usize, y: usize, on: bool) -> Result<()> {
+        let (x, y) = self.physical(x, y)?;
+        self.framebuffer.set_pixel(x, y, on)
+    }
+
+    /// Framebuffer state of the pixel at chain-wide coordinates (`x`, `y`).
+    pub fn get_pixel_xy(&self, x: usize, y: usize) -> Result<bool> {
+        let (x, y) = self.physical(x, y)?;
+        self.framebuffer.get_pixel(x, y)
+    }
+
+    /// Every pixel of the chain-wide surface as `(x, y, on)`, row by row
+    /// from the top-left corner, in the coordinates of
+    /// [`get_pixel_xy`](Self::get_pixel_xy).
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        (0..self.height()).flat_map(move |y| {
+            (0..self.width()).map(move |x| (x, y, self.get_pixel_xy(x, y) == Ok(true)))
+        })
+    }
+
+    /// Every pixel of one module as `(x, y, on)` in the coordinates of
+    /// [`get_pixel`](Self::get_pixel), row by row.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceIndex`] if `device_index` is out of range.
+    pub fn device_pixels(
+        &self,
+        device_index: usize,
+    ) -> Result<impl Iterator<Item = (usize, usize, bool)> + '_> {
+        self.row(device_index, 0)?;
+        Ok((0..MODULE_SIZE).flat_map(move |y| {
+            (0..MODULE_SIZE).map(move |x| (x, y, self.get_pixel(device_index, x, y) == Ok(true)))
+        }))
+    }
+
+    /// Set a pixel given signed chain-wide coordinates, ignoring pixels off
+    /// the surface. Used by drawing code that clips at the edges.
+    pub(super) fn draw_pixel(&mut self, x: i32, y: i32, on: bool) -> Result<()> {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) if x < self.width() && y < self.height() => self.set_pixel_xy(x, y, on),
+            _ => Ok(()),
+        }
+    }
+
+    /// Map chain-wide coordinates to `(device_index, x, y)` within a module.
+    pub fn locate(&self, x: usize, y: usize) -> Result<(usize, usize, usize)> {
+        let (x, y) = self.physical(x, y)?;
+        let stride = self.framebuffer.modules_wide();
+        let (module_x, module_y) = (x / MODULE_SIZE, y / MODULE_SIZE);
+        let column = if self.is_reversed_row(module_y) {
+            stride - 1 - module_x
+        } else {
+            module_x
+        };
+        Ok((module_y * stride + column, x % MODULE_SIZE, y % MODULE_SIZE))
+    }
+
+    /// Row byte `row` of one module.
+    pub fn row(&self, device_index: usize, row: usize) -> Result<u8> {
+        let index = self.row_index(device_index, row)?;
+        self.framebuffer
+            .as_bytes()
+            .get(index)
+            .copied()
+            .ok_or(Error::InvalidPixel)
+    }
+
+    /// Set row `row` of one module, bit 7 being the leftmost pixel.
+    pub fn set_row(&mut self, device_index: usize, row: usize, bits: u8) -> Result<()> {
+        *self.row_mut(device_index, row)? = bits;
+        Ok(())
+    }
+
+    /// Column `column` of one module, bit 7 being the top pixel.
+    pub fn column(&self, device_index: usize, column: usize) -> Result<u8> {
+        if column >= MODULE_SIZE {
+            return Err(Error::InvalidPixel);
+        }
+        let mut bits = 0;
+        for row in 0..MODULE_SIZE {
+            if self.row(device_index, row)? & (0x80 >> column) != 0 {
+                bits |= 0x80 >> row;
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Set column `column` of one module, bit 7 being the top pixel.
+    pub fn set_column(&mut self, device_index: usize, column: usize, bits: u8) -> Result<()> {
+        if column >= MODULE_SIZE {
+            return Err(Error::InvalidPixel);
+        }
+        let mask = 0x80 >> column;
+        for row in 0..MODULE_SIZE {
+            let byte = self.row_mut(device_index, row)?;
+            if bits & (0x80 >> row) != 0 {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set 8 pixels of chain-wide column `x` from `y` downward, bit 7 being
+    /// the pixel at `y`. Pixels past the bottom edge are ignored.
+    ///
+    /// Handy for column-oriented fonts and scrolling, which shift whole
+    /// columns across module boundaries.
+    pub fn set_column_xy(&mut self, x: usize, y: usize, bits: u8) -> Result<()> {
+        if x >= self.width() || y >= self.height() {
+            return Err(Error::InvalidPixel);
+        }
+        for offset in 0..MODULE_SIZE.min(self.height() - y) {
+            self.set_pixel_xy(x, y + offset, bits & (0x80 >> offset) != 0)?;
+        }
+        Ok(())
+    }
+
+    /// 8 pixels of chain-wide column `x` from `y` downward, bit 7 being the
+    /// pixel at `y`. Pixels past the bottom edge read as off.
+    pub fn column_xy(&self, x: usize, y: usize) -> Result<u8> {
+        if x >= self.width() || y >= self.height() {
+            return Err(Error::InvalidPixel);
+        }
+        let mut bits = 0;
+        for offset in 0..MODULE_SIZE.min(self.height() - y) {
+            if self.get_pixel_xy(x, y + offset)? {
+                bits |= 0x80 >> offset;
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Move the drawing surface content `n` pixels left. With `wrap` pixels
+    /// leaving the left edge re-enter on the right, otherwise the vacated
+    /// columns are turned off.
+    pub fn shift_left(&mut self, n: usize, wrap: bool) {
+        let dx = shift_amount(n, self.width(), wrap);
+        self.shift(-dx, 0, wrap);
+    }
+
+    /// Move the drawing surface content `n` pixels right, see [`shift_left`](Self::shift_left).
+    pub fn shift_right(&mut self, n: usize, wrap: bool) {
+        let dx = shift_amount(n, self.width(), wrap);
+        self.shift(dx, 0, wrap);
+    }
+
+    /// Move the drawing surface content `n` pixels up, see [`shift_left`](Self::shift_left).
+    pub fn shift_up(&mut self, n: usize, wrap: bool) {
+        let dy = shift_amount(n, self.height(), wrap);
+        self.shift(0, -dy, wrap);
+    }
+
+    /// Move the drawing surface content `n` pixels down, see [`shift_left`](Self::shift_left).
+    pub fn shift_down(&mut self, n: usize, wrap: bool) {
+        let dy = shift_amount(n, self.height(), wrap);
+        self.shift(0, dy, wrap);
+    }
+
+    /// Shift in drawing surface coordinates, following the canvas rotation.
+    fn shift(&mut self, dx: isize, dy: isize, wrap: bool) {
+        let (dx, dy) = match self.canvas_rotation {
+            Rotation::Deg0 => (dx, dy),
+            Rotation::Deg90 => (-dy, dx),
+            Rotation::Deg180 => (-dx, -dy),
+            Rotation::Deg270 => (dy, -dx),
+        };
+        self.framebuffer.shift(dx, dy, wrap);
+    }
+
+    /// Flip every framebuffer pixel of one module.
+    pub fn invert(&mut self, device_index: usize) -> Result<()> {
+        for row in 0..MODULE_SIZE {
+            let byte = self.row_mut(device_index, row)?;
+            *byte = !*byte;
+        }
+        Ok(())
+    }
+
+    /// Flip every framebuffer pixel.
+    pub fn invert_all(&mut self) {
+        for byte in self.framebuffer.as_bytes_mut() {
+            *byte = !*byte;
+        }
+    }
+
+    /// Show the framebuffer inverted on every flush without changing it,
+    /// e.g. for alert states or highlighting a selection.
+    pub fn set_inverted(&mut self, inverted: bool) {
+        self.inverted = inverted;
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Turn every pixel of the framebuffer off.
+    pub fn clear(&mut self) {
+        self.framebuffer.clear();
+    }
+
+    pub fn fill(&mut self, on: bool) {
+        self.framebuffer.fill(on);
+    }
+
+    /// Mirror every module by `flip` on flush.
+    pub fn with_flip(mut self, flip: Flip) -> Self {
+        self.set_flip_all(flip);
+        self
+    }
+
+    /// Mirror every module by `flip` from the next flush on.
+    pub fn set_flip_all(&mut self, flip: Flip) {
+        self.flips = [flip; MAX_DISPLAYS];
+    }
+
+    /// Mirror one module by `flip` on flush.
+    pub fn set_flip(&mut self, device_index: usize, flip: Flip) -> Result<()> {
+        if device_index >= self.device_count() {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count(),
+            });
+        }
+        if let Some(slot) = self.flips.get_mut(device_index) {
+            *slot = flip;
+        }
+        Ok(())
+    }
+
+    pub fn flip(&self, device_index: usize) -> Result<Flip> {
+        if device_index >= self.device_count() {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count(),
+            });
+        }
+        Ok(self.flips.get(device_index).copied().unwrap_or_default())
+    }
+
+    /// Check every flushed frame against `limit`, see [`CurrentLimit`].
+    pub fn with_current_limit(mut self, limit: CurrentLimit) -> Self {
+        self.current_limit = Some(limit);
+        self
+    }
+
+    /// Replace the current limit; `None` turns checking off, lifting an
+    /// intensity clamp with the next flush.
+    pub fn set_current_limit(&mut self, limit: Option<CurrentLimit>) {
+        self.current_limit = limit;
+    }
+
+    pub fn current_limit(&self) -> Option<CurrentLimit> {
+        self.current_limit
+    }
+
+    /// Write the framebuffer to the chain, one transaction per digit register.
+    ///
+    /// With a [current limit](Self::with_current_limit), an intensity clamp
+    /// is applied before a frame over the limit is written and lifted after
+    /// the first frame within it.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if a custom remap returns a position outside the chain.
+    /// - [`Error::CurrentLimitExceeded`] if the current limit rejects the
+    ///   frame; nothing is written then.
+    /// - [`Error::NotInitialized`] before [`init`](Self::init).
+    /// - With the `strict` feature, [`Error::DigitNotScanned`] if a module
+    ///   lights rows beyond its scan limit.
+    pub fn flush(&mut self) -> Result<()> {
+        self.flush_from(&mut [0; MAX_TILES_SIZE])
+    }
+
+    /// [`flush`](Self::flush) through `buf` instead of a buffer on the
+    /// stack: the frames are encoded into it and each is written straight
+    /// from it. Lets HALs whose DMA transfers need `'static` or specially
+    /// placed memory take the frames without a copy, e.g. from a
+    /// `static mut [u8; MAX_TILES_SIZE]`.
+    ///
+    /// # Errors
+    /// - Any error of [`flush`](Self::flush).
+    /// - [`Error::BufferTooSmall`] if `buf` can't hold all frames; size it
+    ///   with [`MAX_TILES_SIZE`](crate::encoder::MAX_TILES_SIZE) to fit any
+    ///   chain.
+    pub fn flush_from(&mut self, buf: &mut [u8]) -> Result<()> {
+        let (frames, cap) = self.prepare_flush(buf)?;
+        for frame in frames.chunks_exact(frames.len() / MODULE_SIZE) {
+            self.driver.send_frame(frame)?;
+        }
+        self.driver.set_intensity_cap(cap)
+    }
+
+    /// Check and encode the frames [`flush`](Self::flush) sends, applying
+    /// the intensity clamp a frame over the current limit needs first.
+    ///
+    /// Returns the frames and the clamp to set once they are written.
+    pub(super) fn prepare_flush<'b>(
+        &mut self,
+        buf: &'b mut [u8],
+    ) -> Result<(&'b [u8], Option<u8>)> {
+        self.driver.check_initialized()?;
+        let device_count = self.device_count();
+        let mut tiles = [[0; MODULE_SIZE]; MAX_DISPLAYS];
+        let tiles = tiles.get_mut(..device_count).unwrap_or_default();
+        self.device_tiles(tiles)?;
+        let cap = self.frame_intensity_cap(tiles)?;
+        let frames = self.driver.encode_tiles(buf, tiles)?;
+        if cap.is_some() {
+            self.driver.set_intensity_cap(cap)?;
+        }
+        Ok((frames, cap))
+    }
+
+    /// Encode what [`flush`](Self::flush) would send into `buf` instead:
+    /// 8 frames back to back, one per digit register, each to be sent in its
+    /// own chip-select frame (see [`encode_tiles`](crate::encoder::encode_tiles)).
+    /// Size `buf` with [`MAX_TILES_SIZE`](crate::encoder::MAX_TILES_SIZE) to
+    /// fit any chain.
+    ///
+    /// The current limit is not applied.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if a custom remap returns a position outside the chain.
+    /// - [`Error::DecodeModeMismatch`] if any device has Code B decoding enabled.
+    /// - [`Error::BufferTooSmall`] if `buf` can't hold all frames.
+    pub fn encode_flush<'b>(&self, buf: &'b mut [u8]) -> Result<&'b [u8]> {
+        let device_count = self.device_count();
+        let mut tiles = [[0; MODULE_SIZE]; MAX_DISPLAYS];
+        let tiles = tiles.get_mut(..device_count).unwrap_or_default();
+        self.device_tiles(tiles)?;
+        self.driver.encode_tiles(buf, tiles)
+    }
+
+    /// Row bytes each device shows after a flush, one tile per device.
+    pub(super) fn device_tiles(&self, tiles: &mut [[u8; MODULE_SIZE]]) -> Result<()> {
+        let composed = self.visible_frame();
+        let frame = composed.as_ref().unwrap_or(&self.framebuffer);
+        if let Some(mapping) = self.mapping {
+            match mapping {
+                Mapping::Remap(remap) => Self::remap_tiles(frame, &remap, tiles)?,
+                Mapping::Custom(mapping) => Self::remap_tiles(frame, mapping, tiles)?,
+            }
+        } else {
+            let transforms = self.rotations.iter().zip(&self.flips);
+            for ((device_index, tile), (rotation, flip)) in
+                tiles.iter_mut().enumerate().zip(transforms)
+            {
+                let (module_x, module_y) = self.module_position(device_index);
+                let upright = frame.tile(module_x, module_y)?;
+                let mut rotated = rotation.apply(&upright);
+                if self.is_reversed_row(module_y) {
+                    rotated = Rotation::Deg180.apply(&rotated);
+                }
+                *tile = flip.apply(&rotated);
+            }
+        }
+        if self.inverted {
+            for row in tiles.iter_mut().flatten() {
+                *row = !*row;
+            }
+        }
+        Ok(())
+    }
+
+    fn remap_tiles(
+        frame: &Canvas,
+        mapping: &dyn PixelMapping,
+        tiles: &mut [[u8; MODULE_SIZE]],
+    ) -> Result<()> {
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                if !frame.get_pixel(x, y)? {
+                    continue;
+                }
+                let Some((device_index, row, bit)) = mapping.map(x, y, frame.modules_wide()) else {
+                    continue;
+                };
+                let row = tiles
+                    .get_mut(device_index)
+                    .and_then(|tile| tile.get_mut(usize::from(row)))
+                    .filter(|_| bit < 8)
+                    .ok_or(Error::InvalidPixel)?;
+                *row |= 1 << bit;
+            }
+        }
+        Ok(())
+    }
+
+    /// Byte offset of a module row in the framebuffer.
+    fn row_index(&self, device_index: usize, row: usize) -> Result<usize> {
+        if device_index >= self.device_count() {
+            return Err(Error::InvalidDeviceIndex {
+                index: device_index,
+                device_count: self.device_count(),
+            });
+        }
+        if row >= MODULE_SIZE {
+            return Err(Error::InvalidPixel);
+        }
+        let stride = self.framebuffer.modules_wide();
+        let (module_x, module_y) = self.module_position(device_index);
+        Ok((module_y * MODULE_SIZE + row) * stride + module_x)
+    }
+
+    /// Framebuffer byte of a module row.
+    fn row_mut(&mut self, device_index: usize, row: usize) -> Result<&mut u8> {
+        let index = self.row_index(device_index, row)?;
+        self.framebuffer
+            .as_bytes_mut()
+            .get_mut(index)
+            .ok_or(Error::InvalidPixel)
+    }
+
+    /// Framebuffer coordinates of a point on the rotated drawing surface.
+    pub(super) fn physical(&self, x: usize, y: usize) -> Result<(usize, usize)> {
+        if x >= self.width() || y >= self.height() {
+            return Err(Error::InvalidPixel);
+        }
+        Ok(Self::rotate_point(
+            self.canvas_rotation,
+            &self.framebuffer,
+            x,
+            y,
+        ))
+    }
+
+    /// Framebuffer coordinates of a point within the surface of `canvas`
+    /// turned by `rotation`.
+    fn rotate_point(rotation: Rotation, canvas: &Canvas, x: usize, y: usize) -> (usize, usize) {
+        let (width, height) = (canvas.width(), canvas.height());
+        match rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (width - 1 - y, x),
+            Rotation::Deg180 => (width - 1 - x, height - 1 - y),
+            Rotation::Deg270 => (y, height - 1 - x),
+        }
+    }
+
+    /// Grid position `(module_x, module_y)` of a device.
+    fn module_position(&self, device_index: usize) -> (usize, usize) {
+        let stride = self.framebuffer.modules_wide();
+        let (column, module_y) = (device_index % stride, device_index / stride);
+        if self.is_reversed_row(module_y) {
+            (stride - 1 - column, module_y)
+        } else {
+            (column, module_y)
+        }
+    }
+
+    fn is_reversed_row(&self, module_y: usize) -> bool {
+        self.serpentine && module_y % 2 == 1
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Wrap the driver in a [`LedMatrix`] of one module row, see
+    /// [`LedMatrix::new`]; [`LedMatrix::into_inner`] gives it back.
+    ///
+    /// Devices set to Code B decoding or a reduced scan limit, e.g. after
+    /// driving 7-segment content, are switched to raw rows and all 8 rows
+    /// scanned first.
+    pub fn into_matrix(mut self) -> Result<LedMatrix<SPI>> {
+        let device_count = self.device_count();
+        let mut decoded = false;
+        let mut limited = false;
+        for device_index in 0..device_count {
+            decoded |= self.decode_mask(device_index)? != 0;
+            limited |= self.scan_limit(device_index)? != MODULE_SIZE as u8;
+        }
+        if decoded {
+            self.set_decode_mode_all(DecodeMode::NoDecode)?;
+        }
+        if limited {
+            self.set_scan_limit_all(MODULE_SIZE as u8)?;
+        }
+        LedMatrix::new(self)
+    }
+}
+
+/// Shift by `n` across `len` pixels as an offset that can't overflow:
+/// reduced modulo `len` when wrapping, otherwise capped at `len`, which
+/// already moves everything out.
+fn shift_amount(n: usize, len: usize, wrap: bool) -> isize {
+    let n = if wrap {
+        n.checked_rem(len).unwrap_or(0)
+    } else {
+        n.min(len)
+    };
+    isize::try_from(n).unwrap_or(isize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        encoder::MAX_TILES_SIZE,
+        host::{EmulatedSpi, Emulator},
+    };
+
+    #[test]
+    fn test_geometry() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let matrix = LedMatrix::with_geometry(driver, 2, 2).unwrap();
+        assert_eq!((matrix.width(), matrix.height()), (16, 16));
+
+        let driver = matrix.into_inner();
+        assert!(matches!(
+            LedMatrix::with_geometry(driver, 3, 1),
+            Err(Error::InvalidCanvasSize)
+        ));
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        assert!(matches!(
+            LedMatrix::with_geometry(driver, usize::MAX / 2 + 1, 2),
+            Err(Error::InvalidCanvasSize)
+        ));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        matrix.set_row(1, 2, 0b1010_0000).unwrap();
+        matrix.set_column(0, 7, 0xFF).unwrap();
+        assert_eq!(matrix.row(1, 2), Ok(0b1010_0000));
+        assert_eq!(matrix.row(0, 2), Ok(0x01));
+        assert_eq!(matrix.column(1, 0), Ok(0b0010_0000));
+        assert_eq!(matrix.framebuffer().get_pixel(8, 2), Ok(true));
+
+        assert_eq!(
+            matrix.row(2, 0),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+        assert_eq!(matrix.set_row(0, 8, 0), Err(Error::InvalidPixel));
+        assert_eq!(matrix.set_column(0, 8, 0), Err(Error::InvalidPixel));
+    }
+
+    #[test]
+    fn test_chain_columns() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::with_geometry(driver, 2, 2).unwrap();
+
+        // Spans the boundary between module rows
+        matrix.set_column_xy(9, 4, 0b1000_0001).unwrap();
+        assert_eq!(matrix.get_pixel(1, 1, 4), Ok(true));
+        assert_eq!(matrix.get_pixel(3, 1, 3), Ok(true));
+        assert_eq!(matrix.column_xy(9, 4), Ok(0b1000_0001));
+        assert_eq!(matrix.column(1, 1), Ok(0b0000_1000));
+
+        // Clipped at the bottom edge
+        matrix.set_column_xy(0, 12, 0xFF).unwrap();
+        assert_eq!(matrix.column_xy(0, 12), Ok(0xF0));
+        assert_eq!(matrix.set_column_xy(16, 0, 0xFF), Err(Error::InvalidPixel));
+    }
+
+    #[test]
+    fn test_module_pixels() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        matrix.set_pixel(1, 0, 3, true).unwrap();
+        matrix.set_pixel(1, 7, 3, true).unwrap();
+        assert_eq!(matrix.row(1, 3), Ok(0x81));
+        assert_eq!(matrix.get_pixel(1, 7, 3), Ok(true));
+        assert_eq!(matrix.get_pixel(0, 7, 3), Ok(false));
+
+        matrix.set_pixel(1, 0, 3, false).unwrap();
+        assert_eq!(matrix.row(1, 3), Ok(0x01));
+
+        assert_eq!(matrix.set_pixel(0, 8, 0, true), Err(Error::InvalidPixel));
+        assert_eq!(matrix.get_pixel(0, 0, 8), Err(Error::InvalidPixel));
+        assert_eq!(
+            matrix.set_pixel(2, 0, 0, true),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_chain_pixels() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(4)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            assert_eq!(matrix.width(), 32);
+
+            matrix.set_pixel_xy(0, 0, true).unwrap();
+            matrix.set_pixel_xy(31, 7, true).unwrap();
+            matrix.set_pixel_xy(10, 4, true).unwrap();
+            assert_eq!(matrix.get_pixel_xy(10, 4), Ok(true));
+            assert_eq!(matrix.get_pixel(1, 2, 4), Ok(true));
+            assert_eq!(matrix.locate(31, 7), Ok((3, 7, 7)));
+            assert_eq!(matrix.locate(32, 0), Err(Error::InvalidPixel));
+            assert_eq!(matrix.set_pixel_xy(0, 8, true), Err(Error::InvalidPixel));
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap()[0], 0x80);
+        assert_eq!(emulator.digits(1).unwrap()[4], 0x20);
+        assert_eq!(emulator.digits(3).unwrap()[7], 0x01);
+    }
+
+    #[test]
+    fn test_pixel_iterators() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver)
+            .unwrap()
+            .with_canvas_rotation(Rotation::Deg90);
+        assert_eq!((matrix.width(), matrix.height()), (8, 16));
+        matrix.set_pixel_xy(2, 12, true).unwrap();
+
+        assert_eq!(matrix.pixels().count(), 128);
+        let lit: Vec<_> = matrix.pixels().filter(|&(_, _, on)| on).collect();
+        assert_eq!(lit, [(2, 12, true)]);
+
+        let (device_index, x, y) = matrix.locate(2, 12).unwrap();
+        let lit: Vec<_> = matrix
+            .device_pixels(device_index)
+            .unwrap()
+            .filter(|&(_, _, on)| on)
+            .collect();
+        assert_eq!(lit, [(x, y, true)]);
+        assert!(matches!(
+            matrix.device_pixels(2),
+            Err(Error::InvalidDeviceIndex { .. })
+        ));
+    }
+
+    #[test]
+    fn test_locate_grid() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let matrix = LedMatrix::with_geometry(driver, 2, 2).unwrap();
+        assert_eq!(matrix.locate(9, 3), Ok((1, 1, 3)));
+        assert_eq!(matrix.locate(3, 12), Ok((2, 3, 4)));
+    }
+
+    #[test]
+    fn test_flush_applies_rotation() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver)
+                .unwrap()
+                .with_rotation(Rotation::Deg180);
+            matrix.set_rotation(0, Rotation::Deg90).unwrap();
+            assert_eq!(matrix.rotation(1), Ok(Rotation::Deg180));
+            assert_eq!(
+                matrix.set_rotation(2, Rotation::Deg0),
+                Err(Error::InvalidDeviceIndex {
+                    index: 2,
+                    device_count: 2
+                })
+            );
+
+            matrix.set_pixel(0, 0, 0, true).unwrap();
+            matrix.set_pixel(1, 0, 0, true).unwrap();
+            matrix.flush().unwrap();
+            // The framebuffer itself stays upright
+            assert_eq!(matrix.row(0, 0), Ok(0x80));
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap()[0], 0x01);
+        assert_eq!(emulator.digits(1).unwrap()[7], 0x01);
+    }
+
+    #[test]
+    fn test_flush_applies_flip() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap().with_flip(Flip::X);
+            matrix.set_flip(1, Flip::Y).unwrap();
+            assert_eq!(matrix.flip(0), Ok(Flip::X));
+            assert_eq!(
+                matrix.flip(2),
+                Err(Error::InvalidDeviceIndex {
+                    index: 2,
+                    device_count: 2
+                })
+            );
+
+            matrix.set_row(0, 1, 0xF0).unwrap();
+            matrix.set_row(1, 1, 0xF0).unwrap();
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap()[1], 0x0F);
+        assert_eq!(emulator.digits(1).unwrap()[6], 0xF0);
+    }
+
+    #[test]
+    fn test_flush_layout_preset() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let driver = Max7219::new(&mut spi).assume_initialized();
+            let mut matrix = LedMatrix::new(driver).unwrap().with_layout(Layout::Parola);
+            matrix.set_pixel(0, 0, 2, true).unwrap();
+            matrix.flush().unwrap();
+        }
+        assert_eq!(spi.emulator().digits(0).unwrap()[2], 0x01);
+    }
+
+    #[test]
+    fn test_flush_custom_remap() {
+        // Column-major wiring across the chain, devices in reverse order
+        fn remap(x: usize, y: usize) -> (usize, u8, u8) {
+            (1 - x / 8, (x % 8) as u8, y as u8)
+        }
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver)
+                .unwrap()
+                .with_rotation(Rotation::Deg90)
+                .with_layout(Layout::Custom(remap));
+            matrix.set_pixel_xy(3, 5, true).unwrap();
+            matrix.set_pixel_xy(12, 0, true).unwrap();
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(1).unwrap()[3], 1 << 5);
+        assert_eq!(emulator.digits(0).unwrap()[4], 1 << 0);
+    }
+
+    #[test]
+    fn test_flush_custom_remap_out_of_range() {
+        fn remap(x: usize, y: usize) -> (usize, u8, u8) {
+            (x, y as u8, 0)
+        }
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let driver = Max7219::new(&mut spi).assume_initialized();
+        let mut matrix = LedMatrix::new(driver)
+            .unwrap()
+            .with_layout(Layout::Custom(remap));
+        matrix.set_pixel(0, 1, 0, true).unwrap();
+        assert_eq!(matrix.flush(), Err(Error::InvalidPixel));
+    }
+
+    #[test]
+    fn test_flush_pixel_mapping() {
+        // Ring of 8 LEDs on the first row of each module, the rest unwired
+        struct Ring;
+        impl PixelMapping for Ring {
+            fn map(&self, x: usize, y: usize, _modules_wide: usize) -> Option<(usize, u8, u8)> {
+                (y == 0).then_some((x / 8, 0, (x % 8) as u8))
+            }
+        }
+        static RING: Ring = Ring;
+
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap().with_pixel_mapping(&RING);
+            matrix.set_pixel_xy(9, 0, true).unwrap();
+            matrix.set_pixel_xy(3, 4, true).unwrap();
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap(), [0; 8]);
+        assert_eq!(emulator.digits(1).unwrap()[0], 1 << 1);
+    }
+
+    #[test]
+    fn test_layout_mapping_matches_flush() {
+        for layout in [Layout::Generic, Layout::Parola, Layout::IcStation] {
+            let mut spi = EmulatedSpi::new(2).unwrap();
+            {
+                let driver = Max7219::new(&mut spi)
+                    .assume_initialized()
+                    .with_device_count(2)
+                    .unwrap();
+                let mut matrix = LedMatrix::new(driver).unwrap().with_layout(layout);
+                matrix.set_pixel_xy(11, 2, true).unwrap();
+                matrix.flush().unwrap();
+            }
+            let (device_index, row, bit) = layout.map(11, 2, 2).unwrap();
+            let digits = spi.emulator().digits(device_index).unwrap();
+            assert_eq!(digits[usize::from(row)], 1 << bit);
+        }
+    }
+
+    #[test]
+    fn test_encode_flush() {
+        let mut spi = EmulatedSpi::new(3).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(3)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver)
+            .unwrap()
+            .with_rotation(Rotation::Deg180);
+        matrix.set_pixel_xy(0, 0, true).unwrap();
+        matrix.set_pixel_xy(20, 3, true).unwrap();
+
+        let mut buf = [0; MAX_TILES_SIZE];
+        let frames = matrix.encode_flush(&mut buf).unwrap();
+        assert_eq!(frames.len(), 8 * 3 * 2);
+
+        // Replaying the frames gives what a flush would show
+        let mut emulator = Emulator::new(3).unwrap();
+        for frame in frames.chunks(6) {
+            emulator.write(frame);
+        }
+        matrix.flush().unwrap();
+        for device_index in 0..3 {
+            assert_eq!(
+                emulator.digits(device_index),
+                spi.emulator().digits(device_index)
+            );
+        }
+        assert_eq!(emulator.digits(0).unwrap()[7], 0x01);
+    }
+
+    #[test]
+    fn test_flush_from_caller_buffer() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut buf = [0; MAX_TILES_SIZE];
+        {
+            let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.init().unwrap();
+            matrix.set_pixel_xy(9, 2, true).unwrap();
+
+            assert_eq!(
+                matrix.flush_from(&mut buf[..2 * 8 * 2 - 1]),
+                Err(Error::BufferTooSmall)
+            );
+            matrix.driver_mut().reset_stats();
+            matrix.flush_from(&mut buf).unwrap();
+            assert_eq!(matrix.driver().stats().transactions, 8);
+        }
+        // The frames stay in the buffer they were sent from; frame 2 sets
+        // DIG2 of both devices, x 9 being bit 6 on device 1
+        assert_eq!(buf[2 * 4..2 * 4 + 4], [0x03, 0x00, 0x03, 0x40]);
+        assert_eq!(spi.emulator().digits(1).unwrap()[2], 0x40);
+    }
+
+    #[test]
+    fn test_flush_grid() {
+        let mut spi = EmulatedSpi::new(8).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(8)
+                .unwrap();
+            let mut matrix = LedMatrix::with_geometry(driver, 4, 2).unwrap();
+            assert_eq!((matrix.modules_wide(), matrix.modules_high()), (4, 2));
+            assert_eq!((matrix.width(), matrix.height()), (32, 16));
+
+            matrix.set_pixel_xy(0, 8, true).unwrap();
+            matrix.set_pixel_xy(31, 15, true).unwrap();
+            matrix.set_pixel_xy(9, 7, true).unwrap();
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(4).unwrap()[0], 0x80);
+        assert_eq!(emulator.digits(7).unwrap()[7], 0x01);
+        assert_eq!(emulator.digits(1).unwrap()[7], 0x40);
+        assert_eq!(emulator.digits(5).unwrap(), [0; 8]);
+    }
+
+    #[test]
+    fn test_serpentine_grid() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(4)
+                .unwrap();
+            let mut matrix = LedMatrix::with_geometry(driver, 2, 2)
+                .unwrap()
+                .with_serpentine(true);
+            // Second module row runs right to left
+            assert_eq!(matrix.locate(0, 8), Ok((3, 0, 0)));
+            assert_eq!(matrix.locate(15, 15), Ok((2, 7, 7)));
+            assert_eq!(matrix.locate(15, 0), Ok((1, 7, 0)));
+
+            matrix.set_pixel_xy(8, 8, true).unwrap();
+            assert_eq!(matrix.get_pixel(2, 0, 0), Ok(true));
+            matrix.flush().unwrap();
+        }
+        // Device 2 is upside down, so its top-left pixel is sent bottom-right
+        assert_eq!(spi.emulator().digits(2).unwrap()[7], 0x01);
+    }
+
+    #[test]
+    fn test_canvas_rotation() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver)
+            .unwrap()
+            .with_canvas_rotation(Rotation::Deg90);
+        assert_eq!((matrix.width(), matrix.height()), (8, 32));
+
+        // Top-left of the portrait surface is the panel's top-right corner
+        matrix.set_pixel_xy(0, 0, true).unwrap();
+        assert_eq!(matrix.framebuffer().get_pixel(31, 0), Ok(true));
+        assert_eq!(matrix.locate(0, 0), Ok((3, 7, 0)));
+        assert_eq!(matrix.locate(7, 31), Ok((0, 0, 7)));
+        assert_eq!(matrix.set_pixel_xy(8, 0, true), Err(Error::InvalidPixel));
+
+        matrix.set_canvas_rotation(Rotation::Deg270);
+        assert_eq!(matrix.locate(0, 0), Ok((0, 0, 7)));
+        matrix.set_canvas_rotation(Rotation::Deg180);
+        assert_eq!((matrix.width(), matrix.height()), (32, 8));
+        assert_eq!(matrix.get_pixel_xy(0, 7), Ok(true));
+    }
+
+    #[test]
+    fn test_reorient_canvas() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.set_pixel_xy(0, 0, true).unwrap();
+        matrix.set_pixel_xy(20, 3, true).unwrap();
+
+        // Upside down: the drawing keeps reading upright
+        matrix.reorient_canvas(Rotation::Deg180);
+        assert_eq!(matrix.canvas_rotation(), Rotation::Deg180);
+        assert_eq!(matrix.get_pixel_xy(0, 0), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(20, 3), Ok(true));
+        assert_eq!(matrix.framebuffer().get_pixel(31, 7), Ok(true));
+
+        // Portrait is only 8 pixels wide; x 20 no longer fits
+        matrix.reorient_canvas(Rotation::Deg90);
+        assert_eq!(matrix.get_pixel_xy(0, 0), Ok(true));
+        assert_eq!(matrix.framebuffer().get_pixel(31, 0), Ok(true));
+        assert_eq!(
+            matrix
+                .framebuffer()
+                .pixels()
+                .filter(|&(_, _, on)| on)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_runtime_module_orientation() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            assert_eq!(matrix.flush(), Err(Error::NotInitialized));
+            matrix.init().unwrap();
+            matrix.driver_mut().reset_stats();
+            matrix.set_pixel_xy(0, 0, true).unwrap();
+            matrix.flush().unwrap();
+
+            matrix.set_rotation_all(Rotation::Deg180);
+            assert_eq!(matrix.rotation(1), Ok(Rotation::Deg180));
+            matrix.flush().unwrap();
+            assert_eq!(matrix.driver().stats().transactions, 2 * 8);
+
+            matrix.set_flip_all(Flip::X);
+            matrix.set_layout(Layout::Fc16);
+            assert_eq!(matrix.rotation(0), Ok(Rotation::Deg0));
+            assert_eq!(matrix.flip(0), Ok(Flip::NONE));
+            matrix.set_serpentine(true);
+            assert!(matrix.is_serpentine());
+            matrix.set_pixel_mapping(None);
+        }
+        // The framebuffer was re-mapped by the second flush
+        assert_eq!(spi.emulator().digits(0).unwrap()[7], 0x01);
+        assert_eq!(spi.emulator().digits(0).unwrap()[0], 0x00);
+    }
+
+    #[test]
+    fn test_invert() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        matrix.set_row(0, 0, 0x0F).unwrap();
+        matrix.invert(0).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0xF0));
+        assert_eq!(matrix.row(0, 7), Ok(0xFF));
+        assert_eq!(matrix.row(1, 0), Ok(0x00));
+
+        matrix.invert_all();
+        assert_eq!(matrix.row(0, 0), Ok(0x0F));
+        assert_eq!(matrix.row(1, 0), Ok(0xFF));
+        assert_eq!(
+            matrix.invert(2),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_flush_inverted() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+            matrix.init().unwrap();
+            matrix.set_row(0, 2, 0x81).unwrap();
+            matrix.set_inverted(true);
+            assert!(matrix.is_inverted());
+            matrix.flush().unwrap();
+            // The framebuffer itself is untouched
+            assert_eq!(matrix.row(0, 2), Ok(0x81));
+        }
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[2], 0x7E);
+        assert_eq!(digits[0], 0xFF);
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        matrix.set_pixel_xy(0, 3, true).unwrap();
+        matrix.shift_left(1, true);
+        assert_eq!(matrix.get_pixel_xy(31, 3), Ok(true));
+        matrix.shift_right(1, false);
+        assert_eq!(matrix.get_pixel_xy(31, 3), Ok(false));
+
+        matrix.set_pixel_xy(10, 0, true).unwrap();
+        matrix.shift_down(7, false);
+        assert_eq!(matrix.get_pixel_xy(10, 7), Ok(true));
+        matrix.shift_up(8, true);
+        assert_eq!(matrix.get_pixel_xy(10, 7), Ok(true));
+
+        // Shifts beyond the surface wrap modulo its size or clear it
+        matrix.clear();
+        matrix.set_pixel_xy(0, 0, true).unwrap();
+        matrix.shift_left(usize::MAX, true);
+        assert_eq!(matrix.get_pixel_xy(1, 0), Ok(true));
+        matrix.shift_down(usize::MAX, false);
+        assert_eq!(matrix.get_pixel_xy(1, 0), Ok(false));
+    }
+
+    #[test]
+    fn test_shift_follows_canvas_rotation() {
+        for rotation in [
+            Rotation::Deg0,
+            Rotation::Deg90,
+            Rotation::Deg180,
+            Rotation::Deg270,
+        ] {
+            let mut spi = EmulatedSpi::new(4).unwrap();
+            let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+            let mut matrix = LedMatrix::new(driver)
+                .unwrap()
+                .with_canvas_rotation(rotation);
+            matrix.set_pixel_xy(3, 3, true).unwrap();
+            matrix.shift_left(2, false);
+            matrix.shift_down(1, false);
+            assert_eq!(matrix.get_pixel_xy(1, 4), Ok(true), "{rotation:?}");
+        }
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.set_row(0, 0, 0x81).unwrap();
+            matrix.set_row(1, 7, 0x18).unwrap();
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(0).unwrap()[0], 0x81);
+        assert_eq!(emulator.digits(1).unwrap()[7], 0x18);
+        assert_eq!(spi.transactions(), 8);
+    }
+
+    #[test]
+    fn test_into_matrix_restores_raw_rows() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver.init().unwrap();
+            driver.set_decode_mode_all(DecodeMode::AllDigits).unwrap();
+            driver.set_device_scan_limit(1, 4).unwrap();
+            let mut matrix = driver.into_matrix().unwrap();
+            matrix.set_pixel_xy(15, 7, true).unwrap();
+            matrix.flush().unwrap();
+        }
+        let emulator = spi.emulator();
+        for device_index in 0..2 {
+            let device = emulator.device(device_index).unwrap();
+            assert_eq!((device.decode_mode, device.scan_limit), (0, 7));
+        }
+    }
+}
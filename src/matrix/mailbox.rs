@@ -0,0 +1,135 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use super::{Canvas, MODULE_SIZE};
+use crate::MAX_DISPLAYS;
+
+/// Bytes of the largest canvas
+const SLOT_BYTES: usize = MAX_DISPLAYS * MODULE_SIZE;
+
+/// Set in `middle` while it holds a frame the reader hasn't taken
+const FRESH: u8 = 0b100;
+const INDEX: u8 = 0b011;
+
+/// Single-slot, latest-frame-wins handoff of a [`Canvas`] from one task
+/// composing frames to another that owns the SPI bus and flushes them.
+///
+/// Lock-free triple buffering: [`post`](Self::post) never waits for the
+/// reader and [`take`](Self::take) never sees a half-written frame. Frames
+/// posted faster than they are taken replace each other. Both methods take
+/// `&self`, so the mailbox can live in a `static`; only one task may post
+/// and only one may take. Needs atomic swap support on the target.
+pub struct FrameMailbox {
+    slots: [[AtomicU8; SLOT_BYTES]; 3],
+    // Slot the writer fills next
+    back: AtomicU8,
+    // Slot handed over last, plus the FRESH flag
+    middle: AtomicU8,
+    // Slot the reader took last
+    front: AtomicU8,
+}
+
+impl FrameMailbox {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { [const { AtomicU8::new(0) }; SLOT_BYTES] }; 3],
+            back: AtomicU8::new(0),
+            middle: AtomicU8::new(1),
+            front: AtomicU8::new(2),
+        }
+    }
+
+    /// Hand `frame` over, replacing a frame that wasn't taken yet.
+    pub fn post(&self, frame: &Canvas) {
+        let back = self.back.load(Ordering::Relaxed);
+        for (slot, &byte) in self.slots[usize::from(back)].iter().zip(frame.as_bytes()) {
+            slot.store(byte, Ordering::Relaxed);
+        }
+        let previous = self.middle.swap(back | FRESH, Ordering::AcqRel);
+        self.back.store(previous & INDEX, Ordering::Relaxed);
+    }
+
+    /// Whether a frame was posted since the last [`take`](Self::take).
+    pub fn has_frame(&self) -> bool {
+        self.middle.load(Ordering::Acquire) & FRESH != 0
+    }
+
+    /// Copy the newest posted frame into `frame`, which must have the size
+    /// of the posted canvases. Returns `false`, leaving `frame` untouched,
+    /// if nothing new was posted.
+    pub fn take(&self, frame: &mut Canvas) -> bool {
+        if !self.has_frame() {
+            return false;
+        }
+        let front = self.front.load(Ordering::Relaxed);
+        let latest = self.middle.swap(front, Ordering::AcqRel) & INDEX;
+        self.front.store(latest, Ordering::Relaxed);
+        for (byte, slot) in frame
+            .as_bytes_mut()
+            .iter_mut()
+            .zip(&self.slots[usize::from(latest)])
+        {
+            *byte = slot.load(Ordering::Relaxed);
+        }
+        true
+    }
+}
+
+impl Default for FrameMailbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(fill: u8) -> Canvas {
+        let mut canvas = Canvas::for_chain(4).unwrap();
+        canvas.as_bytes_mut().fill(fill);
+        canvas
+    }
+
+    #[test]
+    fn test_latest_frame_wins() {
+        let mailbox = FrameMailbox::new();
+        let mut received = Canvas::for_chain(4).unwrap();
+        assert!(!mailbox.take(&mut received));
+
+        mailbox.post(&frame(0x11));
+        mailbox.post(&frame(0x22));
+        assert!(mailbox.has_frame());
+        assert!(mailbox.take(&mut received));
+        assert_eq!(received, frame(0x22));
+        assert!(!mailbox.take(&mut received));
+
+        // Slots keep rotating correctly
+        for fill in 0x30..0x40 {
+            mailbox.post(&frame(fill));
+            assert!(mailbox.take(&mut received));
+            assert_eq!(received, frame(fill));
+        }
+    }
+
+    #[test]
+    fn test_frames_are_never_torn() {
+        static MAILBOX: FrameMailbox = FrameMailbox::new();
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for fill in 1..=200 {
+                    MAILBOX.post(&frame(fill));
+                }
+            });
+            let mut received = Canvas::for_chain(4).unwrap();
+            let mut last = 0;
+            while last < 200 {
+                if MAILBOX.take(&mut received) {
+                    let fill = received.as_bytes()[0];
+                    assert!(received.as_bytes().iter().all(|&byte| byte == fill));
+                    assert!(fill > last);
+                    last = fill;
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,246 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Font, LedMatrix, MODULE_SIZE, Viewport};
+use crate::Result;
+
+/// Blank columns between the end of a scrolling label and its next pass
+const SCROLL_GAP: usize = MODULE_SIZE;
+
+/// List of labels to pick from, the start of on-device settings screens.
+///
+/// Each entry takes one module row, so an 8x32 panel shows the selected
+/// entry only and a 4 x 2 grid shows two. On every [`tick`](Self::tick)
+/// the visible entries are redrawn: the selected one scrolls if it's wider
+/// than the surface, is inverted when other entries are visible and can
+/// [blink](Self::with_blink).
+#[derive(Debug, Clone)]
+pub struct Menu<'a, F> {
+    labels: &'a [&'a str],
+    font: F,
+    selected: usize,
+    top: usize,
+    wrap: bool,
+    blink: u16,
+    phase: usize,
+}
+
+impl<'a, F> Menu<'a, F>
+where
+    F: Font,
+{
+    /// Menu of `labels` drawn in `font`, the first one selected.
+    pub fn new(labels: &'a [&'a str], font: F) -> Self {
+        Self {
+            labels,
+            font,
+            selected: 0,
+            top: 0,
+            wrap: false,
+            blink: 0,
+            phase: 0,
+        }
+    }
+
+    /// Move from the last entry to the first and back instead of stopping.
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Blink the selected entry, shown and hidden for `ticks` ticks each.
+    /// 0, the default, keeps it steady.
+    pub fn with_blink(mut self, ticks: u16) -> Self {
+        self.blink = ticks;
+        self
+    }
+
+    pub fn labels(&self) -> &'a [&'a str] {
+        self.labels
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Label of the selected entry, `None` for an empty menu.
+    pub fn selected_label(&self) -> Option<&'a str> {
+        self.labels.get(self.selected).copied()
+    }
+
+    /// Select entry `index`, or the last entry if it's past the end.
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.labels.len().saturating_sub(1));
+        self.phase = 0;
+    }
+
+    /// Select the previous entry. Returns `false` if the selection didn't
+    /// move, i.e. the first entry is selected and wrapping is off.
+    pub fn move_up(&mut self) -> bool {
+        let previous = match self.selected.checked_sub(1) {
+            Some(index) => index,
+            None if self.wrap => self.labels.len().saturating_sub(1),
+            None => return false,
+        };
+        self.select_moved(previous)
+    }
+
+    /// Select the next entry. Returns `false` if the selection didn't
+    /// move, i.e. the last entry is selected and wrapping is off.
+    pub fn move_down(&mut self) -> bool {
+        let next = match self.selected + 1 {
+            index if index < self.labels.len() => index,
+            _ if self.wrap => 0,
+            _ => return false,
+        };
+        self.select_moved(next)
+    }
+
+    /// Show the next frame of the menu across the whole surface.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if the font has no glyph for a character nor for the fallback.
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let rows = (matrix.height() / MODULE_SIZE).max(1);
+        // Scroll the list just enough to keep the selection in view
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + rows {
+            self.top = self.selected + 1 - rows;
+        }
+
+        matrix.clear();
+        let width = matrix.width();
+        let visible = self.labels.iter().enumerate().skip(self.top).take(rows);
+        for (row, (index, label)) in visible.enumerate() {
+            let selected = index == self.selected;
+            let hidden =
+                selected && self.blink > 0 && (self.phase / usize::from(self.blink)) % 2 == 1;
+            let x = if selected {
+                self.scroll_offset(matrix, label, width)?
+            } else {
+                0
+            };
+
+            let mut window =
+                matrix.window(Viewport::new(0, row * MODULE_SIZE, width, MODULE_SIZE))?;
+            if !hidden {
+                let end = window.draw_text(x, 0, label, &self.font)?;
+                if x < 0 {
+                    // Next pass following the gap
+                    window.draw_text(end + SCROLL_GAP as i32, 0, label, &self.font)?;
+                }
+            }
+            if selected && rows > 1 && self.labels.len() > 1 {
+                window.invert()?;
+            }
+        }
+        self.phase = self.phase.wrapping_add(1);
+        matrix.flush()
+    }
+
+    fn select_moved(&mut self, index: usize) -> bool {
+        let moved = index != self.selected;
+        self.set_selected(index);
+        moved
+    }
+
+    /// x of a label that scrolls left one column per tick when wider than
+    /// `width`.
+    fn scroll_offset<SPI>(&self, matrix: &LedMatrix<SPI>, label: &str, width: usize) -> Result<i32>
+    where
+        SPI: SpiDevice,
+    {
+        let text_width = matrix.text_width(label, &self.font)?;
+        if text_width <= width {
+            return Ok(0);
+        }
+        let period = text_width + matrix.letter_spacing() + SCROLL_GAP;
+        Ok(-((self.phase % period) as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    /// Every glyph is a full 3-column block.
+    struct Block;
+
+    impl Font for Block {
+        fn height(&self) -> usize {
+            MODULE_SIZE
+        }
+
+        fn glyph(&self, _c: char) -> Result<&[u8]> {
+            Ok(&[0xFF; 3])
+        }
+    }
+
+    const LABELS: [&str; 3] = ["ab", "c", "defghijk"];
+
+    #[test]
+    fn test_moving_selection() {
+        let mut menu = Menu::new(&LABELS, Block);
+        assert!(!menu.move_up());
+        assert!(menu.move_down());
+        assert!(menu.move_down());
+        assert!(!menu.move_down());
+        assert_eq!(menu.selected_label(), Some("defghijk"));
+
+        let mut menu = menu.with_wrap(true);
+        assert!(menu.move_down());
+        assert_eq!(menu.selected(), 0);
+        assert!(menu.move_up());
+        assert_eq!(menu.selected(), 2);
+        menu.set_selected(7);
+        assert_eq!(menu.selected(), 2);
+
+        let mut empty = Menu::new(&[], Block).with_wrap(true);
+        assert!(!empty.move_down());
+        assert_eq!(empty.selected_label(), None);
+    }
+
+    #[test]
+    fn test_two_rows_invert_selection() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+        let mut matrix = LedMatrix::with_geometry(driver, 1, 2).unwrap();
+        let mut menu = Menu::new(&LABELS, Block);
+        menu.move_down();
+        menu.tick(&mut matrix).unwrap();
+        // "ab" on top, "c" inverted below
+        assert_eq!(matrix.row(0, 0), Ok(0xEE));
+        assert_eq!(matrix.row(1, 0), Ok(0x1F));
+
+        // Selecting the third entry scrolls the list by one
+        menu.move_down();
+        menu.tick(&mut matrix).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0xE0));
+    }
+
+    #[test]
+    fn test_selected_scrolls_and_blinks() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        let mut menu = Menu::new(&LABELS, Block).with_blink(2);
+        menu.set_selected(2);
+
+        menu.tick(&mut matrix).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0xEE));
+        menu.tick(&mut matrix).unwrap();
+        // One column further left, and not inverted on a single row
+        assert_eq!(matrix.row(0, 0), Ok(0xDD));
+        menu.tick(&mut matrix).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0x00));
+    }
+}
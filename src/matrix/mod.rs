@@ -0,0 +1,115 @@
+#[cfg(feature = "effects")]
+mod animation;
+mod bargraph;
+#[cfg(feature = "font-8x16")]
+mod big_digits;
+#[cfg(feature = "effects")]
+mod blink;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+mod canvas;
+#[cfg(feature = "sevenseg")]
+mod clock_face;
+mod compose;
+mod dump;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+mod font;
+mod frame;
+mod graph;
+#[cfg(feature = "embedded-graphics-core")]
+mod graphics;
+mod grayscale;
+mod heatmap;
+/// Common 8x8 icons as const [`Sprite`]s for [`LedMatrix::blit`].
+#[cfg(feature = "icons")]
+pub mod icons;
+mod layers;
+mod layout;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+mod led_matrix;
+mod mailbox;
+mod menu;
+mod multi;
+mod pacer;
+mod pattern;
+#[cfg(feature = "effects")]
+mod playlist;
+mod power;
+mod progress;
+#[cfg(feature = "effects")]
+mod screensaver;
+#[cfg(feature = "effects")]
+mod scroll;
+mod shapes;
+mod snapshot;
+mod sparkline;
+#[cfg(feature = "effects")]
+mod splash;
+mod split;
+mod sprite;
+#[cfg(feature = "effects")]
+mod storage;
+#[cfg(feature = "effects")]
+mod strobe;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+mod text;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+mod transform;
+#[cfg(feature = "effects")]
+mod transition;
+#[cfg_attr(not(test), deny(clippy::indexing_slicing))]
+mod viewport;
+
+#[cfg(feature = "effects")]
+pub use animation::{Animation, Looping};
+pub use bargraph::{BarOrientation, Bargraph};
+#[cfg(feature = "font-8x16")]
+pub use big_digits::{BIG_DIGIT_HEIGHT, BIG_DIGIT_WIDTH};
+#[cfg(feature = "effects")]
+pub use blink::Blink;
+pub use canvas::Canvas;
+#[cfg(feature = "sevenseg")]
+pub use clock_face::ClockFace;
+pub use dump::{ASCII_OFF, ASCII_ON};
+#[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+pub use font::BuiltinFont;
+pub use font::Font;
+#[cfg(feature = "font-8x8")]
+pub use font::render_static;
+pub use frame::Frame;
+pub use graph::ScrollingGraph;
+pub use grayscale::{GRAY_LEVELS, GrayCanvas};
+pub use heatmap::Heatmap;
+pub use layers::Layer;
+pub use layout::{Layout, PixelMapping, PixelRemap};
+pub use led_matrix::LedMatrix;
+pub use mailbox::FrameMailbox;
+pub use menu::Menu;
+pub use multi::{MultiDisplay, Stacking};
+pub use pacer::FramePacer;
+pub use pattern::Pattern;
+#[cfg(feature = "effects")]
+pub use playlist::{Message, Playlist};
+pub use power::{CurrentLimit, DEFAULT_SEGMENT_CURRENT_MA, LimitAction};
+pub use progress::{ProgressBar, ProgressStyle};
+#[cfg(feature = "effects")]
+pub use screensaver::{Screensaver, ScreensaverMode};
+#[cfg(feature = "effects")]
+pub use scroll::{ScrollMode, ScrollProfile, Scroller};
+pub use snapshot::{RowDiff, Snapshot};
+pub use sparkline::{Sparkline, SparklineStyle};
+#[cfg(feature = "effects")]
+pub use splash::Splash;
+pub use split::{FrameProducer, FrameRenderer};
+#[doc(hidden)]
+pub use sprite::{__sprite_bytes, __sprite_width};
+pub use sprite::{BlitMode, Sprite};
+#[cfg(feature = "effects")]
+pub use strobe::{Strobe, StrobeStyle};
+pub use text::Alignment;
+pub use transform::{Flip, Rotation};
+#[cfg(feature = "effects")]
+pub use transition::Effect;
+pub use viewport::{Viewport, Window};
+
+/// Width and height, in pixels, of one 8x8 matrix module
+pub const MODULE_SIZE: usize = 8;
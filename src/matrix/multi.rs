@@ -0,0 +1,271 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, MODULE_SIZE};
+use crate::{Result, encoder::MAX_TILES_SIZE, error::Error};
+
+/// How the chains of a [`MultiDisplay`] are put together into one surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Stacking {
+    /// Chains side by side, the first one leftmost; all must be equally tall
+    #[default]
+    Horizontal,
+    /// Chains on top of each other, the first one at the top; all must be
+    /// equally wide
+    Vertical,
+}
+
+/// Several independent chains, each on its own SPI device, driven as one
+/// drawing surface or as separately named displays.
+///
+/// Every chain keeps its own [`LedMatrix`] settings (rotations, layout,
+/// current limit). [`flush`](Self::flush) interleaves the chains' frames
+/// digit register by digit register, so the rows of all chains change
+/// together instead of one chain after the other, and splitting a large
+/// installation across chains cuts the refresh time of each.
+pub struct MultiDisplay<'n, SPI, const N: usize> {
+    displays: [LedMatrix<SPI>; N],
+    names: [&'n str; N],
+    stacking: Stacking,
+}
+
+impl<'n, SPI, const N: usize> MultiDisplay<'n, SPI, N>
+where
+    SPI: SpiDevice,
+{
+    /// Put `displays` next to each other, see [`Stacking`].
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if there are no displays or their
+    ///   heights (widths when stacked vertically) differ.
+    pub fn new(displays: [LedMatrix<SPI>; N], stacking: Stacking) -> Result<Self> {
+        let Some(first) = displays.first() else {
+            return Err(Error::InvalidCanvasSize);
+        };
+        let (width, height) = (first.width(), first.height());
+        let uniform = displays.iter().all(|display| match stacking {
+            Stacking::Horizontal => display.height() == height,
+            Stacking::Vertical => display.width() == width,
+        });
+        if !uniform {
+            return Err(Error::InvalidCanvasSize);
+        }
+        Ok(Self {
+            displays,
+            names: [""; N],
+            stacking,
+        })
+    }
+
+    /// Name the displays, in order, for [`display_named`](Self::display_named).
+    pub fn with_names(mut self, names: [&'n str; N]) -> Self {
+        self.names = names;
+        self
+    }
+
+    pub fn stacking(&self) -> Stacking {
+        self.stacking
+    }
+
+    /// Width in pixels of the combined surface.
+    pub fn width(&self) -> usize {
+        match self.stacking {
+            Stacking::Horizontal => self.displays.iter().map(LedMatrix::width).sum(),
+            Stacking::Vertical => self.displays[0].width(),
+        }
+    }
+
+    /// Height in pixels of the combined surface.
+    pub fn height(&self) -> usize {
+        match self.stacking {
+            Stacking::Horizontal => self.displays[0].height(),
+            Stacking::Vertical => self.displays.iter().map(LedMatrix::height).sum(),
+        }
+    }
+
+    pub fn display(&self, index: usize) -> Option<&LedMatrix<SPI>> {
+        self.displays.get(index)
+    }
+
+    pub fn display_mut(&mut self, index: usize) -> Option<&mut LedMatrix<SPI>> {
+        self.displays.get_mut(index)
+    }
+
+    /// The display given `name` with [`with_names`](Self::with_names).
+    pub fn display_named(&mut self, name: &str) -> Option<&mut LedMatrix<SPI>> {
+        let index = self.names.iter().position(|&known| known == name)?;
+        self.displays.get_mut(index)
+    }
+
+    pub fn displays_mut(&mut self) -> &mut [LedMatrix<SPI>; N] {
+        &mut self.displays
+    }
+
+    pub fn into_inner(self) -> [LedMatrix<SPI>; N] {
+        self.displays
+    }
+
+    /// Initialize every chain.
+    pub fn init(&mut self) -> Result<()> {
+        self.displays.iter_mut().try_for_each(LedMatrix::init)
+    }
+
+    /// Set a pixel of the combined surface.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if (`x`, `y`) is outside the surface.
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) -> Result<()> {
+        let (index, x, y) = self.locate(x, y)?;
+        self.displays[index].set_pixel_xy(x, y, on)
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Result<bool> {
+        let (index, x, y) = self.locate(x, y)?;
+        self.displays[index].get_pixel_xy(x, y)
+    }
+
+    /// Display index and its own coordinates for a point of the surface.
+    pub fn locate(&self, x: usize, y: usize) -> Result<(usize, usize, usize)> {
+        // Position along the stacking direction and across it
+        let (mut along, across) = match self.stacking {
+            Stacking::Horizontal => (x, y),
+            Stacking::Vertical => (y, x),
+        };
+        for (index, display) in self.displays.iter().enumerate() {
+            let (length, breadth) = match self.stacking {
+                Stacking::Horizontal => (display.width(), display.height()),
+                Stacking::Vertical => (display.height(), display.width()),
+            };
+            if along < length {
+                if across >= breadth {
+                    break;
+                }
+                return Ok(match self.stacking {
+                    Stacking::Horizontal => (index, along, across),
+                    Stacking::Vertical => (index, across, along),
+                });
+            }
+            along -= length;
+        }
+        Err(Error::InvalidPixel)
+    }
+
+    pub fn clear(&mut self) {
+        self.displays.iter_mut().for_each(LedMatrix::clear);
+    }
+
+    pub fn fill(&mut self, on: bool) {
+        for display in &mut self.displays {
+            display.fill(on);
+        }
+    }
+
+    /// Write every chain's framebuffer, sending digit register n of all
+    /// chains before digit register n + 1 of any.
+    ///
+    /// Every chain is checked and encoded before the first frame is sent,
+    /// so a frame one chain rejects doesn't leave the others half updated.
+    ///
+    /// # Errors
+    /// - Any error of [`LedMatrix::flush`].
+    pub fn flush(&mut self) -> Result<()> {
+        let mut bufs = [[0; MAX_TILES_SIZE]; N];
+        let mut prepared: [(&[u8], Option<u8>); N] = [(&[], None); N];
+        for ((display, buf), slot) in self.displays.iter_mut().zip(&mut bufs).zip(&mut prepared) {
+            *slot = display.prepare_flush(buf)?;
+        }
+        let mut frames =
+            prepared.map(|(frames, _)| frames.chunks_exact(frames.len() / MODULE_SIZE));
+        for _ in 0..MODULE_SIZE {
+            for (display, chunks) in self.displays.iter_mut().zip(&mut frames) {
+                if let Some(frame) = chunks.next() {
+                    display.driver_mut().send_frame(frame)?;
+                }
+            }
+        }
+        for (display, (_, cap)) in self.displays.iter_mut().zip(prepared) {
+            display.driver_mut().set_intensity_cap(cap)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_horizontal_surface() {
+        let mut left = EmulatedSpi::new(2).unwrap();
+        let mut right = EmulatedSpi::new(1).unwrap();
+        {
+            let displays = [
+                LedMatrix::new(Max7219::new(&mut left).with_device_count(2).unwrap()).unwrap(),
+                LedMatrix::new(Max7219::new(&mut right)).unwrap(),
+            ];
+            let mut multi = MultiDisplay::new(displays, Stacking::Horizontal)
+                .unwrap()
+                .with_names(["main", "side"]);
+            assert_eq!((multi.width(), multi.height()), (24, 8));
+            assert_eq!(multi.locate(17, 3), Ok((1, 1, 3)));
+            assert_eq!(multi.locate(24, 0), Err(Error::InvalidPixel));
+            assert_eq!(multi.locate(0, 8), Err(Error::InvalidPixel));
+
+            multi.init().unwrap();
+            multi.set_pixel(0, 0, true).unwrap();
+            multi.set_pixel(23, 7, true).unwrap();
+            assert!(multi.get_pixel(23, 7).unwrap());
+            multi
+                .display_named("side")
+                .unwrap()
+                .set_pixel_xy(0, 0, true)
+                .unwrap();
+            assert!(multi.display_named("other").is_none());
+            multi.flush().unwrap();
+        }
+        // Init plus one frame per digit register on each chain
+        assert_eq!(left.transactions(), right.transactions());
+        assert_eq!(left.emulator().digits(0).unwrap()[0], 0x80);
+        assert_eq!(right.emulator().digits(0).unwrap()[0], 0x80);
+        assert_eq!(right.emulator().digits(0).unwrap()[7], 0x01);
+    }
+
+    #[test]
+    fn test_vertical_surface() {
+        let mut top = EmulatedSpi::new(1).unwrap();
+        let mut bottom = EmulatedSpi::new(1).unwrap();
+        {
+            let displays = [
+                LedMatrix::new(Max7219::new(&mut top).assume_initialized()).unwrap(),
+                LedMatrix::new(Max7219::new(&mut bottom).assume_initialized()).unwrap(),
+            ];
+            let mut multi = MultiDisplay::new(displays, Stacking::Vertical).unwrap();
+            assert_eq!((multi.width(), multi.height()), (8, 16));
+            multi.set_pixel(2, 9, true).unwrap();
+            assert_eq!(multi.display(1).unwrap().row(0, 1), Ok(0x20));
+            multi.flush().unwrap();
+        }
+        assert_eq!(top.emulator().digits(0).unwrap(), [0; 8]);
+        assert_eq!(bottom.emulator().digits(0).unwrap()[1], 0x20);
+    }
+
+    #[test]
+    fn test_mismatched_chains() {
+        let mut wide = EmulatedSpi::new(1).unwrap();
+        let mut tall = EmulatedSpi::new(2).unwrap();
+        let displays = [
+            LedMatrix::new(Max7219::new(&mut wide)).unwrap(),
+            LedMatrix::with_geometry(Max7219::new(&mut tall).with_device_count(2).unwrap(), 1, 2)
+                .unwrap(),
+        ];
+        assert!(matches!(
+            MultiDisplay::new(displays, Stacking::Horizontal),
+            Err(Error::InvalidCanvasSize)
+        ));
+        let empty: [LedMatrix<EmulatedSpi>; 0] = [];
+        assert!(matches!(
+            MultiDisplay::new(empty, Stacking::Vertical),
+            Err(Error::InvalidCanvasSize)
+        ));
+    }
+}
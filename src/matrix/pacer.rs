@@ -0,0 +1,189 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::LedMatrix;
+use crate::{
+    Result,
+    time::{TickSource, TimestampTracker},
+};
+
+/// Limits flushes to a target frame rate.
+///
+/// Call [`mark_dirty`](Self::mark_dirty) after drawing instead of flushing
+/// right away, and drive the pacer with [`tick`](Self::tick),
+/// [`advance_to`](Self::advance_to) or [`wait_and_flush`](Self::wait_and_flush).
+/// Any number of updates between two frames are coalesced into one flush,
+/// and nothing is sent while the framebuffer is unchanged.
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    interval_ms: u32,
+    // Time since the last flush
+    elapsed_ms: u32,
+    dirty: bool,
+    timestamps: TimestampTracker,
+}
+
+impl FramePacer {
+    /// At most `fps` flushes per second; 0 is treated as 1.
+    pub fn new(fps: u32) -> Self {
+        Self::with_interval_ms(1000 / fps.clamp(1, 1000))
+    }
+
+    /// At least `interval_ms` milliseconds between flushes.
+    pub fn with_interval_ms(interval_ms: u32) -> Self {
+        Self {
+            interval_ms,
+            elapsed_ms: interval_ms,
+            dirty: false,
+            timestamps: TimestampTracker::new(),
+        }
+    }
+
+    pub fn interval_ms(&self) -> u32 {
+        self.interval_ms
+    }
+
+    /// Note that the framebuffer changed and needs a flush.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Advance by `delta_ms` and flush if the framebuffer is dirty and the
+    /// interval has passed since the last flush. Returns whether it flushed.
+    ///
+    /// # Errors
+    /// Any error from [`LedMatrix::flush`]; the framebuffer stays dirty.
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>, delta_ms: u32) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(delta_ms);
+        if !self.dirty || self.elapsed_ms < self.interval_ms {
+            return Ok(false);
+        }
+        matrix.flush()?;
+        self.dirty = false;
+        self.elapsed_ms = 0;
+        Ok(true)
+    }
+
+    /// Advance to the absolute timestamp `now_ms` instead of by a delta, see
+    /// [`TimestampTracker`]. The first call only establishes the time base.
+    pub fn advance_to<SPI>(&mut self, matrix: &mut LedMatrix<SPI>, now_ms: u32) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        let delta_ms = self.timestamps.delta_to(now_ms);
+        self.tick(matrix, delta_ms)
+    }
+
+    /// Advance to the current time of `clock`, like
+    /// [`advance_to`](Self::advance_to).
+    pub fn advance_with<SPI, T>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI>,
+        clock: &mut T,
+    ) -> Result<bool>
+    where
+        SPI: SpiDevice,
+        T: TickSource,
+    {
+        self.advance_to(matrix, clock.now_ms())
+    }
+
+    /// Wait with `delay` for the rest of the interval, then flush if dirty.
+    ///
+    /// Only time spent in the pacer is counted, and every call starts a new
+    /// interval, so a render loop calling this once per frame runs at the
+    /// target rate at most.
+    pub fn wait_and_flush<SPI, D>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI>,
+        delay: &mut D,
+    ) -> Result<bool>
+    where
+        SPI: SpiDevice,
+        D: DelayNs,
+    {
+        let remaining_ms = self.interval_ms.saturating_sub(self.elapsed_ms);
+        delay.delay_ms(remaining_ms);
+        let flushed = self.tick(matrix, remaining_ms)?;
+        self.elapsed_ms = 0;
+        Ok(flushed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    #[test]
+    fn test_tick_coalesces_updates() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi).assume_initialized()).unwrap();
+            let mut pacer = FramePacer::new(20);
+            assert_eq!(pacer.interval_ms(), 50);
+
+            // Nothing to show
+            assert_eq!(pacer.tick(&mut matrix, 0), Ok(false));
+
+            // First frame goes out right away
+            matrix.set_row(0, 0, 0x01).unwrap();
+            pacer.mark_dirty();
+            assert_eq!(pacer.tick(&mut matrix, 0), Ok(true));
+
+            // Three updates within the interval, one flush at its end
+            for bits in [0x02, 0x04, 0x08] {
+                matrix.set_row(0, 0, bits).unwrap();
+                pacer.mark_dirty();
+                assert_eq!(pacer.tick(&mut matrix, 10), Ok(false));
+            }
+            assert_eq!(pacer.tick(&mut matrix, 20), Ok(true));
+            assert!(!pacer.is_dirty());
+        }
+        assert_eq!(spi.transactions(), 2 * 8);
+        assert_eq!(spi.emulator().digits(0).unwrap()[0], 0x08);
+    }
+
+    #[test]
+    fn test_advance_to_and_wait() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        let mut pacer = FramePacer::with_interval_ms(100);
+
+        pacer.mark_dirty();
+        assert_eq!(pacer.advance_to(&mut matrix, 5_000), Ok(true));
+        pacer.mark_dirty();
+        assert_eq!(pacer.advance_to(&mut matrix, 5_060), Ok(false));
+
+        let mut delay = CountingDelay::default();
+        assert_eq!(pacer.wait_and_flush(&mut matrix, &mut delay), Ok(true));
+        assert_eq!(delay.ms, 40);
+        // Clean: waits whole intervals without flushing
+        assert_eq!(pacer.wait_and_flush(&mut matrix, &mut delay), Ok(false));
+        assert_eq!(pacer.wait_and_flush(&mut matrix, &mut delay), Ok(false));
+        assert_eq!(delay.ms, 240);
+    }
+}
@@ -0,0 +1,182 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::LedMatrix;
+use crate::Result;
+
+/// 3x5 digits for [`Pattern::DeviceIndex`], one byte per row with bit 2
+/// leftmost
+const DIGITS_3X5: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Wiring and orientation checks for [`LedMatrix::show_test_pattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Alternating pixels, then the inverse, to spot dead or stuck LEDs
+    Checkerboard,
+    /// Light one row of the drawing surface at a time, top to bottom
+    RowSweep,
+    /// Light one column of the drawing surface at a time, left to right
+    ColumnSweep,
+    /// Show each device's chain position as a digit on itself, with a dot
+    /// in the module's top-left corner. Drawn in each module's own
+    /// orientation after its rotation and flip, ignoring the canvas
+    /// rotation, so a sideways digit points at a wrong module rotation.
+    DeviceIndex,
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Run a test pattern, blocking until done, to verify wiring, module
+    /// order and orientation.
+    ///
+    /// Each frame of the pattern is flushed and shown for `step_ms`
+    /// milliseconds using `delay`; the last one stays on the chain.
+    ///
+    /// # Errors
+    /// - Any error from [`flush`](Self::flush).
+    pub fn show_test_pattern<D>(
+        &mut self,
+        pattern: Pattern,
+        delay: &mut D,
+        step_ms: u32,
+    ) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        let steps = match pattern {
+            Pattern::Checkerboard => 2,
+            Pattern::RowSweep => self.height(),
+            Pattern::ColumnSweep => self.width(),
+            Pattern::DeviceIndex => 1,
+        };
+        for step in 0..steps {
+            self.draw_test_pattern(pattern, step)?;
+            self.flush()?;
+            delay.delay_ms(step_ms);
+        }
+        Ok(())
+    }
+
+    fn draw_test_pattern(&mut self, pattern: Pattern, step: usize) -> Result<()> {
+        let lit = |x: usize, y: usize| match pattern {
+            Pattern::Checkerboard => (x + y + step).is_multiple_of(2),
+            Pattern::RowSweep => y == step,
+            Pattern::ColumnSweep => x == step,
+            Pattern::DeviceIndex => false,
+        };
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.set_pixel_xy(x, y, lit(x, y))?;
+            }
+        }
+
+        if pattern == Pattern::DeviceIndex {
+            for device_index in 0..self.device_count() {
+                let digit = &DIGITS_3X5[device_index % DIGITS_3X5.len()];
+                for (row, &bits) in (2..).zip(digit) {
+                    self.set_row(device_index, row, bits << 2)?;
+                }
+                let corner = self.row(device_index, 0)?;
+                self.set_row(device_index, 0, corner | 0x80)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    #[test]
+    fn test_checkerboard() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        matrix
+            .show_test_pattern(Pattern::Checkerboard, &mut delay, 500)
+            .unwrap();
+        assert_eq!(delay.ms, 1000);
+        // Ends on the inverted phase
+        assert_eq!(matrix.row(0, 0), Ok(0x55));
+        assert_eq!(matrix.row(0, 1), Ok(0xAA));
+    }
+
+    #[test]
+    fn test_sweeps() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix
+                .show_test_pattern(Pattern::ColumnSweep, &mut delay, 10)
+                .unwrap();
+            assert_eq!(delay.ms, 16 * 10);
+            assert_eq!(matrix.column_xy(15, 0), Ok(0xFF));
+            assert_eq!(matrix.column_xy(14, 0), Ok(0x00));
+
+            matrix
+                .show_test_pattern(Pattern::RowSweep, &mut delay, 10)
+                .unwrap();
+            assert_eq!(matrix.row(0, 7), Ok(0xFF));
+            assert_eq!(matrix.row(1, 7), Ok(0xFF));
+            assert_eq!(matrix.row(1, 6), Ok(0x00));
+        }
+        assert_eq!(spi.transactions(), (16 + 8) * 8);
+    }
+
+    #[test]
+    fn test_device_index() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix
+                .show_test_pattern(Pattern::DeviceIndex, &mut delay, 0)
+                .unwrap();
+        }
+        let emulator = spi.emulator();
+        // Row 0 is the corner dot, rows 2-6 the digit in columns 3-5
+        let one = emulator.digits(1).unwrap();
+        assert_eq!(one, [0x80, 0x00, 0x08, 0x18, 0x08, 0x08, 0x1C, 0x00]);
+        assert_eq!(emulator.digits(0).unwrap()[2], 0x1C);
+    }
+}
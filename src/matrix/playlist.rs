@@ -0,0 +1,326 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{Font, LedMatrix, ScrollMode, ScrollProfile, Scroller, storage::Storage};
+use crate::Result;
+
+/// One entry of a [`Playlist`]: text with its own font, effect and pace.
+///
+/// All messages of a playlist share the font type; use
+/// [`BuiltinFont`](super::BuiltinFont) or `&dyn Font` to mix fonts. The
+/// text is stored like a [`Scroller`]'s.
+#[derive(Debug, Clone)]
+pub struct Message<F, T> {
+    text: T,
+    font: F,
+    mode: ScrollMode,
+    profile: ScrollProfile,
+    repeat: u16,
+}
+
+impl<F, T> Message<F, T>
+where
+    T: AsRef<str>,
+{
+    /// A marquee shown once at the constant pace.
+    pub const fn new(text: T, font: F) -> Self {
+        Self {
+            text,
+            font,
+            mode: ScrollMode::Marquee,
+            profile: ScrollProfile::CONSTANT,
+            repeat: 1,
+        }
+    }
+
+    pub const fn with_mode(mut self, mode: ScrollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Speed, pauses and easing, see [`ScrollProfile`].
+    pub const fn with_profile(mut self, profile: ScrollProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Number of passes before moving on to the next message; 0 is treated
+    /// as 1.
+    pub const fn with_repeat(mut self, count: u16) -> Self {
+        self.repeat = count;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.as_ref()
+    }
+
+    pub fn font(&self) -> &F {
+        &self.font
+    }
+
+    pub fn mode(&self) -> ScrollMode {
+        self.mode
+    }
+
+    pub fn profile(&self) -> ScrollProfile {
+        self.profile
+    }
+
+    pub fn repeat(&self) -> u16 {
+        self.repeat
+    }
+}
+
+/// Cycles a [`Scroller`] through a list of [`Message`]s, e.g. for signage.
+///
+/// Each message runs for its repeat count, then the next one starts; after
+/// the last message the playlist starts over with the first. An empty
+/// playlist leaves the matrix untouched.
+#[derive(Debug, Clone)]
+pub struct Playlist<'a, F, T> {
+    messages: Storage<'a, Message<F, T>>,
+    y: i32,
+    index: usize,
+    // Passes of the current message completed so far
+    passes: u16,
+    // Frame of the current pass shown by the next tick
+    step: usize,
+}
+
+impl<'a, F, T> Playlist<'a, F, T>
+where
+    F: Font,
+    T: AsRef<str>,
+{
+    pub fn new(messages: &'a [Message<F, T>]) -> Self {
+        Self::with_messages(Storage::Borrowed(messages))
+    }
+
+    /// A playlist owning its messages, e.g. built at runtime.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(messages: alloc::vec::Vec<Message<F, T>>) -> Self {
+        Self::with_messages(Storage::Owned(messages))
+    }
+
+    fn with_messages(messages: Storage<'a, Message<F, T>>) -> Self {
+        Self {
+            messages,
+            y: 0,
+            index: 0,
+            passes: 0,
+            step: 0,
+        }
+    }
+
+    /// Draw all messages with their top on row `y` instead of row 0.
+    pub fn with_row(mut self, y: i32) -> Self {
+        self.y = y;
+        self
+    }
+
+    pub fn messages(&self) -> &[Message<F, T>] {
+        &self.messages
+    }
+
+    /// Index of the message shown by the next tick.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The message shown by the next tick, `None` for an empty playlist.
+    pub fn current(&self) -> Option<&Message<F, T>> {
+        self.messages.get(self.index)
+    }
+
+    /// Abandon the current message and start the next one with the next
+    /// tick.
+    pub fn skip(&mut self) {
+        self.advance();
+    }
+
+    /// Start over with the first message with the next tick.
+    pub fn reset(&mut self) {
+        self.select(0);
+    }
+
+    /// Show the next frame of the current message. Returns `true` when this
+    /// frame completed the last pass of the last message, i.e. the next tick
+    /// starts the playlist over.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if a message's font has no glyph for a character nor for the
+    ///   fallback.
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        let Some(message) = self.messages.get(self.index) else {
+            return Ok(true);
+        };
+        let mut scroller = Scroller::new(message.text(), &message.font)
+            .with_row(self.y)
+            .with_mode(message.mode)
+            .with_profile(message.profile)
+            .with_step(self.step);
+        let done = scroller.tick(matrix)?;
+        self.step = scroller.step();
+        if !done {
+            return Ok(false);
+        }
+
+        self.passes += 1;
+        let repeat = message.repeat.max(1);
+        if self.passes < repeat {
+            return Ok(false);
+        }
+        let last = self.index + 1 == self.messages.len();
+        self.advance();
+        Ok(last)
+    }
+
+    /// Play the whole playlist once, showing each frame for `tick_ms`
+    /// milliseconds using `delay`.
+    ///
+    /// # Errors
+    /// Same as [`tick`](Self::tick); playback stops at the first error.
+    pub fn play<SPI, D>(
+        &mut self,
+        matrix: &mut LedMatrix<SPI>,
+        delay: &mut D,
+        tick_ms: u32,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+        D: DelayNs,
+    {
+        self.reset();
+        loop {
+            let done = self.tick(matrix)?;
+            delay.delay_ms(tick_ms);
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    fn advance(&mut self) {
+        let next = match self.messages.len() {
+            0 => 0,
+            len => (self.index + 1) % len,
+        };
+        self.select(next);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.index = index;
+        self.passes = 0;
+        self.step = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, matrix::MODULE_SIZE};
+
+    /// Every glyph is `width` lit columns.
+    struct Block {
+        width: usize,
+    }
+
+    impl Font for Block {
+        fn height(&self) -> usize {
+            MODULE_SIZE
+        }
+
+        fn glyph(&self, _c: char) -> Result<&[u8]> {
+            Ok(&[0xFF; 8][..self.width])
+        }
+    }
+
+    const FAST: ScrollProfile = ScrollProfile {
+        step: 16,
+        ..ScrollProfile::CONSTANT
+    };
+
+    #[test]
+    fn test_playlist_cycles_messages() {
+        let messages = [
+            // Enters in one tick, leaves in the next
+            Message::new("a", Block { width: 2 })
+                .with_profile(FAST)
+                .with_repeat(2),
+            // Fits, so each bounce pass is a single still frame
+            Message::new("b", Block { width: 3 }).with_mode(ScrollMode::Bounce),
+        ];
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        let mut playlist = Playlist::new(&messages);
+
+        let mut results = [false; 5];
+        let mut indices = [0; 5];
+        for (result, index) in results.iter_mut().zip(&mut indices) {
+            *result = playlist.tick(&mut matrix).unwrap();
+            *index = playlist.index();
+        }
+        assert_eq!(results, [false, false, false, false, true]);
+        assert_eq!(indices, [0, 0, 0, 1, 0]);
+        // Second message left on screen at x 0
+        assert_eq!(matrix.column_xy(2, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(3, 0), Ok(0x00));
+
+        // Starts over with the first message entering
+        assert_eq!(playlist.tick(&mut matrix), Ok(false));
+        assert_eq!(matrix.column_xy(0, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(2, 0), Ok(0x00));
+    }
+
+    #[test]
+    fn test_playlist_skip_and_empty() {
+        let messages = [
+            Message::new("a", Block { width: 1 }),
+            Message::new("b", Block { width: 1 }),
+        ];
+        let mut playlist = Playlist::new(&messages);
+        playlist.skip();
+        assert_eq!(playlist.current().map(Message::text), Some("b"));
+        playlist.skip();
+        assert_eq!(playlist.index(), 0);
+
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        let mut empty = Playlist::<Block, &str>::new(&[]);
+        assert!(empty.current().is_none());
+        assert_eq!(empty.tick(&mut matrix), Ok(true));
+        empty.skip();
+        assert_eq!(spi.transactions(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_owned_messages() {
+        use std::string::ToString;
+
+        let messages = (1..=2)
+            .map(|n| Message::new(n.to_string(), Block { width: n }).with_profile(FAST))
+            .collect();
+        let mut playlist = Playlist::from_vec(messages);
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        assert_eq!(playlist.tick(&mut matrix), Ok(false));
+        assert_eq!(playlist.current().map(Message::text), Some("1"));
+        assert_eq!(playlist.tick(&mut matrix), Ok(false));
+        assert_eq!(playlist.current().map(Message::text), Some("2"));
+    }
+}
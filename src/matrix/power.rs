@@ -0,0 +1,195 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, MODULE_SIZE};
+use crate::{MAX_DISPLAYS, Result, error::Error, registers::Intensity};
+
+/// Typical peak segment current, in milliamps, of a module built with the
+/// common 10 kΩ RSET resistor.
+pub const DEFAULT_SEGMENT_CURRENT_MA: u32 = 40;
+
+/// What [`LedMatrix::flush`] does with a frame over its [`CurrentLimit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitAction {
+    /// Show the frame with every device's intensity held at or below this
+    /// level until a frame within the limit is flushed.
+    ClampIntensity(Intensity),
+    /// Don't show the frame; flush returns
+    /// [`Error::CurrentLimitExceeded`].
+    Reject,
+}
+
+/// Cap on the number of LEDs lit at once across the chain, checked on every
+/// flush to protect weak 5V supplies from full-white frames.
+///
+/// Only scanned rows count, after inversion and per-device orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentLimit {
+    pub max_lit: usize,
+    pub action: LimitAction,
+}
+
+impl CurrentLimit {
+    pub const fn new(max_lit: usize, action: LimitAction) -> Self {
+        Self { max_lit, action }
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Estimate the average LED current, in microamps, the framebuffer will
+    /// draw once flushed, for modules whose RSET gives a peak current of
+    /// `segment_ma` per segment (see [`DEFAULT_SEGMENT_CURRENT_MA`]).
+    ///
+    /// Each lit LED draws `segment_ma` for the PWM duty of its device's
+    /// intensity, (2 × intensity + 1) / 32, while its row is scanned, i.e.
+    /// 1 / scan limit of the time. Rows beyond the scan limit stay dark.
+    /// The chips' own supply current and shut down devices are not taken
+    /// into account.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`](crate::error::Error::InvalidPixel) if a
+    ///   custom remap returns a position outside the chain.
+    pub fn estimated_current_ua(&self, segment_ma: u32) -> Result<u32> {
+        let device_count = self.device_count();
+        let mut tiles = [[0; MODULE_SIZE]; MAX_DISPLAYS];
+        self.device_tiles(&mut tiles[..device_count])?;
+
+        let driver = self.driver();
+        let mut total = 0;
+        for (device_index, tile) in tiles[..device_count].iter().enumerate() {
+            let scanned = usize::from(driver.scan_limit(device_index)?);
+            let lit: u32 = tile[..scanned].iter().map(|row| row.count_ones()).sum();
+            let intensity = driver.calibrated(device_index, driver.intensity(device_index)?);
+            let duty = 2 * u64::from(intensity) + 1;
+            total += u64::from(lit) * u64::from(segment_ma) * 1000 * duty / (32 * scanned as u64);
+        }
+        Ok(u32::try_from(total).unwrap_or(u32::MAX))
+    }
+
+    /// Intensity cap for showing `tiles` under the current limit, `None`
+    /// when there's no limit or the frame is within it.
+    ///
+    /// # Errors
+    /// - [`Error::CurrentLimitExceeded`] if the limit rejects the frame.
+    pub(super) fn frame_intensity_cap(&self, tiles: &[[u8; MODULE_SIZE]]) -> Result<Option<u8>> {
+        let Some(limit) = self.current_limit() else {
+            return Ok(None);
+        };
+        let mut lit = 0;
+        for (device_index, tile) in tiles.iter().enumerate() {
+            let scanned = usize::from(self.driver().scan_limit(device_index)?);
+            lit += tile[..scanned]
+                .iter()
+                .map(|row| row.count_ones() as usize)
+                .sum::<usize>();
+        }
+        if lit <= limit.max_lit {
+            return Ok(None);
+        }
+        match limit.action {
+            LimitAction::ClampIntensity(max) => Ok(Some(max.value())),
+            LimitAction::Reject => Err(Error::CurrentLimitExceeded),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_estimated_current() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        assert_eq!(matrix.estimated_current_ua(40), Ok(0));
+
+        // Full module at full intensity: 64 × 40 mA × 31/32 / 8
+        matrix.driver_mut().set_intensity(0, 15).unwrap();
+        for row in 0..MODULE_SIZE {
+            matrix.set_row(0, row, 0xFF).unwrap();
+        }
+        assert_eq!(matrix.estimated_current_ua(40), Ok(310_000));
+
+        // Half the rows scanned, each lit twice as long
+        matrix.driver_mut().set_device_scan_limit(0, 4).unwrap();
+        assert_eq!(matrix.estimated_current_ua(40), Ok(310_000));
+
+        // Inverted, the second device lights all 64 at intensity 0
+        matrix.set_inverted(true);
+        matrix.driver_mut().set_device_scan_limit(0, 8).unwrap();
+        assert_eq!(matrix.estimated_current_ua(40), Ok(10_000));
+    }
+
+    #[test]
+    fn test_estimated_current_calibrated() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let driver = Max7219::new(&mut spi).with_calibration(&[2]).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.set_row(0, 0, 0x80).unwrap();
+        // One LED at register intensity 2: 20 mA × 5/32 / 8
+        assert_eq!(matrix.estimated_current_ua(20), Ok(390));
+    }
+
+    #[test]
+    fn test_current_limit_clamps_intensity() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let limit =
+                CurrentLimit::new(64, LimitAction::ClampIntensity(Intensity::new(3).unwrap()));
+            let mut matrix = LedMatrix::new(driver).unwrap().with_current_limit(limit);
+            matrix.driver_mut().set_intensities(&[10, 2]).unwrap();
+
+            matrix.fill(true);
+            matrix.flush().unwrap();
+            assert_eq!(matrix.driver().intensity(0), Ok(10));
+            assert_eq!(
+                matrix.estimated_current_ua(32),
+                Ok(64 * 32_000 * (7 + 5) / 256)
+            );
+
+            // Back within the limit, the nominal intensities return
+            matrix.set_inverted(true);
+            matrix.set_pixel(1, 0, 0, false).unwrap();
+            matrix.flush().unwrap();
+        }
+        // Initial intensities, clamped frame, restore
+        assert_eq!(spi.transactions(), 1 + (1 + 8) + (8 + 1));
+        let emulator = spi.emulator();
+        assert_eq!(emulator.device(0).unwrap().intensity, 10);
+        assert_eq!(emulator.device(1).unwrap().intensity, 2);
+        assert_eq!(emulator.digits(1).unwrap()[0], 0x80);
+    }
+
+    #[test]
+    fn test_current_limit_rejects_frame() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let driver = Max7219::new(&mut spi).assume_initialized();
+            let limit = CurrentLimit::new(8, LimitAction::Reject);
+            let mut matrix = LedMatrix::new(driver).unwrap().with_current_limit(limit);
+            matrix.set_row(0, 7, 0xFF).unwrap();
+            matrix.flush().unwrap();
+
+            matrix.set_row(0, 6, 0x01).unwrap();
+            assert_eq!(matrix.flush(), Err(Error::CurrentLimitExceeded));
+
+            // Rows beyond the scan limit are dark and don't count
+            matrix.driver_mut().set_scan_limit_all(7).unwrap();
+            #[cfg(feature = "strict")]
+            {
+                assert_eq!(matrix.flush(), Err(Error::DigitNotScanned));
+                matrix.set_row(0, 7, 0x00).unwrap();
+            }
+            matrix.flush().unwrap();
+        }
+        assert_eq!(spi.transactions(), 8 + 1 + 8);
+    }
+}
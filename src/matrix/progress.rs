@@ -0,0 +1,139 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{BarOrientation, LedMatrix, Viewport};
+use crate::Result;
+
+/// Look of a [`ProgressBar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressStyle {
+    /// The whole region fills up
+    #[default]
+    Solid,
+    /// A one pixel outline with the inside filling up
+    Bordered,
+}
+
+/// Progress indicator for [`LedMatrix::draw_progress`], e.g. for firmware
+/// updates. Horizontal bars fill from the left, vertical ones from the
+/// bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProgressBar {
+    pub style: ProgressStyle,
+    pub orientation: BarOrientation,
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw `bar` over the region `viewport` covers showing `done` out of
+    /// `total` steps complete, rounded down so the bar is only full once
+    /// `done` reaches `total`. A `total` of 0 counts as complete.
+    ///
+    /// The rest of the surface is left as it is.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`](crate::error::Error::InvalidPixel) if
+    ///   `viewport` doesn't fit the surface, see [`window`](Self::window).
+    pub fn draw_progress(
+        &mut self,
+        viewport: Viewport,
+        done: u32,
+        total: u32,
+        bar: ProgressBar,
+    ) -> Result<()> {
+        let mut window = self.window(viewport)?;
+        window.clear()?;
+        let (width, height) = (window.width(), window.height());
+        let inset = match bar.style {
+            ProgressStyle::Solid => 0,
+            ProgressStyle::Bordered => {
+                for x in 0..width {
+                    window.set_pixel(x, 0, true)?;
+                    window.set_pixel(x, height - 1, true)?;
+                }
+                for y in 0..height {
+                    window.set_pixel(0, y, true)?;
+                    window.set_pixel(width - 1, y, true)?;
+                }
+                1
+            }
+        };
+
+        let (inner_width, inner_height) = (
+            width.saturating_sub(2 * inset),
+            height.saturating_sub(2 * inset),
+        );
+        let length = match bar.orientation {
+            BarOrientation::Horizontal => inner_width,
+            BarOrientation::Vertical => inner_height,
+        };
+        let filled = if total == 0 {
+            length
+        } else {
+            (u64::from(done.min(total)) * length as u64 / u64::from(total)) as usize
+        };
+        let (columns, rows) = match bar.orientation {
+            BarOrientation::Horizontal => (0..filled, 0..inner_height),
+            BarOrientation::Vertical => (0..inner_width, inner_height - filled..inner_height),
+        };
+        for y in rows {
+            for x in columns.clone() {
+                window.set_pixel(inset + x, inset + y, true)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, error::Error, host::EmulatedSpi};
+
+    #[test]
+    fn test_bordered_horizontal() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.fill(true);
+        let bar = ProgressBar {
+            style: ProgressStyle::Bordered,
+            orientation: BarOrientation::Horizontal,
+        };
+        matrix
+            .draw_progress(Viewport::new(8, 0, 24, 8), 1, 2, bar)
+            .unwrap();
+
+        // Outline, 11 of the 22 inner columns, then empty
+        assert_eq!(matrix.column_xy(7, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(8, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(19, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(20, 0), Ok(0x81));
+        assert_eq!(matrix.column_xy(31, 0), Ok(0xFF));
+
+        assert_eq!(
+            matrix.draw_progress(Viewport::new(8, 0, 32, 8), 1, 2, bar),
+            Err(Error::InvalidPixel)
+        );
+    }
+
+    #[test]
+    fn test_solid_vertical() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        let bar = ProgressBar {
+            orientation: BarOrientation::Vertical,
+            ..ProgressBar::default()
+        };
+        let viewport = Viewport::new(2, 0, 2, 8);
+        matrix.draw_progress(viewport, 3, 4, bar).unwrap();
+        assert_eq!(matrix.column_xy(2, 0), Ok(0x3F));
+
+        matrix.draw_progress(viewport, 99, 100, bar).unwrap();
+        assert_eq!(matrix.column_xy(3, 0), Ok(0x7F));
+        matrix.draw_progress(viewport, 5, 0, bar).unwrap();
+        assert_eq!(matrix.column_xy(3, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(4, 0), Ok(0x00));
+    }
+}
@@ -0,0 +1,190 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Canvas, LedMatrix};
+use crate::Result;
+
+/// Content offsets the [`ScreensaverMode::Orbit`] cycles through
+const ORBIT: [(usize, usize); 4] = [(0, 0), (1, 0), (1, 1), (0, 1)];
+
+/// What a [`Screensaver`] does once the content has been idle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScreensaverMode {
+    /// Move the content one pixel right and/or down every period, circling
+    /// back to its place; the pixels moved off the far edges are hidden
+    #[default]
+    Orbit,
+    /// Show the content inverted for one tick every period
+    Invert,
+    /// Replace the content with a lit column sweeping across the surface,
+    /// one column per tick
+    Sweep,
+}
+
+/// Opt-in burn-in mitigation for static content such as clocks.
+///
+/// Drive it with [`tick`](Self::tick) at a steady rate and call
+/// [`touch`](Self::touch) before changing the content. After
+/// [`idle_ticks`](Self::with_idle_ticks) ticks without a touch it starts
+/// altering what is shown so LEDs age more evenly; a touch brings the
+/// content back.
+#[derive(Debug, Clone)]
+pub struct Screensaver {
+    mode: ScreensaverMode,
+    idle_ticks: u32,
+    period: u32,
+    idle: u32,
+    // Ticks since the screensaver started
+    phase: u32,
+    // Framebuffer when the screensaver started
+    saved: Option<Canvas>,
+}
+
+impl Screensaver {
+    /// Start after 600 ticks and step every 60, i.e. 10 minutes and every
+    /// minute at one tick per second.
+    pub fn new(mode: ScreensaverMode) -> Self {
+        Self {
+            mode,
+            idle_ticks: 600,
+            period: 60,
+            idle: 0,
+            phase: 0,
+            saved: None,
+        }
+    }
+
+    pub fn with_idle_ticks(mut self, ticks: u32) -> Self {
+        self.idle_ticks = ticks;
+        self
+    }
+
+    /// Ticks between orbit moves or inverted flashes; 0 is treated as 1.
+    pub fn with_period(mut self, ticks: u32) -> Self {
+        self.period = ticks;
+        self
+    }
+
+    pub fn mode(&self) -> ScreensaverMode {
+        self.mode
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.saved.is_some()
+    }
+
+    /// Note new activity: restores the framebuffer if the screensaver was
+    /// running and restarts the idle count. Call it before drawing new
+    /// content; the next flush shows the restored content.
+    pub fn touch<SPI>(&mut self, matrix: &mut LedMatrix<SPI>)
+    where
+        SPI: SpiDevice,
+    {
+        if let Some(saved) = self.saved.take() {
+            *matrix.framebuffer_mut() = saved;
+        }
+        self.idle = 0;
+    }
+
+    /// Count one idle tick, or show the next screensaver frame once idle.
+    /// Returns whether the screensaver is running.
+    ///
+    /// # Errors
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        let Some(saved) = &self.saved else {
+            self.idle = self.idle.saturating_add(1);
+            if self.idle >= self.idle_ticks {
+                self.saved = Some(matrix.framebuffer().clone());
+                self.phase = 0;
+            }
+            return Ok(false);
+        };
+
+        let period = self.period.max(1);
+        *matrix.framebuffer_mut() = saved.clone();
+        match self.mode {
+            ScreensaverMode::Orbit => {
+                let (dx, dy) = ORBIT[(self.phase / period) as usize % ORBIT.len()];
+                matrix.shift_right(dx, false);
+                matrix.shift_down(dy, false);
+            }
+            ScreensaverMode::Invert => {
+                if (self.phase + 1).is_multiple_of(period) {
+                    matrix.invert_all();
+                }
+            }
+            ScreensaverMode::Sweep => {
+                matrix.clear();
+                let x = self.phase as usize % matrix.width();
+                for y in 0..matrix.height() {
+                    matrix.set_pixel_xy(x, y, true)?;
+                }
+            }
+        }
+        matrix.flush()?;
+        self.phase = self.phase.wrapping_add(1);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_orbit_after_idle() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        matrix.set_pixel_xy(0, 0, true).unwrap();
+        let mut saver = Screensaver::new(ScreensaverMode::Orbit)
+            .with_idle_ticks(2)
+            .with_period(1);
+
+        assert_eq!(saver.tick(&mut matrix), Ok(false));
+        assert_eq!(saver.tick(&mut matrix), Ok(false));
+        assert!(saver.is_active());
+
+        // Offsets (0, 0), (1, 0), (1, 1), (0, 1)
+        let mut positions = [(0, 0); 4];
+        for position in &mut positions {
+            assert_eq!(saver.tick(&mut matrix), Ok(true));
+            let lit = (0..64).find(|&i| matrix.get_pixel_xy(i % 8, i / 8).unwrap());
+            *position = lit.map(|i| (i % 8, i / 8)).unwrap();
+        }
+        assert_eq!(positions, [(0, 0), (1, 0), (1, 1), (0, 1)]);
+
+        saver.touch(&mut matrix);
+        assert!(!saver.is_active());
+        assert_eq!(matrix.get_pixel_xy(0, 0), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(0, 1), Ok(false));
+    }
+
+    #[test]
+    fn test_invert_and_sweep() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        let mut saver = Screensaver::new(ScreensaverMode::Invert)
+            .with_idle_ticks(0)
+            .with_period(3);
+        saver.tick(&mut matrix).unwrap();
+        let mut inverted = [false; 3];
+        for inverted in &mut inverted {
+            saver.tick(&mut matrix).unwrap();
+            *inverted = matrix.get_pixel_xy(0, 0).unwrap();
+        }
+        assert_eq!(inverted, [false, false, true]);
+
+        let mut saver = Screensaver::new(ScreensaverMode::Sweep).with_idle_ticks(0);
+        saver.tick(&mut matrix).unwrap();
+        saver.tick(&mut matrix).unwrap();
+        saver.tick(&mut matrix).unwrap();
+        assert_eq!(matrix.column_xy(1, 0), Ok(0xFF));
+        assert_eq!(matrix.column_xy(0, 0), Ok(0x00));
+    }
+}
@@ -0,0 +1,573 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{Font, LedMatrix};
+#[cfg(feature = "heapless")]
+use crate::error::Error;
+use crate::{Result, easing::Easing};
+
+/// How a [`Scroller`] moves its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollMode {
+    /// Enter at the right edge and scroll until the text has left on the
+    /// left
+    #[default]
+    Marquee,
+    /// Start left-aligned, scroll until the end of the text reaches the
+    /// right edge, then scroll back. Suits text only slightly wider than
+    /// the surface; text that fits stays still.
+    Bounce,
+}
+
+/// Timing of a [`Scroller`], counted in ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollProfile {
+    /// Columns moved per tick; 0 is treated as 1
+    pub step: u8,
+    /// Ticks to hold once the start of the text is readable: left-aligned
+    /// for [`ScrollMode::Marquee`], at the start of a bounce
+    pub pause_start: u16,
+    /// Ticks to hold at the end of a pass: on the blank surface after a
+    /// marquee, with the end of the text shown for a bounce
+    pub pause_end: u16,
+    /// Speed curve within each movement. Every curve takes as many ticks
+    /// as [`Easing::Linear`], which moves `step` columns per tick
+    pub easing: Easing,
+}
+
+impl ScrollProfile {
+    /// One column per tick without pauses.
+    pub const CONSTANT: Self = Self {
+        step: 1,
+        pause_start: 0,
+        pause_end: 0,
+        easing: Easing::Linear,
+    };
+}
+
+impl Default for ScrollProfile {
+    fn default() -> Self {
+        Self::CONSTANT
+    }
+}
+
+/// One part of a scroll pass.
+#[derive(Clone, Copy)]
+enum Segment {
+    /// Move to x
+    Move(i32),
+    /// Stay for a number of ticks
+    Hold(usize),
+}
+
+/// Tick-driven text scroller for a [`LedMatrix`].
+///
+/// Each [`tick`](Self::tick) redraws and flushes the surface with the text
+/// moved one frame on, so scrolling can be driven from a timer interrupt
+/// or an async task without blocking. How a pass runs depends on the
+/// [`ScrollMode`] and the pace on the [`ScrollProfile`]; the tick after the
+/// last frame of a pass starts a new one.
+///
+/// The text is anything that derefs to a string: a `&str` borrowed for as
+/// long as the scroller lives or, to build messages at runtime, an owned
+/// string such as `String` with the `alloc` feature.
+#[derive(Debug, Clone)]
+pub struct Scroller<F, T> {
+    text: T,
+    font: F,
+    y: i32,
+    mode: ScrollMode,
+    profile: ScrollProfile,
+    // Frame of the current pass shown by the next tick
+    step: usize,
+}
+
+impl<F, T> Scroller<F, T>
+where
+    F: Font,
+    T: AsRef<str>,
+{
+    pub fn new(text: T, font: F) -> Self {
+        Self {
+            text,
+            font,
+            y: 0,
+            mode: ScrollMode::default(),
+            profile: ScrollProfile::CONSTANT,
+            step: 0,
+        }
+    }
+
+    /// Draw the text with its top on row `y` instead of row 0.
+    pub fn with_row(mut self, y: i32) -> Self {
+        self.y = y;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ScrollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_profile(mut self, profile: ScrollProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Hold for `ticks` both at the start and at the end of each pass, see
+    /// [`ScrollProfile`].
+    pub fn with_pause(mut self, ticks: u16) -> Self {
+        self.profile.pause_start = ticks;
+        self.profile.pause_end = ticks;
+        self
+    }
+
+    pub fn set_profile(&mut self, profile: ScrollProfile) {
+        self.profile = profile;
+    }
+
+    pub fn profile(&self) -> ScrollProfile {
+        self.profile
+    }
+
+    pub fn mode(&self) -> ScrollMode {
+        self.mode
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.as_ref()
+    }
+
+    /// Replace the text and start over with the next tick.
+    pub fn set_text(&mut self, text: T) {
+        self.text = text;
+        self.reset();
+    }
+
+    /// Frame within the current pass shown by the next tick, 0 when it
+    /// starts a new pass.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Start a new pass with the next tick.
+    pub fn reset(&mut self) {
+        self.step = 0;
+    }
+
+    /// Continue a pass at frame `step`.
+    pub(crate) fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Show the next frame. Returns `true` when this frame completed a pass,
+    /// e.g. the marquee text has just left the surface.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if the font has no glyph for a character nor for the fallback.
+    /// - Any error from [`LedMatrix::flush`].
+    pub fn tick<SPI>(&mut self, matrix: &mut LedMatrix<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        self.tick_with(matrix, |_, _| Ok(()))
+    }
+
+    /// Like [`tick`](Self::tick), calling `overlay` with the
+    /// [`step`](Self::step) and the matrix after the text is drawn and
+    /// before it is flushed, e.g. to keep an icon or a reading on screen
+    /// next to the scrolling text.
+    ///
+    /// # Errors
+    /// Same as [`tick`](Self::tick), plus any error `overlay` returns;
+    /// nothing is flushed then.
+    pub fn tick_with<SPI, O>(&mut self, matrix: &mut LedMatrix<SPI>, mut overlay: O) -> Result<bool>
+    where
+        SPI: SpiDevice,
+        O: FnMut(usize, &mut LedMatrix<SPI>) -> Result<()>,
+    {
+        let text = self.text.as_ref();
+        let text_width = matrix.text_width(text, &self.font)? as i32;
+        let (x, frames) = self.frame(matrix.width() as i32, text_width);
+        matrix.clear();
+        matrix.draw_text(x, self.y, text, &self.font)?;
+        overlay(self.step, matrix)?;
+        matrix.flush()?;
+
+        self.step += 1;
+        let done = self.step >= frames;
+        if done {
+            self.step = 0;
+        }
+        Ok(done)
+    }
+
+    /// x of the frame at the current step and the number of frames in a
+    /// pass.
+    fn frame(&self, width: i32, text_width: i32) -> (i32, usize) {
+        let profile = self.profile;
+        let (pause_start, pause_end) = (profile.pause_start.into(), profile.pause_end.into());
+        let (start, segments) = match self.mode {
+            ScrollMode::Marquee => (
+                width,
+                [
+                    Segment::Move(0),
+                    Segment::Hold(pause_start),
+                    Segment::Move(-text_width),
+                    Segment::Hold(pause_end),
+                ],
+            ),
+            ScrollMode::Bounce => {
+                let end = (width - text_width).min(0);
+                (
+                    0,
+                    [
+                        Segment::Hold(pause_start),
+                        Segment::Move(end),
+                        Segment::Hold(pause_end),
+                        Segment::Move(0),
+                    ],
+                )
+            }
+        };
+
+        let step = usize::from(profile.step.max(1));
+        let mut position = start;
+        let mut x = None;
+        let mut frames = 0;
+        for segment in segments {
+            let (count, end) = match segment {
+                Segment::Move(to) => (((to - position).unsigned_abs() as usize).div_ceil(step), to),
+                Segment::Hold(ticks) => (ticks, position),
+            };
+            if x.is_none() && self.step < frames + count {
+                let frame = self.step - frames;
+                x = Some(match segment {
+                    Segment::Move(to) => {
+                        position
+                            + eased_offset(to - position, frame + 1, count, step, profile.easing)
+                    }
+                    Segment::Hold(_) => position,
+                });
+            }
+            frames += count;
+            position = end;
+        }
+        // Text that fits a bounce without pauses still shows one frame
+        (x.unwrap_or(position), frames.max(1))
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<F, const N: usize> Scroller<F, heapless::String<N>>
+where
+    F: Font,
+{
+    /// Replace the owned text with `args` formatted, e.g. a sensor reading
+    /// with `format_args!("{temp} C")`, and start over with the next tick.
+    ///
+    /// This links in `core::fmt`; numbers can be formatted without it by
+    /// [`number_str`](crate::seven_segment::number_str) when flash is tight.
+    ///
+    /// # Errors
+    /// - [`Error::TextTooLong`] if the text needs more than `N` bytes, in
+    ///   which case it is cut short.
+    pub fn write_text(&mut self, args: core::fmt::Arguments<'_>) -> Result<()> {
+        use core::fmt::Write;
+
+        self.text.clear();
+        self.reset();
+        self.text.write_fmt(args).map_err(|_| Error::TextTooLong)
+    }
+}
+
+/// Distance covered after `frame` of the `frames` ticks needed to move
+/// `distance` columns at `step` columns per tick.
+fn eased_offset(distance: i32, frame: usize, frames: usize, step: usize, easing: Easing) -> i32 {
+    let total = u128::from(distance.unsigned_abs());
+    let offset = match easing {
+        Easing::Linear => (frame as u128 * step as u128).min(total),
+        _ => {
+            const FULL: u128 = u16::MAX as u128;
+            let time = (frame as u128 * FULL / frames.max(1) as u128).min(FULL);
+            let progress = easing.apply(u16::try_from(time).unwrap_or(u16::MAX));
+            // Rounded to the nearest column
+            (total * u128::from(progress) + FULL / 2) / FULL
+        }
+    };
+    // The offset never exceeds `distance`, so it fits once the sign is back
+    let offset = i64::try_from(offset).unwrap_or(i64::MAX);
+    i32::try_from(i64::from(distance.signum()) * offset).unwrap_or(distance)
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Scroll `text` right to left across the whole surface, blocking until
+    /// it has fully left on the left side.
+    ///
+    /// The message enters at the right edge on row 0 and moves one column
+    /// per frame; each frame is flushed and then shown for `column_ms`
+    /// milliseconds using `delay`. The framebuffer is cleared for every
+    /// frame, so the surface ends up blank.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character nor for the fallback;
+    ///   nothing is shown then.
+    /// - Any error from [`flush`](Self::flush).
+    ///
+    /// See [`Scroller`] to scroll without blocking.
+    pub fn scroll_text<F, D>(
+        &mut self,
+        text: &str,
+        font: F,
+        delay: &mut D,
+        column_ms: u32,
+    ) -> Result<()>
+    where
+        F: Font,
+        D: DelayNs,
+    {
+        self.text_width(text, &font)?;
+        let mut scroller = Scroller::new(text, font);
+        loop {
+            let done = scroller.tick(self)?;
+            delay.delay_ms(column_ms);
+            if done {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, matrix::MODULE_SIZE};
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    /// Every glyph is one lit column followed by a blank one.
+    struct Bars;
+
+    impl Font for Bars {
+        fn height(&self) -> usize {
+            MODULE_SIZE
+        }
+
+        fn glyph(&self, _c: char) -> Result<&[u8]> {
+            Ok(&[0xFF, 0x00])
+        }
+    }
+
+    #[test]
+    fn test_scroll_text() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi).assume_initialized())
+                .unwrap()
+                .with_letter_spacing(0);
+            matrix.scroll_text("ab", Bars, &mut delay, 50).unwrap();
+            assert!(matrix.framebuffer().as_bytes().iter().all(|&row| row == 0));
+        }
+
+        // 8 columns to cross the panel plus 4 for the text to leave it
+        assert_eq!(delay.ms, 12 * 50);
+        assert_eq!(spi.transactions(), 12 * MODULE_SIZE);
+        assert_eq!(spi.emulator().digits(0).unwrap(), [0; 8]);
+    }
+
+    #[test]
+    fn test_scroller_tick() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+            .unwrap()
+            .with_letter_spacing(0);
+        matrix.init().unwrap();
+        let mut scroller = Scroller::new("a", Bars).with_row(2);
+
+        // Enters at the right edge on row 2
+        assert_eq!(scroller.tick(&mut matrix), Ok(false));
+        assert_eq!(matrix.column_xy(7, 0), Ok(0b0011_1111));
+        assert_eq!(scroller.step(), 1);
+
+        let mut ticks = 1;
+        while !scroller.tick(&mut matrix).unwrap() {
+            ticks += 1;
+        }
+        assert_eq!(ticks + 1, 8 + 2);
+        assert_eq!(scroller.step(), 0);
+
+        // The next pass starts over
+        scroller.tick(&mut matrix).unwrap();
+        assert_eq!(matrix.column_xy(7, 0), Ok(0b0011_1111));
+        scroller.set_text("ab");
+        assert_eq!(scroller.step(), 0);
+    }
+
+    #[test]
+    fn test_scroller_overlay() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+            .unwrap()
+            .with_letter_spacing(0);
+        matrix.init().unwrap();
+        let mut scroller = Scroller::new("a", Bars);
+
+        let mut steps = [0; 2];
+        for expected in &mut steps {
+            scroller
+                .tick_with(&mut matrix, |step, matrix| {
+                    *expected = step;
+                    matrix.set_pixel_xy(0, 0, true)
+                })
+                .unwrap();
+        }
+        assert_eq!(steps, [0, 1]);
+        // Text at column 6, the overlay pixel kept at column 0
+        assert_eq!(matrix.row(0, 0), Ok(0x82));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_owned_heapless_text() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.init().unwrap();
+        let mut scroller = Scroller::new(heapless::String::<4>::new(), Bars);
+
+        let reading = 21;
+        scroller.write_text(format_args!("{reading}C")).unwrap();
+        assert_eq!(scroller.text(), "21C");
+        scroller.tick(&mut matrix).unwrap();
+        assert_eq!(scroller.step(), 1);
+
+        assert_eq!(
+            scroller.write_text(format_args!("{}C", 12345)),
+            Err(Error::TextTooLong)
+        );
+        assert!(scroller.text().len() <= 4);
+        assert_eq!(scroller.step(), 0);
+    }
+
+    #[test]
+    fn test_bounce() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+            .unwrap()
+            .with_letter_spacing(0);
+        matrix.init().unwrap();
+        // 10 columns wide, 2 more than the panel
+        let mut scroller = Scroller::new("abcde", Bars)
+            .with_mode(ScrollMode::Bounce)
+            .with_pause(1);
+
+        let mut first_columns = [0u8; 6];
+        for (tick, column) in first_columns.iter_mut().enumerate() {
+            let done = scroller.tick(&mut matrix).unwrap();
+            assert_eq!(done, tick == 5);
+            *column = matrix.column_xy(0, 0).unwrap();
+        }
+        // x: 0 (pause), -1, -2, -2 (pause), -1, 0
+        assert_eq!(first_columns, [0xFF, 0x00, 0xFF, 0xFF, 0x00, 0xFF]);
+
+        // Text that fits never moves and completes every tick
+        let mut scroller = Scroller::new("ab", Bars).with_mode(ScrollMode::Bounce);
+        assert_eq!(scroller.tick(&mut matrix), Ok(true));
+        assert_eq!(matrix.column_xy(0, 0), Ok(0xFF));
+    }
+
+    /// x of the leftmost lit column after each tick, `None` when blank.
+    fn positions<const N: usize>(scroller: &mut Scroller<Bars, &str>) -> [Option<i32>; N] {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap().with_letter_spacing(0);
+        matrix.init().unwrap();
+        core::array::from_fn(|_| {
+            scroller.tick(&mut matrix).unwrap();
+            (0..16)
+                .find(|&x| matrix.get_pixel_xy(x, 0).unwrap())
+                .map(|x| x as i32)
+        })
+    }
+
+    #[test]
+    fn test_profile_step_and_pauses() {
+        let profile = ScrollProfile {
+            step: 4,
+            pause_start: 2,
+            pause_end: 1,
+            easing: Easing::Linear,
+        };
+        let mut scroller = Scroller::new("a", Bars).with_profile(profile);
+        // 16 columns in 4 ticks, hold 2 at x 0, leave in 1 tick, 1 blank
+        assert_eq!(
+            positions::<9>(&mut scroller),
+            [
+                Some(12),
+                Some(8),
+                Some(4),
+                Some(0),
+                Some(0),
+                Some(0),
+                None,
+                None,
+                Some(12)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_easing() {
+        let profile = ScrollProfile {
+            easing: Easing::EaseInOut,
+            ..ScrollProfile::CONSTANT
+        };
+        let mut scroller = Scroller::new("a", Bars).with_profile(profile);
+        // Same 16 ticks as linear, slow at both ends
+        let entering = positions::<16>(&mut scroller).map(|x| x.unwrap_or(16));
+        assert_eq!(
+            entering,
+            [16, 16, 15, 14, 13, 12, 10, 8, 6, 5, 3, 2, 1, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_eased_offset_long_text() {
+        let distance = -2_000_000;
+        let frames = 2_000_000;
+        // Progress is resolved to 1/65535 of the move
+        let half = eased_offset(distance, frames / 2, frames, 1, Easing::EaseInOut);
+        assert!((half - distance / 2).abs() <= 31, "{half}");
+        assert_eq!(
+            eased_offset(distance, frames, frames, 1, Easing::EaseInOut),
+            distance
+        );
+        assert_eq!(
+            eased_offset(i32::MIN, 1 << 31, 1 << 31, 1, Easing::EaseInOut),
+            i32::MIN
+        );
+        assert_eq!(
+            eased_offset(i32::MIN, usize::MAX, 1, 255, Easing::Linear),
+            i32::MIN
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{BlitMode, Canvas, LedMatrix};
+use crate::Result;
+
+impl Canvas {
+    /// Draw a straight line from (`x0`, `y0`) to (`x1`, `y1`), both ends
+    /// included. Parts outside the canvas are clipped.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, on: bool) {
+        let bounds = (self.width(), self.height());
+        let _ = line((x0, y0), (x1, y1), bounds, |x, y| {
+            self.blend_pixel(x, y, on, BlitMode::Copy);
+            Ok(())
+        });
+    }
+
+    /// Draw the one pixel wide outline of the `width` x `height` rectangle
+    /// with its top-left corner at (`x`, `y`), clipped to the canvas.
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: usize, height: usize, on: bool) {
+        let bounds = (self.width(), self.height());
+        let _ = rect(x, y, width, height, bounds, |x, y| {
+            self.blend_pixel(x, y, on, BlitMode::Copy);
+            Ok(())
+        });
+    }
+
+    /// Set every pixel of the `width` x `height` rectangle with its top-left
+    /// corner at (`x`, `y`) to `on`, clipped to the canvas.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: usize, height: usize, on: bool) {
+        let (columns, rows) = (clip(x, width, self.width()), clip(y, height, self.height()));
+        for row in rows {
+            for column in columns.clone() {
+                let _ = self.set_pixel(column, row, on);
+            }
+        }
+    }
+
+    /// Draw the outline of a circle of `radius` pixels around (`cx`, `cy`),
+    /// clipped to the canvas. A radius of 0 is a single pixel.
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, radius: usize, on: bool) {
+        self.draw_ellipse(cx, cy, radius, radius, on);
+    }
+
+    /// Set every pixel of a circle of `radius` pixels around (`cx`, `cy`)
+    /// to `on`, clipped to the canvas.
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: usize, on: bool) {
+        self.fill_ellipse(cx, cy, radius, radius, on);
+    }
+
+    /// Draw the outline of an ellipse around (`cx`, `cy`) reaching `rx`
+    /// pixels left and right and `ry` pixels up and down, clipped to the
+    /// canvas.
+    pub fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: usize, ry: usize, on: bool) {
+        let bounds = (self.width(), self.height());
+        let _ = ellipse((cx, cy), (rx, ry), false, bounds, |x, y| {
+            self.blend_pixel(x, y, on, BlitMode::Copy);
+            Ok(())
+        });
+    }
+
+    /// Set every pixel of the ellipse [`draw_ellipse`](Self::draw_ellipse)
+    /// outlines to `on`, clipped to the canvas.
+    pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: usize, ry: usize, on: bool) {
+        let bounds = (self.width(), self.height());
+        let _ = ellipse((cx, cy), (rx, ry), true, bounds, |x, y| {
+            self.blend_pixel(x, y, on, BlitMode::Copy);
+            Ok(())
+        });
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw a straight line between chain-wide (`x0`, `y0`) and
+    /// (`x1`, `y1`), both ends included. Parts outside the surface are
+    /// clipped.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, on: bool) -> Result<()> {
+        let bounds = (self.width(), self.height());
+        line((x0, y0), (x1, y1), bounds, |x, y| self.draw_pixel(x, y, on))
+    }
+
+    /// Draw the one pixel wide outline of the `width` x `height` rectangle
+    /// with its top-left corner at chain-wide (`x`, `y`), clipped to the
+    /// surface.
+    pub fn draw_rect(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        on: bool,
+    ) -> Result<()> {
+        let bounds = (self.width(), self.height());
+        rect(x, y, width, height, bounds, |x, y| {
+            self.draw_pixel(x, y, on)
+        })
+    }
+
+    /// Set every pixel of the `width` x `height` rectangle with its top-left
+    /// corner at chain-wide (`x`, `y`) to `on`, clipped to the surface.
+    pub fn fill_rect(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        on: bool,
+    ) -> Result<()> {
+        let (columns, rows) = (clip(x, width, self.width()), clip(y, height, self.height()));
+        for row in rows {
+            for column in columns.clone() {
+                self.set_pixel_xy(column, row, on)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the outline of a circle of `radius` pixels around chain-wide
+    /// (`cx`, `cy`), clipped to the surface. A radius of 0 is a single pixel.
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, radius: usize, on: bool) -> Result<()> {
+        self.draw_ellipse(cx, cy, radius, radius, on)
+    }
+
+    /// Set every pixel of a circle of `radius` pixels around chain-wide
+    /// (`cx`, `cy`) to `on`, clipped to the surface.
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: usize, on: bool) -> Result<()> {
+        self.fill_ellipse(cx, cy, radius, radius, on)
+    }
+
+    /// Draw the outline of an ellipse around chain-wide (`cx`, `cy`)
+    /// reaching `rx` pixels left and right and `ry` pixels up and down,
+    /// clipped to the surface.
+    pub fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: usize, ry: usize, on: bool) -> Result<()> {
+        let bounds = (self.width(), self.height());
+        ellipse((cx, cy), (rx, ry), false, bounds, |x, y| {
+            self.draw_pixel(x, y, on)
+        })
+    }
+
+    /// Set every pixel of the ellipse [`draw_ellipse`](Self::draw_ellipse)
+    /// outlines to `on`, clipped to the surface.
+    pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: usize, ry: usize, on: bool) -> Result<()> {
+        let bounds = (self.width(), self.height());
+        ellipse((cx, cy), (rx, ry), true, bounds, |x, y| {
+            self.draw_pixel(x, y, on)
+        })
+    }
+}
+
+/// Bresenham's line, calling `plot` for its pixels within `bounds`
+/// (width, height), in order from the first end.
+///
+/// Each pixel is computed from its step along the major axis, so only the
+/// steps inside `bounds` are visited however long the line is.
+fn line(
+    (x0, y0): (i32, i32),
+    (x1, y1): (i32, i32),
+    bounds: (usize, usize),
+    mut plot: impl FnMut(i32, i32) -> Result<()>,
+) -> Result<()> {
+    // Widened so lines spanning all of i32 can't overflow
+    let (x0, y0, x1, y1) = (i64::from(x0), i64::from(y0), i64::from(x1), i64::from(y1));
+    let x_major = (x1 - x0).abs() >= (y1 - y0).abs();
+    let (major0, major1, minor0, minor1, major_len) = if x_major {
+        (x0, x1, y0, y1, bounds.0)
+    } else {
+        (y0, y1, x0, x1, bounds.1)
+    };
+    let (major_span, minor_span) = ((major1 - major0).abs(), (minor1 - minor0).abs());
+    let (major_step, minor_step) = ((major1 - major0).signum(), (minor1 - minor0).signum());
+    let major_len = i64::try_from(major_len).unwrap_or(i64::MAX);
+
+    // Steps whose major coordinate is in 0..major_len
+    let (first, last) = if major_step >= 0 {
+        (-major0, major_len - 1 - major0)
+    } else {
+        (major0 - (major_len - 1), major0)
+    };
+    for step in first.max(0)..=last.min(major_span) {
+        let major = major0 + major_step * step;
+        // Nearest minor coordinate, rounding halves away from the start
+        let offset = (2 * i128::from(step) * i128::from(minor_span) + i128::from(major_span))
+            / i128::from(2 * major_span).max(1);
+        let minor = minor0 + minor_step * i64::try_from(offset).unwrap_or(i64::MAX);
+        let (x, y) = if x_major {
+            (major, minor)
+        } else {
+            (minor, major)
+        };
+        if let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) {
+            plot(x, y)?;
+        }
+    }
+    Ok(())
+}
+
+fn rect(
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    bounds: (usize, usize),
+    mut plot: impl FnMut(i32, i32) -> Result<()>,
+) -> Result<()> {
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    let right = x.saturating_add(i32::try_from(width - 1).unwrap_or(i32::MAX));
+    let bottom = y.saturating_add(i32::try_from(height - 1).unwrap_or(i32::MAX));
+    line((x, y), (right, y), bounds, &mut plot)?;
+    line((x, bottom), (right, bottom), bounds, &mut plot)?;
+    line((x, y), (x, bottom), bounds, &mut plot)?;
+    line((right, y), (right, bottom), bounds, &mut plot)
+}
+
+/// Ellipse with half-axes of `radii` plus half a pixel around `center`,
+/// calling `plot` for its pixels within `bounds` (width, height), or only
+/// those on its edge unless `filled`.
+fn ellipse(
+    center: (i32, i32),
+    (rx, ry): (usize, usize),
+    filled: bool,
+    bounds: (usize, usize),
+    mut plot: impl FnMut(i32, i32) -> Result<()>,
+) -> Result<()> {
+    // Larger radii can't be told apart on an i32 plane, and keep the
+    // arithmetic below within u128
+    const MAX_RADIUS: u128 = 1 << 30;
+    // Doubled axes, so the extra half pixel stays an integer
+    let a = (rx as u128).min(MAX_RADIUS) * 2 + 1;
+    let b = (ry as u128).min(MAX_RADIUS) * 2 + 1;
+    let ry = (b - 1) / 2;
+    // Half-width of the row `dy` rows from the center, if it's inside
+    let half_width = |dy: i64| -> Option<i64> {
+        let dy = u128::from(dy.unsigned_abs());
+        (dy <= ry).then(|| (a * a * (b * b - 4 * dy * dy) / (4 * b * b)).isqrt() as i64)
+    };
+
+    let (cx, cy) = (i64::from(center.0), i64::from(center.1));
+    let (width, height) = (bounds.0 as i64, bounds.1 as i64);
+    let ry = ry as i64;
+    for dy in (-ry).max(-cy)..=ry.min(height - 1 - cy) {
+        let Some(half) = half_width(dy) else {
+            continue;
+        };
+        // Half-width of the row's pixels with no neighbour outside
+        // the ellipse, which the outline skips
+        let inner = if filled {
+            -1
+        } else {
+            let above = half_width(dy - 1).unwrap_or(-1);
+            let below = half_width(dy + 1).unwrap_or(-1);
+            above.min(below).min(half - 1)
+        };
+        let spans = if inner < 0 {
+            [(-half, half), (1, 0)]
+        } else {
+            [(-half, -inner - 1), (inner + 1, half)]
+        };
+        let y = (cy + dy) as i32;
+        for (from, to) in spans {
+            for x in (cx + from).max(0)..=(cx + to).min(width - 1) {
+                plot(x as i32, y)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Part of `len` pixels starting at `start` that lies within `0..max`.
+fn clip(start: i32, len: usize, max: usize) -> core::ops::Range<usize> {
+    let end = i64::from(start).saturating_add(i64::try_from(len).unwrap_or(i64::MAX));
+    let bound = |value: i64| usize::try_from(value.max(0)).unwrap_or(usize::MAX).min(max);
+    bound(i64::from(start))..bound(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_canvas_lines() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        canvas.draw_line(0, 0, 7, 7, true);
+        canvas.draw_line(-4, 6, 20, 6, true);
+        canvas.draw_line(7, 1, 5, 2, true);
+        assert_eq!(
+            canvas.as_bytes(),
+            &[0x80, 0x41, 0x26, 0x10, 0x08, 0x04, 0xFF, 0x01]
+        );
+
+        // Single point, and lines entering from far off the canvas
+        canvas.clear();
+        canvas.draw_line(3, 3, 3, 3, true);
+        canvas.draw_line(0, i32::MIN, 0, i32::MAX, true);
+        canvas.draw_line(i32::MAX, 0, i32::MIN + 1, 0, true);
+        canvas.draw_line(i32::MIN, i32::MIN, i32::MAX, i32::MAX, true);
+        assert_eq!(&canvas.as_bytes()[..4], &[0xFF, 0xC0, 0xA0, 0x90]);
+    }
+
+    #[test]
+    fn test_canvas_rects() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        canvas.draw_rect(1, 1, 4, 3, true);
+        assert_eq!(&canvas.as_bytes()[..5], &[0x00, 0x78, 0x48, 0x78, 0x00]);
+
+        canvas.fill(true);
+        canvas.fill_rect(-2, 6, 5, 10, false);
+        assert_eq!(&canvas.as_bytes()[5..], &[0xFF, 0x1F, 0x1F]);
+        canvas.fill_rect(0, 0, 0, 8, false);
+        canvas.draw_rect(4, 4, 0, 2, false);
+        assert_eq!(canvas.as_bytes()[0], 0xFF);
+    }
+
+    #[test]
+    fn test_matrix_primitives() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.draw_rect(-1, 0, 18, 8, true).unwrap();
+        matrix.fill_rect(6, 3, 4, 2, true).unwrap();
+        matrix.draw_line(i32::MIN, 5, i32::MAX, 5, false).unwrap();
+
+        assert_eq!(matrix.row(0, 0), Ok(0xFF));
+        assert_eq!(matrix.row(0, 3), Ok(0x03));
+        assert_eq!(matrix.row(1, 4), Ok(0xC0));
+        assert_eq!(matrix.row(1, 5), Ok(0x00));
+        assert_eq!(matrix.get_pixel_xy(15, 1), Ok(false));
+    }
+
+    #[test]
+    fn test_canvas_circles() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        canvas.draw_circle(3, 3, 2, true);
+        canvas.draw_circle(7, 7, 0, true);
+        assert_eq!(
+            canvas.as_bytes(),
+            &[0x00, 0x38, 0x44, 0x44, 0x44, 0x38, 0x00, 0x01]
+        );
+
+        canvas.clear();
+        canvas.fill_circle(3, 3, 2, true);
+        canvas.draw_circle(3, 3, 1, false);
+        assert_eq!(
+            canvas.as_bytes(),
+            &[0x00, 0x38, 0x44, 0x54, 0x44, 0x38, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_canvas_ellipses() {
+        let mut canvas = Canvas::for_chain(2).unwrap();
+        canvas.draw_ellipse(7, 3, 6, 2, true);
+        assert_eq!(canvas.get_pixel(1, 3), Ok(true));
+        assert_eq!(canvas.get_pixel(13, 3), Ok(true));
+        assert_eq!(canvas.get_pixel(7, 1), Ok(true));
+        assert_eq!(canvas.get_pixel(7, 5), Ok(true));
+        assert_eq!(canvas.get_pixel(7, 3), Ok(false));
+        assert_eq!(canvas.get_pixel(0, 3), Ok(false));
+
+        // Clipped, even when huge
+        canvas.clear();
+        canvas.fill_ellipse(-3, 8, 4, 3, true);
+        canvas.draw_circle(8, 4, usize::MAX, true);
+        assert_eq!(canvas.get_pixel(0, 7), Ok(true));
+        assert_eq!(canvas.get_pixel(1, 5), Ok(false));
+        assert_eq!(canvas.get_pixel(15, 0), Ok(false));
+    }
+
+    #[test]
+    fn test_matrix_circles() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.fill_ellipse(3, 3, 3, 1, true).unwrap();
+        matrix.draw_circle(3, 3, 3, true).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0x38));
+        assert_eq!(matrix.row(0, 3), Ok(0xFE));
+        assert_eq!(matrix.row(0, 6), Ok(0x38));
+    }
+}
@@ -0,0 +1,158 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{LedMatrix, MODULE_SIZE};
+use crate::{MAX_DISPLAYS, Result, error::Error};
+
+/// Row bytes each device of a chain shows, one 8x8 tile per device, e.g.
+/// what a [`LedMatrix`] would send on its next flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    tiles: [[u8; MODULE_SIZE]; MAX_DISPLAYS],
+    device_count: usize,
+}
+
+/// A row that differs between two [`Snapshot`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowDiff {
+    pub device_index: usize,
+    pub row: usize,
+    pub before: u8,
+    pub after: u8,
+}
+
+impl RowDiff {
+    /// Bits set for the pixels that changed, bit 7 leftmost.
+    pub fn changed(&self) -> u8 {
+        self.before ^ self.after
+    }
+}
+
+impl Snapshot {
+    /// Snapshot of `tiles`, `tiles[0]` being device 0's, e.g. from
+    /// [`Emulator::digits`](crate::host::Emulator::digits).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceCount`] if there are more than [`MAX_DISPLAYS`] tiles.
+    pub fn from_tiles(tiles: &[[u8; MODULE_SIZE]]) -> Result<Self> {
+        let mut snapshot = Self {
+            tiles: [[0; MODULE_SIZE]; MAX_DISPLAYS],
+            device_count: tiles.len(),
+        };
+        snapshot
+            .tiles
+            .get_mut(..tiles.len())
+            .ok_or(Error::InvalidDeviceCount)?
+            .copy_from_slice(tiles);
+        Ok(snapshot)
+    }
+
+    pub fn device_count(&self) -> usize {
+        self.device_count
+    }
+
+    pub fn tiles(&self) -> &[[u8; MODULE_SIZE]] {
+        &self.tiles[..self.device_count]
+    }
+
+    /// Rows that differ from `other`, device by device. Devices only one
+    /// of the snapshots has are compared against blank tiles.
+    pub fn diff<'a>(&'a self, other: &'a Snapshot) -> impl Iterator<Item = RowDiff> + 'a {
+        let device_count = self.device_count.max(other.device_count);
+        (0..device_count).flat_map(move |device_index| {
+            (0..MODULE_SIZE).filter_map(move |row| {
+                let before = self.tiles[device_index][row];
+                let after = other.tiles[device_index][row];
+                (before != after).then_some(RowDiff {
+                    device_index,
+                    row,
+                    before,
+                    after,
+                })
+            })
+        })
+    }
+
+    /// Number of pixels that differ from `other`.
+    pub fn changed_pixels(&self, other: &Snapshot) -> u32 {
+        self.diff(other)
+            .map(|diff| diff.changed().count_ones())
+            .sum()
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// What the chain shows after the next [`flush`](Self::flush), with
+    /// rotations, flips and remapping applied.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if a custom remap returns a position outside the chain.
+    pub fn snapshot(&self) -> Result<Snapshot> {
+        let mut tiles = [[0; MODULE_SIZE]; MAX_DISPLAYS];
+        self.device_tiles(&mut tiles[..self.device_count()])?;
+        Ok(Snapshot {
+            tiles,
+            device_count: self.device_count(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, matrix::Rotation};
+
+    #[test]
+    fn test_snapshot_matches_display() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver)
+            .unwrap()
+            .with_rotation(Rotation::Deg180);
+        matrix.set_pixel_xy(0, 0, true).unwrap();
+        matrix.set_pixel_xy(9, 2, true).unwrap();
+        let expected = matrix.snapshot().unwrap();
+        assert_eq!(expected.tiles()[0][7], 0x01);
+        matrix.flush().unwrap();
+
+        let emulator = spi.emulator();
+        let shown = [emulator.digits(0).unwrap(), emulator.digits(1).unwrap()];
+        assert_eq!(Snapshot::from_tiles(&shown), Ok(expected));
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = Snapshot::from_tiles(&[[0; 8], [0x0F; 8]]).unwrap();
+        let mut tiles = [[0; 8], [0x0F; 8]];
+        tiles[1][3] = 0xFF;
+        let after = Snapshot::from_tiles(&tiles).unwrap();
+
+        let diffs: std::vec::Vec<_> = before.diff(&after).collect();
+        assert_eq!(
+            diffs,
+            [RowDiff {
+                device_index: 1,
+                row: 3,
+                before: 0x0F,
+                after: 0xFF
+            }]
+        );
+        assert_eq!(diffs[0].changed(), 0xF0);
+        assert_eq!(before.changed_pixels(&after), 4);
+        assert_eq!(before.diff(&before).count(), 0);
+
+        // A device missing on one side counts as blank
+        let shorter = Snapshot::from_tiles(&[[0; 8]]).unwrap();
+        assert_eq!(shorter.changed_pixels(&before), 32);
+        assert_eq!(
+            Snapshot::from_tiles(&[[0; 8]; MAX_DISPLAYS + 1]),
+            Err(Error::InvalidDeviceCount)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Canvas, LedMatrix};
+use crate::Result;
+
+/// How a [`Sparkline`] draws each sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SparklineStyle {
+    /// A column filled from the bottom up to the sample
+    #[default]
+    Columns,
+    /// One pixel per sample, joined to the previous sample by a line
+    Line,
+}
+
+/// Chart of a data series for [`LedMatrix::draw_sparkline`], e.g. a
+/// sensor reading over time on an 8x32 panel.
+///
+/// Each sample takes one column, with the last sample in the rightmost
+/// column; older samples that don't fit are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sparkline {
+    pub style: SparklineStyle,
+    /// Values drawn at the bottom and top rows, samples outside being
+    /// clamped. `None` scales to the lowest and highest samples drawn.
+    pub range: Option<(u16, u16)>,
+}
+
+impl Sparkline {
+    /// Call `segment` with the end points of the lines making up the chart
+    /// of `data` on a `width` x `height` surface.
+    fn render(
+        &self,
+        data: &[u16],
+        width: usize,
+        height: usize,
+        mut segment: impl FnMut((i32, i32), (i32, i32)) -> Result<()>,
+    ) -> Result<()> {
+        let data = &data[data.len().saturating_sub(width)..];
+        let (min, max) = self.range.unwrap_or_else(|| {
+            let min = data.iter().copied().min().unwrap_or(0);
+            (min, data.iter().copied().max().unwrap_or(0))
+        });
+        let (min, max) = (min.min(max), min.max(max));
+        let bottom = height.saturating_sub(1) as i32;
+        let row = |value: u16| {
+            let value = u32::from(value.clamp(min, max) - min);
+            let span = u32::from(max - min).max(1);
+            // Rounded to the nearest row
+            let level = (value * bottom as u32 * 2 + span) / (span * 2);
+            bottom - level as i32
+        };
+
+        let left = (width - data.len()) as i32;
+        let mut previous = None;
+        for (x, &value) in (left..).zip(data) {
+            let y = row(value);
+            match self.style {
+                SparklineStyle::Columns => segment((x, y), (x, bottom))?,
+                SparklineStyle::Line => segment(previous.unwrap_or((x, y)), (x, y))?,
+            }
+            previous = Some((x, y));
+        }
+        Ok(())
+    }
+}
+
+impl Canvas {
+    /// Light the pixels of `data` charted per `sparkline` across the whole
+    /// canvas. Other pixels are left as they are.
+    pub fn draw_sparkline(&mut self, data: &[u16], sparkline: Sparkline) {
+        let _ = sparkline.render(data, self.width(), self.height(), |(x0, y0), (x1, y1)| {
+            self.draw_line(x0, y0, x1, y1, true);
+            Ok(())
+        });
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Light the pixels of `data` charted per `sparkline` across the
+    /// chain-wide surface. Other pixels are left as they are, so
+    /// [`clear`](Self::clear) first to redraw a changed series.
+    pub fn draw_sparkline(&mut self, data: &[u16], sparkline: Sparkline) -> Result<()> {
+        let (width, height) = (self.width(), self.height());
+        sparkline.render(data, width, height, |(x0, y0), (x1, y1)| {
+            self.draw_line(x0, y0, x1, y1, true)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_columns_auto_scaled() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        canvas.draw_sparkline(
+            &[500, 100, 240, 170, 380, 310, 450, 520, 590],
+            Sparkline::default(),
+        );
+        // The first sample doesn't fit; 100 is the bottom row, 590 the top
+        let heights: Vec<_> = (0..8)
+            .map(|x| {
+                (0..8)
+                    .filter(|&y| canvas.get_pixel(x, y) == Ok(true))
+                    .count()
+            })
+            .collect();
+        assert_eq!(heights, [1, 3, 2, 5, 4, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_line_with_fixed_range() {
+        let mut canvas = Canvas::for_chain(1).unwrap();
+        let sparkline = Sparkline {
+            style: SparklineStyle::Line,
+            range: Some((0, 7)),
+        };
+        canvas.draw_sparkline(&[0, 7, 9], sparkline);
+        // Right aligned, the last sample clamped to the top
+        assert_eq!(canvas.get_pixel(5, 7), Ok(true));
+        assert_eq!(canvas.get_pixel(6, 0), Ok(true));
+        assert_eq!(canvas.get_pixel(5, 4), Ok(true));
+        assert_eq!(canvas.get_pixel(6, 3), Ok(true));
+        assert_eq!(canvas.get_pixel(7, 0), Ok(true));
+        assert_eq!(canvas.pixels().filter(|&(_, _, on)| on).count(), 9);
+        assert_eq!(canvas.get_pixel(4, 7), Ok(false));
+
+        // A flat series sits on the bottom row
+        canvas.clear();
+        let auto = Sparkline {
+            range: None,
+            ..sparkline
+        };
+        canvas.draw_sparkline(&[3; 4], auto);
+        canvas.draw_sparkline(&[], sparkline);
+        assert_eq!(canvas.as_bytes()[7], 0x0F);
+    }
+
+    #[test]
+    fn test_matrix_sparkline() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        let data: Vec<u16> = (0..40).collect();
+        matrix.draw_sparkline(&data, Sparkline::default()).unwrap();
+        assert_eq!(matrix.get_pixel_xy(0, 7), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(0, 6), Ok(false));
+        assert_eq!(matrix.column_xy(31, 0), Ok(0xFF));
+    }
+}
@@ -0,0 +1,102 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{Animation, BlitMode, LedMatrix, Looping, Sprite};
+use crate::Result;
+
+/// Power-up content for [`LedMatrix::init_with_splash`], e.g. a logo or a
+/// version pattern.
+#[derive(Debug, Clone)]
+pub enum Splash<'a> {
+    /// Image drawn at the top-left of the drawing surface
+    Frame(Sprite<'a>),
+    /// Played once regardless of its looping, each frame shown for its
+    /// duration or the given milliseconds
+    Animation(Animation<'a>, u32),
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// [`init`](Self::init), then show `splash` and keep it for `hold_ms`
+    /// milliseconds before blanking the chain again.
+    ///
+    /// # Errors
+    /// - Any error from [`init`](Self::init) or [`flush`](Self::flush).
+    pub fn init_with_splash<D>(
+        &mut self,
+        splash: Splash<'_>,
+        delay: &mut D,
+        hold_ms: u32,
+    ) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        self.init()?;
+        match splash {
+            Splash::Frame(frame) => {
+                self.blit(&frame, 0, 0, BlitMode::Copy)?;
+                self.flush()?;
+            }
+            Splash::Animation(animation, frame_ms) => {
+                animation
+                    .with_looping(Looping::Once)
+                    .play(self, delay, frame_ms)?;
+            }
+        }
+        delay.delay_ms(hold_ms);
+        self.clear();
+        self.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, matrix::MODULE_SIZE};
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    #[test]
+    fn test_splash_frame() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        let logo = Sprite::new(8, 8, &[0xFF; 8]).unwrap();
+        {
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+            matrix
+                .init_with_splash(Splash::Frame(logo), &mut delay, 1500)
+                .unwrap();
+            assert!(matrix.framebuffer().as_bytes().iter().all(|&row| row == 0));
+        }
+        assert_eq!(delay.ms, 1500);
+        assert_eq!(spi.emulator().digits(0).unwrap(), [0; 8]);
+    }
+
+    #[test]
+    fn test_splash_animation_plays_once() {
+        let frames = [[0x0F; MODULE_SIZE], [0xF0; MODULE_SIZE]];
+        let animation = Animation::new(&frames).with_looping(Looping::Forever);
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut delay = CountingDelay::default();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix
+            .init_with_splash(Splash::Animation(animation, 100), &mut delay, 500)
+            .unwrap();
+        assert_eq!(delay.ms, 2 * 100 + 500);
+    }
+}
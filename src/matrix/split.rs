@@ -0,0 +1,125 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Canvas, FrameMailbox, LedMatrix};
+use crate::Result;
+
+/// Drawing half of a [`LedMatrix::split`]: owns a canvas the size of the
+/// matrix framebuffer but no SPI, so it can live in a task without bus
+/// access.
+pub struct FrameProducer<'a> {
+    canvas: Canvas,
+    mailbox: &'a FrameMailbox,
+}
+
+impl FrameProducer<'_> {
+    pub fn canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+
+    pub fn canvas_mut(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Hand the canvas over to the renderer. The canvas keeps its contents,
+    /// so the next frame can be drawn on top of this one.
+    pub fn publish(&self) {
+        self.mailbox.post(&self.canvas);
+    }
+}
+
+/// Bus half of a [`LedMatrix::split`]: owns the matrix and shows the frames
+/// the [`FrameProducer`] publishes.
+pub struct FrameRenderer<'a, SPI> {
+    matrix: LedMatrix<SPI>,
+    mailbox: &'a FrameMailbox,
+}
+
+impl<SPI> FrameRenderer<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Flush the newest published frame, if there is one. Returns whether
+    /// it flushed.
+    ///
+    /// # Errors
+    /// Any error from [`LedMatrix::flush`]; the frame is dropped then.
+    pub fn render(&mut self) -> Result<bool> {
+        if !self.mailbox.take(self.matrix.framebuffer_mut()) {
+            return Ok(false);
+        }
+        self.matrix.flush()?;
+        Ok(true)
+    }
+
+    /// Whether a frame is waiting to be rendered.
+    pub fn has_frame(&self) -> bool {
+        self.mailbox.has_frame()
+    }
+
+    /// The matrix, e.g. for driver settings. Its framebuffer is overwritten
+    /// by the next rendered frame.
+    pub fn matrix_mut(&mut self) -> &mut LedMatrix<SPI> {
+        &mut self.matrix
+    }
+
+    pub fn into_inner(self) -> LedMatrix<SPI> {
+        self.matrix
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Split into a [`FrameProducer`] for drawing and a [`FrameRenderer`]
+    /// for the bus, connected through `mailbox`. The producer starts with a
+    /// copy of the framebuffer.
+    pub fn split(self, mailbox: &FrameMailbox) -> (FrameProducer<'_>, FrameRenderer<'_, SPI>) {
+        let producer = FrameProducer {
+            canvas: self.framebuffer().clone(),
+            mailbox,
+        };
+        let renderer = FrameRenderer {
+            matrix: self,
+            mailbox,
+        };
+        (producer, renderer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_producer_to_renderer() {
+        let mailbox = FrameMailbox::new();
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let matrix = LedMatrix::new(
+                Max7219::new(&mut spi)
+                    .assume_initialized()
+                    .with_device_count(2)
+                    .unwrap(),
+            );
+            let (mut producer, mut renderer) = matrix.unwrap().split(&mailbox);
+            assert_eq!(producer.canvas().width(), 16);
+            assert_eq!(renderer.render(), Ok(false));
+
+            producer.canvas_mut().set_pixel(0, 0, true).unwrap();
+            producer.publish();
+            producer.canvas_mut().set_pixel(15, 7, true).unwrap();
+            producer.publish();
+            assert!(renderer.has_frame());
+            assert_eq!(renderer.render(), Ok(true));
+            assert_eq!(renderer.render(), Ok(false));
+
+            let matrix = renderer.into_inner();
+            assert_eq!(matrix.framebuffer(), producer.canvas());
+        }
+        assert_eq!(spi.transactions(), 8);
+        assert_eq!(spi.emulator().digits(0).unwrap()[0], 0x80);
+        assert_eq!(spi.emulator().digits(1).unwrap()[7], 0x01);
+    }
+}
@@ -0,0 +1,334 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::LedMatrix;
+use crate::{Result, error::Error};
+
+/// How [`LedMatrix::blit`] combines sprite pixels with the framebuffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlitMode {
+    /// Replace the covered area, off pixels included
+    #[default]
+    Copy,
+    /// Turn on the sprite's on pixels, leave the rest
+    Or,
+    /// Toggle pixels under the sprite's on pixels
+    Xor,
+    /// Turn off pixels under the sprite's off pixels
+    And,
+}
+
+impl BlitMode {
+    /// Pixel resulting from drawing `on` over `current`.
+    pub(super) fn blend(self, current: bool, on: bool) -> bool {
+        match self {
+            Self::Copy => on,
+            Self::Or => current || on,
+            Self::Xor => current != on,
+            Self::And => current && on,
+        }
+    }
+}
+
+/// A 1-bit image of any size, borrowed from a byte slice.
+///
+/// Pixels are row-major with the most significant bit leftmost, like
+/// [`Canvas`](super::Canvas); each row starts on a new byte, so a 5 pixel
+/// wide sprite uses one byte per row with the low 3 bits unused. This is
+/// the horizontal 1bpp layout most bitmap converters export, so their
+/// output can be embedded with `include_bytes!` and passed to
+/// [`new`](Self::new), or use [`from_pbm`](Self::from_pbm) for a binary
+/// PBM file that carries its own size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sprite<'a> {
+    width: usize,
+    height: usize,
+    data: &'a [u8],
+}
+
+impl<'a> Sprite<'a> {
+    /// Wrap `data` as a `width` x `height` sprite.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if a side is zero or `data` is not
+    ///   exactly `height` rows of `width.div_ceil(8)` bytes.
+    pub const fn new(width: usize, height: usize, data: &'a [u8]) -> Result<Self> {
+        if width == 0 || height == 0 || data.len() != width.div_ceil(8) * height {
+            return Err(Error::InvalidCanvasSize);
+        }
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Wrap the pixels of a binary PBM (`P4`) image, e.g. from
+    /// `include_bytes!("logo.pbm")`. Black (1) pixels are lit.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidBitmap`] if `pbm` is not a well-formed binary PBM.
+    /// - [`Error::InvalidCanvasSize`] if the image is empty.
+    pub fn from_pbm(pbm: &'a [u8]) -> Result<Self> {
+        let rest = pbm.strip_prefix(b"P4").ok_or(Error::InvalidBitmap)?;
+        let (width, rest) = pbm_number(rest)?;
+        let (height, rest) = pbm_number(rest)?;
+        // Exactly one whitespace byte separates the header from the pixels
+        let (&separator, data) = rest.split_first().ok_or(Error::InvalidBitmap)?;
+        if !separator.is_ascii_whitespace() {
+            return Err(Error::InvalidBitmap);
+        }
+        let data = width
+            .div_ceil(8)
+            .checked_mul(height)
+            .and_then(|len| data.get(..len))
+            .ok_or(Error::InvalidBitmap)?;
+        Self::new(width, height, data)
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Whether pixel (`x`, `y`) is on; `false` outside the sprite.
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < self.width
+            && y < self.height
+            && self.data[y * self.width.div_ceil(8) + x / 8] & (0x80 >> (x % 8)) != 0
+    }
+}
+
+/// Parse a decimal header field of a PBM file, skipping leading whitespace
+/// and `#` comments.
+fn pbm_number(mut header: &[u8]) -> Result<(usize, &[u8])> {
+    loop {
+        match header.first() {
+            Some(byte) if byte.is_ascii_whitespace() => header = &header[1..],
+            Some(b'#') => {
+                let end = header
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .ok_or(Error::InvalidBitmap)?;
+                header = &header[end..];
+            }
+            _ => break,
+        }
+    }
+    let digits = header
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .unwrap_or(header.len());
+    if digits == 0 {
+        return Err(Error::InvalidBitmap);
+    }
+    let mut value: usize = 0;
+    for &digit in &header[..digits] {
+        value = value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(usize::from(digit - b'0')))
+            .ok_or(Error::InvalidBitmap)?;
+    }
+    Ok((value, &header[digits..]))
+}
+
+/// Build a const [`Sprite`] from ASCII art, one string literal per row:
+/// `#` is an on pixel, `.` an off pixel.
+///
+/// ```
+/// use max7219_driver_project::{matrix::Sprite, sprite};
+///
+/// const HEART: Sprite = sprite![
+///     ".#.#.",
+///     "#####",
+///     ".###.",
+///     "..#..",
+/// ];
+/// assert_eq!((HEART.width(), HEART.height()), (5, 4));
+/// ```
+///
+/// Rows of different lengths or other characters fail to compile.
+#[macro_export]
+macro_rules! sprite {
+    ($($row:literal),+ $(,)?) => {{
+        const ROWS: &[&str] = &[$($row),+];
+        const WIDTH: usize = $crate::matrix::__sprite_width(ROWS);
+        const DATA: [u8; WIDTH.div_ceil(8) * ROWS.len()] = $crate::matrix::__sprite_bytes(ROWS);
+        match $crate::matrix::Sprite::new(WIDTH, ROWS.len(), &DATA) {
+            Ok(sprite) => sprite,
+            Err(_) => panic!("sprite rows must not be empty"),
+        }
+    }};
+}
+
+/// Width shared by every row of a [`sprite!`] literal.
+#[doc(hidden)]
+pub const fn __sprite_width(rows: &[&str]) -> usize {
+    let width = rows[0].len();
+    let mut row = 1;
+    while row < rows.len() {
+        if rows[row].len() != width {
+            panic!("sprite rows must have the same length");
+        }
+        row += 1;
+    }
+    width
+}
+
+/// Packed rows of a [`sprite!`] literal, see [`Sprite`] for the layout.
+#[doc(hidden)]
+pub const fn __sprite_bytes<const N: usize>(rows: &[&str]) -> [u8; N] {
+    let stride = N / rows.len();
+    let mut data = [0; N];
+    let mut y = 0;
+    while y < rows.len() {
+        let row = rows[y].as_bytes();
+        let mut x = 0;
+        while x < row.len() {
+            match row[x] {
+                b'#' => data[y * stride + x / 8] |= 0x80 >> (x % 8),
+                b'.' => {}
+                _ => panic!("sprite pixels must be '#' or '.'"),
+            }
+            x += 1;
+        }
+        y += 1;
+    }
+    data
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw `sprite` with its top-left corner at chain-wide (`x`, `y`),
+    /// combining pixels per `mode`.
+    ///
+    /// Parts outside the surface are clipped, so sprites can move in from
+    /// any edge.
+    pub fn blit(&mut self, sprite: &Sprite, x: i32, y: i32, mode: BlitMode) -> Result<()> {
+        for (row, sprite_y) in (y..).zip(0..sprite.height()) {
+            for (column, sprite_x) in (x..).zip(0..sprite.width()) {
+                self.blend_pixel(column, row, sprite.pixel(sprite_x, sprite_y), mode)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Combine one image pixel into the framebuffer at signed chain-wide
+    /// coordinates, ignoring pixels off the surface.
+    pub(super) fn blend_pixel(&mut self, x: i32, y: i32, on: bool, mode: BlitMode) -> Result<()> {
+        let (Ok(px), Ok(py)) = (usize::try_from(x), usize::try_from(y)) else {
+            return Ok(());
+        };
+        if px >= self.width() || py >= self.height() {
+            return Ok(());
+        }
+        let current = self.get_pixel_xy(px, py)?;
+        self.set_pixel_xy(px, py, mode.blend(current, on))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    // 3x2 sprite: #.#
+    //             .#.
+    const CHECKER: [u8; 2] = [0b1010_0000, 0b0100_0000];
+
+    #[test]
+    fn test_new_invalid_sizes() {
+        assert!(Sprite::new(3, 2, &CHECKER).is_ok());
+        assert_eq!(Sprite::new(0, 2, &CHECKER), Err(Error::InvalidCanvasSize));
+        assert_eq!(Sprite::new(3, 3, &CHECKER), Err(Error::InvalidCanvasSize));
+        // Two bytes per row for 9 pixels
+        assert!(Sprite::new(9, 1, &CHECKER).is_ok());
+        assert_eq!(Sprite::new(9, 2, &CHECKER), Err(Error::InvalidCanvasSize));
+    }
+
+    #[test]
+    fn test_sprite_pixels() {
+        let sprite = Sprite::new(3, 2, &CHECKER).unwrap();
+        assert!(sprite.pixel(0, 0));
+        assert!(!sprite.pixel(1, 0));
+        assert!(sprite.pixel(1, 1));
+        assert!(!sprite.pixel(3, 0));
+    }
+
+    #[test]
+    fn test_from_pbm() {
+        let pbm = b"P4\n# checker\n3 2\n\xA0\x40";
+        assert_eq!(
+            Sprite::from_pbm(pbm),
+            Ok(Sprite::new(3, 2, &CHECKER).unwrap())
+        );
+        // Trailing bytes after the pixel data are ignored
+        assert!(Sprite::from_pbm(b"P4 3 2 \xA0\x40\n").is_ok());
+
+        assert_eq!(Sprite::from_pbm(b"P1 3 2 "), Err(Error::InvalidBitmap));
+        assert_eq!(Sprite::from_pbm(b"P4 3 2 \xA0"), Err(Error::InvalidBitmap));
+        assert_eq!(Sprite::from_pbm(b"P4 3"), Err(Error::InvalidBitmap));
+        assert_eq!(Sprite::from_pbm(b"P4 0 0 "), Err(Error::InvalidCanvasSize));
+    }
+
+    #[test]
+    fn test_sprite_macro() {
+        const WIDE: Sprite = sprite!["#........#", ".########."];
+        assert_eq!((WIDE.width(), WIDE.height()), (10, 2));
+        assert_eq!(WIDE.as_bytes(), &[0x80, 0x40, 0x7F, 0x80]);
+
+        let checker = sprite!["#.#", ".#."];
+        assert_eq!(checker, Sprite::new(3, 2, &CHECKER).unwrap());
+    }
+
+    #[test]
+    fn test_blit_modes() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        let sprite = Sprite::new(3, 2, &CHECKER).unwrap();
+
+        matrix.set_row(0, 0, 0b1100_0000).unwrap();
+        matrix.set_row(0, 1, 0b1100_0000).unwrap();
+        matrix.blit(&sprite, 0, 0, BlitMode::Or).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0b1110_0000));
+        assert_eq!(matrix.row(0, 1), Ok(0b1100_0000));
+
+        matrix.blit(&sprite, 0, 0, BlitMode::Xor).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0b0100_0000));
+        assert_eq!(matrix.row(0, 1), Ok(0b1000_0000));
+
+        matrix.fill(true);
+        matrix.blit(&sprite, 0, 0, BlitMode::And).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0b1011_1111));
+        assert_eq!(matrix.row(0, 1), Ok(0b0101_1111));
+
+        matrix.fill(true);
+        matrix.blit(&sprite, 5, 0, BlitMode::Copy).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0b1111_1101));
+        assert_eq!(matrix.row(0, 2), Ok(0xFF));
+    }
+
+    #[test]
+    fn test_blit_clips() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        let sprite = Sprite::new(3, 2, &CHECKER).unwrap();
+
+        matrix.blit(&sprite, -2, -1, BlitMode::Copy).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0b0000_0000));
+        matrix.blit(&sprite, -2, 0, BlitMode::Copy).unwrap();
+        assert_eq!(matrix.row(0, 0), Ok(0b1000_0000));
+        matrix.blit(&sprite, 15, 7, BlitMode::Copy).unwrap();
+        assert_eq!(matrix.row(1, 7), Ok(0b0000_0001));
+        matrix.blit(&sprite, 100, 100, BlitMode::Copy).unwrap();
+    }
+}
@@ -0,0 +1,21 @@
+use core::ops::Deref;
+
+/// Items borrowed from the caller or, with the `alloc` feature, owned.
+#[derive(Debug, Clone)]
+pub(crate) enum Storage<'a, T> {
+    Borrowed(&'a [T]),
+    #[cfg(feature = "alloc")]
+    Owned(alloc::vec::Vec<T>),
+}
+
+impl<T> Deref for Storage<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Borrowed(items) => items,
+            #[cfg(feature = "alloc")]
+            Self::Owned(items) => items,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{LedMatrix, MODULE_SIZE};
+use crate::Result;
+
+/// What a [`Strobe`] shows between the flashes of content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrobeStyle {
+    /// All pixels off
+    #[default]
+    Blank,
+    /// The content with every pixel toggled
+    Invert,
+}
+
+/// Alert flashing for [`LedMatrix::strobe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strobe {
+    /// Number of flashes
+    pub count: u16,
+    pub style: StrobeStyle,
+    /// Milliseconds the content is shown per flash
+    pub on_ms: u32,
+    /// Milliseconds the blank or inverted content is shown per flash
+    pub off_ms: u32,
+}
+
+impl Default for Strobe {
+    /// Three blank flashes at 200 ms on, 200 ms off.
+    fn default() -> Self {
+        Self {
+            count: 3,
+            style: StrobeStyle::Blank,
+            on_ms: 200,
+            off_ms: 200,
+        }
+    }
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Flash one device, or the whole chain for `None`, blocking until
+    /// done, e.g. for alarms and notifications.
+    ///
+    /// Each flash shows the blank or inverted content for `off_ms` then the
+    /// framebuffer content for `on_ms`, using `delay`. The framebuffer is
+    /// left as it was.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDeviceIndex`](crate::error::Error::InvalidDeviceIndex)
+    ///   if the device is not on the chain.
+    /// - Any error from [`flush`](Self::flush).
+    pub fn strobe<D>(
+        &mut self,
+        device_index: Option<usize>,
+        strobe: Strobe,
+        delay: &mut D,
+    ) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        let content = self.framebuffer().clone();
+        if let Some(device_index) = device_index {
+            // Fail before anything is shown
+            self.row(device_index, 0)?;
+        }
+        for _ in 0..strobe.count {
+            match device_index {
+                Some(device_index) => {
+                    for row in 0..MODULE_SIZE {
+                        let bits = self.row(device_index, row)?;
+                        self.set_row(device_index, row, alternate(bits, strobe.style))?;
+                    }
+                }
+                None => {
+                    for bits in self.framebuffer_mut().as_bytes_mut() {
+                        *bits = alternate(*bits, strobe.style);
+                    }
+                }
+            }
+            self.flush()?;
+            delay.delay_ms(strobe.off_ms);
+
+            *self.framebuffer_mut() = content.clone();
+            self.flush()?;
+            delay.delay_ms(strobe.on_ms);
+        }
+        Ok(())
+    }
+}
+
+fn alternate(bits: u8, style: StrobeStyle) -> u8 {
+    match style {
+        StrobeStyle::Blank => 0,
+        StrobeStyle::Invert => !bits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, error::Error, host::EmulatedSpi};
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    #[test]
+    fn test_strobe_chain() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut delay = CountingDelay::default();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.set_row(0, 0, 0xF0).unwrap();
+            let strobe = Strobe {
+                count: 2,
+                on_ms: 100,
+                off_ms: 50,
+                ..Strobe::default()
+            };
+            matrix.strobe(None, strobe, &mut delay).unwrap();
+            assert_eq!(matrix.row(0, 0), Ok(0xF0));
+        }
+        assert_eq!(delay.ms, 2 * (100 + 50));
+        // Two flushes per flash, ending on the content
+        assert_eq!(spi.transactions(), 2 * 2 * 8);
+        assert_eq!(spi.emulator().digits(0).unwrap()[0], 0xF0);
+    }
+
+    #[test]
+    fn test_strobe_device_inverted() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut delay = CountingDelay::default();
+        let driver = Max7219::new(&mut spi)
+            .assume_initialized()
+            .with_device_count(2)
+            .unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.set_row(1, 0, 0xF0).unwrap();
+        let strobe = Strobe {
+            count: 1,
+            style: StrobeStyle::Invert,
+            ..Strobe::default()
+        };
+        matrix.strobe(Some(1), strobe, &mut delay).unwrap();
+        assert_eq!(matrix.row(1, 0), Ok(0xF0));
+        assert_eq!(
+            matrix.strobe(Some(2), strobe, &mut delay),
+            Err(Error::InvalidDeviceIndex {
+                index: 2,
+                device_count: 2
+            })
+        );
+    }
+}
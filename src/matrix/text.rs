@@ -0,0 +1,467 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Font, LedMatrix, MODULE_SIZE};
+use crate::Result;
+
+/// Horizontal placement for [`LedMatrix::draw_text_aligned`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Draw `c` in `font`, its top-left corner at chain-wide (`x`, `y`).
+    ///
+    /// The glyph and the [letter spacing](Self::set_letter_spacing) after it
+    /// are written in full, so text overwrites what was there. Coordinates
+    /// may be negative or past the edge; pixels outside the surface are
+    /// clipped, which lets text scroll in and out. Returns `x` of the next
+    /// character.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for `c` nor for the
+    ///   [fallback character](Self::set_fallback_char).
+    pub fn draw_char<F: Font>(&mut self, x: i32, y: i32, c: char, font: F) -> Result<i32> {
+        self.draw_glyph(x, y, c, &font, false)
+    }
+
+    /// Draw `text` dark on a lit background, i.e. in inverse video, e.g. to
+    /// highlight the active field or an alarm. Otherwise like
+    /// [`draw_text`](Self::draw_text).
+    ///
+    /// The background is the text's character cells plus a margin of the
+    /// [letter spacing](Self::set_letter_spacing) before the first
+    /// character, matching the gap after the last one.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character nor for the fallback;
+    ///   characters before it have already been drawn.
+    pub fn draw_text_inverted<F: Font>(
+        &mut self,
+        x: i32,
+        y: i32,
+        text: &str,
+        font: F,
+    ) -> Result<i32> {
+        let margin = i32::try_from(self.letter_spacing()).unwrap_or(i32::MAX);
+        for column in x.saturating_sub(margin)..x {
+            for (row, _) in (y..=i32::MAX).zip(0..font.height().min(MODULE_SIZE)) {
+                self.draw_pixel(column, row, true)?;
+            }
+        }
+        text.chars()
+            .try_fold(x, |x, c| self.draw_glyph(x, y, c, &font, true))
+    }
+
+    /// Draw the cell of `c`, with lit and dark pixels swapped if `inverted`.
+    fn draw_glyph<F: Font>(
+        &mut self,
+        x: i32,
+        y: i32,
+        c: char,
+        font: &F,
+        inverted: bool,
+    ) -> Result<i32> {
+        let glyph = self.glyph(font, c)?;
+        let advance = glyph.len().saturating_add(self.letter_spacing());
+        for (column, offset) in (x..=i32::MAX).zip(0..advance) {
+            // Columns past the glyph are the gap to the next character
+            let bits = glyph.get(offset).copied().unwrap_or(0);
+            for (row, bit) in (y..=i32::MAX).zip(0..font.height().min(MODULE_SIZE)) {
+                self.draw_pixel(column, row, (bits & (0x80 >> bit) != 0) != inverted)?;
+            }
+        }
+        Ok(advanced(x, advance))
+    }
+
+    /// Draw `text` left to right starting at chain-wide (`x`, `y`), see
+    /// [`draw_char`](Self::draw_char). Returns `x` of the character that
+    /// would follow.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character nor for the fallback;
+    ///   characters before it have already been drawn.
+    pub fn draw_text<F: Font>(&mut self, x: i32, y: i32, text: &str, font: F) -> Result<i32> {
+        text.chars()
+            .try_fold(x, |x, c| self.draw_char(x, y, c, &font))
+    }
+
+    /// Draw `c` turned 90° clockwise, its cell's top-left corner at
+    /// chain-wide (`x`, `y`).
+    ///
+    /// The glyph's top faces right and characters advance downward, so
+    /// text reads top to bottom along a vertically mounted chain. The cell
+    /// is [`Font::height`] wide and the glyph width plus the letter spacing
+    /// tall; otherwise behaves like [`draw_char`](Self::draw_char). Returns
+    /// `y` of the next character.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for `c` nor for the fallback character.
+    pub fn draw_char_vertical<F: Font>(&mut self, x: i32, y: i32, c: char, font: F) -> Result<i32> {
+        let glyph = self.glyph(&font, c)?;
+        let height = font.height().min(MODULE_SIZE);
+        let advance = glyph.len().saturating_add(self.letter_spacing());
+        for (row, offset) in (y..=i32::MAX).zip(0..advance) {
+            let bits = glyph.get(offset).copied().unwrap_or(0);
+            // Glyph row 0 ends up in the rightmost column of the cell
+            for (column, bit) in (x..=i32::MAX).zip((0..height).rev()) {
+                self.draw_pixel(column, row, bits & (0x80 >> bit) != 0)?;
+            }
+        }
+        Ok(advanced(y, advance))
+    }
+
+    /// Draw `text` top to bottom starting at chain-wide (`x`, `y`), see
+    /// [`draw_char_vertical`](Self::draw_char_vertical). Returns `y` of the
+    /// character that would follow.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character nor for the fallback;
+    ///   characters before it have already been drawn.
+    pub fn draw_text_vertical<F: Font>(
+        &mut self,
+        x: i32,
+        y: i32,
+        text: &str,
+        font: F,
+    ) -> Result<i32> {
+        text.chars()
+            .try_fold(y, |y, c| self.draw_char_vertical(x, y, c, &font))
+    }
+
+    /// Draw `text` on row `y`, placed across the full [`width`](Self::width)
+    /// by `alignment`. Text wider than the surface is clipped on the right
+    /// (left aligned), on both sides (centered) or on the left (right
+    /// aligned). Returns `x` the text starts at.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character nor for the fallback;
+    ///   nothing is drawn then.
+    pub fn draw_text_aligned<F: Font>(
+        &mut self,
+        text: &str,
+        alignment: Alignment,
+        y: i32,
+        font: F,
+    ) -> Result<i32> {
+        let text_width = i32::try_from(self.text_width(text, &font)?).unwrap_or(i32::MAX);
+        let free = i32::try_from(self.width())
+            .unwrap_or(i32::MAX)
+            .saturating_sub(text_width);
+        let x = match alignment {
+            Alignment::Left => 0,
+            Alignment::Center => free.div_euclid(2),
+            Alignment::Right => free,
+        };
+        self.draw_text(x, y, text, font)?;
+        Ok(x)
+    }
+
+    /// Width in pixels of `text` drawn in `font`, without the spacing after
+    /// the last character. Use it to center text or to know when a scrolling
+    /// message has fully left the surface.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`](crate::error::Error::UnsupportedChar)
+    ///   if `font` has no glyph for a character nor for the fallback.
+    pub fn text_width<F: Font>(&self, text: &str, font: F) -> Result<usize> {
+        let mut width = 0;
+        for c in text.chars() {
+            width = self
+                .glyph(&font, c)?
+                .len()
+                .saturating_add(self.letter_spacing())
+                .saturating_add(width);
+        }
+        Ok(width.saturating_sub(self.letter_spacing()))
+    }
+
+    /// Glyph of `c` in `font`, or of the fallback character if it has none.
+    pub(super) fn glyph<'f, F: Font>(&self, font: &'f F, c: char) -> Result<&'f [u8]> {
+        match (font.glyph(c), self.fallback_char()) {
+            (Err(_), Some(fallback)) => font.glyph(fallback),
+            (glyph, _) => glyph,
+        }
+    }
+}
+
+/// `pos` moved on by `advance` pixels, saturating at [`i32::MAX`].
+pub(super) fn advanced(pos: i32, advance: usize) -> i32 {
+    pos.saturating_add_unsigned(u32::try_from(advance).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "font-8x8", feature = "font-5x7"))]
+    use crate::matrix::BuiltinFont;
+    use crate::{driver::Max7219, error::Error, host::EmulatedSpi};
+
+    #[test]
+    #[cfg(feature = "font-8x8")]
+    fn test_draw_char() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.fill(true);
+        assert_eq!(matrix.draw_char(0, 0, 'I', BuiltinFont::Large), Ok(8));
+
+        let rows: [u8; 8] = core::array::from_fn(|row| matrix.row(0, row).unwrap());
+        assert_eq!(rows, [0x78, 0x30, 0x30, 0x30, 0x30, 0x30, 0x78, 0x00]);
+        assert_eq!(
+            matrix.draw_char(0, 0, 'é', BuiltinFont::Large),
+            Err(Error::UnsupportedChar)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-8x8")]
+    fn test_draw_text_spans_modules() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        assert_eq!(matrix.draw_text(4, 0, "Hi", BuiltinFont::Large), Ok(20));
+
+        // 'H' starts half way through module 0 and ends in module 1
+        assert_eq!(matrix.row(0, 3), Ok(0x0F));
+        assert_eq!(matrix.row(1, 3), Ok(0xC3));
+        // Top row holds the right leg of 'H' and the dot of 'i'
+        assert_eq!(matrix.row(1, 0), Ok(0xC3));
+        assert_eq!(matrix.row(2, 0), Ok(0x00));
+    }
+
+    #[test]
+    #[cfg(feature = "font-8x8")]
+    fn test_draw_text_clips() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+
+        // Only the right half and upper rows of 'H' are visible
+        assert_eq!(matrix.draw_text(-4, 2, "H", BuiltinFont::Large), Ok(4));
+        assert_eq!(matrix.row(0, 5), Ok(0xC0));
+        assert_eq!(matrix.row(0, 7), Ok(0xC0));
+        assert_eq!(matrix.row(0, 1), Ok(0x00));
+        assert_eq!(
+            matrix.draw_text(-100, -100, "far away", BuiltinFont::Large),
+            Ok(-36)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-5x7")]
+    fn test_draw_text_compact() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        matrix.fill(true);
+        assert_eq!(
+            matrix.draw_text(0, 0, "12:34", BuiltinFont::Compact),
+            Ok(30)
+        );
+
+        // Five characters fit on 8x32; cells are 7 rows, so row 7 stays lit
+        assert_eq!(matrix.column_xy(0, 0), Ok(0b0000_0001));
+        assert_eq!(matrix.column_xy(2, 0), Ok(0b1111_1111));
+        assert_eq!(matrix.column_xy(5, 0), Ok(0b0000_0001));
+        assert_eq!(matrix.column_xy(30, 0), Ok(0xFF));
+    }
+
+    #[test]
+    #[cfg(feature = "font-5x7")]
+    fn test_proportional_spacing() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        // 'i' is 3 columns, 'l' 3, 'W' 5, plus one column after each
+        assert_eq!(matrix.text_width("ilW", BuiltinFont::Proportional), Ok(13));
+        assert_eq!(
+            matrix.draw_text(0, 0, "ilW", BuiltinFont::Proportional),
+            Ok(14)
+        );
+
+        matrix.set_letter_spacing(3);
+        assert_eq!(matrix.text_width("ilW", BuiltinFont::Proportional), Ok(17));
+        assert_eq!(matrix.text_width("", BuiltinFont::Proportional), Ok(0));
+        matrix.clear();
+        assert_eq!(
+            matrix.draw_text(0, 0, "!!", BuiltinFont::Proportional),
+            Ok(8)
+        );
+        assert_eq!(matrix.row(0, 0), Ok(0b1000_1000));
+    }
+
+    /// Two-row font with a single glyph, as a user would supply.
+    struct Arrows;
+
+    impl Font for Arrows {
+        fn height(&self) -> usize {
+            2
+        }
+
+        fn glyph(&self, c: char) -> Result<&[u8]> {
+            match c {
+                '>' => Ok(&[0x80, 0x40]),
+                _ => Err(Error::UnsupportedChar),
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_font() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+            .unwrap()
+            .with_letter_spacing(0);
+        matrix.fill(true);
+
+        assert_eq!(matrix.text_width(">>>", Arrows), Ok(6));
+        assert_eq!(matrix.draw_text(1, 3, ">>", &Arrows), Ok(5));
+        assert_eq!(matrix.row(0, 3), Ok(0b1101_0111));
+        assert_eq!(matrix.row(0, 4), Ok(0b1010_1111));
+        // Rows below the font height are untouched
+        assert_eq!(matrix.row(0, 5), Ok(0xFF));
+        assert_eq!(
+            matrix.draw_char(0, 0, 'x', Arrows),
+            Err(Error::UnsupportedChar)
+        );
+    }
+
+    #[test]
+    fn test_draw_text_saturates_at_coordinate_limit() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+            .unwrap()
+            .with_letter_spacing(usize::MAX);
+        let edge = i32::MAX - 1;
+        assert_eq!(
+            matrix.draw_text(edge, i32::MAX, ">>", &Arrows),
+            Ok(i32::MAX)
+        );
+        assert_eq!(
+            matrix.draw_text_vertical(i32::MAX, edge, ">>", &Arrows),
+            Ok(i32::MAX)
+        );
+
+        matrix.set_letter_spacing(1);
+        assert_eq!(
+            matrix.draw_text_inverted(edge, i32::MAX, ">>", &Arrows),
+            Ok(i32::MAX)
+        );
+        assert_eq!(matrix.text_width(">>", &Arrows), Ok(5));
+    }
+
+    #[test]
+    fn test_draw_text_inverted() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        assert_eq!(matrix.draw_text_inverted(1, 3, ">>", &Arrows), Ok(7));
+        // Lit margin, glyph pixels dark, lit spacing after each character
+        assert_eq!(matrix.row(0, 3), Ok(0b1011_0110));
+        assert_eq!(matrix.row(0, 4), Ok(0b1101_1010));
+        assert_eq!(matrix.row(0, 5), Ok(0x00));
+
+        // Clipped at the left edge
+        matrix.clear();
+        assert_eq!(matrix.draw_text_inverted(0, 0, ">", &Arrows), Ok(3));
+        assert_eq!(matrix.row(0, 0), Ok(0b0110_0000));
+        assert_eq!(
+            matrix.draw_text_inverted(0, 0, "x", Arrows),
+            Err(Error::UnsupportedChar)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-5x7")]
+    fn test_symbols_and_fallback() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        assert_eq!(matrix.text_width("21°C", BuiltinFont::Compact), Ok(23));
+        assert_eq!(
+            BuiltinFont::Compact.glyph('μ'),
+            BuiltinFont::Compact.glyph('µ')
+        );
+        assert_eq!(
+            matrix.draw_text(0, 0, "€5", BuiltinFont::Compact),
+            Err(Error::UnsupportedChar)
+        );
+
+        matrix.set_fallback_char(Some('?'));
+        assert_eq!(matrix.draw_text(0, 0, "€5", BuiltinFont::Compact), Ok(12));
+        assert_eq!(
+            matrix.column_xy(1, 0),
+            BuiltinFont::Compact.glyph('?').map(|g| g[1])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-5x7")]
+    fn test_draw_text_aligned() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        let font = BuiltinFont::Compact;
+
+        // "12" is 11 pixels wide on a 32 pixel surface
+        assert_eq!(
+            matrix.draw_text_aligned("12", Alignment::Left, 0, font),
+            Ok(0)
+        );
+        assert_eq!(
+            matrix.draw_text_aligned("12", Alignment::Center, 0, font),
+            Ok(10)
+        );
+        assert_eq!(
+            matrix.draw_text_aligned("12", Alignment::Right, 0, font),
+            Ok(21)
+        );
+        assert_eq!(
+            matrix.column_xy(31, 0),
+            font.glyph('2').map(|glyph| glyph[4])
+        );
+
+        // Too wide: centered text overhangs both edges
+        assert_eq!(
+            matrix.draw_text_aligned("1234567", Alignment::Center, 0, font),
+            Ok(-5)
+        );
+        assert_eq!(
+            matrix.draw_text_aligned("€", Alignment::Center, 0, font),
+            Err(Error::UnsupportedChar)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "font-5x7")]
+    fn test_draw_text_vertical() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        // 8x32 chain standing upright
+        let mut matrix = LedMatrix::with_geometry(driver, 1, 4).unwrap();
+        let font = BuiltinFont::Compact;
+
+        assert_eq!(matrix.draw_text_vertical(0, 0, "AB", font), Ok(12));
+        // Glyph columns become rows, the glyph's top row the right column
+        let a = font.glyph('A').unwrap();
+        for (row, &bits) in a.iter().enumerate() {
+            let expected = bits.reverse_bits() << 1;
+            assert_eq!(matrix.row(0, row), Ok(expected));
+        }
+        assert_eq!(matrix.row(0, 5), Ok(0x00));
+        assert_eq!(matrix.get_pixel_xy(6, 6), Ok(true));
+        assert_eq!(matrix.draw_text_vertical(0, 30, "A", font), Ok(36));
+    }
+}
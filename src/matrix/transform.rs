@@ -0,0 +1,158 @@
+use super::MODULE_SIZE;
+
+/// Quarter-turn applied to a module's tile when it is written to the
+/// hardware, turning the image clockwise.
+///
+/// Use this to compensate for modules mounted rotated relative to an FC-16
+/// style board so content drawn upright in the framebuffer shows upright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    /// Rotate an 8x8 tile (row bytes, bit 7 leftmost) clockwise.
+    pub fn apply(self, tile: &[u8; MODULE_SIZE]) -> [u8; MODULE_SIZE] {
+        let last = MODULE_SIZE - 1;
+        map_pixels(tile, |x, y| match self {
+            Self::Deg0 => (x, y),
+            Self::Deg90 => (y, last - x),
+            Self::Deg180 => (last - x, last - y),
+            Self::Deg270 => (last - y, x),
+        })
+    }
+
+    /// Where the pixel at (`x`, `y`) of a tile ends up after [`apply`](Self::apply).
+    pub fn map_point(self, x: usize, y: usize) -> (usize, usize) {
+        let last = MODULE_SIZE - 1;
+        match self {
+            Self::Deg0 => (x, y),
+            Self::Deg90 => (last - y, x),
+            Self::Deg180 => (last - x, last - y),
+            Self::Deg270 => (y, last - x),
+        }
+    }
+}
+
+/// Mirroring applied to a module's tile when it is written to the hardware,
+/// for clone modules whose rows or columns are wired in reverse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Flip {
+    /// Mirror left to right
+    pub x: bool,
+    /// Mirror top to bottom
+    pub y: bool,
+}
+
+impl Flip {
+    pub const NONE: Self = Self { x: false, y: false };
+    pub const X: Self = Self { x: true, y: false };
+    pub const Y: Self = Self { x: false, y: true };
+    pub const BOTH: Self = Self { x: true, y: true };
+
+    /// Mirror an 8x8 tile (row bytes, bit 7 leftmost).
+    pub fn apply(self, tile: &[u8; MODULE_SIZE]) -> [u8; MODULE_SIZE] {
+        let last = MODULE_SIZE - 1;
+        map_pixels(tile, |x, y| {
+            (
+                if self.x { last - x } else { x },
+                if self.y { last - y } else { y },
+            )
+        })
+    }
+
+    /// Where the pixel at (`x`, `y`) of a tile ends up after [`apply`](Self::apply).
+    pub fn map_point(self, x: usize, y: usize) -> (usize, usize) {
+        let last = MODULE_SIZE - 1;
+        (
+            if self.x { last - x } else { x },
+            if self.y { last - y } else { y },
+        )
+    }
+}
+
+/// Build a tile whose pixel (x, y) is the source pixel at `source(x, y)`.
+fn map_pixels(
+    tile: &[u8; MODULE_SIZE],
+    source: impl Fn(usize, usize) -> (usize, usize),
+) -> [u8; MODULE_SIZE] {
+    let mut out = [0; MODULE_SIZE];
+    for (y, row) in out.iter_mut().enumerate() {
+        for x in 0..MODULE_SIZE {
+            let (source_x, source_y) = source(x, y);
+            let lit = tile
+                .get(source_y)
+                .is_some_and(|&bits| source_x < MODULE_SIZE && bits & (0x80 >> source_x) != 0);
+            if lit {
+                *row |= 0x80 >> x;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Single pixel at the top-left corner
+    const CORNER: [u8; 8] = [0x80, 0, 0, 0, 0, 0, 0, 0];
+
+    #[test]
+    fn test_rotation_moves_corner() {
+        assert_eq!(Rotation::Deg0.apply(&CORNER), CORNER);
+        // Clockwise: top-left -> top-right -> bottom-right -> bottom-left
+        assert_eq!(Rotation::Deg90.apply(&CORNER), [0x01, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Rotation::Deg180.apply(&CORNER), [0, 0, 0, 0, 0, 0, 0, 0x01]);
+        assert_eq!(Rotation::Deg270.apply(&CORNER), [0, 0, 0, 0, 0, 0, 0, 0x80]);
+    }
+
+    #[test]
+    fn test_flip() {
+        let tile = [0xC0, 0x80, 0, 0, 0, 0, 0, 0];
+        assert_eq!(Flip::NONE.apply(&tile), tile);
+        assert_eq!(Flip::X.apply(&tile), [0x03, 0x01, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Flip::Y.apply(&tile), [0, 0, 0, 0, 0, 0, 0x80, 0xC0]);
+        assert_eq!(Flip::BOTH.apply(&tile), Rotation::Deg180.apply(&tile));
+    }
+
+    #[test]
+    fn test_map_point_matches_apply() {
+        let rotations = [
+            Rotation::Deg0,
+            Rotation::Deg90,
+            Rotation::Deg180,
+            Rotation::Deg270,
+        ];
+        let flips = [Flip::NONE, Flip::X, Flip::Y, Flip::BOTH];
+        for y in 0..MODULE_SIZE {
+            for x in 0..MODULE_SIZE {
+                let mut tile = [0; MODULE_SIZE];
+                tile[y] = 0x80 >> x;
+                for rotation in rotations {
+                    let (rx, ry) = rotation.map_point(x, y);
+                    assert_eq!(rotation.apply(&tile)[ry], 0x80 >> rx);
+                }
+                for flip in flips {
+                    let (fx, fy) = flip.map_point(x, y);
+                    assert_eq!(flip.apply(&tile)[fy], 0x80 >> fx);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_round_trip() {
+        let tile = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+        let quarter = Rotation::Deg90.apply(&tile);
+        assert_eq!(Rotation::Deg270.apply(&quarter), tile);
+        assert_eq!(
+            Rotation::Deg90.apply(&quarter),
+            Rotation::Deg180.apply(&tile)
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use super::{Canvas, LedMatrix, Sprite};
+use crate::Result;
+
+/// Frames of a [`Effect::Dissolve`]
+const DISSOLVE_STEPS: usize = 16;
+
+/// How [`LedMatrix::transition_to`] moves from the current content to a new
+/// frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Effect {
+    /// Replace column by column, starting at the right edge; one frame per
+    /// column
+    #[default]
+    WipeLeft,
+    /// Replace column by column, starting at the left edge
+    WipeRight,
+    /// Push the current content out at the top while the new frame enters
+    /// from the bottom; one frame per row
+    SlideUp,
+    /// Push the current content out at the bottom while the new frame enters
+    /// from the top
+    SlideDown,
+    /// Replace pixels in a scattered order over 16 frames. The order is
+    /// pseudo-random but the same every time.
+    Dissolve,
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Animate from the current framebuffer to `frame`, blocking until done.
+    ///
+    /// `frame` is drawn at the top-left of the drawing surface, honouring
+    /// the canvas rotation; pixels it doesn't cover end up off. Each
+    /// intermediate frame is flushed and shown for `step_ms` milliseconds
+    /// using `delay`, and the last one leaves exactly `frame` on the chain.
+    ///
+    /// # Errors
+    /// - Any error from [`flush`](Self::flush).
+    pub fn transition_to<D>(
+        &mut self,
+        frame: &Sprite,
+        effect: Effect,
+        delay: &mut D,
+        step_ms: u32,
+    ) -> Result<()>
+    where
+        D: DelayNs,
+    {
+        let from = self.framebuffer().clone();
+        let steps = match effect {
+            Effect::WipeLeft | Effect::WipeRight => self.width(),
+            Effect::SlideUp | Effect::SlideDown => self.height(),
+            Effect::Dissolve => DISSOLVE_STEPS,
+        };
+        for step in 1..=steps {
+            self.draw_transition(&from, frame, effect, step, steps)?;
+            self.flush()?;
+            delay.delay_ms(step_ms);
+        }
+        Ok(())
+    }
+
+    /// Draw frame `step` of `steps` of a transition from `from`, a copy of
+    /// the framebuffer taken before it started.
+    fn draw_transition(
+        &mut self,
+        from: &Canvas,
+        frame: &Sprite,
+        effect: Effect,
+        step: usize,
+        steps: usize,
+    ) -> Result<()> {
+        let (width, height) = (self.width(), self.height());
+        let old = |matrix: &Self, x: usize, y: usize| -> Result<bool> {
+            let (px, py) = matrix.physical(x, y)?;
+            from.get_pixel(px, py)
+        };
+
+        if effect == Effect::Dissolve {
+            let revealed = width * height * step / steps;
+            for y in 0..height {
+                for x in 0..width {
+                    let on = old(self, x, y)?;
+                    self.set_pixel_xy(x, y, on)?;
+                }
+            }
+            for index in DissolveOrder::new(width * height).take(revealed) {
+                let (x, y) = (index % width, index / width);
+                self.set_pixel_xy(x, y, frame.pixel(x, y))?;
+            }
+            return Ok(());
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let on = match effect {
+                    Effect::WipeLeft if x + step >= width => frame.pixel(x, y),
+                    Effect::WipeRight if x < step => frame.pixel(x, y),
+                    Effect::SlideUp if y + step >= height => frame.pixel(x, y + step - height),
+                    Effect::SlideUp => old(self, x, y + step)?,
+                    Effect::SlideDown if y < step => frame.pixel(x, y + height - step),
+                    Effect::SlideDown => old(self, x, y - step)?,
+                    _ => old(self, x, y)?,
+                };
+                self.set_pixel_xy(x, y, on)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every index below `count` exactly once, in a scattered order.
+///
+/// Runs a full-period linear congruential generator modulo the next power
+/// of two and skips values past `count`.
+struct DissolveOrder {
+    count: usize,
+    mask: u32,
+    state: u32,
+    remaining: usize,
+}
+
+impl DissolveOrder {
+    fn new(count: usize) -> Self {
+        Self {
+            count,
+            mask: (count.next_power_of_two() - 1) as u32,
+            state: 0,
+            remaining: count,
+        }
+    }
+}
+
+impl Iterator for DissolveOrder {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            // Multiplier = 1 mod 4 and odd increment give the full period
+            self.state = self.state.wrapping_mul(1_103_515_245).wrapping_add(12_345) & self.mask;
+            let index = self.state as usize;
+            if index < self.count {
+                self.remaining -= 1;
+                return Some(index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, matrix::Rotation};
+
+    /// Records the total time waited.
+    #[derive(Default)]
+    struct CountingDelay {
+        ms: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.ms += ns / 1_000_000;
+        }
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.ms += ms;
+        }
+    }
+
+    /// Row bytes of a single module after `step` of `steps` from all lit to
+    /// blank.
+    fn rows_after(effect: Effect, step: usize, steps: usize) -> [u8; 8] {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi)).unwrap();
+        matrix.fill(true);
+        let from = matrix.framebuffer().clone();
+        let blank = Sprite::new(8, 8, &[0; 8]).unwrap();
+        matrix
+            .draw_transition(&from, &blank, effect, step, steps)
+            .unwrap();
+        core::array::from_fn(|row| matrix.row(0, row).unwrap())
+    }
+
+    #[test]
+    fn test_wipes() {
+        assert_eq!(rows_after(Effect::WipeLeft, 3, 8), [0xF8; 8]);
+        assert_eq!(rows_after(Effect::WipeRight, 3, 8), [0x1F; 8]);
+    }
+
+    #[test]
+    fn test_slides() {
+        let up = rows_after(Effect::SlideUp, 3, 8);
+        assert_eq!(up, [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00]);
+        let down = rows_after(Effect::SlideDown, 3, 8);
+        assert_eq!(down, [0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_dissolve_order() {
+        let mut seen = [false; 40];
+        for index in DissolveOrder::new(40) {
+            assert!(!seen[index]);
+            seen[index] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+
+        let half = rows_after(Effect::Dissolve, 8, 16);
+        let lit: u32 = half.iter().map(|row| row.count_ones()).sum();
+        assert_eq!(lit, 32);
+    }
+
+    #[test]
+    fn test_transition_to_ends_on_frame() {
+        let arrow = [0x18, 0x3C, 0x7E, 0xFF, 0x18, 0x18, 0x18, 0x18];
+        let frame = Sprite::new(8, 8, &arrow).unwrap();
+        for effect in [
+            Effect::WipeLeft,
+            Effect::WipeRight,
+            Effect::SlideUp,
+            Effect::SlideDown,
+            Effect::Dissolve,
+        ] {
+            let mut spi = EmulatedSpi::new(1).unwrap();
+            let mut delay = CountingDelay::default();
+            let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+                .unwrap()
+                .with_canvas_rotation(Rotation::Deg90);
+            matrix.init().unwrap();
+            matrix.fill(true);
+            matrix
+                .transition_to(&frame, effect, &mut delay, 10)
+                .unwrap();
+            for y in 0..8 {
+                for x in 0..8 {
+                    assert_eq!(matrix.get_pixel_xy(x, y), Ok(frame.pixel(x, y)));
+                }
+            }
+            let steps = if effect == Effect::Dissolve { 16 } else { 8 };
+            assert_eq!(delay.ms, steps * 10);
+        }
+    }
+}
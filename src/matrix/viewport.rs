@@ -0,0 +1,293 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{BlitMode, Font, LedMatrix, MODULE_SIZE, Sprite, text::advanced};
+use crate::{Result, error::Error};
+
+/// Rectangle of the chain-wide surface a widget draws into, see
+/// [`LedMatrix::window`].
+///
+/// Declare one per widget, e.g.
+/// `const CLOCK: Viewport = Viewport::new(0, 0, 16, 8);`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Viewport {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Viewport {
+    /// Region of `width` x `height` pixels with its top-left corner at
+    /// chain-wide (`x`, `y`).
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Region covering modules `first..first + count` of a single-row chain.
+    pub const fn modules(first: usize, count: usize) -> Self {
+        Self::new(first * MODULE_SIZE, 0, count * MODULE_SIZE, MODULE_SIZE)
+    }
+
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether window-relative (`x`, `y`) is inside the region.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        matches!(
+            (usize::try_from(x), usize::try_from(y)),
+            (Ok(x), Ok(y)) if x < self.width && y < self.height
+        )
+    }
+}
+
+/// Drawing view of a [`LedMatrix`] limited to a [`Viewport`].
+///
+/// Coordinates are relative to the viewport's top-left corner and pixels
+/// outside it are clipped, so widgets sharing a chain can't draw over each
+/// other. Changes reach the chain on the matrix's next flush.
+pub struct Window<'m, SPI> {
+    matrix: &'m mut LedMatrix<SPI>,
+    viewport: Viewport,
+}
+
+impl<SPI> LedMatrix<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Borrow the part of the surface `viewport` covers for drawing.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if `viewport` is empty or reaches past the
+    ///   edge of the surface.
+    pub fn window(&mut self, viewport: Viewport) -> Result<Window<'_, SPI>> {
+        let fits = |start: usize, len: usize, max: usize| {
+            len > 0 && start.checked_add(len).is_some_and(|end| end <= max)
+        };
+        if !fits(viewport.x, viewport.width, self.width())
+            || !fits(viewport.y, viewport.height, self.height())
+        {
+            return Err(Error::InvalidPixel);
+        }
+        Ok(Window {
+            matrix: self,
+            viewport,
+        })
+    }
+}
+
+impl<SPI> Window<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    pub fn viewport(&self) -> Viewport {
+        self.viewport
+    }
+
+    pub fn width(&self) -> usize {
+        self.viewport.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.viewport.height
+    }
+
+    /// Light or clear the pixel at window-relative (`x`, `y`).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if the pixel is outside the window.
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) -> Result<()> {
+        let (x, y) = self.surface(x, y)?;
+        self.matrix.set_pixel_xy(x, y, on)
+    }
+
+    /// # Errors
+    /// - [`Error::InvalidPixel`] if the pixel is outside the window.
+    pub fn get_pixel(&self, x: usize, y: usize) -> Result<bool> {
+        let (x, y) = self.surface(x, y)?;
+        self.matrix.get_pixel_xy(x, y)
+    }
+
+    /// Clear every pixel of the window, leaving the rest of the surface.
+    pub fn clear(&mut self) -> Result<()> {
+        self.fill(false)
+    }
+
+    /// Set every pixel of the window to `on`.
+    pub fn fill(&mut self, on: bool) -> Result<()> {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.set_pixel(x, y, on)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Toggle every pixel of the window, e.g. to highlight it.
+    pub fn invert(&mut self) -> Result<()> {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let on = self.get_pixel(x, y)?;
+                self.set_pixel(x, y, !on)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw `sprite` with its top-left corner at window-relative (`x`, `y`),
+    /// see [`LedMatrix::blit`]. Parts outside the window are clipped.
+    pub fn blit(&mut self, sprite: &Sprite, x: i32, y: i32, mode: BlitMode) -> Result<()> {
+        for (row, sprite_y) in (y..=i32::MAX).zip(0..sprite.height()) {
+            for (column, sprite_x) in (x..=i32::MAX).zip(0..sprite.width()) {
+                if self.viewport.contains(column, row) {
+                    let (px, py) = self.offset(column, row);
+                    self.matrix
+                        .blend_pixel(px, py, sprite.pixel(sprite_x, sprite_y), mode)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw `c` at window-relative (`x`, `y`) like
+    /// [`LedMatrix::draw_char`], clipped to the window.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if `font` has no glyph for `c` nor for
+    ///   the matrix's fallback character.
+    pub fn draw_char<F: Font>(&mut self, x: i32, y: i32, c: char, font: F) -> Result<i32> {
+        let glyph = self.matrix.glyph(&font, c)?;
+        let advance = glyph.len().saturating_add(self.matrix.letter_spacing());
+        for (column, offset) in (x..=i32::MAX).zip(0..advance) {
+            let bits = glyph.get(offset).copied().unwrap_or(0);
+            for (row, bit) in (y..=i32::MAX).zip(0..font.height().min(MODULE_SIZE)) {
+                if self.viewport.contains(column, row) {
+                    let (px, py) = self.offset(column, row);
+                    self.matrix.draw_pixel(px, py, bits & (0x80 >> bit) != 0)?;
+                }
+            }
+        }
+        Ok(advanced(x, advance))
+    }
+
+    /// Draw `text` at window-relative (`x`, `y`), see
+    /// [`draw_char`](Self::draw_char). Returns `x` of the character that
+    /// would follow.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if `font` has no glyph for a character
+    ///   nor for the fallback; characters before it have already been drawn.
+    pub fn draw_text<F: Font>(&mut self, x: i32, y: i32, text: &str, font: F) -> Result<i32> {
+        text.chars()
+            .try_fold(x, |x, c| self.draw_char(x, y, c, &font))
+    }
+
+    fn surface(&self, x: usize, y: usize) -> Result<(usize, usize)> {
+        if x >= self.width() || y >= self.height() {
+            return Err(Error::InvalidPixel);
+        }
+        Ok((self.viewport.x + x, self.viewport.y + y))
+    }
+
+    fn offset(&self, x: i32, y: i32) -> (i32, i32) {
+        (x + self.viewport.x as i32, y + self.viewport.y as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    /// Every glyph is a 3-column block.
+    struct Block;
+
+    impl Font for Block {
+        fn height(&self) -> usize {
+            MODULE_SIZE
+        }
+
+        fn glyph(&self, _c: char) -> Result<&[u8]> {
+            Ok(&[0xFF; 3])
+        }
+    }
+
+    const CLOCK: Viewport = Viewport::modules(0, 2);
+    const TICKER: Viewport = Viewport::modules(2, 2);
+
+    #[test]
+    fn test_windows_clip_to_their_viewport() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(4).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+
+        let mut ticker = matrix.window(TICKER).unwrap();
+        ticker.fill(true).unwrap();
+        assert_eq!(ticker.width(), 16);
+
+        // Clock text runs past its window but stops at the ticker's edge
+        let mut clock = matrix.window(CLOCK).unwrap();
+        clock.clear().unwrap();
+        assert_eq!(clock.draw_text(-1, 0, "12:34", Block), Ok(19));
+        assert_eq!(clock.set_pixel(16, 0, false), Err(Error::InvalidPixel));
+        assert_eq!(clock.get_pixel(0, 0), Ok(true));
+
+        let dot = Sprite::new(1, 1, &[0x80]).unwrap();
+        let mut ticker = matrix.window(TICKER).unwrap();
+        ticker.blit(&dot, 0, 0, BlitMode::Xor).unwrap();
+        ticker.blit(&dot, -1, 0, BlitMode::Xor).unwrap();
+
+        assert_eq!(matrix.get_pixel_xy(15, 7), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(16, 0), Ok(false));
+        assert_eq!(matrix.get_pixel_xy(16, 1), Ok(true));
+        assert_eq!(matrix.get_pixel_xy(15, 0), Ok(true));
+    }
+
+    #[test]
+    fn test_window_saturates_at_coordinate_limit() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut matrix = LedMatrix::new(Max7219::new(&mut spi))
+            .unwrap()
+            .with_letter_spacing(usize::MAX);
+        let mut window = matrix.window(Viewport::modules(0, 1)).unwrap();
+
+        let edge = i32::MAX - 1;
+        assert_eq!(window.draw_text(edge, i32::MAX, "ab", Block), Ok(i32::MAX));
+        let dot = Sprite::new(2, 2, &[0xC0, 0xC0]).unwrap();
+        window.blit(&dot, i32::MAX, i32::MAX, BlitMode::Or).unwrap();
+        assert_eq!(matrix.get_pixel_xy(7, 7), Ok(false));
+    }
+
+    #[test]
+    fn test_window_must_fit_surface() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+        let mut matrix = LedMatrix::new(driver).unwrap();
+        assert!(matrix.window(Viewport::new(4, 2, 12, 6)).is_ok());
+        for viewport in [
+            Viewport::new(4, 2, 13, 6),
+            Viewport::new(0, 0, 0, 8),
+            Viewport::modules(1, 2),
+            Viewport::new(usize::MAX, 0, 2, 1),
+        ] {
+            assert!(matches!(matrix.window(viewport), Err(Error::InvalidPixel)));
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use core::{iter::Peekable, str::Chars};
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{Result, driver::Max7219, error::Error};
+
+/// Where a [`Morse`] message is keyed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorseTarget {
+    /// Every LED of the device, via display test mode, so the digit
+    /// registers keep their content
+    Device(usize),
+    /// The LEDs of `bits` in one digit register, e.g. a single matrix
+    /// pixel or 7-segment segment. The rest of the digit is dark.
+    Digit {
+        device_index: usize,
+        digit: u8,
+        bits: u8,
+    },
+}
+
+/// ITU Morse code of `c`, letters in either case, e.g. `".-"` for `a`.
+pub fn code(c: char) -> Option<&'static str> {
+    Some(match c.to_ascii_uppercase() {
+        'A' => ".-",
+        'B' => "-...",
+        'C' => "-.-.",
+        'D' => "-..",
+        'E' => ".",
+        'F' => "..-.",
+        'G' => "--.",
+        'H' => "....",
+        'I' => "..",
+        'J' => ".---",
+        'K' => "-.-",
+        'L' => ".-..",
+        'M' => "--",
+        'N' => "-.",
+        'O' => "---",
+        'P' => ".--.",
+        'Q' => "--.-",
+        'R' => ".-.",
+        'S' => "...",
+        'T' => "-",
+        'U' => "..-",
+        'V' => "...-",
+        'W' => ".--",
+        'X' => "-..-",
+        'Y' => "-.--",
+        'Z' => "--..",
+        '0' => "-----",
+        '1' => ".----",
+        '2' => "..---",
+        '3' => "...--",
+        '4' => "....-",
+        '5' => ".....",
+        '6' => "-....",
+        '7' => "--...",
+        '8' => "---..",
+        '9' => "----.",
+        '.' => ".-.-.-",
+        ',' => "--..--",
+        '?' => "..--..",
+        '\'' => ".----.",
+        '/' => "-..-.",
+        '(' => "-.--.",
+        ')' => "-.--.-",
+        ':' => "---...",
+        '=' => "-...-",
+        '+' => ".-.-.",
+        '-' => "-....-",
+        '"' => ".-..-.",
+        '@' => ".--.-.",
+        _ => return None,
+    })
+}
+
+/// Whether the LEDs are lit for each time unit of a message, with standard
+/// timing: a dot is 1 unit, a dash 3, and the gaps are 1 unit within a
+/// character, 3 between characters and 7 between words.
+#[derive(Debug, Clone)]
+pub struct MorseUnits<'a> {
+    chars: Peekable<Chars<'a>>,
+    code: &'static str,
+    on: u8,
+    off: u8,
+}
+
+impl<'a> MorseUnits<'a> {
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if a character other than a space has
+    ///   no Morse code.
+    pub fn new(text: &'a str) -> Result<Self> {
+        if text.chars().any(|c| c != ' ' && code(c).is_none()) {
+            return Err(Error::UnsupportedChar);
+        }
+        Ok(Self {
+            chars: text.chars().peekable(),
+            code: "",
+            on: 0,
+            off: 0,
+        })
+    }
+}
+
+impl Iterator for MorseUnits<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.on > 0 {
+            self.on -= 1;
+            return Some(true);
+        }
+        if self.off > 0 {
+            self.off -= 1;
+            return Some(false);
+        }
+        while self.code.is_empty() {
+            let c = self.chars.next()?;
+            self.code = code(c).unwrap_or("");
+        }
+        let (element, rest) = self.code.split_at(1);
+        self.code = rest;
+        self.on = if element == "-" { 2 } else { 0 };
+        self.off = match self.chars.peek() {
+            _ if !rest.is_empty() => 1,
+            None => 0,
+            Some(' ') => 7,
+            Some(_) => 3,
+        };
+        Some(true)
+    }
+}
+
+/// Keys a message in Morse on a [`MorseTarget`], one time unit per
+/// [`tick`](Self::tick).
+#[derive(Debug, Clone)]
+pub struct Morse<'a> {
+    units: MorseUnits<'a>,
+    target: MorseTarget,
+    lit: Option<bool>,
+}
+
+impl<'a> Morse<'a> {
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if a character other than a space has
+    ///   no Morse code.
+    pub fn new(text: &'a str, target: MorseTarget) -> Result<Self> {
+        Ok(Self {
+            units: MorseUnits::new(text)?,
+            target,
+            lit: None,
+        })
+    }
+
+    pub fn target(&self) -> MorseTarget {
+        self.target
+    }
+
+    /// Show the next time unit, writing to the chain only when the LEDs
+    /// change. Returns `true` once the message is done and the LEDs are off.
+    pub fn tick<SPI>(&mut self, driver: &mut Max7219<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        let next = self.units.next();
+        let lit = next.unwrap_or(false);
+        if self.lit != Some(lit) {
+            match self.target {
+                MorseTarget::Device(device_index) => driver.test_device(device_index, lit)?,
+                MorseTarget::Digit {
+                    device_index,
+                    digit,
+                    bits,
+                } => driver.write_raw_digit(device_index, digit, if lit { bits } else { 0 })?,
+            }
+            self.lit = Some(lit);
+        }
+        Ok(next.is_none())
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Key `text` in Morse on `target`, blocking until done, with time
+    /// units of `unit_ms` milliseconds timed by `delay`. 60 ms is about 20
+    /// words per minute.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if a character other than a space has
+    ///   no Morse code; nothing is sent then.
+    /// - Returns an SPI error if a write fails.
+    pub fn send_morse<D: DelayNs>(
+        &mut self,
+        target: MorseTarget,
+        text: &str,
+        unit_ms: u32,
+        delay: &mut D,
+    ) -> Result<()> {
+        let mut morse = Morse::new(text, target)?;
+        while !morse.tick(self)? {
+            delay.delay_ms(unit_ms);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{host::EmulatedSpi, registers::Register};
+    use embedded_hal_mock::eh1::{
+        delay::NoopDelay,
+        spi::{Mock as SpiMock, Transaction},
+    };
+
+    fn keyed(text: &str) -> String {
+        MorseUnits::new(text)
+            .unwrap()
+            .map(|on| if on { '=' } else { '.' })
+            .collect()
+    }
+
+    #[test]
+    fn test_timing() {
+        assert_eq!(keyed("a"), "=.===");
+        assert_eq!(keyed("Et"), "=...===");
+        assert_eq!(keyed(" e  e"), "=.......=");
+        assert_eq!(keyed(""), "");
+        assert!(matches!(MorseUnits::new("a#"), Err(Error::UnsupportedChar)));
+    }
+
+    #[test]
+    fn test_tick_writes_on_changes() {
+        let target = MorseTarget::Digit {
+            device_index: 0,
+            digit: 3,
+            bits: 0x10,
+        };
+        let write = |data| {
+            [
+                Transaction::transaction_start(),
+                Transaction::write_vec(vec![Register::Digit3.addr(), data]),
+                Transaction::transaction_end(),
+            ]
+        };
+        let expected: Vec<_> = [0x10, 0x00, 0x10, 0x00]
+            .into_iter()
+            .flat_map(write)
+            .collect();
+        let mut spi = SpiMock::new(&expected);
+        let mut driver = Max7219::new(&mut spi).assume_initialized();
+        let mut morse = Morse::new("i", target).unwrap();
+        let mut ticks = 0;
+        while !morse.tick(&mut driver).unwrap() {
+            ticks += 1;
+        }
+        assert_eq!(ticks, 3);
+        spi.done();
+    }
+
+    #[test]
+    fn test_send_morse_on_device() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            driver
+                .send_morse(MorseTarget::Device(1), "sos", 60, &mut NoopDelay::new())
+                .unwrap();
+            assert_eq!(
+                driver.send_morse(MorseTarget::Device(0), "~", 60, &mut NoopDelay::new()),
+                Err(Error::UnsupportedChar)
+            );
+        }
+        assert!(!spi.emulator().device(1).unwrap().display_test);
+    }
+}
@@ -0,0 +1,269 @@
+use embedded_hal::spi::SpiDevice;
+
+use crate::{
+    MAX_DISPLAYS, Result,
+    driver::Max7219,
+    error::Error,
+    matrix::{MODULE_SIZE, Snapshot},
+    registers::Register,
+};
+
+const FRAME_TAG: u8 = 0x01;
+const REGISTER_TAG: u8 = 0x02;
+/// Device byte of a register write addressed to every device
+const ALL_DEVICES: u8 = 0xFF;
+
+/// Largest encoded [`Message`]: a frame for [`MAX_DISPLAYS`] devices.
+pub const MAX_MESSAGE_SIZE: usize = 2 + MAX_DISPLAYS * MODULE_SIZE;
+
+/// Display content a host streams to a bridge MCU, which replays it into
+/// its chain with [`Message::apply`].
+///
+/// Wire format, all single bytes:
+/// - frame: `0x01`, device count, then 8 row bytes per device from device 0
+/// - register write: `0x02`, device index (`0xFF` for all), address, data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Message {
+    /// Row bytes for every device of the chain
+    Frame(Snapshot),
+    /// `data` written to `register` of one device, or of every device if
+    /// `device_index` is `None`
+    Register {
+        device_index: Option<u8>,
+        register: Register,
+        data: u8,
+    },
+}
+
+impl Message {
+    /// Encode the message into the start of `buf`.
+    ///
+    /// # Errors
+    /// - [`Error::BufferTooSmall`] if `buf` can't hold the message.
+    /// - [`Error::InvalidDeviceIndex`] if `device_index` is `Some(0xFF)`.
+    pub fn encode<'b>(&self, buf: &'b mut [u8]) -> Result<&'b [u8]> {
+        let len = match self {
+            Self::Frame(snapshot) => {
+                let tiles = snapshot.tiles();
+                let len = 2 + tiles.len() * MODULE_SIZE;
+                let out = buf.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+                let (header, rows) = out.split_at_mut(2);
+                header.copy_from_slice(&[FRAME_TAG, tiles.len() as u8]);
+                for (chunk, tile) in rows.chunks_exact_mut(MODULE_SIZE).zip(tiles) {
+                    chunk.copy_from_slice(tile);
+                }
+                len
+            }
+            Self::Register {
+                device_index,
+                register,
+                data,
+            } => {
+                let device = match *device_index {
+                    Some(ALL_DEVICES) => {
+                        return Err(Error::InvalidDeviceIndex {
+                            index: usize::from(ALL_DEVICES),
+                            device_count: MAX_DISPLAYS,
+                        });
+                    }
+                    Some(index) => index,
+                    None => ALL_DEVICES,
+                };
+                let out = buf.get_mut(..4).ok_or(Error::BufferTooSmall)?;
+                out.copy_from_slice(&[REGISTER_TAG, device, register.addr(), *data]);
+                4
+            }
+        };
+        Ok(&buf[..len])
+    }
+
+    /// Decode the message at the start of `bytes`, returning it and the
+    /// number of bytes it took up.
+    ///
+    /// # Errors
+    /// - [`Error::BufferTooSmall`] if `bytes` ends before the message does;
+    ///   a bridge reading a stream should wait for more bytes.
+    /// - [`Error::InvalidMessage`] if `bytes` doesn't start with a valid message.
+    pub fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        match bytes {
+            [] => Err(Error::BufferTooSmall),
+            [FRAME_TAG, rest @ ..] => {
+                let &count = rest.first().ok_or(Error::BufferTooSmall)?;
+                let count = usize::from(count);
+                if count > MAX_DISPLAYS {
+                    return Err(Error::InvalidMessage);
+                }
+                let rows = rest
+                    .get(1..1 + count * MODULE_SIZE)
+                    .ok_or(Error::BufferTooSmall)?;
+                let mut tiles = [[0; MODULE_SIZE]; MAX_DISPLAYS];
+                for (tile, chunk) in tiles.iter_mut().zip(rows.chunks_exact(MODULE_SIZE)) {
+                    tile.copy_from_slice(chunk);
+                }
+                let snapshot = Snapshot::from_tiles(&tiles[..count])?;
+                Ok((Self::Frame(snapshot), 2 + count * MODULE_SIZE))
+            }
+            [REGISTER_TAG, rest @ ..] => {
+                let [device, addr, data, ..] = *rest else {
+                    return Err(Error::BufferTooSmall);
+                };
+                let register = Register::try_from(addr).map_err(|_| Error::InvalidMessage)?;
+                let device_index = (device != ALL_DEVICES).then_some(device);
+                let message = Self::Register {
+                    device_index,
+                    register,
+                    data,
+                };
+                Ok((message, 4))
+            }
+            _ => Err(Error::InvalidMessage),
+        }
+    }
+
+    /// Replay the message into the chain `driver` drives.
+    ///
+    /// Register writes are sent as they are: the intensity, scan limit and
+    /// decode mode the driver tracks are not updated.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidCanvasSize`] if a frame's device count isn't the chain's.
+    /// - [`Error::InvalidDeviceIndex`] if a register write targets a device
+    ///   past the end of the chain.
+    /// - Returns an SPI error if the write fails.
+    pub fn apply<SPI>(&self, driver: &mut Max7219<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        match *self {
+            Self::Frame(snapshot) => driver.write_tiles(snapshot.tiles()),
+            Self::Register {
+                device_index: Some(index),
+                register,
+                data,
+            } => driver.write_device_register(usize::from(index), register, data),
+            Self::Register {
+                device_index: None,
+                register,
+                data,
+            } => {
+                let ops = [(register, data); MAX_DISPLAYS];
+                driver.write_all_registers(&ops[..driver.device_count()])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{host::EmulatedSpi, matrix::LedMatrix};
+
+    #[test]
+    fn test_round_trip() {
+        let mut tiles = [[0; MODULE_SIZE]; 3];
+        tiles[2][5] = 0x3C;
+        let messages = [
+            Message::Frame(Snapshot::from_tiles(&tiles).unwrap()),
+            Message::Register {
+                device_index: Some(1),
+                register: Register::Intensity,
+                data: 9,
+            },
+            Message::Register {
+                device_index: None,
+                register: Register::Shutdown,
+                data: 1,
+            },
+        ];
+
+        let mut stream = [0; 64];
+        let mut len = 0;
+        for message in &messages {
+            len += message.encode(&mut stream[len..]).unwrap().len();
+        }
+        assert_eq!(len, 26 + 4 + 4);
+        assert_eq!(&stream[26..34], &[0x02, 0x01, 0x0A, 9, 0x02, 0xFF, 0x0C, 1]);
+
+        let mut offset = 0;
+        for message in &messages {
+            let (decoded, used) = Message::decode(&stream[offset..len]).unwrap();
+            assert_eq!(&decoded, message);
+            offset += used;
+        }
+        assert_eq!(offset, len);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(Message::decode(&[]), Err(Error::BufferTooSmall));
+        assert_eq!(
+            Message::decode(&[0x01, 1, 0, 0]),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(
+            Message::decode(&[0x02, 0, 0x0A]),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(Message::decode(&[0x01, 9]), Err(Error::InvalidMessage));
+        assert_eq!(
+            Message::decode(&[0x02, 0, 0x0D, 0]),
+            Err(Error::InvalidMessage)
+        );
+        assert_eq!(Message::decode(&[0x7F]), Err(Error::InvalidMessage));
+
+        let frame = Message::Frame(Snapshot::from_tiles(&[[0; 8]; 2]).unwrap());
+        assert_eq!(frame.encode(&mut [0; 17]), Err(Error::BufferTooSmall));
+    }
+
+    #[test]
+    fn test_apply_replays_into_chain() {
+        // Host side renders into a matrix and streams a snapshot
+        let mut host = EmulatedSpi::new(2).unwrap();
+        let snapshot = {
+            let driver = Max7219::new(&mut host)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut matrix = LedMatrix::new(driver).unwrap();
+            matrix.set_pixel_xy(9, 4, true).unwrap();
+            matrix.snapshot().unwrap()
+        };
+        let mut buf = [0; MAX_MESSAGE_SIZE];
+        let mut stream = [0; MAX_MESSAGE_SIZE + 4];
+        let frame = Message::Frame(snapshot).encode(&mut buf).unwrap();
+        stream[..frame.len()].copy_from_slice(frame);
+        let len = frame.len();
+        let intensity = Message::Register {
+            device_index: Some(0),
+            register: Register::Intensity,
+            data: 3,
+        };
+        let len = len + intensity.encode(&mut stream[len..]).unwrap().len();
+
+        // Bridge side only decodes and replays
+        let mut bridge = EmulatedSpi::new(2).unwrap();
+        {
+            let mut driver = Max7219::new(&mut bridge).with_device_count(2).unwrap();
+            driver.init().unwrap();
+            let mut offset = 0;
+            while offset < len {
+                let (message, used) = Message::decode(&stream[offset..len]).unwrap();
+                message.apply(&mut driver).unwrap();
+                offset += used;
+            }
+            let too_far = Message::Register {
+                device_index: Some(2),
+                register: Register::Intensity,
+                data: 3,
+            };
+            assert!(matches!(
+                too_far.apply(&mut driver),
+                Err(Error::InvalidDeviceIndex { .. })
+            ));
+        }
+        let emulator = bridge.emulator();
+        assert_eq!(emulator.digits(1).unwrap()[4], 0x40);
+        assert_eq!(emulator.device(0).unwrap().intensity, 3);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{Result, error::Error};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Register {
     /// No-op register
@@ -73,6 +74,23 @@ impl Register {
     }
 }
 
+impl TryFrom<u8> for Register {
+    type Error = Error;
+
+    fn try_from(addr: u8) -> Result<Self> {
+        match addr {
+            0x00 => Ok(Register::NoOp),
+            0x01..=0x08 => Register::try_digit(addr - 1),
+            0x09 => Ok(Register::DecodeMode),
+            0x0A => Ok(Register::Intensity),
+            0x0B => Ok(Register::ScanLimit),
+            0x0C => Ok(Register::Shutdown),
+            0x0F => Ok(Register::DisplayTest),
+            _ => Err(Error::InvalidRegister),
+        }
+    }
+}
+
 /// Decode mode configuration for the MAX7219 display driver.
 ///
 /// Code B decoding allows the driver to automatically convert certain values
@@ -113,6 +131,113 @@ impl DecodeMode {
     }
 }
 
+/// Validated value of the Intensity register, 0 (1/32 duty cycle) to 15
+/// (31/32).
+///
+/// Constants and [`Intensity::new`] in a const context check the range at
+/// compile time; [`TryFrom<u8>`] checks it at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Intensity(u8);
+
+impl Intensity {
+    pub const MIN: Self = Self(0x00);
+    pub const MAX: Self = Self(0x0F);
+
+    /// `None` if `value` is above 15.
+    pub const fn new(value: u8) -> Option<Self> {
+        if value > Self::MAX.0 {
+            return None;
+        }
+        Some(Self(value))
+    }
+
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for Intensity {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Self::new(value).ok_or(Error::InvalidIntensity {
+            value,
+            max: Self::MAX.0,
+        })
+    }
+}
+
+impl From<Intensity> for u8 {
+    fn from(intensity: Intensity) -> Self {
+        intensity.value()
+    }
+}
+
+/// Character shown by a digit set to Code B decoding: `0`-`9`, `-`, `E`,
+/// `H`, `L`, `P` or blank, optionally with the decimal point lit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CodeBChar(u8);
+
+impl CodeBChar {
+    pub const DASH: Self = Self(0x0A);
+    pub const E: Self = Self(0x0B);
+    pub const H: Self = Self(0x0C);
+    pub const L: Self = Self(0x0D);
+    pub const P: Self = Self(0x0E);
+    pub const BLANK: Self = Self(0x0F);
+
+    /// `None` if `digit` is above 9.
+    pub const fn digit(digit: u8) -> Option<Self> {
+        if digit > 9 {
+            return None;
+        }
+        Some(Self(digit))
+    }
+
+    /// `None` for characters Code B has no code for; letters are accepted
+    /// in either case.
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '0'..='9' => Self::digit(c as u8 - b'0'),
+            '-' => Some(Self::DASH),
+            'E' | 'e' => Some(Self::E),
+            'H' | 'h' => Some(Self::H),
+            'L' | 'l' => Some(Self::L),
+            'P' | 'p' => Some(Self::P),
+            ' ' => Some(Self::BLANK),
+            _ => None,
+        }
+    }
+
+    /// The same character with the decimal point lit.
+    pub const fn with_dp(self) -> Self {
+        Self(self.0 | 0x80)
+    }
+
+    pub const fn has_dp(self) -> bool {
+        self.0 & 0x80 != 0
+    }
+
+    /// Digit register value, bit 7 being the decimal point.
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<char> for CodeBChar {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Self::from_char(c).ok_or(Error::UnsupportedChar)
+    }
+}
+
+impl From<CodeBChar> for u8 {
+    fn from(c: CodeBChar) -> Self {
+        c.value()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +254,15 @@ mod tests {
         assert_eq!(Register::DisplayTest.addr(), 0x0F);
     }
 
+    #[test]
+    fn test_register_from_addr() {
+        for register in Register::digits().chain([Register::NoOp, Register::DisplayTest]) {
+            assert_eq!(Register::try_from(register.addr()), Ok(register));
+        }
+        assert_eq!(Register::try_from(0x0D), Err(Error::InvalidRegister));
+        assert_eq!(Register::try_from(0x10), Err(Error::InvalidRegister));
+    }
+
     #[test]
     fn test_digits_iterator() {
         let expected = [
@@ -153,6 +287,18 @@ mod tests {
         assert_eq!(DecodeMode::AllDigits.value(), 0xFF);
     }
 
+    #[test]
+    fn test_intensity() {
+        assert_eq!(Intensity::new(15), Some(Intensity::MAX));
+        assert_eq!(Intensity::new(16), None);
+        assert_eq!(Intensity::try_from(7).map(u8::from), Ok(7));
+        assert_eq!(
+            Intensity::try_from(0x10),
+            Err(Error::InvalidIntensity { value: 16, max: 15 })
+        );
+        assert!(Intensity::MIN < Intensity::MAX);
+    }
+
     #[test]
     fn test_try_digit_valid() {
         assert_eq!(Register::try_digit(0), Ok(Register::Digit0));
@@ -170,4 +316,15 @@ mod tests {
         assert_eq!(Register::try_digit(8), Err(Error::InvalidDigit));
         assert_eq!(Register::try_digit(255), Err(Error::InvalidDigit));
     }
+
+    #[test]
+    fn test_code_b_char() {
+        assert_eq!(CodeBChar::digit(7).map(CodeBChar::value), Some(0x07));
+        assert_eq!(CodeBChar::digit(10), None);
+        assert_eq!(CodeBChar::from_char('h'), Some(CodeBChar::H));
+        assert_eq!(CodeBChar::try_from('x'), Err(Error::UnsupportedChar));
+        let dash = CodeBChar::DASH.with_dp();
+        assert!(dash.has_dp());
+        assert_eq!(u8::from(dash), 0x8A);
+    }
 }
@@ -0,0 +1,119 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Segments, SevenSegment, display::CHAIN_DIGITS};
+use crate::{Result, error::Error};
+
+/// Steps of [`SevenSegment::write_chain_bar`] per digit: the left then the
+/// right pair of vertical segments
+pub const BAR_STEPS_PER_DIGIT: u32 = 2;
+
+/// Highest level of [`SevenSegment::write_chain_levels`]: the bottom,
+/// middle and top segments lit
+pub const MAX_DIGIT_LEVEL: u8 = 3;
+
+const LEFT_PAIR: Segments = Segments::E.union(Segments::F);
+const RIGHT_PAIR: Segments = Segments::B.union(Segments::C);
+const LEVELS: [Segments; MAX_DIGIT_LEVEL as usize + 1] = [
+    Segments::NONE,
+    Segments::D,
+    Segments::D.union(Segments::G),
+    Segments::D.union(Segments::G).union(Segments::A),
+];
+
+impl<SPI> SevenSegment<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Show `value` out of `max` as a bar growing left to right across the
+    /// whole chain, e.g. a level meter on a numeric module.
+    ///
+    /// Each digit lights its left then its right pair of vertical segments,
+    /// so 8 digits give 16 steps. The bar is rounded to the nearest step
+    /// and values above `max` show a full bar.
+    pub fn write_chain_bar(&mut self, value: u32, max: u32) -> Result<()> {
+        let width = self.digit_count();
+        let steps = width as u64 * u64::from(BAR_STEPS_PER_DIGIT);
+        let max = u64::from(max.max(1));
+        let lit = (u64::from(value).min(max) * steps * 2 + max) / (max * 2);
+
+        let mut line = [Segments::NONE; CHAIN_DIGITS];
+        for (position, segments) in line[..width].iter_mut().enumerate() {
+            let first = position as u64 * u64::from(BAR_STEPS_PER_DIGIT);
+            *segments = match lit.saturating_sub(first) {
+                0 => Segments::NONE,
+                1 => LEFT_PAIR,
+                _ => LEFT_PAIR.union(RIGHT_PAIR),
+            };
+        }
+        self.write_chain_line(&line[..width])
+    }
+
+    /// Show one level in `0..=3` per digit from the left, stacking the
+    /// bottom, middle and top segments, e.g. an 8-band spectrum. Digits
+    /// after the last level are blanked and higher levels are clamped.
+    ///
+    /// # Errors
+    /// - [`Error::TextTooLong`] if there are more levels than digits.
+    pub fn write_chain_levels(&mut self, levels: &[u8]) -> Result<()> {
+        let width = self.digit_count();
+        if levels.len() > width {
+            return Err(Error::TextTooLong);
+        }
+        let mut line = [Segments::NONE; CHAIN_DIGITS];
+        for (segments, &level) in line.iter_mut().zip(levels) {
+            *segments = LEVELS[usize::from(level.min(MAX_DIGIT_LEVEL))];
+        }
+        self.write_chain_line(&line[..width])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_chain_bar() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap();
+            let mut display = SevenSegment::new(driver);
+            // 32 steps across 16 digits, 5 lit
+            display.write_chain_bar(16, 100).unwrap();
+        }
+        let mut digits = spi.emulator().digits(0).unwrap();
+        digits.reverse();
+        assert_eq!(digits, [0x36, 0x36, 0x06, 0, 0, 0, 0, 0]);
+        assert_eq!(spi.emulator().digits(1).unwrap(), [0; 8]);
+    }
+
+    #[test]
+    fn test_chain_bar_full_and_empty() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        SevenSegment::new(Max7219::new(&mut spi).assume_initialized())
+            .write_chain_bar(500, 100)
+            .unwrap();
+        assert_eq!(spi.emulator().digits(0).unwrap(), [0x36; 8]);
+        SevenSegment::new(Max7219::new(&mut spi).assume_initialized())
+            .write_chain_bar(0, 0)
+            .unwrap();
+        assert_eq!(spi.emulator().digits(0).unwrap(), [0; 8]);
+    }
+
+    #[test]
+    fn test_chain_levels() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut display = SevenSegment::new(Max7219::new(&mut spi));
+            display.init().unwrap();
+            display.write_chain_levels(&[0, 1, 2, 3, 9]).unwrap();
+            assert_eq!(display.write_chain_levels(&[0; 9]), Err(Error::TextTooLong));
+        }
+        let mut digits = spi.emulator().digits(0).unwrap();
+        digits.reverse();
+        assert_eq!(digits, [0x00, 0x08, 0x09, 0x49, 0x49, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,202 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Segments, SevenSegment};
+use crate::{NUM_DIGITS, Result, error::Error};
+
+/// Blinks selected digits of one device, e.g. the field being edited in a
+/// set-the-clock menu.
+///
+/// The helper keeps its own copy of the device's content since the chip
+/// registers can't be read back. Each [`tick`](Self::tick) toggles the
+/// blinking digits between their content and blank; the other digits are
+/// left alone.
+#[derive(Debug, Clone)]
+pub struct DigitBlink {
+    device_index: usize,
+    content: [Segments; NUM_DIGITS as usize],
+    // bit n set = position n blinks
+    mask: u8,
+    visible: bool,
+}
+
+impl DigitBlink {
+    pub fn new(device_index: usize) -> Self {
+        Self {
+            device_index,
+            content: [Segments::NONE; NUM_DIGITS as usize],
+            mask: 0,
+            visible: true,
+        }
+    }
+
+    /// Set the content of one digit (0 = leftmost).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if `position` is out of range.
+    pub fn set_segments(&mut self, position: u8, segments: Segments) -> Result<()> {
+        let slot = self
+            .content
+            .get_mut(usize::from(position))
+            .ok_or(Error::InvalidDigit)?;
+        *slot = segments;
+        Ok(())
+    }
+
+    /// Replace the whole content with left-aligned `text`, resolved with the
+    /// display's glyphs.
+    pub fn set_str<SPI>(&mut self, display: &SevenSegment<SPI>, text: &str) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        display.render_text(text, &mut line[..display.device_digits()])?;
+        self.content = line;
+        Ok(())
+    }
+
+    pub fn content(&self) -> &[Segments; NUM_DIGITS as usize] {
+        &self.content
+    }
+
+    /// Start or stop blinking the digit at `position`.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if `position` is out of range.
+    pub fn set_blinking(&mut self, position: u8, enabled: bool) -> Result<()> {
+        if position >= NUM_DIGITS {
+            return Err(Error::InvalidDigit);
+        }
+        if enabled {
+            self.mask |= 1 << position;
+        } else {
+            self.mask &= !(1 << position);
+        }
+        Ok(())
+    }
+
+    /// Blink `width` digits starting at `start`, stopping all others.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if the range runs past the last digit.
+    pub fn blink_range(&mut self, start: u8, width: u8) -> Result<()> {
+        if start.checked_add(width).is_none_or(|end| end > NUM_DIGITS) {
+            return Err(Error::InvalidDigit);
+        }
+        self.mask = (((1u16 << width) - 1) << start) as u8;
+        Ok(())
+    }
+
+    pub fn clear_blinking(&mut self) {
+        self.mask = 0;
+    }
+
+    pub fn is_blinking(&self, position: u8) -> bool {
+        position < NUM_DIGITS && self.mask & (1 << position) != 0
+    }
+
+    /// Whether blinking digits are currently showing their content.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Write every digit for the current phase.
+    pub fn show<SPI>(&self, display: &mut SevenSegment<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let mut line = self.content;
+        if !self.visible {
+            for (position, segments) in line.iter_mut().enumerate() {
+                if self.mask & (1 << position) != 0 {
+                    *segments = Segments::NONE;
+                }
+            }
+        }
+        display.write_line(self.device_index, &line)
+    }
+
+    /// Toggle the blink phase and rewrite only the blinking digits.
+    ///
+    /// Call this at the blink rate, e.g. every 500 ms. Returns whether the
+    /// blinking digits are now visible.
+    pub fn tick<SPI>(&mut self, display: &mut SevenSegment<SPI>) -> Result<bool>
+    where
+        SPI: SpiDevice,
+    {
+        self.visible = !self.visible;
+        for position in 0..NUM_DIGITS {
+            if self.is_blinking(position) {
+                let segments = if self.visible {
+                    self.content[usize::from(position)]
+                } else {
+                    Segments::NONE
+                };
+                display.write_segments(self.device_index, position, segments)?;
+            }
+        }
+        Ok(self.visible)
+    }
+
+    /// Make the blinking digits visible again without writing, e.g. after
+    /// the user changed a value so the edit shows immediately on the next
+    /// [`show`](Self::show).
+    pub fn restart(&mut self) {
+        self.visible = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_invalid_positions() {
+        let mut blink = DigitBlink::new(0);
+        assert_eq!(blink.set_blinking(8, true), Err(Error::InvalidDigit));
+        assert_eq!(blink.blink_range(6, 3), Err(Error::InvalidDigit));
+        assert_eq!(blink.set_segments(8, Segments::A), Err(Error::InvalidDigit));
+        assert!(!blink.is_blinking(8));
+    }
+
+    #[test]
+    fn test_blink_range() {
+        let mut blink = DigitBlink::new(0);
+        blink.set_blinking(0, true).unwrap();
+        blink.blink_range(3, 2).unwrap();
+        assert!(!blink.is_blinking(0));
+        assert!(blink.is_blinking(3));
+        assert!(blink.is_blinking(4));
+        assert!(!blink.is_blinking(5));
+
+        blink.blink_range(0, 8).unwrap();
+        assert!(blink.is_blinking(7));
+        blink.clear_blinking();
+        assert!(!blink.is_blinking(7));
+    }
+
+    #[test]
+    fn test_tick_toggles_blinking_digits() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+            let mut blink = DigitBlink::new(0);
+            blink.set_str(&display, "12-34").unwrap();
+            blink.blink_range(3, 2).unwrap();
+
+            blink.show(&mut display).unwrap();
+            assert_eq!(blink.tick(&mut display), Ok(false));
+            assert_eq!(blink.tick(&mut display), Ok(true));
+            assert_eq!(blink.tick(&mut display), Ok(false));
+        }
+
+        // Digit 0 (DIG0) is the rightmost position
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[7], 0x30); // '1' untouched
+        assert_eq!(digits[5], 0x01); // '-' untouched
+        assert_eq!(digits[4], 0x00); // '3' blanked
+        assert_eq!(digits[3], 0x00); // '4' blanked
+        // show writes all 8 digits, each tick only the 2 blinking ones
+        assert_eq!(spi.transactions(), 8 + 3 * 2);
+    }
+}
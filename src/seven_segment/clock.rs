@@ -0,0 +1,341 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::SevenSegment;
+use crate::{Result, error::Error};
+
+/// Wall-clock time shown by [`SevenSegment::write_time`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeOfDay {
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+}
+
+impl TimeOfDay {
+    /// # Errors
+    /// - [`Error::InvalidTime`] if a field is out of range.
+    pub fn new(hours: u8, minutes: u8, seconds: u8) -> Result<Self> {
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return Err(Error::InvalidTime);
+        }
+        Ok(Self {
+            hours,
+            minutes,
+            seconds,
+        })
+    }
+
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    pub fn seconds(&self) -> u8 {
+        self.seconds
+    }
+}
+
+/// Calendar date shown by [`SevenSegment::write_date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl CalendarDate {
+    /// # Errors
+    /// - [`Error::InvalidTime`] if the month or day is out of range or the
+    ///   year has more than four digits.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self> {
+        if year > 9999 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(Error::InvalidTime);
+        }
+        Ok(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+/// Layout used by [`SevenSegment::write_time`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClockFormat {
+    /// `12-34-56` across all eight digits
+    #[default]
+    HoursMinutesSeconds,
+    /// `12.34`, the decimal point standing in for the colon
+    HoursMinutes,
+}
+
+/// Layout used by [`SevenSegment::write_date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateFormat {
+    /// `15.10.2026`
+    #[default]
+    DayMonthYear,
+    /// `2026.10.15`
+    YearMonthDay,
+}
+
+impl<SPI> SevenSegment<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Show a time of day on one device, left-aligned.
+    pub fn write_time(
+        &mut self,
+        device_index: usize,
+        time: impl Into<TimeOfDay>,
+        format: ClockFormat,
+    ) -> Result<()> {
+        let time = time.into();
+        let mut text = [0; 8];
+        let len = match format {
+            ClockFormat::HoursMinutesSeconds => {
+                push_two_digits(&mut text[0..2], time.hours);
+                text[2] = b'-';
+                push_two_digits(&mut text[3..5], time.minutes);
+                text[5] = b'-';
+                push_two_digits(&mut text[6..8], time.seconds);
+                8
+            }
+            ClockFormat::HoursMinutes => {
+                push_two_digits(&mut text[0..2], time.hours);
+                text[2] = b'.';
+                push_two_digits(&mut text[3..5], time.minutes);
+                5
+            }
+        };
+        self.write_ascii(device_index, &text[..len])
+    }
+
+    /// Show a calendar date on one device.
+    pub fn write_date(
+        &mut self,
+        device_index: usize,
+        date: impl Into<CalendarDate>,
+        format: DateFormat,
+    ) -> Result<()> {
+        let date = date.into();
+        let mut text = [b'.'; 10];
+        let (year, month, day) = match format {
+            DateFormat::DayMonthYear => (6..10, 3..5, 0..2),
+            DateFormat::YearMonthDay => (0..4, 5..7, 8..10),
+        };
+        push_two_digits(
+            &mut text[year.start..year.start + 2],
+            (date.year / 100) as u8,
+        );
+        push_two_digits(&mut text[year.start + 2..year.end], (date.year % 100) as u8);
+        push_two_digits(&mut text[month], date.month);
+        push_two_digits(&mut text[day], date.day);
+        self.write_ascii(device_index, &text)
+    }
+
+    fn write_ascii(&mut self, device_index: usize, text: &[u8]) -> Result<()> {
+        // The callers above only produce ASCII digits, '-' and '.'
+        let text = core::str::from_utf8(text).map_err(|_| Error::UnsupportedChar)?;
+        self.write_str(device_index, text)
+    }
+}
+
+fn push_two_digits(out: &mut [u8], value: u8) {
+    out[0] = b'0' + value / 10 % 10;
+    out[1] = b'0' + value % 10;
+}
+
+#[cfg(feature = "time")]
+impl From<time::Time> for TimeOfDay {
+    fn from(value: time::Time) -> Self {
+        Self {
+            hours: value.hour(),
+            minutes: value.minute(),
+            seconds: value.second(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::PrimitiveDateTime> for TimeOfDay {
+    fn from(value: time::PrimitiveDateTime) -> Self {
+        value.time().into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Date> for CalendarDate {
+    /// Years outside `0..=9999` are clamped to that range.
+    fn from(value: time::Date) -> Self {
+        Self {
+            year: value.year().clamp(0, 9999) as u16,
+            month: u8::from(value.month()),
+            day: value.day(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::PrimitiveDateTime> for CalendarDate {
+    fn from(value: time::PrimitiveDateTime) -> Self {
+        value.date().into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for TimeOfDay {
+    fn from(value: chrono::NaiveTime) -> Self {
+        use chrono::Timelike;
+
+        Self {
+            hours: value.hour() as u8,
+            minutes: value.minute() as u8,
+            // Leap seconds are reported as second 59
+            seconds: value.second().min(59) as u8,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for TimeOfDay {
+    fn from(value: chrono::NaiveDateTime) -> Self {
+        value.time().into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for CalendarDate {
+    /// Years outside `0..=9999` are clamped to that range.
+    fn from(value: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+
+        Self {
+            year: value.year().clamp(0, 9999) as u16,
+            month: value.month() as u8,
+            day: value.day() as u8,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for CalendarDate {
+    fn from(value: chrono::NaiveDateTime) -> Self {
+        value.date().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(TimeOfDay::new(24, 0, 0), Err(Error::InvalidTime));
+        assert_eq!(TimeOfDay::new(0, 60, 0), Err(Error::InvalidTime));
+        assert_eq!(CalendarDate::new(2026, 13, 1), Err(Error::InvalidTime));
+        assert_eq!(CalendarDate::new(2026, 1, 0), Err(Error::InvalidTime));
+        assert_eq!(CalendarDate::new(10_000, 1, 1), Err(Error::InvalidTime));
+        assert!(CalendarDate::new(2026, 10, 15).is_ok());
+    }
+
+    fn render(draw: impl FnOnce(&mut SevenSegment<&mut EmulatedSpi>)) -> [u8; 8] {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        draw(&mut display);
+        spi.emulator().digits(0).unwrap()
+    }
+
+    #[test]
+    fn test_write_time() {
+        let time = TimeOfDay::new(12, 34, 56).unwrap();
+        let digits = render(|display| {
+            display
+                .write_time(0, time, ClockFormat::HoursMinutesSeconds)
+                .unwrap()
+        });
+        // DIG0..DIG7 = 6 5 - 4 3 - 2 1 (reads "12-34-56" left to right)
+        assert_eq!(digits, [0x5F, 0x5B, 0x01, 0x33, 0x79, 0x01, 0x6D, 0x30]);
+    }
+
+    #[test]
+    fn test_write_time_hours_minutes() {
+        let time = TimeOfDay::new(7, 5, 0).unwrap();
+        let digits = render(|display| {
+            display
+                .write_time(0, time, ClockFormat::HoursMinutes)
+                .unwrap()
+        });
+        assert_eq!(digits, [0, 0, 0, 0, 0x5B, 0x7E, 0x70 | 0x80, 0x7E]);
+    }
+
+    #[test]
+    fn test_write_date() {
+        let date = CalendarDate::new(2026, 10, 5).unwrap();
+
+        // 05.10.2026
+        let digits = render(|display| {
+            display
+                .write_date(0, date, DateFormat::DayMonthYear)
+                .unwrap()
+        });
+        assert_eq!(
+            digits,
+            [0x5F, 0x6D, 0x7E, 0x6D, 0x7E | 0x80, 0x30, 0x5B | 0x80, 0x7E]
+        );
+
+        // 2026.10.05
+        let digits = render(|display| {
+            display
+                .write_date(0, date, DateFormat::YearMonthDay)
+                .unwrap()
+        });
+        assert_eq!(
+            digits,
+            [0x5B, 0x7E, 0x7E | 0x80, 0x30, 0x5F | 0x80, 0x6D, 0x7E, 0x6D]
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_from_time_crate() {
+        let date = time::Date::from_calendar_date(2026, time::Month::October, 15).unwrap();
+        let datetime = time::PrimitiveDateTime::new(date, time::Time::from_hms(8, 30, 5).unwrap());
+
+        assert_eq!(TimeOfDay::from(datetime), TimeOfDay::new(8, 30, 5).unwrap());
+        assert_eq!(
+            CalendarDate::from(datetime),
+            CalendarDate::new(2026, 10, 15).unwrap()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_chrono() {
+        let datetime = chrono::NaiveDate::from_ymd_opt(2026, 10, 15)
+            .unwrap()
+            .and_time(chrono::NaiveTime::from_hms_opt(23, 59, 59).unwrap());
+
+        assert_eq!(
+            TimeOfDay::from(datetime),
+            TimeOfDay::new(23, 59, 59).unwrap()
+        );
+        assert_eq!(
+            CalendarDate::from(datetime),
+            CalendarDate::new(2026, 10, 15).unwrap()
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{
+    Segments, SevenSegment,
+    number::{MAX_DECIMAL_DIGITS, decimal_digits},
+};
+use crate::{NUM_DIGITS, Result, error::Error};
+
+/// Up/down counter bound to a range of digits on one device.
+///
+/// Useful for stopwatches, lap and production counters. The value rolls
+/// over at the range's capacity (e.g. `9999` + 1 = `0` on four digits), and
+/// [`render`](Self::render) only rewrites the digits whose glyph changed.
+#[derive(Debug, Clone)]
+pub struct Counter {
+    device_index: usize,
+    start: u8,
+    width: u8,
+    value: u32,
+    leading_zeros: bool,
+    shown: [Option<Segments>; NUM_DIGITS as usize],
+}
+
+impl Counter {
+    /// Bind a counter to `width` digits starting at `start` (0 = leftmost).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if the range is empty or runs past the last digit.
+    pub fn new(device_index: usize, start: u8, width: u8) -> Result<Self> {
+        if width == 0 || start.checked_add(width).is_none_or(|end| end > NUM_DIGITS) {
+            return Err(Error::InvalidDigit);
+        }
+        Ok(Self {
+            device_index,
+            start,
+            width,
+            value: 0,
+            leading_zeros: false,
+            shown: [None; NUM_DIGITS as usize],
+        })
+    }
+
+    /// Pad with zeros instead of blanks, e.g. `0042`.
+    pub fn with_leading_zeros(mut self, enabled: bool) -> Self {
+        self.leading_zeros = enabled;
+        self
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Largest value the digit range can show.
+    pub fn max_value(&self) -> u32 {
+        10u32.pow(u32::from(self.width)) - 1
+    }
+
+    /// Set the value, rolling over values that exceed [`max_value`](Self::max_value).
+    pub fn set(&mut self, value: u32) {
+        self.value = value % (self.max_value() + 1);
+    }
+
+    pub fn increment(&mut self) {
+        self.add(1);
+    }
+
+    pub fn decrement(&mut self) {
+        self.value = self.value.checked_sub(1).unwrap_or(self.max_value());
+    }
+
+    pub fn add(&mut self, amount: u32) {
+        let modulus = u64::from(self.max_value()) + 1;
+        self.value = ((u64::from(self.value) + u64::from(amount)) % modulus) as u32;
+    }
+
+    pub fn reset(&mut self) {
+        self.value = 0;
+    }
+
+    /// Forget what is on the display so the next render rewrites every digit.
+    pub fn invalidate(&mut self) {
+        self.shown = [None; NUM_DIGITS as usize];
+    }
+
+    /// Write the digits that changed since the last render.
+    ///
+    /// Returns the number of digits written.
+    pub fn render<SPI>(&mut self, display: &mut SevenSegment<SPI>) -> Result<usize>
+    where
+        SPI: SpiDevice,
+    {
+        let width = usize::from(self.width);
+        let mut digits = [0; MAX_DECIMAL_DIGITS];
+        let len = decimal_digits(self.value, &mut digits);
+
+        let mut written = 0;
+        for offset in 0..width {
+            // offset 0 is the rightmost digit of the range
+            let segments = match digits[..len].get(offset) {
+                Some(digit) => display.char_glyph(char::from(b'0' + digit))?,
+                None if self.leading_zeros => display.char_glyph('0')?,
+                None => Segments::NONE,
+            };
+            let position = self.start + self.width - 1 - offset as u8;
+            let shown = &mut self.shown[usize::from(position)];
+            if *shown != Some(segments) {
+                display.write_segments(self.device_index, position, segments)?;
+                *shown = Some(segments);
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    #[test]
+    fn test_new_invalid_range() {
+        assert!(matches!(Counter::new(0, 0, 0), Err(Error::InvalidDigit)));
+        assert!(matches!(Counter::new(0, 6, 3), Err(Error::InvalidDigit)));
+        assert!(Counter::new(0, 4, 4).is_ok());
+    }
+
+    #[test]
+    fn test_rollover() {
+        let mut counter = Counter::new(0, 0, 2).unwrap();
+        counter.set(99);
+        counter.increment();
+        assert_eq!(counter.value(), 0);
+        counter.decrement();
+        assert_eq!(counter.value(), 99);
+        counter.add(251);
+        assert_eq!(counter.value(), 50);
+        counter.set(1234);
+        assert_eq!(counter.value(), 34);
+    }
+
+    #[test]
+    fn test_render_only_changed_digits() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        let mut counter = Counter::new(0, 4, 4).unwrap().with_leading_zeros(true);
+
+        assert_eq!(counter.render(&mut display), Ok(4));
+        counter.set(9);
+        assert_eq!(counter.render(&mut display), Ok(1));
+        counter.increment();
+        assert_eq!(counter.render(&mut display), Ok(2));
+        assert_eq!(counter.render(&mut display), Ok(0));
+        counter.invalidate();
+        assert_eq!(counter.render(&mut display), Ok(4));
+
+        // 0010 on the rightmost four digits (DIG3..DIG0)
+        assert_eq!(
+            spi.emulator().digits(0).unwrap()[..4],
+            [0x7E, 0x30, 0x7E, 0x7E]
+        );
+    }
+
+    #[test]
+    fn test_render_blank_padding() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        let mut counter = Counter::new(0, 0, 3).unwrap();
+
+        counter.set(7);
+        counter.render(&mut display).unwrap();
+        assert_eq!(spi.emulator().digits(0).unwrap()[5..], [0x70, 0x00, 0x00]);
+    }
+}
@@ -0,0 +1,977 @@
+use core::fmt;
+
+use embedded_hal::spi::SpiDevice;
+
+use super::{
+    MAX_CUSTOM_GLYPHS, OverflowPolicy, SegmentMap, Segments, font,
+    number::{MAX_DECIMAL_DIGITS, decimal_digits, max_magnitude},
+};
+use crate::{
+    MAX_DISPLAYS, NUM_DIGITS, Result,
+    driver::Max7219,
+    error::Error,
+    registers::{DecodeMode, Register},
+};
+
+/// One rendered line of digits, leftmost position first.
+type Line = [Segments; NUM_DIGITS as usize];
+
+/// Digit positions in the longest supported chain
+pub(crate) const CHAIN_DIGITS: usize = MAX_DISPLAYS * NUM_DIGITS as usize;
+
+/// Number of digits populated on each 7-segment module.
+///
+/// Smaller boards wire their digits to DIG0 upwards, so a 4-digit module
+/// uses DIG3 (leftmost) to DIG0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleSize {
+    Four,
+    Six,
+    #[default]
+    Eight,
+}
+
+impl ModuleSize {
+    pub const fn digits(self) -> u8 {
+        match self {
+            Self::Four => 4,
+            Self::Six => 6,
+            Self::Eight => 8,
+        }
+    }
+}
+
+/// Text and glyph oriented wrapper over [`Max7219`] for 7-segment modules.
+///
+/// Positions are counted from the left: position 0 is the leftmost digit
+/// (DIG7 on an 8-digit module) and position 7 the rightmost (DIG0). The
+/// driver must be in no-decode mode, which is what [`Max7219::init`]
+/// configures.
+///
+/// The number, decimal, clock and counter helpers extract digits by hand
+/// and never go through `core::fmt`, whose machinery costs several KB of
+/// flash on small Cortex-M0 parts. Only [`writer`](Self::writer) pulls it
+/// in.
+pub struct SevenSegment<SPI> {
+    driver: Max7219<SPI>,
+    glyphs: [(char, Segments); MAX_CUSTOM_GLYPHS],
+    glyph_count: usize,
+    overflow: OverflowPolicy,
+    thousands_separator: bool,
+    module_size: ModuleSize,
+    segment_map: SegmentMap,
+    fallback_glyph: Option<Segments>,
+}
+
+impl<SPI> SevenSegment<SPI>
+where
+    SPI: SpiDevice,
+{
+    pub fn new(driver: Max7219<SPI>) -> Self {
+        Self {
+            driver,
+            glyphs: [(' ', Segments::NONE); MAX_CUSTOM_GLYPHS],
+            glyph_count: 0,
+            overflow: OverflowPolicy::default(),
+            thousands_separator: false,
+            module_size: ModuleSize::default(),
+            segment_map: SegmentMap::IDENTITY,
+            fallback_glyph: None,
+        }
+    }
+
+    /// Translate every write through `map`, for boards with nonstandard
+    /// segment wiring. Glyphs and patterns keep using the standard layout.
+    pub fn with_segment_map(mut self, map: SegmentMap) -> Self {
+        self.segment_map = map;
+        self
+    }
+
+    pub fn set_segment_map(&mut self, map: SegmentMap) {
+        self.segment_map = map;
+    }
+
+    pub fn segment_map(&self) -> SegmentMap {
+        self.segment_map
+    }
+
+    /// Use modules with only 4 or 6 populated digits.
+    ///
+    /// Positions then run from 0 to `digits - 1`, text and numbers are
+    /// aligned within the populated digits and [`init`](Self::init) sets the
+    /// scan limit to match.
+    pub fn with_module_size(mut self, size: ModuleSize) -> Self {
+        self.module_size = size;
+        self
+    }
+
+    pub fn module_size(&self) -> ModuleSize {
+        self.module_size
+    }
+
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow = policy;
+    }
+
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    /// Light decimal points as thousands separators in numeric output,
+    /// e.g. `1.234.567`.
+    pub fn with_thousands_separator(mut self, enabled: bool) -> Self {
+        self.thousands_separator = enabled;
+        self
+    }
+
+    pub fn set_thousands_separator(&mut self, enabled: bool) {
+        self.thousands_separator = enabled;
+    }
+
+    pub fn thousands_separator(&self) -> bool {
+        self.thousands_separator
+    }
+
+    pub fn driver(&self) -> &Max7219<SPI> {
+        &self.driver
+    }
+
+    pub fn driver_mut(&mut self) -> &mut Max7219<SPI> {
+        &mut self.driver
+    }
+
+    pub fn into_inner(self) -> Max7219<SPI> {
+        self.driver
+    }
+
+    /// Initialize the driver and limit scanning to the populated digits.
+    pub fn init(&mut self) -> Result<()> {
+        self.driver.init()?;
+        if self.module_size != ModuleSize::Eight {
+            self.driver.set_scan_limit_all(self.module_size.digits())?;
+        }
+        Ok(())
+    }
+
+    /// Register a custom glyph for `c`, used by [`write_str`](Self::write_str)
+    /// and the [`fmt::Write`] adapter ahead of the built-in font.
+    ///
+    /// Registering a character that already has a custom glyph replaces it.
+    ///
+    /// # Errors
+    /// - [`Error::GlyphTableFull`] if [`MAX_CUSTOM_GLYPHS`] glyphs are already registered.
+    pub fn register_glyph(&mut self, c: char, segments: Segments) -> Result<()> {
+        if let Some(entry) = self.glyphs[..self.glyph_count]
+            .iter_mut()
+            .find(|(existing, _)| *existing == c)
+        {
+            entry.1 = segments;
+            return Ok(());
+        }
+
+        if self.glyph_count >= MAX_CUSTOM_GLYPHS {
+            return Err(Error::GlyphTableFull);
+        }
+        self.glyphs[self.glyph_count] = (c, segments);
+        self.glyph_count += 1;
+        Ok(())
+    }
+
+    /// Register every entry of a glyph table, e.g. one built with
+    /// [`glyphs!`](crate::glyphs), see [`register_glyph`](Self::register_glyph).
+    ///
+    /// # Errors
+    /// - [`Error::GlyphTableFull`] if the table doesn't fit; entries before
+    ///   the failing one stay registered.
+    pub fn register_glyphs(&mut self, glyphs: &[(char, Segments)]) -> Result<()> {
+        glyphs
+            .iter()
+            .try_for_each(|&(c, segments)| self.register_glyph(c, segments))
+    }
+
+    /// Pattern shown for characters without a custom or built-in glyph,
+    /// e.g. [`Segments::G`] for a dash. Without one, text output fails on
+    /// such characters.
+    pub fn with_fallback_glyph(mut self, fallback: Option<Segments>) -> Self {
+        self.fallback_glyph = fallback;
+        self
+    }
+
+    pub fn set_fallback_glyph(&mut self, fallback: Option<Segments>) {
+        self.fallback_glyph = fallback;
+    }
+
+    pub fn fallback_glyph(&self) -> Option<Segments> {
+        self.fallback_glyph
+    }
+
+    /// Remove every registered custom glyph.
+    pub fn clear_glyphs(&mut self) {
+        self.glyph_count = 0;
+    }
+
+    /// Resolve `c` to a segment pattern, preferring custom glyphs over the
+    /// built-in font.
+    pub fn glyph(&self, c: char) -> Option<Segments> {
+        self.glyphs[..self.glyph_count]
+            .iter()
+            .find(|(existing, _)| *existing == c)
+            .map(|&(_, segments)| segments)
+            .or_else(|| font::glyph(c))
+    }
+
+    /// Write a raw segment pattern at `position` (0 = leftmost digit).
+    pub fn write_segments(
+        &mut self,
+        device_index: usize,
+        position: u8,
+        segments: Segments,
+    ) -> Result<()> {
+        let digits = self.module_size.digits();
+        if position >= digits {
+            return Err(Error::InvalidDigit);
+        }
+        self.driver.write_raw_digit(
+            device_index,
+            digits - 1 - position,
+            self.segment_map.apply(segments),
+        )
+    }
+
+    /// Blank the digit at `position` (0 = leftmost digit).
+    pub fn clear_digit(&mut self, device_index: usize, position: u8) -> Result<()> {
+        self.write_segments(device_index, position, Segments::NONE)
+    }
+
+    /// Total number of digit positions across the chain.
+    pub fn digit_count(&self) -> usize {
+        self.driver.device_count() * self.device_digits()
+    }
+
+    /// Write a raw segment pattern at a chain-wide `position`.
+    ///
+    /// The chain is treated as one display of `8 * device_count` digits:
+    /// positions 0-7 are device 0 (leftmost first), 8-15 device 1, and so on.
+    /// Smaller [`ModuleSize`]s shrink each device's share accordingly.
+    pub fn write_digit(&mut self, position: usize, segments: Segments) -> Result<()> {
+        if position >= self.digit_count() {
+            return Err(Error::InvalidDigit);
+        }
+        let digits = self.device_digits();
+        self.write_segments(position / digits, (position % digits) as u8, segments)
+    }
+
+    /// Show `text` left-aligned on one device, blanking the remaining digits.
+    ///
+    /// A `.` lights the decimal point of the preceding character instead of
+    /// taking a digit of its own.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if a character has no custom or built-in glyph.
+    /// - [`Error::TextTooLong`] if the text needs more digits than the module has.
+    pub fn write_str(&mut self, device_index: usize, text: &str) -> Result<()> {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        let line = &mut line[..self.device_digits()];
+        self.render_text(text, line)?;
+        self.write_line(device_index, line)
+    }
+
+    /// Show `text` left-aligned across the whole chain, see [`write_digit`](Self::write_digit)
+    /// for the position order.
+    pub fn write_chain_str(&mut self, text: &str) -> Result<()> {
+        let mut line = [Segments::NONE; CHAIN_DIGITS];
+        let width = self.digit_count();
+        self.render_text(text, &mut line[..width])?;
+        self.write_chain_line(&line[..width])
+    }
+
+    /// Show `value` right-aligned on one device.
+    ///
+    /// Numbers wider than the display are handled according to the
+    /// configured [`OverflowPolicy`].
+    ///
+    /// # Errors
+    /// - [`Error::Overflow`] if the number does not fit and the policy is
+    ///   [`OverflowPolicy::Error`].
+    pub fn write_number(&mut self, device_index: usize, value: i32) -> Result<()> {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        let line = &mut line[..self.device_digits()];
+        self.render_number(value, line)?;
+        self.write_line(device_index, line)
+    }
+
+    /// Show the fixed-point value `mantissa / 10^decimals` right-aligned on
+    /// one device, e.g. `write_decimal(0, -2345, 2)` shows `-23.45`.
+    ///
+    /// Overflow is handled like [`write_number`](Self::write_number).
+    pub fn write_decimal(
+        &mut self,
+        device_index: usize,
+        mantissa: i32,
+        decimals: u8,
+    ) -> Result<()> {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        let line = &mut line[..self.device_digits()];
+        self.render_decimal(mantissa, decimals, line)?;
+        self.write_line(device_index, line)
+    }
+
+    /// Show `value` right-aligned across the whole chain.
+    ///
+    /// All digits are rendered first and then sent one digit register at a
+    /// time across every device, so an 8-digit-module chain of any length
+    /// updates in 8 transactions.
+    pub fn write_chain_number(&mut self, value: i32) -> Result<()> {
+        let mut line = [Segments::NONE; CHAIN_DIGITS];
+        let width = self.digit_count();
+        self.render_number(value, &mut line[..width])?;
+        self.write_chain_line(&line[..width])
+    }
+
+    /// Create a [`fmt::Write`] adapter that renders formatted output onto one
+    /// device, e.g. `write!(display.writer(0), "{:.1}", temp)`.
+    ///
+    /// This links in `core::fmt`; on flash-constrained targets prefer
+    /// [`write_number`](Self::write_number) and
+    /// [`write_decimal`](Self::write_decimal), which don't.
+    pub fn writer(&mut self, device_index: usize) -> DigitWriter<'_, SPI> {
+        DigitWriter {
+            display: self,
+            device_index,
+            line: [Segments::NONE; NUM_DIGITS as usize],
+            len: 0,
+        }
+    }
+
+    /// Populated digits per device.
+    pub(crate) fn device_digits(&self) -> usize {
+        usize::from(self.module_size.digits())
+    }
+
+    pub(crate) fn char_glyph(&self, c: char) -> Result<Segments> {
+        self.glyph(c)
+            .or(self.fallback_glyph)
+            .ok_or(Error::UnsupportedChar)
+    }
+
+    /// Render `text` left-aligned, returning the number of digits used.
+    pub(crate) fn render_text(&self, text: &str, line: &mut [Segments]) -> Result<usize> {
+        let mut len = 0;
+        for c in text.chars() {
+            self.push_char(line, &mut len, c)?;
+        }
+        Ok(len)
+    }
+
+    pub(crate) fn render_number(&self, value: i32, line: &mut [Segments]) -> Result<()> {
+        self.render_decimal(value, 0, line)
+    }
+
+    pub(crate) fn render_decimal(
+        &self,
+        value: i32,
+        decimals: u8,
+        line: &mut [Segments],
+    ) -> Result<()> {
+        let width = line.len();
+        let decimals = usize::from(decimals);
+        if decimals >= MAX_DECIMAL_DIGITS {
+            return Err(Error::Overflow);
+        }
+        let negative = value < 0;
+        let mut digits = [0; MAX_DECIMAL_DIGITS];
+        // Keep at least one digit before the decimal point, e.g. 0.05
+        let mut len = decimal_digits(value.unsigned_abs(), &mut digits).max(decimals + 1);
+
+        if len + usize::from(negative) > width {
+            match self.overflow {
+                OverflowPolicy::Error => return Err(Error::Overflow),
+                // A 1-digit field has no room for both the sign and a digit
+                OverflowPolicy::Saturate if negative && width < 2 => {
+                    line.fill(self.char_glyph('-')?);
+                    return Ok(());
+                }
+                OverflowPolicy::Saturate => {
+                    len = decimal_digits(max_magnitude(width, negative), &mut digits);
+                }
+                OverflowPolicy::Dashes => {
+                    line.fill(self.char_glyph('-')?);
+                    return Ok(());
+                }
+            }
+        }
+
+        for (index, (slot, digit)) in line.iter_mut().rev().zip(&digits[..len]).enumerate() {
+            *slot = self.char_glyph(char::from(b'0' + digit))?;
+            let integer_index = index.wrapping_sub(decimals);
+            if (decimals > 0 && index == decimals)
+                || (self.thousands_separator && index > decimals && integer_index % 3 == 0)
+            {
+                *slot |= Segments::DP;
+            }
+        }
+        if negative {
+            line[width - 1 - len] = self.char_glyph('-')?;
+        }
+        Ok(())
+    }
+
+    fn push_char(&self, line: &mut [Segments], len: &mut usize, c: char) -> Result<()> {
+        if c == '.'
+            && let Some(prev) = len.checked_sub(1).map(|i| &mut line[i])
+            && !prev.contains(Segments::DP)
+        {
+            *prev |= Segments::DP;
+            return Ok(());
+        }
+
+        let segments = if c == '.' {
+            Segments::DP
+        } else {
+            self.char_glyph(c)?
+        };
+
+        if *len >= line.len() {
+            return Err(Error::TextTooLong);
+        }
+        line[*len] = segments;
+        *len += 1;
+        Ok(())
+    }
+
+    /// Write a line starting at position 0, skipping blank digits beyond
+    /// the scan limit so a partly scanned device can still show short text.
+    pub(crate) fn write_line(&mut self, device_index: usize, line: &[Segments]) -> Result<()> {
+        let digits = self.module_size.digits();
+        if (0..digits).zip(line.iter()).any(|(position, segments)| {
+            !segments.is_empty() && !self.is_scanned(device_index, position)
+        }) {
+            return Err(Error::DigitNotScanned);
+        }
+        for (position, segments) in (0..digits).zip(line.iter()) {
+            if self.is_scanned(device_index, position) {
+                self.write_segments(device_index, position, *segments)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_scanned(&self, device_index: usize, position: u8) -> bool {
+        self.driver
+            .is_scanned(device_index, self.module_size.digits() - 1 - position)
+    }
+
+    /// Write a chain-wide line one digit register at a time, digit n of
+    /// every device going out in a single transaction: a full line takes
+    /// one transaction per module digit however long the chain.
+    ///
+    /// Every device is checked before the first write; blank digits beyond
+    /// the scan limit are skipped as in [`write_line`](Self::write_line).
+    pub(crate) fn write_chain_line(&mut self, line: &[Segments]) -> Result<()> {
+        if line.len() > self.digit_count() {
+            return Err(Error::TextTooLong);
+        }
+        let digits = self.module_size.digits();
+        for (index, segments) in line.iter().enumerate() {
+            let device_index = index / usize::from(digits);
+            let position = (index % usize::from(digits)) as u8;
+            if !self.is_scanned(device_index, position) {
+                if !segments.is_empty() {
+                    return Err(Error::DigitNotScanned);
+                }
+            } else if self.driver.is_decoded(device_index, digits - 1 - position) {
+                return Err(Error::DecodeModeMismatch);
+            }
+        }
+        self.driver.check_initialized()?;
+
+        let count = self.driver.device_count();
+        for position in 0..digits {
+            let register = Register::try_digit(digits - 1 - position)?;
+            let mut ops = [(Register::NoOp, 0); MAX_DISPLAYS];
+            let mut touched = false;
+            for (device_index, op) in ops[..count].iter_mut().enumerate() {
+                let index = device_index * usize::from(digits) + usize::from(position);
+                if let Some(&segments) = line.get(index)
+                    && self.is_scanned(device_index, position)
+                {
+                    *op = (register, self.segment_map.apply(segments));
+                    touched = true;
+                }
+            }
+            if touched {
+                self.driver.write_all_registers(&ops[..count])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// [`fmt::Write`] adapter returned by [`SevenSegment::writer`].
+///
+/// Output accumulates from the leftmost digit; every `write_str` call
+/// refreshes the whole device so unused digits stay blank.
+pub struct DigitWriter<'a, SPI> {
+    display: &'a mut SevenSegment<SPI>,
+    device_index: usize,
+    line: Line,
+    len: usize,
+}
+
+impl<SPI> fmt::Write for DigitWriter<'_, SPI>
+where
+    SPI: SpiDevice,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let digits = self.display.device_digits();
+        for c in s.chars() {
+            self.display
+                .push_char(&mut self.line[..digits], &mut self.len, c)
+                .map_err(|_| fmt::Error)?;
+        }
+        self.display
+            .write_line(self.device_index, &self.line[..digits])
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl<SPI> Max7219<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Wrap the driver in a [`SevenSegment`] display, see
+    /// [`SevenSegment::new`]; [`SevenSegment::into_inner`] gives it back.
+    ///
+    /// Devices set to Code B decoding are switched to the no-decode mode
+    /// the wrapper's glyphs need first.
+    pub fn into_seven_segment(mut self) -> Result<SevenSegment<SPI>> {
+        let mut decoded = false;
+        for device_index in 0..self.device_count() {
+            decoded |= self.decode_mask(device_index)? != 0;
+        }
+        if decoded {
+            self.set_decode_mode_all(DecodeMode::NoDecode)?;
+        }
+        Ok(SevenSegment::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::EmulatedSpi;
+    use core::fmt::Write;
+    use embedded_hal_mock::eh1::{spi::Mock as SpiMock, spi::Transaction};
+
+    fn line_transactions(line: [u8; 8]) -> Vec<Transaction<u8>> {
+        let mut expected = Vec::new();
+        for (position, value) in line.iter().enumerate() {
+            let register = Register::try_digit(7 - position as u8).unwrap();
+            expected.push(Transaction::transaction_start());
+            expected.push(Transaction::write_vec(vec![register.addr(), *value]));
+            expected.push(Transaction::transaction_end());
+        }
+        expected
+    }
+
+    #[test]
+    fn test_write_str() {
+        let expected = line_transactions([0x30, 0x6D | 0x80, 0x79, 0, 0, 0, 0, 0]);
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display.write_str(0, "12.3").expect("should write text");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_str_custom_glyph() {
+        let expected = line_transactions([0x63, 0x4E, 0, 0, 0, 0, 0, 0]);
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display
+            .register_glyph('*', Segments::A | Segments::B | Segments::F | Segments::G)
+            .expect("should register glyph");
+        display.write_str(0, "*C").expect("should write text");
+        spi.done();
+    }
+
+    #[test]
+    fn test_register_glyphs() {
+        const GLYPHS: [(char, Segments); 2] = crate::glyphs! {
+            'n' => [C, E, G],
+            '0' => [G],
+        };
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        display.register_glyphs(&GLYPHS).unwrap();
+        display.write_str(0, "n0").unwrap();
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[7], 0x15);
+        assert_eq!(digits[6], 0x01);
+    }
+
+    #[test]
+    fn test_custom_glyph_overrides_builtin() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+
+        display.register_glyph('0', Segments::G).unwrap();
+        assert_eq!(display.glyph('0'), Some(Segments::G));
+        display.clear_glyphs();
+        assert_eq!(display.glyph('0'), font::glyph('0'));
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_str_fallback_glyph() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        assert_eq!(display.write_str(0, "5€"), Err(Error::UnsupportedChar));
+
+        display.set_fallback_glyph(Some(Segments::G));
+        display.write_str(0, "5€").unwrap();
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[7], 0x5B);
+        assert_eq!(digits[6], 0x01);
+    }
+
+    #[test]
+    fn test_glyph_table_full() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+
+        for c in ('a'..='z').take(MAX_CUSTOM_GLYPHS) {
+            display.register_glyph(c, Segments::A).unwrap();
+        }
+        // Replacing an existing glyph still works once the table is full
+        assert_eq!(display.register_glyph('a', Segments::B), Ok(()));
+        assert_eq!(
+            display.register_glyph('#', Segments::A),
+            Err(Error::GlyphTableFull)
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_str_errors() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+
+        assert_eq!(display.write_str(0, "#"), Err(Error::UnsupportedChar));
+        assert_eq!(display.write_str(0, "123456789"), Err(Error::TextTooLong));
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_number() {
+        let expected = line_transactions([0, 0, 0, 0, 0x01, 0x30, 0x6D, 0x79]);
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display.write_number(0, -123).expect("should write number");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_number_overflow_error() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+
+        assert_eq!(display.overflow_policy(), OverflowPolicy::Error);
+        assert_eq!(display.write_number(0, 123_456_789), Err(Error::Overflow));
+        assert_eq!(display.write_number(0, -12_345_678), Err(Error::Overflow));
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_number_overflow_saturate() {
+        let mut expected = line_transactions([0x7B; 8]);
+        expected.extend(line_transactions([
+            0x01, 0x7B, 0x7B, 0x7B, 0x7B, 0x7B, 0x7B, 0x7B,
+        ]));
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized())
+            .with_overflow_policy(OverflowPolicy::Saturate);
+
+        display.write_number(0, i32::MAX).expect("should saturate");
+        display.write_number(0, i32::MIN).expect("should saturate");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_number_overflow_dashes() {
+        let expected = line_transactions([0x01; 8]);
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display.set_overflow_policy(OverflowPolicy::Dashes);
+        display
+            .write_number(0, 100_000_000)
+            .expect("should show dashes");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_number_thousands_separator() {
+        // 1.234.567
+        let expected =
+            line_transactions([0, 0x30 | 0x80, 0x6D, 0x79, 0x33 | 0x80, 0x5B, 0x5F, 0x70]);
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized())
+            .with_thousands_separator(true);
+
+        assert!(display.thousands_separator());
+        display
+            .write_number(0, 1_234_567)
+            .expect("should write number");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_decimal() {
+        let mut expected = line_transactions([0, 0, 0, 0, 0x01, 0x6D, 0x79 | 0x80, 0x33]);
+        // Leading zero before the decimal point
+        expected.extend(line_transactions([0, 0, 0, 0, 0, 0x7E | 0x80, 0x7E, 0x5B]));
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display
+            .write_decimal(0, -234, 1)
+            .expect("should write decimal");
+        display
+            .write_decimal(0, 5, 2)
+            .expect("should write decimal");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_decimal_overflow() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+
+        assert_eq!(display.write_decimal(0, 1, 8), Err(Error::Overflow));
+        assert_eq!(display.write_decimal(0, 1, 10), Err(Error::Overflow));
+        spi.done();
+    }
+
+    #[test]
+    fn test_clear_digit() {
+        let expected = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![Register::Digit7.addr(), 0x00]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        display.clear_digit(0, 0).expect("should clear digit");
+        assert_eq!(display.clear_digit(0, 8), Err(Error::InvalidDigit));
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_digit_global_position() {
+        let expected = [
+            Transaction::transaction_start(),
+            Transaction::write_vec(vec![0x00, 0x00, Register::Digit5.addr(), 0x30]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(
+            Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap(),
+        );
+
+        assert_eq!(display.digit_count(), 16);
+        display
+            .write_digit(10, Segments::B | Segments::C)
+            .expect("should write digit");
+        assert_eq!(
+            display.write_digit(16, Segments::A),
+            Err(Error::InvalidDigit)
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_chain_number() {
+        // One transaction per digit register, carrying both devices' digit
+        let first = [0, 0, 0, 0, 0, 0, 0, 0x30];
+        let second = [0x7E, 0x7E, 0x7E, 0x7E, 0x7E, 0x7E, 0x7E, 0x30];
+        let mut expected = Vec::new();
+        for (position, (&left, &right)) in first.iter().zip(&second).enumerate() {
+            let register = Register::try_digit(7 - position as u8).unwrap().addr();
+            expected.push(Transaction::transaction_start());
+            expected.push(Transaction::write_vec(vec![
+                register, left, register, right,
+            ]));
+            expected.push(Transaction::transaction_end());
+        }
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(
+            Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(2)
+                .unwrap(),
+        );
+
+        display
+            .write_chain_number(100_000_001)
+            .expect("should span both devices");
+        spi.done();
+    }
+
+    #[test]
+    fn test_chain_line_digit_passes() {
+        let mut spi = EmulatedSpi::new(4).unwrap();
+        {
+            let driver = Max7219::new(&mut spi)
+                .assume_initialized()
+                .with_device_count(4)
+                .unwrap();
+            let mut display = SevenSegment::new(driver);
+            display.driver_mut().set_device_scan_limit(0, 4).unwrap();
+            let before = display.driver().stats().transactions;
+            display.write_chain_number(-12_345_678).unwrap();
+            assert_eq!(display.driver().stats().transactions - before, 8);
+
+            // Rejected before anything is sent
+            assert_eq!(display.write_chain_str("8"), Err(Error::DigitNotScanned));
+            assert_eq!(display.driver().stats().transactions - before, 8);
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.digits(2).unwrap(), [1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(emulator.digits(3).unwrap()[0], 0x7F);
+    }
+
+    #[test]
+    fn test_write_chain_str_too_long() {
+        let mut spi = SpiMock::new(&[]);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).with_device_count(2).unwrap());
+
+        assert_eq!(
+            display.write_chain_str("0123456789abcdefg"),
+            Err(Error::TextTooLong)
+        );
+        spi.done();
+    }
+
+    #[test]
+    fn test_fmt_writer() {
+        let mut expected = line_transactions([0x30, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend(line_transactions([0x30, 0x6D, 0, 0, 0, 0, 0, 0]));
+        let mut spi = SpiMock::new(&expected);
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+
+        let mut writer = display.writer(0);
+        write!(writer, "{}", 1).expect("should format");
+        write!(writer, "{}", 2).expect("should format");
+        spi.done();
+    }
+
+    #[test]
+    fn test_write_respects_scan_limit() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut display = SevenSegment::new(Max7219::new(&mut spi).assume_initialized());
+            display.driver_mut().set_device_scan_limit(0, 4).unwrap();
+
+            // Only DIG0-DIG3, the rightmost four positions, are scanned
+            display.write_number(0, 42).unwrap();
+            assert_eq!(display.write_str(0, "HI"), Err(Error::DigitNotScanned));
+            assert_eq!(
+                display.write_segments(0, 0, Segments::G),
+                Err(Error::DigitNotScanned)
+            );
+        }
+        // scan limit + the four scanned digits
+        assert_eq!(spi.transactions(), 1 + 4);
+        assert_eq!(spi.emulator().digits(0).unwrap()[..2], [0x6D, 0x33]);
+    }
+
+    #[test]
+    fn test_four_digit_module() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut display =
+                SevenSegment::new(Max7219::new(&mut spi)).with_module_size(ModuleSize::Four);
+            display.init().unwrap();
+            assert_eq!(display.driver().scan_limit(0), Ok(4));
+            assert_eq!(display.digit_count(), 4);
+
+            display.write_str(0, "12").unwrap();
+            assert_eq!(display.write_str(0, "12345"), Err(Error::TextTooLong));
+            assert_eq!(display.write_number(0, 12345), Err(Error::Overflow));
+            assert_eq!(
+                display.write_segments(0, 4, Segments::G),
+                Err(Error::InvalidDigit)
+            );
+        }
+        // Position 0 is DIG3 on a 4-digit module
+        assert_eq!(
+            spi.emulator().digits(0).unwrap(),
+            [0, 0, 0x6D, 0x30, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_six_digit_chain_number() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        {
+            let driver = Max7219::new(&mut spi).with_device_count(2).unwrap();
+            let mut display = SevenSegment::new(driver).with_module_size(ModuleSize::Six);
+            display.init().unwrap();
+            display.write_chain_number(1_234_567).unwrap();
+        }
+        let emulator = spi.emulator();
+        // "     1" on device 0, "234567" on device 1, each on DIG5-DIG0
+        assert_eq!(emulator.digits(0).unwrap()[..6], [0x30, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            emulator.digits(1).unwrap()[..6],
+            [0x70, 0x5F, 0x5B, 0x33, 0x79, 0x6D]
+        );
+    }
+
+    #[test]
+    fn test_segment_map_applied() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let map = SegmentMap::new([6, 1, 2, 3, 4, 5, 0, 7]).unwrap();
+            let mut display = SevenSegment::new(Max7219::new(&mut spi)).with_segment_map(map);
+            display.init().unwrap();
+            display.write_str(0, "-7.").unwrap();
+        }
+        let digits = spi.emulator().digits(0).unwrap();
+        // '-' is G only, lands on the A bit; '7' is A B C, A lands on G
+        assert_eq!(digits[7], 0x40);
+        assert_eq!(digits[6], 0x80 | 0x31);
+    }
+
+    #[test]
+    fn test_into_seven_segment_clears_decode_mode() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        {
+            let mut driver = Max7219::new(&mut spi);
+            driver.init().unwrap();
+            driver.set_decode_mode_all(DecodeMode::AllDigits).unwrap();
+            let mut display = driver.into_seven_segment().unwrap();
+            assert_eq!(display.driver().decode_mask(0), Ok(0));
+            display.write_str(0, "1").unwrap();
+            display.into_inner();
+        }
+        let emulator = spi.emulator();
+        assert_eq!(emulator.device(0).unwrap().decode_mode, 0);
+        assert_eq!(emulator.digits(0).unwrap()[7], 0x30);
+    }
+}
@@ -0,0 +1,75 @@
+use ::fixed::{FixedI32, types::extra::LeEqU32};
+use embedded_hal::spi::SpiDevice;
+
+use super::SevenSegment;
+use crate::Result;
+
+impl<SPI> SevenSegment<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Show a `fixed` crate value with `decimals` fractional digits,
+    /// rounding half away from zero, e.g. a sensor's `I16F16` reading.
+    ///
+    /// Overflow is handled like [`write_number`](Self::write_number).
+    pub fn write_fixed<Frac>(
+        &mut self,
+        device_index: usize,
+        value: FixedI32<Frac>,
+        decimals: u8,
+    ) -> Result<()>
+    where
+        Frac: LeEqU32,
+    {
+        let mantissa = fixed_mantissa(value.to_bits(), FixedI32::<Frac>::FRAC_NBITS, decimals);
+        self.write_decimal(device_index, mantissa, decimals)
+    }
+}
+
+/// Convert raw fixed-point `bits` with `frac_bits` fractional bits to a
+/// decimal mantissa with `decimals` fractional digits, saturating at the
+/// `i32` range.
+fn fixed_mantissa(bits: i32, frac_bits: u32, decimals: u8) -> i32 {
+    let scaled = i128::from(bits) * 10i128.pow(u32::from(decimals.min(9)));
+    let denominator = 1i128 << frac_bits;
+    let half = if scaled < 0 {
+        -denominator / 2
+    } else {
+        denominator / 2
+    };
+    let rounded = (scaled + half) / denominator;
+    rounded.clamp(i128::from(i32::MIN), i128::from(i32::MAX)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixed::types::I16F16;
+
+    #[test]
+    fn test_fixed_mantissa() {
+        // 1.5 with 16 fractional bits
+        assert_eq!(fixed_mantissa(0x0001_8000, 16, 1), 15);
+        assert_eq!(fixed_mantissa(-0x0001_8000, 16, 2), -150);
+        // 1/3 rounds to 0.33
+        assert_eq!(fixed_mantissa(0x5555, 16, 2), 33);
+        assert_eq!(fixed_mantissa(i32::MAX, 0, 9), i32::MAX);
+    }
+
+    #[test]
+    fn test_write_fixed() {
+        use crate::{driver::Max7219, host::EmulatedSpi};
+
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        display
+            .write_fixed(0, I16F16::from_bits(-0x0002_4000), 2)
+            .unwrap();
+        // -2.25 right-aligned
+        assert_eq!(
+            spi.emulator().digits(0).unwrap(),
+            [0x5B, 0x6D, 0x6D | 0x80, 0x01, 0, 0, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use super::Segments;
+
+/// Look up the built-in 7-segment pattern for `c`.
+///
+/// Covers the decimal digits, every Latin letter that can be approximated
+/// on seven segments (upper and lower case fall back to each other where only
+/// one form is drawable), a handful of punctuation marks and the symbols
+/// `°`, `µ` and `‾`. Returns `None`
+/// for characters the built-in font cannot show.
+pub fn glyph(c: char) -> Option<Segments> {
+    let bits = match c {
+        ' ' => 0x00,
+        '0' | 'O' | 'D' => 0x7E,
+        '1' => 0x30,
+        '2' | 'Z' | 'z' => 0x6D,
+        '3' => 0x79,
+        '4' => 0x33,
+        '5' | 'S' | 's' => 0x5B,
+        '6' => 0x5F,
+        '7' => 0x70,
+        '8' | 'B' => 0x7F,
+        '9' => 0x7B,
+        'A' | 'a' => 0x77,
+        'b' => 0x1F,
+        'C' => 0x4E,
+        'c' => 0x0D,
+        'd' => 0x3D,
+        'E' | 'e' => 0x4F,
+        'F' | 'f' => 0x47,
+        'G' | 'g' => 0x5E,
+        'H' => 0x37,
+        'h' => 0x17,
+        'I' => 0x06,
+        'i' => 0x10,
+        'J' | 'j' => 0x3C,
+        'K' | 'k' => 0x57,
+        'L' | 'l' => 0x0E,
+        'M' => 0x76,
+        'm' => 0x54,
+        'N' | 'n' => 0x15,
+        'o' => 0x1D,
+        'P' | 'p' => 0x67,
+        'Q' | 'q' => 0x73,
+        'R' | 'r' => 0x05,
+        'T' | 't' => 0x0F,
+        'U' => 0x3E,
+        'u' | 'v' | 'V' => 0x1C,
+        'Y' | 'y' => 0x3B,
+        '-' => 0x01,
+        '_' => 0x08,
+        '=' => 0x09,
+        '\'' => 0x02,
+        '"' => 0x22,
+        '[' | '(' => 0x4E,
+        ']' | ')' => 0x78,
+        '°' => 0x63,
+        'µ' | 'μ' => 0x1C,
+        '‾' | '¯' => 0x40,
+        _ => return None,
+    };
+    Some(Segments::from_bits(bits))
+}
+
+/// Build a const table of custom 7-segment glyphs, one `char => [segments]`
+/// entry per character, for
+/// [`SevenSegment::register_glyphs`](super::SevenSegment::register_glyphs).
+/// Segments are named after the [`Segments`] constants.
+///
+/// ```
+/// use max7219_driver_project::{glyphs, seven_segment::Segments};
+///
+/// const EXTRA: [(char, Segments); 2] = glyphs! {
+///     'r' => [E, G],
+///     '=' => [D, G],
+/// };
+/// assert_eq!(EXTRA[1].1, Segments::D | Segments::G);
+/// ```
+#[macro_export]
+macro_rules! glyphs {
+    ($($c:literal => [$($segment:ident),* $(,)?]),* $(,)?) => {
+        [$((
+            $c,
+            $crate::seven_segment::Segments::NONE
+                $(.union($crate::seven_segment::Segments::$segment))*,
+        )),*]
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_glyphs() {
+        assert_eq!(glyph('0'), Some(Segments::from_bits(0x7E)));
+        assert_eq!(glyph('1'), Some(Segments::B | Segments::C));
+        assert_eq!(glyph('8'), Some(Segments::from_bits(0x7F)));
+    }
+
+    #[test]
+    fn test_letter_fallbacks() {
+        assert_eq!(glyph('a'), glyph('A'));
+        assert_eq!(glyph('S'), glyph('5'));
+        assert_ne!(glyph('c'), glyph('C'));
+        assert_eq!(glyph('k'), glyph('K'));
+        assert_ne!(glyph('m'), glyph('M'));
+    }
+
+    #[test]
+    fn test_unsupported_glyph() {
+        assert_eq!(glyph('W'), None);
+        assert_eq!(glyph('#'), None);
+    }
+
+    #[test]
+    fn test_glyphs_macro() {
+        const TABLE: [(char, Segments); 3] = glyphs! {
+            'r' => [E, G],
+            '_' => [],
+            'P' => [A, B, E, F, G, DP],
+        };
+        assert_eq!(TABLE[0], ('r', Segments::E | Segments::G));
+        assert_eq!(TABLE[1], ('_', Segments::NONE));
+        assert_eq!(TABLE[2].1, glyph('P').unwrap().with_dp());
+    }
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(glyph('µ'), glyph('u'));
+        assert_eq!(glyph('μ'), glyph('µ'));
+        assert_eq!(glyph('‾'), Some(Segments::A));
+        assert_eq!(glyph('€'), None);
+    }
+}
@@ -0,0 +1,30 @@
+mod bargraph;
+mod blink;
+mod clock;
+mod counter;
+mod display;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+mod font;
+mod notation;
+mod number;
+mod pager;
+mod scoreboard;
+mod segments;
+mod window;
+
+pub use bargraph::{BAR_STEPS_PER_DIGIT, MAX_DIGIT_LEVEL};
+pub use blink::DigitBlink;
+pub use clock::{CalendarDate, ClockFormat, DateFormat, TimeOfDay};
+pub use counter::Counter;
+pub use display::{DigitWriter, ModuleSize, SevenSegment};
+pub use font::glyph;
+pub use notation::Notation;
+pub use number::{NUMBER_STR_LEN, OverflowPolicy, number_str};
+pub use pager::Pager;
+pub use scoreboard::{MAX_FIELDS, Scoreboard};
+pub use segments::{SegmentMap, Segments};
+pub use window::{Align, Window};
+
+/// Maximum number of user-defined glyphs a [`SevenSegment`] display can hold
+pub const MAX_CUSTOM_GLYPHS: usize = 16;
@@ -0,0 +1,295 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{OverflowPolicy, Segments, SevenSegment};
+use crate::{NUM_DIGITS, Result, error::Error};
+
+/// Significant decimal digits an `f32` reliably round-trips
+const F32_DIGITS: usize = 6;
+
+/// How [`SevenSegment::write_auto`] shows values that don't fit as plain decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// `1.2345E6`, one digit before the decimal point
+    #[default]
+    Scientific,
+    /// `123.45E3`, exponent always a multiple of three
+    Engineering,
+    /// `123.456k`, SI prefix letter (p, n, u, m, k, M, G, T) instead of an
+    /// exponent; falls back to engineering notation outside that range
+    SiPrefix,
+}
+
+impl<SPI> SevenSegment<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Show `value` right-aligned on one device, auto-ranging into `notation`
+    /// when it is too large or too small for plain decimals.
+    ///
+    /// Values of at least 0.01 whose integer part fits are shown plainly with
+    /// as many decimals as fit (trailing zeros dropped). Values that cannot
+    /// be shown at all are treated as overflow; [`OverflowPolicy::Saturate`]
+    /// behaves like [`OverflowPolicy::Dashes`] here since there is no
+    /// largest value to clamp to.
+    pub fn write_auto(
+        &mut self,
+        device_index: usize,
+        value: f32,
+        notation: Notation,
+    ) -> Result<()> {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        let line = &mut line[..self.device_digits()];
+        match self.render_auto(value, notation, line) {
+            Err(Error::Overflow) if self.overflow_policy() != OverflowPolicy::Error => {
+                line.fill(self.char_glyph('-')?);
+            }
+            result => result?,
+        }
+        self.write_line(device_index, line)
+    }
+
+    fn render_auto(&self, value: f32, notation: Notation, line: &mut [Segments]) -> Result<()> {
+        if !value.is_finite() {
+            return Err(Error::Overflow);
+        }
+        let negative = value < 0.0;
+        let magnitude = if negative { -value } else { value };
+        let sign = usize::from(negative);
+        let width = line.len();
+
+        if magnitude == 0.0 {
+            return self.render_decimal(0, 0, line);
+        }
+        let (mut mantissa, mut exponent) = normalize(magnitude);
+
+        // Plain decimal when the integer part fits and the value is not tiny
+        let integer_digits = usize::try_from(exponent + 1).unwrap_or(0).max(1);
+        if exponent >= -2 && integer_digits + sign <= width {
+            let decimals =
+                (width - sign - integer_digits).min(F32_DIGITS.saturating_sub(integer_digits));
+            let (digits, decimals) = round_trimmed(magnitude, decimals, width - sign);
+            return self.render_decimal(signed(digits, negative), decimals as u8, line);
+        }
+
+        for _ in 0..2 {
+            let scale = match notation {
+                Notation::Scientific => exponent,
+                Notation::Engineering | Notation::SiPrefix => exponent - exponent.rem_euclid(3),
+            };
+            let prefix = match notation {
+                Notation::SiPrefix => si_prefix(scale),
+                _ => None,
+            };
+
+            let mut suffix = [' '; 4];
+            let suffix_len = match prefix {
+                Some(letter) => {
+                    suffix[0] = letter;
+                    1
+                }
+                None => exponent_suffix(scale, &mut suffix),
+            };
+
+            let integer_digits = (exponent - scale + 1) as usize;
+            let available = width
+                .checked_sub(sign + suffix_len)
+                .filter(|available| *available >= integer_digits)
+                .ok_or(Error::Overflow)?;
+            let decimals = (available - integer_digits).min(F32_DIGITS - integer_digits);
+
+            let scaled = mantissa * pow10(integer_digits - 1);
+            let (digits, decimals) = round_trimmed(scaled, decimals, available);
+            if digits >= pow10_u32(integer_digits + decimals) {
+                // Rounding carried into a new digit, e.g. 9.9996 -> 10.000
+                mantissa = 1.0;
+                exponent += 1;
+                continue;
+            }
+
+            let (number, letters) = line.split_at_mut(width - suffix_len);
+            self.render_decimal(signed(digits, negative), decimals as u8, number)?;
+            for (slot, letter) in letters.iter_mut().zip(&suffix[..suffix_len]) {
+                *slot = self.char_glyph(*letter)?;
+            }
+            return Ok(());
+        }
+        Err(Error::Overflow)
+    }
+}
+
+/// Split a positive finite value into a mantissa in `1.0..10.0` and a base-10 exponent.
+fn normalize(mut magnitude: f32) -> (f32, i32) {
+    let mut exponent = 0;
+    while magnitude >= 10.0 {
+        magnitude /= 10.0;
+        exponent += 1;
+    }
+    while magnitude < 1.0 {
+        magnitude *= 10.0;
+        exponent -= 1;
+    }
+    (magnitude, exponent)
+}
+
+/// Round `value` to `decimals` fractional digits, dropping trailing zeros.
+///
+/// Returns the digits as an integer and the number of decimals kept. Fewer
+/// decimals are used if rounding would need more than `max_digits` digits.
+fn round_trimmed(value: f32, mut decimals: usize, max_digits: usize) -> (u32, usize) {
+    loop {
+        let digits = (value * pow10(decimals) + 0.5) as u32;
+        if decimals > 0 && digits >= pow10_u32(max_digits) {
+            decimals -= 1;
+            continue;
+        }
+        let mut digits = digits;
+        while decimals > 0 && digits.is_multiple_of(10) {
+            digits /= 10;
+            decimals -= 1;
+        }
+        return (digits, decimals);
+    }
+}
+
+fn signed(digits: u32, negative: bool) -> i32 {
+    let value = digits.min(i32::MAX as u32) as i32;
+    if negative { -value } else { value }
+}
+
+fn pow10(exponent: usize) -> f32 {
+    (0..exponent).fold(1.0, |acc, _| acc * 10.0)
+}
+
+fn pow10_u32(exponent: usize) -> u32 {
+    (0..exponent.min(9)).fold(1, |acc, _| acc * 10)
+}
+
+fn si_prefix(scale: i32) -> Option<char> {
+    match scale {
+        -12 => Some('p'),
+        -9 => Some('n'),
+        -6 => Some('u'),
+        -3 => Some('m'),
+        3 => Some('k'),
+        6 => Some('M'),
+        9 => Some('G'),
+        12 => Some('T'),
+        _ => None,
+    }
+}
+
+/// Write `E<exponent>` into `suffix`, returning its length.
+fn exponent_suffix(exponent: i32, suffix: &mut [char; 4]) -> usize {
+    suffix[0] = 'E';
+    let mut len = 1;
+    if exponent < 0 {
+        suffix[len] = '-';
+        len += 1;
+    }
+    let magnitude = exponent.unsigned_abs();
+    if magnitude >= 10 {
+        suffix[len] = char::from(b'0' + (magnitude / 10 % 10) as u8);
+        len += 1;
+    }
+    suffix[len] = char::from(b'0' + (magnitude % 10) as u8);
+    len + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi};
+
+    fn render(value: f32, notation: Notation) -> [u8; 8] {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        display.write_auto(0, value, notation).unwrap();
+        let mut digits = spi.emulator().digits(0).unwrap();
+        // Leftmost digit first, like the expected text
+        digits.reverse();
+        digits
+    }
+
+    /// Right-aligned segment bytes for `text`, leftmost digit first.
+    fn expected(text: &str) -> [u8; 8] {
+        let mut line = [0u8; 8];
+        let mut len = 0;
+        let mut rendered = [0u8; 8];
+        for c in text.chars() {
+            if c == '.' {
+                rendered[len - 1] |= 0x80;
+            } else {
+                rendered[len] = crate::seven_segment::glyph(c).unwrap().bits();
+                len += 1;
+            }
+        }
+        line[8 - len..].copy_from_slice(&rendered[..len]);
+        line
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(1234.0).1, 3);
+        assert_eq!(normalize(0.005).1, -3);
+        assert_eq!(normalize(1.0), (1.0, 0));
+    }
+
+    #[test]
+    fn test_plain_values() {
+        assert_eq!(render(12.5, Notation::Scientific), expected("12.5"));
+        assert_eq!(render(-0.05, Notation::Scientific), expected("-0.05"));
+        assert_eq!(render(0.0, Notation::Scientific), expected("0"));
+        assert_eq!(render(1234567.0, Notation::SiPrefix), expected("1234567"));
+    }
+
+    #[test]
+    fn test_scientific() {
+        assert_eq!(render(1.2345e9, Notation::Scientific), expected("1.2345E9"));
+        assert_eq!(render(5e-6, Notation::Scientific), expected("5E-6"));
+        assert_eq!(render(-2.5e-3, Notation::Scientific), expected("-2.5E-3"));
+    }
+
+    #[test]
+    fn test_engineering() {
+        assert_eq!(
+            render(123.45e9, Notation::Engineering),
+            expected("123.45E9")
+        );
+        assert_eq!(render(4.7e-5, Notation::Engineering), expected("47E-6"));
+    }
+
+    #[test]
+    fn test_si_prefix() {
+        assert_eq!(render(4.7e-5, Notation::SiPrefix), expected("47u"));
+        assert_eq!(render(0.0025, Notation::SiPrefix), expected("2.5m"));
+        assert_eq!(
+            render(123_456_789.0, Notation::SiPrefix),
+            expected("123.457M")
+        );
+        // Outside the prefix range falls back to an exponent
+        assert_eq!(render(1e15, Notation::SiPrefix), expected("1E15"));
+    }
+
+    #[test]
+    fn test_rounding_carry() {
+        assert_eq!(render(9.999_999e9, Notation::Scientific), expected("1E10"));
+    }
+
+    #[test]
+    fn test_not_finite() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        assert_eq!(
+            display.write_auto(0, f32::NAN, Notation::Scientific),
+            Err(Error::Overflow)
+        );
+
+        display.set_overflow_policy(OverflowPolicy::Saturate);
+        display
+            .write_auto(0, f32::INFINITY, Notation::Scientific)
+            .unwrap();
+        assert_eq!(spi.emulator().digits(0).unwrap(), [0x01; 8]);
+    }
+}
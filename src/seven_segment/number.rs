@@ -0,0 +1,103 @@
+/// What to do when a number has more digits than the display can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Leave the display untouched and return [`Error::Overflow`](crate::error::Error::Overflow).
+    #[default]
+    Error,
+    /// Clamp to the largest (or smallest) value that fits, e.g. `99999999`.
+    ///
+    /// A negative value in a 1-digit field has no room for a digit after
+    /// the sign and shows a single dash instead.
+    Saturate,
+    /// Show a row of dashes (`--------`) across every digit.
+    Dashes,
+}
+
+/// Largest number of decimal digits in a `u32`
+pub(crate) const MAX_DECIMAL_DIGITS: usize = 10;
+
+/// Split `value` into decimal digits, least significant first.
+///
+/// Returns the number of digits written to `buf` (at least one, so zero
+/// renders as `0`).
+pub(crate) fn decimal_digits(mut value: u32, buf: &mut [u8; MAX_DECIMAL_DIGITS]) -> usize {
+    let mut len = 0;
+    loop {
+        buf[len] = (value % 10) as u8;
+        len += 1;
+        value /= 10;
+        if value == 0 {
+            return len;
+        }
+    }
+}
+
+/// Buffer size needed by [`number_str`] for any `i32` (sign plus ten digits)
+pub const NUMBER_STR_LEN: usize = MAX_DECIMAL_DIGITS + 1;
+
+/// Format `value` as decimal text without going through `core::fmt`.
+///
+/// Handy for feeding numbers to text helpers such as [`Pager`](super::Pager)
+/// or matrix text drawing, where `format_args!` would cost several KB of
+/// flash on small targets.
+pub fn number_str(value: i32, buf: &mut [u8; NUMBER_STR_LEN]) -> &str {
+    let mut digits = [0; MAX_DECIMAL_DIGITS];
+    let len = decimal_digits(value.unsigned_abs(), &mut digits);
+    let start = NUMBER_STR_LEN - len;
+    for (slot, digit) in buf[start..].iter_mut().rev().zip(&digits[..len]) {
+        *slot = b'0' + digit;
+    }
+    let start = if value < 0 {
+        buf[start - 1] = b'-';
+        start - 1
+    } else {
+        start
+    };
+    // Only ASCII digits and '-' were written
+    core::str::from_utf8(&buf[start..]).unwrap_or_default()
+}
+
+/// Largest magnitude that fits in `width` digits, optionally leaving room for a sign.
+pub(crate) fn max_magnitude(width: usize, negative: bool) -> u32 {
+    let digits = if negative {
+        width.saturating_sub(1)
+    } else {
+        width
+    };
+    (0..digits.min(9)).fold(1u32, |acc, _| acc * 10) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_digits() {
+        let mut buf = [0; MAX_DECIMAL_DIGITS];
+        let len = decimal_digits(4096, &mut buf);
+        assert_eq!(&buf[..len], &[6, 9, 0, 4]);
+
+        let len = decimal_digits(0, &mut buf);
+        assert_eq!(&buf[..len], &[0]);
+
+        let len = decimal_digits(u32::MAX, &mut buf);
+        assert_eq!(len, MAX_DECIMAL_DIGITS);
+    }
+
+    #[test]
+    fn test_number_str() {
+        let mut buf = [0; NUMBER_STR_LEN];
+        assert_eq!(number_str(0, &mut buf), "0");
+        assert_eq!(number_str(-42, &mut buf), "-42");
+        assert_eq!(number_str(i32::MIN, &mut buf), "-2147483648");
+        assert_eq!(number_str(i32::MAX, &mut buf), "2147483647");
+    }
+
+    #[test]
+    fn test_max_magnitude() {
+        assert_eq!(max_magnitude(8, false), 99_999_999);
+        assert_eq!(max_magnitude(8, true), 9_999_999);
+        assert_eq!(max_magnitude(4, false), 9_999);
+        assert_eq!(max_magnitude(1, true), 0);
+    }
+}
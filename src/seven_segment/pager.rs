@@ -0,0 +1,163 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::SevenSegment;
+use crate::{NUM_DIGITS, Result};
+
+/// Splits text longer than the display into fixed-width pages.
+///
+/// An alternative to scrolling: each [`tick`](Self::tick) shows the next
+/// page, wrapping back to the first after the last one. Page widths are
+/// counted in digits, so a `.` merged into the preceding digit's decimal
+/// point does not take a slot of its own. Long numbers can be paged by
+/// formatting them with [`number_str`](super::number_str) first.
+#[derive(Debug, Clone)]
+pub struct Pager<'a> {
+    text: &'a str,
+    width: usize,
+    page: usize,
+    page_count: usize,
+}
+
+impl<'a> Pager<'a> {
+    /// Create a pager showing `width` digits per page.
+    pub fn new(text: &'a str, width: usize) -> Self {
+        let mut pager = Self {
+            text,
+            width: width.max(1),
+            page: 0,
+            page_count: 1,
+        };
+        let mut end = pager.page_end(0);
+        while end < text.len() {
+            end = pager.page_end(end);
+            pager.page_count += 1;
+        }
+        pager
+    }
+
+    /// Create a pager for a whole 8-digit device.
+    pub fn for_device(text: &'a str) -> Self {
+        Self::new(text, NUM_DIGITS as usize)
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.page_count
+    }
+
+    pub fn current_page(&self) -> usize {
+        self.page
+    }
+
+    /// Text of the current page.
+    pub fn page_text(&self) -> &'a str {
+        let mut start = 0;
+        for _ in 0..self.page {
+            start = self.page_end(start);
+        }
+        &self.text[start..self.page_end(start)]
+    }
+
+    /// Move to the next page, wrapping to the first, and return its index.
+    pub fn next_page(&mut self) -> usize {
+        self.page = (self.page + 1) % self.page_count;
+        self.page
+    }
+
+    pub fn reset(&mut self) {
+        self.page = 0;
+    }
+
+    /// Render the current page on one device.
+    pub fn show<SPI>(&self, display: &mut SevenSegment<SPI>, device_index: usize) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        display.write_str(device_index, self.page_text())
+    }
+
+    /// Show the current page, then advance so the next call shows the
+    /// following one. Returns the index of the page that was shown.
+    pub fn tick<SPI>(
+        &mut self,
+        display: &mut SevenSegment<SPI>,
+        device_index: usize,
+    ) -> Result<usize>
+    where
+        SPI: SpiDevice,
+    {
+        let shown = self.page;
+        self.show(display, device_index)?;
+        self.next_page();
+        Ok(shown)
+    }
+
+    /// Byte offset where the page starting at `start` ends.
+    fn page_end(&self, start: usize) -> usize {
+        let mut used = 0;
+        let mut prev_has_dp = true;
+        for (offset, c) in self.text[start..].char_indices() {
+            if c == '.' && !prev_has_dp {
+                prev_has_dp = true;
+                continue;
+            }
+            if used == self.width {
+                return start + offset;
+            }
+            used += 1;
+            prev_has_dp = c == '.';
+        }
+        self.text.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages() {
+        let mut pager = Pager::new("HELLO WORLD", 4);
+        assert_eq!(pager.page_count(), 3);
+        assert_eq!(pager.page_text(), "HELL");
+        assert_eq!(pager.next_page(), 1);
+        assert_eq!(pager.page_text(), "O WO");
+        pager.next_page();
+        assert_eq!(pager.page_text(), "RLD");
+        assert_eq!(pager.next_page(), 0);
+    }
+
+    #[test]
+    fn test_decimal_points_do_not_take_digits() {
+        let pager = Pager::new("1.2.3.4.5", 4);
+        assert_eq!(pager.page_count(), 2);
+        assert_eq!(pager.page_text(), "1.2.3.4.");
+    }
+
+    #[test]
+    fn test_short_and_empty_text() {
+        assert_eq!(Pager::for_device("12").page_count(), 1);
+        let pager = Pager::for_device("");
+        assert_eq!(pager.page_count(), 1);
+        assert_eq!(pager.page_text(), "");
+    }
+
+    #[test]
+    fn test_tick_renders_pages_in_turn() {
+        use crate::driver::Max7219;
+        use crate::host::EmulatedSpi;
+
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        let mut pager = Pager::new("123456789", 8);
+
+        assert_eq!(pager.tick(&mut display, 0), Ok(0));
+        assert_eq!(pager.tick(&mut display, 0), Ok(1));
+        // "9" on the leftmost digit (DIG7), the rest blank
+        assert_eq!(
+            spi.emulator().digits(0).unwrap(),
+            [0, 0, 0, 0, 0, 0, 0, 0x7B]
+        );
+        assert_eq!(pager.current_page(), 0);
+    }
+}
@@ -0,0 +1,220 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Segments, SevenSegment, display::CHAIN_DIGITS};
+use crate::{Result, error::Error};
+
+/// Maximum number of fields in a [`Scoreboard`]
+pub const MAX_FIELDS: usize = 4;
+
+/// Splits the chain's digits into numeric fields separated by a glyph.
+///
+/// Covers home/guest scoreboards and dual-channel meters: e.g. fields of
+/// 3 and 4 digits show `" 12-  45"`. Fields are laid out from chain-wide
+/// position 0 (see [`SevenSegment::write_digit`]) and may span devices.
+/// Each value is right-aligned in its field, and numbers that don't fit
+/// are handled per field by the display's
+/// [`OverflowPolicy`](super::OverflowPolicy). A 1-digit field can only
+/// show a negative value as a lone `-`.
+#[derive(Debug, Clone)]
+pub struct Scoreboard {
+    widths: [u8; MAX_FIELDS],
+    field_count: usize,
+    separator: Option<Segments>,
+}
+
+impl Scoreboard {
+    /// Create a layout with one field per entry of `widths`, separated by
+    /// a dash.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if there are no fields, more than
+    ///   [`MAX_FIELDS`], a field of width 0, or the layout is wider than the
+    ///   largest chain.
+    pub fn new(widths: &[u8]) -> Result<Self> {
+        if widths.is_empty() || widths.len() > MAX_FIELDS || widths.contains(&0) {
+            return Err(Error::InvalidDigit);
+        }
+        let mut scoreboard = Self {
+            widths: [0; MAX_FIELDS],
+            field_count: widths.len(),
+            separator: Some(Segments::G),
+        };
+        scoreboard.widths[..widths.len()].copy_from_slice(widths);
+        if scoreboard.width() > CHAIN_DIGITS {
+            return Err(Error::InvalidDigit);
+        }
+        Ok(scoreboard)
+    }
+
+    /// Glyph shown in the digit between fields, [`Segments::NONE`] for a
+    /// blank gap.
+    pub fn with_separator(mut self, separator: Segments) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Place fields directly next to each other without a separator digit.
+    pub fn without_separator(mut self) -> Self {
+        self.separator = None;
+        self
+    }
+
+    pub fn field_count(&self) -> usize {
+        self.field_count
+    }
+
+    /// Width of field `index` in digits.
+    pub fn field_width(&self, index: usize) -> Option<u8> {
+        self.widths[..self.field_count].get(index).copied()
+    }
+
+    /// Total digits used, including separators.
+    pub fn width(&self) -> usize {
+        let fields: usize = self.widths.iter().map(|&width| usize::from(width)).sum();
+        let separators = if self.separator.is_some() {
+            self.field_count - 1
+        } else {
+            0
+        };
+        fields + separators
+    }
+
+    /// Show one value per field.
+    ///
+    /// Digits past [`width`](Self::width) are left untouched.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if `values` doesn't have one entry per field
+    ///   or the layout is wider than the chain.
+    /// - [`Error::Overflow`] if a value doesn't fit its field and the
+    ///   display's policy is [`OverflowPolicy::Error`](super::OverflowPolicy::Error).
+    pub fn write<SPI>(&self, display: &mut SevenSegment<SPI>, values: &[i32]) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let width = self.width();
+        if values.len() != self.field_count || width > display.digit_count() {
+            return Err(Error::InvalidDigit);
+        }
+
+        let mut line = [Segments::NONE; CHAIN_DIGITS];
+        let mut start = 0;
+        for (index, (&field, &value)) in self.widths.iter().zip(values).enumerate() {
+            if index > 0
+                && let Some(separator) = self.separator
+            {
+                line[start] = separator;
+                start += 1;
+            }
+            let end = start + usize::from(field);
+            display.render_decimal(value, 0, &mut line[start..end])?;
+            start = end;
+        }
+        display.write_chain_line(&line[..width])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, seven_segment::OverflowPolicy};
+
+    #[test]
+    fn test_new_invalid_layout() {
+        assert!(matches!(Scoreboard::new(&[]), Err(Error::InvalidDigit)));
+        assert!(matches!(Scoreboard::new(&[3, 0]), Err(Error::InvalidDigit)));
+        assert!(matches!(
+            Scoreboard::new(&[1, 1, 1, 1, 1]),
+            Err(Error::InvalidDigit)
+        ));
+        assert!(matches!(
+            Scoreboard::new(&[32, 32]),
+            Err(Error::InvalidDigit)
+        ));
+    }
+
+    #[test]
+    fn test_width() {
+        let scoreboard = Scoreboard::new(&[3, 4]).unwrap();
+        assert_eq!(scoreboard.width(), 8);
+        assert_eq!(scoreboard.field_width(1), Some(4));
+        assert_eq!(scoreboard.field_width(2), None);
+        assert_eq!(scoreboard.without_separator().width(), 7);
+    }
+
+    #[test]
+    fn test_write_two_fields() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        let scoreboard = Scoreboard::new(&[3, 4]).unwrap();
+        scoreboard.write(&mut display, &[12, -345]).unwrap();
+
+        // " 12--345" leftmost first; DIG7 holds position 0
+        let mut digits = spi.emulator().digits(0).unwrap();
+        digits.reverse();
+        assert_eq!(digits, [0x00, 0x30, 0x6D, 0x01, 0x01, 0x79, 0x33, 0x5B]);
+    }
+
+    #[test]
+    fn test_write_spans_devices() {
+        let mut spi = EmulatedSpi::new(2).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi).with_device_count(2).unwrap());
+        display.init().unwrap();
+        let scoreboard = Scoreboard::new(&[6, 6])
+            .unwrap()
+            .with_separator(Segments::NONE);
+        scoreboard.write(&mut display, &[1, 2]).unwrap();
+
+        let emulator = spi.emulator();
+        // Position 5 (field 0 units) is device 0 DIG2, position 12 is device 1 DIG3
+        assert_eq!(emulator.digits(0).unwrap()[2], 0x30);
+        assert_eq!(emulator.digits(1).unwrap()[3], 0x6D);
+        assert_eq!(emulator.digits(1).unwrap()[2], 0x00);
+    }
+
+    #[test]
+    fn test_write_errors() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+
+        let scoreboard = Scoreboard::new(&[3, 4]).unwrap();
+        assert_eq!(
+            scoreboard.write(&mut display, &[1]),
+            Err(Error::InvalidDigit)
+        );
+        assert_eq!(
+            scoreboard.write(&mut display, &[1000, 1]),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            Scoreboard::new(&[4, 4])
+                .unwrap()
+                .write(&mut display, &[1, 2]),
+            Err(Error::InvalidDigit)
+        );
+
+        display.set_overflow_policy(OverflowPolicy::Saturate);
+        scoreboard.write(&mut display, &[1000, 1]).unwrap();
+    }
+
+    #[test]
+    fn test_write_negative_in_single_digit_field() {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        let scoreboard = Scoreboard::new(&[1, 1]).unwrap();
+
+        assert_eq!(
+            scoreboard.write(&mut display, &[-3, 4]),
+            Err(Error::Overflow)
+        );
+
+        display.set_overflow_policy(OverflowPolicy::Saturate);
+        scoreboard.write(&mut display, &[-3, 4]).unwrap();
+        // "--4" leftmost first: a saturated sign, the separator, then 4
+        let digits = spi.emulator().digits(0).unwrap();
+        assert_eq!(digits[5..], [0x33, 0x01, 0x01]);
+    }
+}
@@ -0,0 +1,187 @@
+use core::ops::{BitAnd, BitOr, BitOrAssign, Not};
+
+use crate::{Result, error::Error};
+
+/// Raw segment pattern for a single 7-segment digit in no-decode mode.
+///
+/// The bit layout follows the MAX7219 datasheet (Table 6):
+///
+/// ```text
+/// bit:  7  6  5  4  3  2  1  0
+///      DP  A  B  C  D  E  F  G
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Segments(u8);
+
+impl Segments {
+    /// All segments off.
+    pub const NONE: Self = Self(0x00);
+    /// Top segment
+    pub const A: Self = Self(0b0100_0000);
+    /// Upper-right segment
+    pub const B: Self = Self(0b0010_0000);
+    /// Lower-right segment
+    pub const C: Self = Self(0b0001_0000);
+    /// Bottom segment
+    pub const D: Self = Self(0b0000_1000);
+    /// Lower-left segment
+    pub const E: Self = Self(0b0000_0100);
+    /// Upper-left segment
+    pub const F: Self = Self(0b0000_0010);
+    /// Middle segment
+    pub const G: Self = Self(0b0000_0001);
+    /// Decimal point
+    pub const DP: Self = Self(0b1000_0000);
+
+    /// Create a segment pattern from the raw register byte.
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Raw register byte for this pattern.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Combine two patterns, usable in `const` contexts.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns `true` if every segment in `other` is also lit in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no segment is lit.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Same pattern with the decimal point lit.
+    pub const fn with_dp(self) -> Self {
+        self.union(Self::DP)
+    }
+}
+
+impl BitOr for Segments {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for Segments {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for Segments {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Not for Segments {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl From<Segments> for u8 {
+    fn from(value: Segments) -> Self {
+        value.bits()
+    }
+}
+
+/// Segment wiring for boards that don't connect SEG A-G/DP in the
+/// datasheet order.
+///
+/// Entry `n` is the register bit that lights the segment whose standard
+/// bit is `n` (see [`Segments`]), e.g. a board with A and G swapped uses
+/// `[6, 1, 2, 3, 4, 5, 0, 7]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentMap([u8; 8]);
+
+impl SegmentMap {
+    /// Standard wiring, every segment on its own bit.
+    pub const IDENTITY: Self = Self([0, 1, 2, 3, 4, 5, 6, 7]);
+
+    /// # Errors
+    /// - [`Error::InvalidSegmentMap`] if the entries are not the bits 0-7,
+    ///   each used once.
+    pub fn new(bits: [u8; 8]) -> Result<Self> {
+        let mut seen = 0u8;
+        for &bit in &bits {
+            if bit > 7 || seen & (1 << bit) != 0 {
+                return Err(Error::InvalidSegmentMap);
+            }
+            seen |= 1 << bit;
+        }
+        Ok(Self(bits))
+    }
+
+    /// Register byte that shows `segments` on this wiring.
+    pub fn apply(&self, segments: Segments) -> u8 {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|&(standard, _)| segments.0 & (1 << standard) != 0)
+            .fold(0, |bits, (_, &wired)| bits | (1 << wired))
+    }
+}
+
+impl Default for SegmentMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_bits() {
+        assert_eq!(Segments::A.bits(), 0x40);
+        assert_eq!(Segments::G.bits(), 0x01);
+        assert_eq!(Segments::DP.bits(), 0x80);
+        assert_eq!(Segments::NONE.bits(), 0x00);
+    }
+
+    #[test]
+    fn test_segment_operators() {
+        let one = Segments::B | Segments::C;
+        assert_eq!(one.bits(), 0x30);
+        assert!(one.contains(Segments::B));
+        assert!(!one.contains(Segments::A));
+        assert_eq!(one.with_dp().bits(), 0xB0);
+        assert_eq!((one & Segments::C), Segments::C);
+        assert_eq!((!Segments::NONE).bits(), 0xFF);
+        assert!(Segments::NONE.is_empty());
+    }
+
+    #[test]
+    fn test_segment_map() {
+        assert_eq!(SegmentMap::IDENTITY.apply(Segments::from_bits(0x5B)), 0x5B);
+
+        let swapped = SegmentMap::new([6, 1, 2, 3, 4, 5, 0, 7]).unwrap();
+        assert_eq!(swapped.apply(Segments::A), Segments::G.bits());
+        assert_eq!(swapped.apply(Segments::G | Segments::DP), 0xC0);
+
+        assert_eq!(
+            SegmentMap::new([0, 1, 2, 3, 4, 5, 6, 8]),
+            Err(Error::InvalidSegmentMap)
+        );
+        assert_eq!(
+            SegmentMap::new([0, 0, 2, 3, 4, 5, 6, 7]),
+            Err(Error::InvalidSegmentMap)
+        );
+    }
+}
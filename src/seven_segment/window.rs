@@ -0,0 +1,232 @@
+use embedded_hal::spi::SpiDevice;
+
+use super::{Segments, SevenSegment};
+use crate::{NUM_DIGITS, Result, error::Error};
+
+/// Horizontal placement of content narrower than its [`Window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    Left,
+    #[default]
+    Right,
+}
+
+/// A range of digits on one device that is written independently of the
+/// rest, e.g. digits 0-3 for temperature and 4-7 for humidity.
+///
+/// Writes only touch the window's own digits, so several windows can share
+/// a device without clobbering each other. Text defaults to right
+/// alignment like numbers.
+#[derive(Debug, Clone)]
+pub struct Window {
+    device_index: usize,
+    start: u8,
+    width: u8,
+    align: Align,
+    leading_zeros: bool,
+}
+
+impl Window {
+    /// Bind a window to `width` digits starting at `start` (0 = leftmost).
+    ///
+    /// # Errors
+    /// - [`Error::InvalidDigit`] if the range is empty or runs past the last digit.
+    pub fn new(device_index: usize, start: u8, width: u8) -> Result<Self> {
+        if width == 0 || start.checked_add(width).is_none_or(|end| end > NUM_DIGITS) {
+            return Err(Error::InvalidDigit);
+        }
+        Ok(Self {
+            device_index,
+            start,
+            width,
+            align: Align::default(),
+            leading_zeros: false,
+        })
+    }
+
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Pad numbers with zeros instead of blanks, e.g. `0042` or `-042`.
+    /// Has no effect on left-aligned windows.
+    pub fn with_leading_zeros(mut self, enabled: bool) -> Self {
+        self.leading_zeros = enabled;
+        self
+    }
+
+    pub fn device_index(&self) -> usize {
+        self.device_index
+    }
+
+    pub fn start(&self) -> u8 {
+        self.start
+    }
+
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Show `text` in the window, blanking its unused digits.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedChar`] if a character has no custom or built-in glyph.
+    /// - [`Error::TextTooLong`] if the text needs more digits than the window has.
+    pub fn write_str<SPI>(&self, display: &mut SevenSegment<SPI>, text: &str) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        let line = &mut line[..usize::from(self.width)];
+        let len = display.render_text(text, line)?;
+        if self.align == Align::Right {
+            line.rotate_right(line.len() - len);
+        }
+        self.write_line(display, line)
+    }
+
+    /// Show `value` in the window.
+    ///
+    /// Numbers wider than the window are handled according to the display's
+    /// [`OverflowPolicy`](super::OverflowPolicy).
+    pub fn write_number<SPI>(&self, display: &mut SevenSegment<SPI>, value: i32) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        self.write_decimal(display, value, 0)
+    }
+
+    /// Show the fixed-point value `mantissa / 10^decimals` in the window,
+    /// see [`SevenSegment::write_decimal`].
+    pub fn write_decimal<SPI>(
+        &self,
+        display: &mut SevenSegment<SPI>,
+        mantissa: i32,
+        decimals: u8,
+    ) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let mut line = [Segments::NONE; NUM_DIGITS as usize];
+        let line = &mut line[..usize::from(self.width)];
+        display.render_decimal(mantissa, decimals, line)?;
+
+        let padding = line
+            .iter()
+            .take_while(|segments| segments.is_empty())
+            .count();
+        match self.align {
+            Align::Left => line.rotate_left(padding),
+            Align::Right if self.leading_zeros && padding > 0 => {
+                let zero = display.char_glyph('0')?;
+                if mantissa < 0 {
+                    // Move the sign to the front, zeros go between it and the digits
+                    line[padding] = zero;
+                    line[0] = display.char_glyph('-')?;
+                    line[1..padding].fill(zero);
+                } else {
+                    line[..padding].fill(zero);
+                }
+            }
+            Align::Right => {}
+        }
+        self.write_line(display, line)
+    }
+
+    /// Blank the window's digits.
+    pub fn clear<SPI>(&self, display: &mut SevenSegment<SPI>) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        let line = [Segments::NONE; NUM_DIGITS as usize];
+        self.write_line(display, &line[..usize::from(self.width)])
+    }
+
+    fn write_line<SPI>(&self, display: &mut SevenSegment<SPI>, line: &[Segments]) -> Result<()>
+    where
+        SPI: SpiDevice,
+    {
+        for (position, segments) in (self.start..).zip(line) {
+            display.write_segments(self.device_index, position, *segments)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{driver::Max7219, host::EmulatedSpi, seven_segment::OverflowPolicy};
+
+    /// Run `f` on a fresh single-device display and return the digits,
+    /// leftmost first.
+    fn render(f: impl FnOnce(&mut SevenSegment<&mut EmulatedSpi>)) -> [u8; 8] {
+        let mut spi = EmulatedSpi::new(1).unwrap();
+        let mut display = SevenSegment::new(Max7219::new(&mut spi));
+        display.init().unwrap();
+        f(&mut display);
+        let mut digits = spi.emulator().digits(0).unwrap();
+        digits.reverse();
+        digits
+    }
+
+    #[test]
+    fn test_new_invalid_range() {
+        assert!(matches!(Window::new(0, 0, 0), Err(Error::InvalidDigit)));
+        assert!(matches!(Window::new(0, 5, 4), Err(Error::InvalidDigit)));
+        assert!(Window::new(0, 4, 4).is_ok());
+    }
+
+    #[test]
+    fn test_independent_windows() {
+        let digits = render(|display| {
+            let temperature = Window::new(0, 0, 4).unwrap();
+            let humidity = Window::new(0, 4, 4).unwrap();
+            temperature.write_decimal(display, 215, 1).unwrap();
+            humidity.write_number(display, 45).unwrap();
+            // Rewriting one window leaves the other alone
+            temperature.write_number(display, -3).unwrap();
+        });
+        assert_eq!(digits, [0, 0, 0x01, 0x79, 0, 0, 0x33, 0x5B]);
+    }
+
+    #[test]
+    fn test_alignment_and_padding() {
+        let digits = render(|display| {
+            let left = Window::new(0, 0, 4).unwrap().with_align(Align::Left);
+            let zeros = Window::new(0, 4, 4).unwrap().with_leading_zeros(true);
+            left.write_number(display, 12).unwrap();
+            zeros.write_number(display, -7).unwrap();
+        });
+        assert_eq!(digits, [0x30, 0x6D, 0, 0, 0x01, 0x7E, 0x7E, 0x70]);
+
+        let digits = render(|display| {
+            let right = Window::new(0, 2, 4).unwrap();
+            right.write_str(display, "Hi").unwrap();
+        });
+        assert_eq!(digits, [0, 0, 0, 0, 0x37, 0x10, 0, 0]);
+    }
+
+    #[test]
+    fn test_text_too_long() {
+        render(|display| {
+            let window = Window::new(0, 0, 2).unwrap();
+            assert_eq!(window.write_str(display, "abc"), Err(Error::TextTooLong));
+            assert_eq!(window.write_number(display, 123), Err(Error::Overflow));
+        });
+    }
+
+    #[test]
+    fn test_single_digit_negative_saturates_to_sign() {
+        let digits = render(|display| {
+            let window = Window::new(0, 7, 1).unwrap();
+            assert_eq!(window.write_number(display, -5), Err(Error::Overflow));
+
+            display.set_overflow_policy(OverflowPolicy::Saturate);
+            window.write_number(display, -5).unwrap();
+            window.write_decimal(display, -25, 1).unwrap();
+        });
+        assert_eq!(digits, [0, 0, 0, 0, 0, 0, 0, 0x01]);
+    }
+}
@@ -0,0 +1,158 @@
+/// Turns absolute millisecond timestamps into the deltas effects advance by.
+///
+/// Lets schedulers with coarse or jittery wakeups (RTC alarms, low-power
+/// timers) pass "the time is now X" instead of accumulating deltas
+/// themselves, so no time is lost between irregular calls. Timestamps may
+/// wrap around `u32::MAX`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimestampTracker {
+    last_ms: Option<u32>,
+}
+
+impl TimestampTracker {
+    pub const fn new() -> Self {
+        Self { last_ms: None }
+    }
+
+    /// Milliseconds since the previous timestamp; the first call returns 0.
+    pub fn delta_to(&mut self, now_ms: u32) -> u32 {
+        let delta = self.last_ms.map_or(0, |last| now_ms.wrapping_sub(last));
+        self.last_ms = Some(now_ms);
+        delta
+    }
+
+    /// Forget the previous timestamp so the next call starts a new baseline.
+    pub fn reset(&mut self) {
+        self.last_ms = None;
+    }
+}
+
+/// Source of the current time in milliseconds, wrapping around
+/// `u32::MAX`.
+///
+/// Effects are driven by ticks or timestamps rather than by a `DelayNs`,
+/// so the same objects work with blocking delays, RTIC monotonics and
+/// Embassy time: implement this for the application's clock, or pass a
+/// closure such as `|| Instant::now().as_millis() as u32`.
+pub trait TickSource {
+    fn now_ms(&mut self) -> u32;
+}
+
+impl<F> TickSource for F
+where
+    F: FnMut() -> u32,
+{
+    fn now_ms(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// Decides when a tick-driven effect, e.g. a
+/// [`Scroller`](crate::matrix::Scroller) or a
+/// [`Blinker`](crate::driver::Blinker), is due, from a [`TickSource`].
+///
+/// Poll it from the main loop or a task and tick the effect whenever it
+/// returns `true`. Time is carried over between periods, so late polls
+/// don't make the effect drift; the first poll is always due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    period_ms: u32,
+    // Time since the last due poll
+    elapsed_ms: u32,
+    timestamps: TimestampTracker,
+}
+
+impl Interval {
+    pub const fn new(period_ms: u32) -> Self {
+        Self {
+            period_ms,
+            elapsed_ms: period_ms,
+            timestamps: TimestampTracker::new(),
+        }
+    }
+
+    pub fn period_ms(&self) -> u32 {
+        self.period_ms
+    }
+
+    /// Whether a period has passed since the last time this returned
+    /// `true`. Several missed periods count as one.
+    pub fn poll<T>(&mut self, clock: &mut T) -> bool
+    where
+        T: TickSource,
+    {
+        self.poll_at(clock.now_ms())
+    }
+
+    /// Like [`poll`](Self::poll), with the current time given directly.
+    pub fn poll_at(&mut self, now_ms: u32) -> bool {
+        let delta_ms = self.timestamps.delta_to(now_ms);
+        self.elapsed_ms = self.elapsed_ms.saturating_add(delta_ms);
+        if self.elapsed_ms < self.period_ms {
+            return false;
+        }
+        self.elapsed_ms = match self.elapsed_ms.checked_rem(self.period_ms) {
+            Some(rest) if self.elapsed_ms < self.period_ms.saturating_mul(2) => rest,
+            _ => 0,
+        };
+        true
+    }
+
+    /// Make the next poll due and start a new time base.
+    pub fn reset(&mut self) {
+        self.elapsed_ms = self.period_ms;
+        self.timestamps.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_to() {
+        let mut tracker = TimestampTracker::new();
+        assert_eq!(tracker.delta_to(1000), 0);
+        assert_eq!(tracker.delta_to(1250), 250);
+        assert_eq!(tracker.delta_to(1250), 0);
+    }
+
+    #[test]
+    fn test_delta_to_wraps() {
+        let mut tracker = TimestampTracker::new();
+        tracker.delta_to(u32::MAX - 9);
+        assert_eq!(tracker.delta_to(10), 20);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tracker = TimestampTracker::new();
+        tracker.delta_to(5);
+        tracker.reset();
+        assert_eq!(tracker.delta_to(500), 0);
+    }
+
+    #[test]
+    fn test_interval() {
+        let mut now = 1000;
+        let mut interval = Interval::new(100);
+        assert!(interval.poll_at(now));
+        assert!(!interval.poll_at(now + 99));
+        // 30 ms late, the next period still ends at +200
+        assert!(interval.poll_at(now + 130));
+        assert!(!interval.poll_at(now + 199));
+        assert!(interval.poll_at(now + 200));
+        // Missed periods count once
+        assert!(interval.poll_at(now + 750));
+        assert!(!interval.poll_at(now + 800));
+
+        interval.reset();
+        let mut clock = || {
+            now += 10;
+            now
+        };
+        assert!(interval.poll(&mut clock));
+        assert!(!interval.poll(&mut clock));
+        assert_eq!(interval.period_ms(), 100);
+    }
+}